@@ -2,7 +2,7 @@ use std::collections::BTreeMap;
 use serde::{Serialize, Deserialize};
 use crate::{allowable_result, allowable_result_fallback};
 
-use nvapi::{self, BaseVoltage, ClockEntry, ClockFrequencyType, ClockRange, ClockTable, Cooler, MemoryInfoEx, PStates, PowerInfoEntry, Sensor, ThermalInfo, ThermalLimit, VfpCurve, VfpEntry
+use nvapi::{self, Nvapi, BaseVoltage, ClockEntry, ClockFrequencyType, ClockRange, ClockTable, Cooler, MemoryInfoEx, PStates, PowerInfoEntry, Sensor, ThermalInfo, ThermalLimit, VfpCurve, VfpEntry
 };
 pub use nvapi::{
     PhysicalGpu,
@@ -13,6 +13,8 @@ pub use nvapi::{
     PerfInfo, PerfStatus,
     ThermalController, ThermalTarget,
     MemoryInfo, PciIdentifiers, DriverModel,
+    EccConfiguration, EccErrors, EccErrorCounts,
+    BusType, PcieInfo, PcieLink,
     Percentage, Celsius,
     Range,
     Kibibytes, Microvolts, MicrovoltsDelta, Kilohertz, KilohertzDelta,
@@ -32,6 +34,7 @@ pub struct GpuInfo {
     pub driver_model: DriverModel,
     pub vendor: Vendor,
     pub pci: PciIdentifiers,
+    pub pcie: Option<PcieInfo>,
     pub memory: MemoryInfo,
     pub memory_ex: MemoryInfoEx,
     pub system_type: SystemType,
@@ -56,6 +59,19 @@ pub struct GpuInfo {
     pub overvolt_limits: Vec<OvervoltLimit>,
     pub vfp_limits: BTreeMap<ClockDomain, VfpRange>,
     pub vfp_locks: Vec<usize>,
+    pub ecc: Option<EccInfo>,
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash)]
+pub struct EccInfo {
+    pub configuration: EccConfiguration,
+    pub enabled: bool,
+    /// The stored setting, which takes effect after the next reboot
+    ///
+    /// This and `enabled_by_default` are `None` if the configuration can't be queried.
+    pub pending: Option<bool>,
+    pub enabled_by_default: Option<bool>,
 }
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -80,6 +96,7 @@ pub struct GpuStatus {
     pub pstate: PState,
     pub clocks: ClockFrequencies,
     pub memory: MemoryInfo,
+    pub pcie: Option<PcieInfo>,
     pub voltage: Option<Microvolts>,
     pub voltage_domains: Option<VoltageStatus>,
     pub voltage_step: Option<VoltageStatus>,
@@ -92,6 +109,7 @@ pub struct GpuStatus {
     pub perf: PerfStatus,
     pub vfp: Option<VfpTable>,
     pub vfp_locks: BTreeMap<usize, Microvolts>,
+    pub ecc_errors: Option<EccErrors>,
 }
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -122,8 +140,10 @@ impl Gpu {
         &self.gpu
     }
 
+    /// Enumerates the GPUs in the system, initializing the library if needed. Each one
+    /// holds on to the session, which keeps the library loaded until they're all dropped.
     pub fn enumerate() -> nvapi::Result<Vec<Self>> {
-        PhysicalGpu::enumerate().map(|v| v.into_iter().map(Gpu::new).collect())
+        Nvapi::new()?.gpus().map(|v| v.into_iter().map(Gpu::new).collect())
     }
 
     pub fn info(&self) -> nvapi::Result<GpuInfo> {
@@ -139,8 +159,9 @@ impl Gpu {
             codename: self.gpu.short_name()?,
             bios_version: self.gpu.vbios_version_string()?,
             driver_model: self.gpu.driver_model()?,
-            vendor: allowable_result_fallback(pci.vendor().map_err(From::from), Vendor::Unknown)?,
+            vendor: pci.vendor_lossless(),
             pci: pci,
+            pcie: allowable_result(self.gpu.pcie_info())?.ok(),
             memory: self.gpu.memory_info()?,
             memory_ex: self.gpu.memory_info_ex()?,
             system_type: allowable_result_fallback(self.gpu.system_type(), SystemType::Unknown)?,
@@ -182,6 +203,18 @@ impl Gpu {
                 Ok(v) => v.into_iter().map(|(id, _)| id).collect(),
                 Err(..) => Default::default(),
             },
+            ecc: match allowable_result(self.gpu.ecc_status())? {
+                Ok(status) if status.supported => {
+                    let config = allowable_result(self.gpu.ecc_configuration())?.ok();
+                    Some(EccInfo {
+                        configuration: status.configuration,
+                        enabled: status.enabled,
+                        pending: config.map(|config| config.enabled),
+                        enabled_by_default: config.map(|config| config.enabled_by_default),
+                    })
+                },
+                _ => None,
+            },
         })
     }
 
@@ -192,6 +225,7 @@ impl Gpu {
             pstate: self.gpu.current_pstate()?,
             clocks: self.gpu.clock_frequencies(ClockFrequencyType::Current)?,
             memory: self.gpu.memory_info()?,
+            pcie: allowable_result(self.gpu.pcie_info())?.ok(),
             voltage: allowable_result(self.gpu.core_voltage())?.ok(),
             voltage_domains: allowable_result(self.gpu.voltage_domains_status())?.ok(),
             voltage_step: allowable_result(self.gpu.voltage_step())?.ok(),
@@ -220,6 +254,7 @@ impl Gpu {
                 }).collect(),
                 Err(..) => Default::default(),
             },
+            ecc_errors: allowable_result(self.gpu.ecc_errors())?.ok(),
         })
     }
 
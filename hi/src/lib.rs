@@ -6,7 +6,7 @@ mod gpu;
 pub use gpu::*;
 
 pub use nvapi::{
-    Status, Result,
+    Status, Error, Result, Nvapi,
     sys,
     initialize, unload, driver_version, interface_version, error_message
 };
@@ -14,15 +14,19 @@ pub use nvapi::{
 pub fn allowable_result_fallback<T>(v: nvapi::Result<T>, fallback: T) -> nvapi::Result<T> {
     match v {
         Ok(v) => Ok(v),
-        Err(Status::NotSupported) | Err(Status::NoImplementation) | Err(Status::ArgumentExceedMaxSize) => Ok(fallback),
-        Err(e) => Err(e),
+        Err(e) => match e.status() {
+            Status::NotSupported | Status::NoImplementation | Status::ArgumentExceedMaxSize => Ok(fallback),
+            _ => Err(e),
+        },
     }
 }
 
 pub fn allowable_result<T>(v: nvapi::Result<T>) -> nvapi::Result<nvapi::Result<T>> {
     match v {
         Ok(v) => Ok(Ok(v)),
-        Err(e @ Status::NotSupported) | Err(e @ Status::NoImplementation) => Ok(Err(e)),
-        Err(e) => Err(e),
+        Err(e) => match e.status() {
+            Status::NotSupported | Status::NoImplementation => Ok(Err(e)),
+            _ => Err(e),
+        },
     }
 }
@@ -0,0 +1,428 @@
+use std::ffi::CStr;
+use std::os::raw::c_char;
+use nvapi_sys::{Api, Status, NvAPI_Status};
+use nvapi_sys::handles::{NvPhysicalGpuHandle, NvLogicalGpuHandle, NvDisplayHandle, NvUnAttachedDisplayHandle};
+use nvapi_sys::types::{self, NvAPI_ShortString};
+use nvapi_sys::gpu::display::{self, NV_GPU_DISPLAYIDS, NV_GPU_CONNECTED_IDS_FLAG, DisplayIdsFlags, NV_EDID};
+use nvapi_sys::dispcontrol::{self, NV_DISPLAYCONFIG_PATH_INFO, NV_DISPLAYCONFIG_FLAGS, DisplayConfigFlags, SourceModeFlags, TargetInfoFlags, PathInfoFlags, ColorFormat, SpanningOrientation, Rotation, Scaling};
+use crate::{call, Result, State, Display, DisplayPath, DisplayTarget};
+use crate::util::{gpu, gpu_handle, logical_handle_of, out, input, check_version, write_str};
+
+/// Display handles are `DISPLAY_HANDLE_BASE + (gpu << 8) + index`.
+const DISPLAY_HANDLE_BASE: usize = 0x10000;
+
+fn display_handle(gpu: usize, index: usize) -> *const std::os::raw::c_void {
+    (DISPLAY_HANDLE_BASE + (gpu << 8) + index) as *const _
+}
+
+/// The GPU and display index a display handle refers to, if the display matches `filter`.
+fn display_index<F: Fn(&Display) -> bool>(state: &State, handle: *const std::os::raw::c_void, filter: F) -> Result<(usize, usize)> {
+    let offset = (handle as usize).checked_sub(DISPLAY_HANDLE_BASE).ok_or(Status::ExpectedDisplayHandle)?;
+    let (gpu, index) = (offset >> 8, offset & 0xff);
+    match state.system.gpus.get(gpu).and_then(|g| g.displays.get(index)) {
+        Some(display) if filter(display) => Ok((gpu, index)),
+        _ => Err(Status::ExpectedDisplayHandle),
+    }
+}
+
+fn display(state: &State, handle: NvDisplayHandle) -> Result<(usize, &Display)> {
+    display_index(state, handle.as_ptr(), Display::active)
+        .map(|(gpu, index)| (gpu, &state.system.gpus[gpu].displays[index]))
+}
+
+fn unattached_display(state: &State, handle: NvUnAttachedDisplayHandle) -> Result<(usize, &Display)> {
+    display_index(state, handle.as_ptr(), unattached)
+        .map(|(gpu, index)| (gpu, &state.system.gpus[gpu].displays[index]))
+}
+
+/// All displays across the system matching `filter`, in enumeration order.
+fn displays<F: Fn(&Display) -> bool>(state: &State, filter: F) -> Vec<(usize, usize, &Display)> {
+    state.system.gpus.iter().enumerate()
+        .flat_map(|(gpu, g)| g.displays.iter().enumerate().map(move |(index, display)| (gpu, index, display)))
+        .filter(|&(_, _, display)| filter(display))
+        .collect()
+}
+
+fn unattached(display: &Display) -> bool {
+    display.connected && !display.active()
+}
+
+fn display_ids<F: Fn(&Display) -> bool>(displays: &[Display], pDisplayIds: *mut NV_GPU_DISPLAYIDS, pDisplayIdCount: *mut u32, filter: F) -> Result<()> {
+    let count = out(pDisplayIdCount)?;
+    let displays: Vec<_> = displays.iter().filter(|d| filter(d)).collect();
+    if pDisplayIds.is_null() {
+        *count = displays.len() as _;
+        return Ok(())
+    }
+
+    if (*count as usize) < displays.len() {
+        *count = displays.len() as _;
+        return Err(Status::InsufficientBuffer)
+    }
+    let ids = unsafe { std::slice::from_raw_parts_mut(pDisplayIds, *count as usize) };
+    for (raw, display) in ids.iter_mut().zip(&displays) {
+        check_version(raw.version, &[display::NV_GPU_DISPLAYIDS_VER1, display::NV_GPU_DISPLAYIDS_VER2])?;
+        raw.connectorType = display.connector.raw();
+        raw.displayId = display.id;
+        let mut flags = display.flags;
+        flags.set(DisplayIdsFlags::CONNECTED, display.connected);
+        raw.flags = flags.bits();
+    }
+    *count = displays.len() as _;
+    Ok(())
+}
+
+pub extern "C" fn NvAPI_GPU_GetConnectedDisplayIds(hPhysicalGPU: NvPhysicalGpuHandle, pDisplayIds: *mut NV_GPU_DISPLAYIDS, pDisplayIdCount: *mut u32, _flags: NV_GPU_CONNECTED_IDS_FLAG) -> NvAPI_Status {
+    call(Api::NvAPI_GPU_GetConnectedDisplayIds, |state| {
+        let gpu = gpu(state, hPhysicalGPU)?;
+        display_ids(&gpu.displays, pDisplayIds, pDisplayIdCount, |d| d.connected)
+    })
+}
+
+pub extern "C" fn NvAPI_GPU_GetAllDisplayIds(hPhysicalGPU: NvPhysicalGpuHandle, pDisplayIds: *mut NV_GPU_DISPLAYIDS, pDisplayIdCount: *mut u32) -> NvAPI_Status {
+    call(Api::NvAPI_GPU_GetAllDisplayIds, |state| {
+        let gpu = gpu(state, hPhysicalGPU)?;
+        display_ids(&gpu.displays, pDisplayIds, pDisplayIdCount, |_| true)
+    })
+}
+
+pub extern "C" fn NvAPI_EnumNvidiaDisplayHandle(thisEnum: u32, pNvDispHandle: *mut NvDisplayHandle) -> NvAPI_Status {
+    call(Api::NvAPI_EnumNvidiaDisplayHandle, |state| {
+        let handle = out(pNvDispHandle)?;
+        let displays = displays(state, Display::active);
+        let &(gpu, index, _) = displays.get(thisEnum as usize).ok_or(Status::EndEnumeration)?;
+        *handle = NvDisplayHandle::from_raw(display_handle(gpu, index));
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_EnumNvidiaUnAttachedDisplayHandle(thisEnum: u32, pNvUnAttachedDispHandle: *mut NvUnAttachedDisplayHandle) -> NvAPI_Status {
+    call(Api::NvAPI_EnumNvidiaUnAttachedDisplayHandle, |state| {
+        let handle = out(pNvUnAttachedDispHandle)?;
+        let displays = displays(state, unattached);
+        let &(gpu, index, _) = displays.get(thisEnum as usize).ok_or(Status::EndEnumeration)?;
+        *handle = NvUnAttachedDisplayHandle::from_raw(display_handle(gpu, index));
+        Ok(())
+    })
+}
+
+fn find_index_by_name<F: Fn(&Display) -> bool>(state: &State, name: *const c_char, filter: F) -> Result<(usize, usize)> {
+    input(name)?;
+    let name = unsafe { CStr::from_ptr(name) }.to_str().map_err(|_| Status::InvalidArgument)?;
+    displays(state, filter).into_iter()
+        .find(|&(_, _, display)| display.name == name)
+        .map(|(gpu, index, _)| (gpu, index))
+        .ok_or(Status::NvidiaDeviceNotFound)
+}
+
+fn find_by_name<F: Fn(&Display) -> bool>(state: &State, name: *const c_char, filter: F) -> Result<*const std::os::raw::c_void> {
+    find_index_by_name(state, name, filter).map(|(gpu, index)| display_handle(gpu, index))
+}
+
+pub extern "C" fn NvAPI_GetAssociatedNvidiaDisplayHandle(szDisplayName: *const c_char, pNvDispHandle: *mut NvDisplayHandle) -> NvAPI_Status {
+    call(Api::NvAPI_GetAssociatedNvidiaDisplayHandle, |state| {
+        let handle = out(pNvDispHandle)?;
+        *handle = NvDisplayHandle::from_raw(find_by_name(state, szDisplayName, Display::active)?);
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_DISP_GetAssociatedUnAttachedNvidiaDisplayHandle(szDisplayName: *const c_char, pNvUnAttachedDispHandle: *mut NvUnAttachedDisplayHandle) -> NvAPI_Status {
+    call(Api::NvAPI_DISP_GetAssociatedUnAttachedNvidiaDisplayHandle, |state| {
+        let handle = out(pNvUnAttachedDispHandle)?;
+        *handle = NvUnAttachedDisplayHandle::from_raw(find_by_name(state, szDisplayName, unattached)?);
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GetLogicalGPUFromDisplay(hNvDisp: NvDisplayHandle, pLogicalGPU: *mut NvLogicalGpuHandle) -> NvAPI_Status {
+    call(Api::NvAPI_GetLogicalGPUFromDisplay, |state| {
+        let (gpu, _) = display(state, hNvDisp)?;
+        *out(pLogicalGPU)? = logical_handle_of(state, gpu)?;
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GetAssociatedNvidiaDisplayName(NvDispHandle: NvDisplayHandle, szDisplayName: *mut NvAPI_ShortString) -> NvAPI_Status {
+    call(Api::NvAPI_GetAssociatedNvidiaDisplayName, |state| {
+        let (_, display) = display(state, NvDispHandle)?;
+        write_str(out(szDisplayName)?, &display.name);
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GetUnAttachedAssociatedDisplayName(hNvUnAttachedDisp: NvUnAttachedDisplayHandle, szDisplayName: *mut NvAPI_ShortString) -> NvAPI_Status {
+    call(Api::NvAPI_GetUnAttachedAssociatedDisplayName, |state| {
+        let (_, display) = unattached_display(state, hNvUnAttachedDisp)?;
+        write_str(out(szDisplayName)?, &display.name);
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GetPhysicalGPUsFromDisplay(hNvDisp: NvDisplayHandle, nvGPUHandle: *mut [NvPhysicalGpuHandle; types::NVAPI_MAX_PHYSICAL_GPUS], pGpuCount: *mut u32) -> NvAPI_Status {
+    call(Api::NvAPI_GetPhysicalGPUsFromDisplay, |state| {
+        let (gpu, _) = display(state, hNvDisp)?;
+        // the GPU driving the output comes first, followed by the rest of its SLI group
+        let gpus: Vec<_> = std::iter::once(gpu).chain(state.system.logical_gpus().into_iter()
+            .filter(|group| group.contains(&gpu))
+            .flatten()
+            .filter(|&i| i != gpu)
+        ).collect();
+        let handles = out(nvGPUHandle)?;
+        let count = out(pGpuCount)?;
+        for (handle, &index) in handles.iter_mut().zip(&gpus) {
+            *handle = gpu_handle(state, index);
+        }
+        *count = gpus.len().min(types::NVAPI_MAX_PHYSICAL_GPUS) as u32;
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GetPhysicalGPUFromUnAttachedDisplay(hNvUnAttachedDisp: NvUnAttachedDisplayHandle, pPhysicalGpu: *mut NvPhysicalGpuHandle) -> NvAPI_Status {
+    call(Api::NvAPI_GetPhysicalGPUFromUnAttachedDisplay, |state| {
+        let (gpu, _) = unattached_display(state, hNvUnAttachedDisp)?;
+        *out(pPhysicalGpu)? = gpu_handle(state, gpu);
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_DISP_GetDisplayIdByDisplayName(displayName: *const c_char, displayId: *mut u32) -> NvAPI_Status {
+    call(Api::NvAPI_DISP_GetDisplayIdByDisplayName, |state| {
+        let id = out(displayId)?;
+        let (gpu, index) = find_index_by_name(state, displayName, |d| d.connected)?;
+        *id = state.system.gpus[gpu].displays[index].id;
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GPU_GetEDID(hPhysicalGpu: NvPhysicalGpuHandle, displayOutputId: u32, pEDID: *mut NV_EDID) -> NvAPI_Status {
+    call(Api::NvAPI_GPU_GetEDID, |state| {
+        let edid = out(pEDID)?;
+        check_version(edid.version, &[display::NV_EDID_VER1, display::NV_EDID_VER2, display::NV_EDID_VER3])?;
+        let gpu = gpu(state, hPhysicalGpu)?;
+        let display = gpu.displays.iter().find(|d| d.id == displayOutputId && d.connected)
+            .ok_or(Status::InvalidArgument)?;
+        if display.edid.is_empty() {
+            return Err(Status::DataNotFound)
+        }
+
+        // only the newest version can page past the first 256 bytes
+        let offset = match edid.version {
+            display::NV_EDID_VER3 => edid.offset as usize,
+            _ => 0,
+        };
+        let page = display.edid.get(offset..).filter(|page| !page.is_empty())
+            .ok_or(Status::InvalidArgument)?;
+        let len = page.len().min(edid.EDID_Data.len());
+        edid.EDID_Data = [0; display::NV_EDID_DATA_SIZE];
+        edid.EDID_Data[..len].copy_from_slice(&page[..len]);
+        if edid.version != display::NV_EDID_VER1 {
+            edid.sizeofEDID = display.edid.len() as _;
+        }
+        if edid.version == display::NV_EDID_VER3 {
+            edid.edidId = display.edid_id;
+        }
+        Ok(())
+    })
+}
+
+/// Overrides replace `Display::edid`, and connect the output once complete. The mock
+/// doesn't keep the EDID of an attached monitor apart from an override, so removing one
+/// leaves the output without an EDID.
+pub extern "C" fn NvAPI_GPU_SetEDID(hPhysicalGpu: NvPhysicalGpuHandle, displayOutputId: u32, pEDID: *mut NV_EDID) -> NvAPI_Status {
+    call(Api::NvAPI_GPU_SetEDID, |state| {
+        let edid = input(pEDID)?;
+        check_version(edid.version, &[display::NV_EDID_VER1, display::NV_EDID_VER2, display::NV_EDID_VER3])?;
+        let gpu = gpu(state, hPhysicalGpu)?;
+        let display = gpu.displays.iter_mut().find(|d| d.id == displayOutputId)
+            .ok_or(Status::InvalidArgument)?;
+
+        let (size, offset) = match edid.version {
+            display::NV_EDID_VER1 => (display::NV_EDID_DATA_SIZE.min((1 + edid.EDID_Data[126] as usize) * 128), 0),
+            display::NV_EDID_VER2 => (edid.sizeofEDID as usize, 0),
+            _ => (edid.sizeofEDID as usize, edid.offset as usize),
+        };
+        if size == 0 {
+            display.edid.clear();
+            display.edid_id += 1;
+            return Ok(())
+        }
+        // pages must be written in order, and only the newest version can page
+        if offset >= size || (offset != 0 && offset != display.edid.len()) || (edid.version != display::NV_EDID_VER3 && size > display::NV_EDID_DATA_SIZE) {
+            return Err(Status::InvalidArgument)
+        }
+
+        let page = &edid.EDID_Data[..(size - offset).min(display::NV_EDID_DATA_SIZE)];
+        display.edid.truncate(offset);
+        display.edid.extend_from_slice(page);
+        if display.edid.len() == size {
+            display.edid_id += 1;
+            display.connected = true;
+        }
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_DISP_GetDisplayConfig(pathInfoCount: *mut u32, pathInfo: *mut NV_DISPLAYCONFIG_PATH_INFO) -> NvAPI_Status {
+    call(Api::NvAPI_DISP_GetDisplayConfig, |state| {
+        let count = out(pathInfoCount)?;
+        let paths = &state.system.display_config;
+        if pathInfo.is_null() {
+            *count = paths.len() as _;
+            return Ok(())
+        }
+        if (*count as usize) < paths.len() {
+            *count = paths.len() as _;
+            return Err(Status::InsufficientBuffer)
+        }
+
+        let raw_paths = unsafe { std::slice::from_raw_parts_mut(pathInfo, paths.len()) };
+        for (raw, path) in raw_paths.iter_mut().zip(paths) {
+            check_version(raw.version, &[dispcontrol::NV_DISPLAYCONFIG_PATH_INFO_VER2])?;
+            raw.sourceId = path.source_id;
+            raw.flags = PathInfoFlags::empty().bits();
+            if let Some(source) = unsafe { raw.sourceModeInfo.as_mut() } {
+                source.resolution.width = path.width;
+                source.resolution.height = path.height;
+                source.resolution.colorDepth = path.color_depth;
+                source.colorFormat = ColorFormat::Unknown.raw();
+                source.position.x = path.position.0;
+                source.position.y = path.position.1;
+                source.spanningOrientation = SpanningOrientation::None.raw();
+                source.flags = if path.gdi_primary { SourceModeFlags::GDI_PRIMARY } else { SourceModeFlags::empty() }.bits();
+            }
+
+            // the second pass only asks for the target counts
+            if !raw.targetInfo.is_null() {
+                if (raw.targetInfoCount as usize) < path.targets.len() {
+                    raw.targetInfoCount = path.targets.len() as _;
+                    return Err(Status::InsufficientBuffer)
+                }
+                let raw_targets = unsafe { std::slice::from_raw_parts_mut(raw.targetInfo, path.targets.len()) };
+                for (raw, target) in raw_targets.iter_mut().zip(&path.targets) {
+                    raw.displayId = target.display_id;
+                    raw.targetId = 0;
+                    if let Some(details) = unsafe { raw.details.as_mut() } {
+                        check_version(details.version, &[dispcontrol::NV_DISPLAYCONFIG_PATH_ADVANCED_TARGET_INFO_VER1])?;
+                        details.rotation = target.rotation.raw();
+                        details.scaling = target.scaling.raw();
+                        details.refreshRate1K = target.refresh_rate;
+                        let mut flags = TargetInfoFlags::empty();
+                        flags.set(TargetInfoFlags::INTERLACED, target.interlaced);
+                        flags.set(TargetInfoFlags::PRIMARY, target.primary);
+                        details.flags = flags.bits();
+                        details.timingOverride = target.timing_override.raw();
+                        details.timing = target.timing;
+                    }
+                }
+            }
+            raw.targetInfoCount = path.targets.len() as _;
+        }
+        *count = paths.len() as _;
+        Ok(())
+    })
+}
+
+fn display_target(state: &State, raw: &dispcontrol::NV_DISPLAYCONFIG_PATH_TARGET_INFO, current: Option<&DisplayTarget>) -> Result<DisplayTarget> {
+    let connected = state.system.gpus.iter().flat_map(|gpu| &gpu.displays)
+        .any(|display| display.id == raw.displayId && display.connected);
+    if !connected {
+        return Err(Status::InvalidArgument)
+    }
+
+    let mut target = current.cloned().unwrap_or_else(|| DisplayTarget {
+        display_id: raw.displayId,
+        .. Default::default()
+    });
+    if let Some(details) = unsafe { raw.details.as_ref() } {
+        check_version(details.version, &[dispcontrol::NV_DISPLAYCONFIG_PATH_ADVANCED_TARGET_INFO_VER1])?;
+        let flags = TargetInfoFlags::from_bits(details.flags).ok_or(Status::InvalidArgument)?;
+        match Rotation::from_raw(details.rotation).map_err(|_| Status::InvalidArgument)? {
+            Rotation::Ignored => (),
+            rotation => target.rotation = rotation,
+        }
+        target.scaling = Scaling::from_raw(details.scaling).map_err(|_| Status::InvalidArgument)?;
+        if details.refreshRate1K != 0 {
+            target.refresh_rate = details.refreshRate1K;
+            target.interlaced = flags.contains(TargetInfoFlags::INTERLACED);
+        }
+        target.primary = flags.contains(TargetInfoFlags::PRIMARY);
+        target.timing_override = dispcontrol::TimingOverride::from_raw(details.timingOverride).map_err(|_| Status::InvalidArgument)?;
+        if target.timing_override == dispcontrol::TimingOverride::Custom {
+            target.timing = details.timing;
+        }
+    }
+    Ok(target)
+}
+
+/// Targets that leave out their details keep their current settings, as do sources
+/// without mode info. Displays left out of the config are removed from the desktop.
+pub extern "C" fn NvAPI_DISP_SetDisplayConfig(pathInfoCount: u32, pathInfo: *mut NV_DISPLAYCONFIG_PATH_INFO, flags: NV_DISPLAYCONFIG_FLAGS) -> NvAPI_Status {
+    call(Api::NvAPI_DISP_SetDisplayConfig, |state| {
+        let flags = DisplayConfigFlags::from_bits(flags).ok_or(Status::InvalidArgument)?;
+        input(pathInfo)?;
+        let raw_paths = unsafe { std::slice::from_raw_parts(pathInfo, pathInfoCount as usize) };
+        if raw_paths.is_empty() {
+            return Err(Status::InvalidArgument)
+        }
+
+        let current_target = |id: u32| state.system.display_config.iter().flat_map(|path| &path.targets)
+            .find(|target| target.display_id == id);
+        let mut paths = Vec::with_capacity(raw_paths.len());
+        for raw in raw_paths {
+            check_version(raw.version, &[dispcontrol::NV_DISPLAYCONFIG_PATH_INFO_VER2])?;
+            input(raw.targetInfo)?;
+            let raw_targets = unsafe { std::slice::from_raw_parts(raw.targetInfo, raw.targetInfoCount as usize) };
+            let targets = raw_targets.iter()
+                .map(|target| display_target(state, target, current_target(target.displayId)))
+                .collect::<Result<Vec<_>>>()?;
+            let first = targets.first().ok_or(Status::InvalidArgument)?.display_id;
+
+            let mut path = match unsafe { raw.sourceModeInfo.as_ref() } {
+                Some(source) => DisplayPath {
+                    source_id: raw.sourceId,
+                    width: source.resolution.width,
+                    height: source.resolution.height,
+                    color_depth: source.resolution.colorDepth,
+                    position: (source.position.x, source.position.y),
+                    gdi_primary: SourceModeFlags::from_bits_truncate(source.flags).contains(SourceModeFlags::GDI_PRIMARY),
+                    targets: Vec::new(),
+                },
+                None => state.system.display_config.iter()
+                    .find(|path| path.targets.iter().any(|target| target.display_id == first))
+                    .cloned().ok_or(Status::InvalidArgument)?,
+            };
+            if path.width == 0 || path.height == 0 {
+                return Err(Status::InvalidArgument)
+            }
+            path.source_id = raw.sourceId;
+            path.targets = targets;
+            paths.push(path);
+        }
+
+        let mut ids: Vec<_> = paths.iter().flat_map(|path| &path.targets).map(|target| target.display_id).collect();
+        let displays = ids.len();
+        ids.sort();
+        ids.dedup();
+        if ids.len() != displays || paths.iter().filter(|path| path.gdi_primary).count() > 1 {
+            return Err(Status::InvalidArgument)
+        }
+        if !paths.iter().any(|path| path.gdi_primary) {
+            paths[0].gdi_primary = true;
+        }
+        if paths.iter().all(|path| path.source_id == 0) {
+            for (source_id, path) in paths.iter_mut().enumerate() {
+                path.source_id = source_id as _;
+            }
+        }
+
+        if flags.contains(DisplayConfigFlags::VALIDATE_ONLY) {
+            return Ok(())
+        }
+        for display in state.system.gpus.iter_mut().flat_map(|gpu| &mut gpu.displays) {
+            display.flags.set(DisplayIdsFlags::ACTIVE, ids.contains(&display.id));
+        }
+        state.system.display_config = paths;
+        Ok(())
+    })
+}
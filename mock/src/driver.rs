@@ -0,0 +1,42 @@
+use nvapi_sys::{Api, Status, NvAPI_Status};
+use nvapi_sys::types::NvAPI_ShortString;
+use crate::call;
+use crate::util::{out, write_str};
+
+pub extern "C" fn NvAPI_Initialize() -> NvAPI_Status {
+    call(Api::NvAPI_Initialize, |state| {
+        state.refcount += 1;
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_Unload() -> NvAPI_Status {
+    call(Api::NvAPI_Unload, |state| {
+        state.refcount -= 1;
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GetErrorMessage(nr: NvAPI_Status, szDesc: *mut NvAPI_ShortString) -> NvAPI_Status {
+    call(Api::NvAPI_GetErrorMessage, |_| {
+        let desc = out(szDesc)?;
+        let status = Status::from_raw(nr).map_err(|_| Status::InvalidArgument)?;
+        write_str(desc, &format!("NVAPI_{:?}", status));
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GetInterfaceVersionString(szDesc: *mut NvAPI_ShortString) -> NvAPI_Status {
+    call(Api::NvAPI_GetInterfaceVersionString, |state| {
+        write_str(out(szDesc)?, &state.system.interface_version);
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_SYS_GetDriverAndBranchVersion(pDriverVersion: *mut u32, szBuildBranchString: *mut NvAPI_ShortString) -> NvAPI_Status {
+    call(Api::NvAPI_SYS_GetDriverAndBranchVersion, |state| {
+        *out(pDriverVersion)? = state.system.driver_version;
+        write_str(out(szBuildBranchString)?, &state.system.driver_branch);
+        Ok(())
+    })
+}
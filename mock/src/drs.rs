@@ -0,0 +1,612 @@
+use std::ptr;
+use nvapi_sys::{Api, Status, NvAPI_Status};
+use nvapi_sys::handles::{NvDRSSessionHandle, NvDRSProfileHandle};
+use nvapi_sys::types::{NvAPI_UnicodeString, GET_NVAPI_SIZE};
+use nvapi_sys::driversettings::*;
+use nvapi_sys::drs::{self, NVDRS_PROFILE, NVDRS_APPLICATION, NVDRS_SETTING, NVDRS_SETTING_UNION, GpuSupport, ApplicationFlags, SettingType, SettingLocation};
+use crate::{call, Result, State, DrsProfile, DrsApplication, DrsSetting, DrsValue, DRS_BASE_PROFILE};
+use crate::util::{out, input, check_version};
+
+/// Session handles are `SESSION_HANDLE_BASE + index`.
+const SESSION_HANDLE_BASE: usize = 0x30000;
+/// Profile handles are `PROFILE_HANDLE_BASE + (session << 16) + id`, ids are never reused
+/// within a session.
+const PROFILE_HANDLE_BASE: usize = 0x1000000;
+
+/// A snapshot of the saved settings, which only reach `System` once saved.
+#[derive(Debug, Default)]
+pub struct Session {
+    profiles: Vec<(usize, DrsProfile)>,
+    next_id: usize,
+    global: String,
+}
+
+impl Session {
+    fn add(&mut self, profile: DrsProfile) -> usize {
+        self.next_id += 1;
+        self.profiles.push((self.next_id, profile));
+        self.profiles.len() - 1
+    }
+
+    fn find(&self, name: &str) -> Option<usize> {
+        self.profiles.iter().position(|(_, profile)| profile.name.eq_ignore_ascii_case(name))
+    }
+}
+
+/// The settings the mock driver knows by name.
+const SETTING_NAMES: &[(u32, &str)] = &[
+    (VSYNCMODE_ID, VSYNCMODE_STRING),
+    (VSYNCTEARCONTROL_ID, VSYNCTEARCONTROL_STRING),
+    (FRL_FPS_ID, FRL_FPS_STRING),
+    (PRERENDERLIMIT_ID, PRERENDERLIMIT_STRING),
+    (LOW_LATENCY_MODE_ID, LOW_LATENCY_MODE_STRING),
+    (PREFERRED_PSTATE_ID, PREFERRED_PSTATE_STRING),
+    (QUALITY_ENHANCEMENTS_ID, QUALITY_ENHANCEMENTS_STRING),
+    (PS_TEXFILTER_NO_NEG_LODBIAS_ID, PS_TEXFILTER_NO_NEG_LODBIAS_STRING),
+    (PS_TEXFILTER_BILINEAR_IN_ANISO_ID, PS_TEXFILTER_BILINEAR_IN_ANISO_STRING),
+    (PS_TEXFILTER_ANISO_OPTS2_ID, PS_TEXFILTER_ANISO_OPTS2_STRING),
+    (ANISO_MODE_SELECTOR_ID, ANISO_MODE_SELECTOR_STRING),
+    (ANISO_MODE_LEVEL_ID, ANISO_MODE_LEVEL_STRING),
+    (OGL_THREAD_CONTROL_ID, OGL_THREAD_CONTROL_STRING),
+    (OGL_TRIPLE_BUFFER_ID, OGL_TRIPLE_BUFFER_STRING),
+    (VRR_APP_OVERRIDE_ID, VRR_APP_OVERRIDE_STRING),
+    (VRR_MODE_ID, VRR_MODE_STRING),
+    (REFRESH_RATE_OVERRIDE_ID, REFRESH_RATE_OVERRIDE_STRING),
+    (FXAA_ENABLE_ID, FXAA_ENABLE_STRING),
+    (MAXWELL_B_SAMPLE_INTERLEAVE_ID, MAXWELL_B_SAMPLE_INTERLEAVE_STRING),
+    (PS_SHADERDISKCACHE_ID, PS_SHADERDISKCACHE_STRING),
+];
+
+fn setting_name(id: u32) -> Option<&'static str> {
+    SETTING_NAMES.iter().find(|&&(known, _)| known == id).map(|&(_, name)| name)
+}
+
+fn session_index(state: &State, handle: NvDRSSessionHandle) -> Result<usize> {
+    (handle.as_ptr() as usize).checked_sub(SESSION_HANDLE_BASE)
+        .filter(|&index| matches!(state.drs_sessions.get(index), Some(Some(..))))
+        .ok_or(Status::InvalidHandle)
+}
+
+fn session(state: &mut State, handle: NvDRSSessionHandle) -> Result<&mut Session> {
+    let index = session_index(state, handle)?;
+    Ok(state.drs_sessions[index].as_mut().unwrap())
+}
+
+/// The session and the index of the profile `hProfile` refers to within it.
+fn profile(state: &mut State, hSession: NvDRSSessionHandle, hProfile: NvDRSProfileHandle) -> Result<(&mut Session, usize)> {
+    let index = session_index(state, hSession)?;
+    let offset = (hProfile.as_ptr() as usize).checked_sub(PROFILE_HANDLE_BASE).ok_or(Status::InvalidHandle)?;
+    if offset >> 16 != index {
+        return Err(Status::InvalidHandle)
+    }
+    let session = state.drs_sessions[index].as_mut().unwrap();
+    let profile = session.profiles.iter().position(|&(id, _)| id == offset & 0xffff)
+        .ok_or(Status::ProfileRemoved)?;
+    Ok((session, profile))
+}
+
+fn profile_handle(session: usize, id: usize) -> NvDRSProfileHandle {
+    NvDRSProfileHandle::from_raw((PROFILE_HANDLE_BASE + (session << 16) + id) as *const _)
+}
+
+fn handle_of(state: &State, hSession: NvDRSSessionHandle, profile: usize) -> Result<NvDRSProfileHandle> {
+    let index = session_index(state, hSession)?;
+    let (id, _) = state.drs_sessions[index].as_ref().unwrap().profiles[profile];
+    Ok(profile_handle(index, id))
+}
+
+fn read_unicode(str: &[u16]) -> String {
+    let len = str.iter().position(|&c| c == 0).unwrap_or(str.len());
+    String::from_utf16_lossy(&str[..len])
+}
+
+fn write_unicode(dest: &mut [u16], str: &str) {
+    for d in dest.iter_mut() {
+        *d = 0;
+    }
+    let len = dest.len() - 1;
+    for (d, s) in dest[..len].iter_mut().zip(str.encode_utf16()) {
+        *d = s;
+    }
+}
+
+fn read_name(name: *const NvAPI_UnicodeString) -> Result<String> {
+    input(name).map(|name| read_unicode(name))
+}
+
+fn restore(profile: &mut DrsProfile) {
+    profile.applications.retain(|app| app.predefined);
+    profile.settings.retain(|setting| setting.predefined.is_some());
+    for setting in &mut profile.settings {
+        setting.value = setting.predefined.clone().unwrap();
+    }
+}
+
+/// Applications are passed as arrays of any version, so only the fields that version
+/// has may be accessed.
+unsafe fn application_version(raw: *const NVDRS_APPLICATION) -> Result<u32> {
+    let version = ptr::addr_of!((*raw).v3.v2.v1.version).read();
+    check_version(version, &[drs::NVDRS_APPLICATION_VER_V1, drs::NVDRS_APPLICATION_VER_V2, drs::NVDRS_APPLICATION_VER_V3, drs::NVDRS_APPLICATION_VER_V4])?;
+    Ok(version)
+}
+
+unsafe fn read_application(raw: *const NVDRS_APPLICATION) -> Result<DrsApplication> {
+    let version = application_version(raw)?;
+    let v1 = &*ptr::addr_of!((*raw).v3.v2.v1);
+    let mut app = DrsApplication {
+        name: read_unicode(&v1.appName),
+        friendly_name: read_unicode(&v1.userFriendlyName),
+        launcher: read_unicode(&v1.launcher),
+        .. Default::default()
+    };
+    if version >= drs::NVDRS_APPLICATION_VER_V2 {
+        app.file_in_folder = read_unicode(&*ptr::addr_of!((*raw).v3.v2.fileInFolder));
+    }
+    if version >= drs::NVDRS_APPLICATION_VER_V3 {
+        let flags = ApplicationFlags::from_bits_truncate(ptr::addr_of!((*raw).v3.flags).read());
+        app.metro = flags.contains(ApplicationFlags::METRO);
+        if flags.contains(ApplicationFlags::COMMAND_LINE) && version >= drs::NVDRS_APPLICATION_VER_V4 {
+            app.command_line = Some(read_unicode(&*ptr::addr_of!((*raw).commandLine)));
+        }
+    }
+    Ok(app)
+}
+
+unsafe fn write_application(raw: *mut NVDRS_APPLICATION, app: &DrsApplication) -> Result<()> {
+    let version = application_version(raw)?;
+    let v1 = &mut *ptr::addr_of_mut!((*raw).v3.v2.v1);
+    v1.isPredefined = app.predefined as _;
+    write_unicode(&mut v1.appName, &app.name);
+    write_unicode(&mut v1.userFriendlyName, &app.friendly_name);
+    write_unicode(&mut v1.launcher, &app.launcher);
+    if version >= drs::NVDRS_APPLICATION_VER_V2 {
+        write_unicode(&mut *ptr::addr_of_mut!((*raw).v3.v2.fileInFolder), &app.file_in_folder);
+    }
+    if version >= drs::NVDRS_APPLICATION_VER_V3 {
+        let mut flags = ApplicationFlags::empty();
+        flags.set(ApplicationFlags::METRO, app.metro);
+        flags.set(ApplicationFlags::COMMAND_LINE, app.command_line.is_some());
+        ptr::addr_of_mut!((*raw).v3.flags).write(flags.bits());
+    }
+    if version >= drs::NVDRS_APPLICATION_VER_V4 {
+        write_unicode(&mut *ptr::addr_of_mut!((*raw).commandLine), app.command_line.as_deref().unwrap_or_default());
+    }
+    Ok(())
+}
+
+/// Whether `name`, which may be a full path, refers to `app`.
+fn matches_application(app: &DrsApplication, name: &str) -> bool {
+    let file = name.rsplit(['\\', '/']).next().unwrap_or(name);
+    app.name.eq_ignore_ascii_case(name) || app.name.eq_ignore_ascii_case(file)
+}
+
+fn read_value(ty: drs::NVDRS_SETTING_TYPE, raw: &NVDRS_SETTING_UNION) -> Result<DrsValue> {
+    Ok(match SettingType::from_raw(ty).map_err(|_| Status::InvalidArgument)? {
+        SettingType::Dword => DrsValue::Dword(unsafe { raw.u32Value }),
+        SettingType::Binary => {
+            let binary = unsafe { &raw.binaryValue };
+            let data = binary.valueData.get(..binary.valueLength as usize).ok_or(Status::SettingSizeTooLarge)?;
+            DrsValue::Binary(data.to_vec())
+        },
+        _ => DrsValue::String(read_unicode(unsafe { &raw.wszValue })),
+    })
+}
+
+fn write_value(raw: &mut NVDRS_SETTING_UNION, value: &DrsValue) {
+    *raw = NVDRS_SETTING_UNION::zeroed();
+    match *value {
+        DrsValue::Dword(value) => raw.u32Value = value,
+        DrsValue::Binary(ref data) => unsafe {
+            raw.binaryValue.valueLength = data.len() as _;
+            raw.binaryValue.valueData[..data.len()].copy_from_slice(data);
+        },
+        DrsValue::String(ref str) => write_unicode(unsafe { &mut raw.wszValue }, str),
+    }
+}
+
+fn value_type(value: &DrsValue) -> SettingType {
+    match *value {
+        DrsValue::Dword(..) => SettingType::Dword,
+        DrsValue::Binary(..) => SettingType::Binary,
+        DrsValue::String(..) => SettingType::WString,
+    }
+}
+
+fn write_setting(raw: &mut NVDRS_SETTING, setting: &DrsSetting, location: SettingLocation) -> Result<()> {
+    check_version(raw.version, &[drs::NVDRS_SETTING_VER1])?;
+    write_unicode(&mut raw.settingName, setting_name(setting.id).unwrap_or_default());
+    raw.settingId = setting.id;
+    raw.settingType = value_type(&setting.value).raw();
+    raw.settingLocation = location.raw();
+    raw.isCurrentPredefined = (setting.predefined.as_ref() == Some(&setting.value)) as _;
+    raw.isPredefinedValid = setting.predefined.is_some() as _;
+    write_value(&mut raw.predefined, setting.predefined.as_ref().unwrap_or(&setting.value));
+    write_value(&mut raw.current, &setting.value);
+    Ok(())
+}
+
+pub extern "C" fn NvAPI_DRS_CreateSession(phSession: *mut NvDRSSessionHandle) -> NvAPI_Status {
+    call(Api::NvAPI_DRS_CreateSession, |state| {
+        let handle = out(phSession)?;
+        state.drs_sessions.push(Some(Default::default()));
+        *handle = NvDRSSessionHandle::from_raw((SESSION_HANDLE_BASE + state.drs_sessions.len() - 1) as *const _);
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_DRS_DestroySession(hSession: NvDRSSessionHandle) -> NvAPI_Status {
+    call(Api::NvAPI_DRS_DestroySession, |state| {
+        let index = session_index(state, hSession)?;
+        state.drs_sessions[index] = None;
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_DRS_LoadSettings(hSession: NvDRSSessionHandle) -> NvAPI_Status {
+    call(Api::NvAPI_DRS_LoadSettings, |state| {
+        let (profiles, global) = (state.system.drs_profiles.clone(), state.system.drs_global_profile.clone());
+        let session = session(state, hSession)?;
+        session.profiles.clear();
+        for profile in profiles {
+            session.add(profile);
+        }
+        session.global = global;
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_DRS_SaveSettings(hSession: NvDRSSessionHandle) -> NvAPI_Status {
+    call(Api::NvAPI_DRS_SaveSettings, |state| {
+        let session = session(state, hSession)?;
+        let profiles = session.profiles.iter().map(|(_, profile)| profile.clone()).collect();
+        let global = session.global.clone();
+        state.system.drs_profiles = profiles;
+        state.system.drs_global_profile = global;
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_DRS_CreateProfile(hSession: NvDRSSessionHandle, pProfileInfo: *mut NVDRS_PROFILE, phProfile: *mut NvDRSProfileHandle) -> NvAPI_Status {
+    call(Api::NvAPI_DRS_CreateProfile, |state| {
+        let info = input(pProfileInfo)?;
+        let handle = out(phProfile)?;
+        check_version(info.version, &[drs::NVDRS_PROFILE_VER1])?;
+        let name = read_unicode(&info.profileName);
+        let session = session(state, hSession)?;
+        if name.is_empty() {
+            return Err(Status::ProfileNameEmpty)
+        }
+        if session.find(&name).is_some() {
+            return Err(Status::ProfileNameInUse)
+        }
+
+        let profile = session.add(DrsProfile {
+            gpu_support: GpuSupport::from_bits_truncate(info.gpuSupport),
+            .. DrsProfile::new(&name)
+        });
+        *handle = handle_of(state, hSession, profile)?;
+        Ok(())
+    })
+}
+
+/// Predefined profiles are restored rather than removed.
+pub extern "C" fn NvAPI_DRS_DeleteProfile(hSession: NvDRSSessionHandle, hProfile: NvDRSProfileHandle) -> NvAPI_Status {
+    call(Api::NvAPI_DRS_DeleteProfile, |state| {
+        let (session, index) = profile(state, hSession, hProfile)?;
+        match session.profiles[index].1.predefined {
+            true => restore(&mut session.profiles[index].1),
+            false => drop(session.profiles.remove(index)),
+        }
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_DRS_SetCurrentGlobalProfile(hSession: NvDRSSessionHandle, wszGlobalProfileName: *const NvAPI_UnicodeString) -> NvAPI_Status {
+    call(Api::NvAPI_DRS_SetCurrentGlobalProfile, |state| {
+        let name = read_name(wszGlobalProfileName)?;
+        let session = session(state, hSession)?;
+        let index = session.find(&name).ok_or(Status::ProfileNotFound)?;
+        session.global = session.profiles[index].1.name.clone();
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_DRS_GetCurrentGlobalProfile(hSession: NvDRSSessionHandle, phProfile: *mut NvDRSProfileHandle) -> NvAPI_Status {
+    call(Api::NvAPI_DRS_GetCurrentGlobalProfile, |state| {
+        let handle = out(phProfile)?;
+        let session = session(state, hSession)?;
+        let index = session.find(&session.global.clone()).ok_or(Status::ProfileNotFound)?;
+        *handle = handle_of(state, hSession, index)?;
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_DRS_GetBaseProfile(hSession: NvDRSSessionHandle, phProfile: *mut NvDRSProfileHandle) -> NvAPI_Status {
+    call(Api::NvAPI_DRS_GetBaseProfile, |state| {
+        let handle = out(phProfile)?;
+        let index = session(state, hSession)?.find(DRS_BASE_PROFILE).ok_or(Status::ProfileNotFound)?;
+        *handle = handle_of(state, hSession, index)?;
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_DRS_GetProfileInfo(hSession: NvDRSSessionHandle, hProfile: NvDRSProfileHandle, pProfileInfo: *mut NVDRS_PROFILE) -> NvAPI_Status {
+    call(Api::NvAPI_DRS_GetProfileInfo, |state| {
+        let info = out(pProfileInfo)?;
+        check_version(info.version, &[drs::NVDRS_PROFILE_VER1])?;
+        let (session, index) = profile(state, hSession, hProfile)?;
+        let profile = &session.profiles[index].1;
+        write_unicode(&mut info.profileName, &profile.name);
+        info.gpuSupport = profile.gpu_support.bits();
+        info.isPredefined = profile.predefined as _;
+        info.numOfApps = profile.applications.len() as _;
+        info.numOfSettings = profile.settings.len() as _;
+        Ok(())
+    })
+}
+
+/// Only the GPU support flags can be changed.
+pub extern "C" fn NvAPI_DRS_SetProfileInfo(hSession: NvDRSSessionHandle, hProfile: NvDRSProfileHandle, pProfileInfo: *mut NVDRS_PROFILE) -> NvAPI_Status {
+    call(Api::NvAPI_DRS_SetProfileInfo, |state| {
+        let info = input(pProfileInfo)?;
+        check_version(info.version, &[drs::NVDRS_PROFILE_VER1])?;
+        let (session, index) = profile(state, hSession, hProfile)?;
+        session.profiles[index].1.gpu_support = GpuSupport::from_bits_truncate(info.gpuSupport);
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_DRS_FindProfileByName(hSession: NvDRSSessionHandle, profileName: *const NvAPI_UnicodeString, phProfile: *mut NvDRSProfileHandle) -> NvAPI_Status {
+    call(Api::NvAPI_DRS_FindProfileByName, |state| {
+        let name = read_name(profileName)?;
+        let handle = out(phProfile)?;
+        let index = session(state, hSession)?.find(&name).ok_or(Status::ProfileNotFound)?;
+        *handle = handle_of(state, hSession, index)?;
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_DRS_EnumProfiles(hSession: NvDRSSessionHandle, index: u32, phProfile: *mut NvDRSProfileHandle) -> NvAPI_Status {
+    call(Api::NvAPI_DRS_EnumProfiles, |state| {
+        let handle = out(phProfile)?;
+        if index as usize >= session(state, hSession)?.profiles.len() {
+            return Err(Status::EndEnumeration)
+        }
+        *handle = handle_of(state, hSession, index as usize)?;
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_DRS_GetNumProfiles(hSession: NvDRSSessionHandle, numProfiles: *mut u32) -> NvAPI_Status {
+    call(Api::NvAPI_DRS_GetNumProfiles, |state| {
+        *out(numProfiles)? = session(state, hSession)?.profiles.len() as _;
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_DRS_CreateApplication(hSession: NvDRSSessionHandle, hProfile: NvDRSProfileHandle, pApplication: *mut NVDRS_APPLICATION) -> NvAPI_Status {
+    call(Api::NvAPI_DRS_CreateApplication, |state| {
+        input(pApplication)?;
+        let app = unsafe { read_application(pApplication)? };
+        let (session, index) = profile(state, hSession, hProfile)?;
+        if app.name.is_empty() {
+            return Err(Status::InvalidArgument)
+        }
+        let in_use = session.profiles.iter().flat_map(|(_, profile)| &profile.applications)
+            .any(|existing| existing.name.eq_ignore_ascii_case(&app.name) && existing.file_in_folder == app.file_in_folder && existing.command_line == app.command_line);
+        if in_use {
+            return Err(Status::ExecutableAlreadyInUse)
+        }
+        session.profiles[index].1.applications.push(app);
+        Ok(())
+    })
+}
+
+fn delete_application(state: &mut State, hSession: NvDRSSessionHandle, hProfile: NvDRSProfileHandle, name: &str) -> Result<()> {
+    let (session, index) = profile(state, hSession, hProfile)?;
+    let applications = &mut session.profiles[index].1.applications;
+    let app = applications.iter().position(|app| app.name.eq_ignore_ascii_case(name))
+        .ok_or(Status::ExecutableNotFound)?;
+    applications.remove(app);
+    Ok(())
+}
+
+pub extern "C" fn NvAPI_DRS_DeleteApplicationEx(hSession: NvDRSSessionHandle, hProfile: NvDRSProfileHandle, pApp: *mut NVDRS_APPLICATION) -> NvAPI_Status {
+    call(Api::NvAPI_DRS_DeleteApplicationEx, |state| {
+        input(pApp)?;
+        let app = unsafe { read_application(pApp)? };
+        delete_application(state, hSession, hProfile, &app.name)
+    })
+}
+
+pub extern "C" fn NvAPI_DRS_DeleteApplication(hSession: NvDRSSessionHandle, hProfile: NvDRSProfileHandle, appName: *const NvAPI_UnicodeString) -> NvAPI_Status {
+    call(Api::NvAPI_DRS_DeleteApplication, |state| {
+        let name = read_name(appName)?;
+        delete_application(state, hSession, hProfile, &name)
+    })
+}
+
+pub extern "C" fn NvAPI_DRS_GetApplicationInfo(hSession: NvDRSSessionHandle, hProfile: NvDRSProfileHandle, appName: *const NvAPI_UnicodeString, pApplication: *mut NVDRS_APPLICATION) -> NvAPI_Status {
+    call(Api::NvAPI_DRS_GetApplicationInfo, |state| {
+        let name = read_name(appName)?;
+        out(pApplication)?;
+        let (session, index) = profile(state, hSession, hProfile)?;
+        let app = session.profiles[index].1.applications.iter().find(|app| app.name.eq_ignore_ascii_case(&name))
+            .ok_or(Status::ExecutableNotFound)?;
+        unsafe { write_application(pApplication, app) }
+    })
+}
+
+pub extern "C" fn NvAPI_DRS_EnumApplications(hSession: NvDRSSessionHandle, hProfile: NvDRSProfileHandle, startIndex: u32, appCount: *mut u32, pApplication: *mut NVDRS_APPLICATION) -> NvAPI_Status {
+    call(Api::NvAPI_DRS_EnumApplications, |state| {
+        let count = out(appCount)?;
+        out(pApplication)?;
+        let version = unsafe { application_version(pApplication)? };
+        let (session, index) = profile(state, hSession, hProfile)?;
+        let applications = session.profiles[index].1.applications.get(startIndex as usize..)
+            .filter(|apps| !apps.is_empty())
+            .ok_or(Status::EndEnumeration)?;
+
+        let applications = &applications[..applications.len().min(*count as usize)];
+        for (i, app) in applications.iter().enumerate() {
+            unsafe {
+                let raw = (pApplication as *mut u8).add(i * GET_NVAPI_SIZE(version)) as *mut NVDRS_APPLICATION;
+                write_application(raw, app)?;
+            }
+        }
+        *count = applications.len() as _;
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_DRS_FindApplicationByName(hSession: NvDRSSessionHandle, appName: *const NvAPI_UnicodeString, phProfile: *mut NvDRSProfileHandle, pApplication: *mut NVDRS_APPLICATION) -> NvAPI_Status {
+    call(Api::NvAPI_DRS_FindApplicationByName, |state| {
+        let name = read_name(appName)?;
+        let handle = out(phProfile)?;
+        out(pApplication)?;
+        let session = session(state, hSession)?;
+        let (index, app) = session.profiles.iter().enumerate()
+            .flat_map(|(index, (_, profile))| profile.applications.iter().map(move |app| (index, app)))
+            .find(|&(_, app)| matches_application(app, &name))
+            .ok_or(Status::ExecutableNotFound)?;
+        unsafe { write_application(pApplication, app)? };
+        *handle = handle_of(state, hSession, index)?;
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_DRS_SetSetting(hSession: NvDRSSessionHandle, hProfile: NvDRSProfileHandle, pSetting: *mut NVDRS_SETTING) -> NvAPI_Status {
+    call(Api::NvAPI_DRS_SetSetting, |state| {
+        let raw = input(pSetting)?;
+        check_version(raw.version, &[drs::NVDRS_SETTING_VER1])?;
+        let value = read_value(raw.settingType, &raw.current)?;
+        let (session, index) = profile(state, hSession, hProfile)?;
+        let settings = &mut session.profiles[index].1.settings;
+        match settings.iter_mut().find(|setting| setting.id == raw.settingId) {
+            Some(setting) if value_type(&setting.value) != value_type(&value) => return Err(Status::DatatypeMismatch),
+            Some(setting) => setting.value = value,
+            None => settings.push(DrsSetting {
+                id: raw.settingId,
+                value,
+                predefined: None,
+            }),
+        }
+        Ok(())
+    })
+}
+
+/// Settings missing from a profile are inherited from the global profile, then the base profile.
+pub extern "C" fn NvAPI_DRS_GetSetting(hSession: NvDRSSessionHandle, hProfile: NvDRSProfileHandle, settingId: u32, pSetting: *mut NVDRS_SETTING) -> NvAPI_Status {
+    call(Api::NvAPI_DRS_GetSetting, |state| {
+        let raw = out(pSetting)?;
+        let (session, index) = profile(state, hSession, hProfile)?;
+        let find = |index: Option<usize>| index
+            .and_then(|index| session.profiles[index].1.settings.iter().find(|setting| setting.id == settingId));
+        let global = session.find(&session.global).filter(|&global| session.profiles[global].1.name != DRS_BASE_PROFILE);
+        let (setting, location) = find(Some(index)).map(|setting| (setting, SettingLocation::CurrentProfile))
+            .or_else(|| find(global).map(|setting| (setting, SettingLocation::GlobalProfile)))
+            .or_else(|| find(session.find(DRS_BASE_PROFILE)).map(|setting| (setting, SettingLocation::BaseProfile)))
+            .ok_or(Status::SettingNotFound)?;
+        write_setting(raw, setting, location)
+    })
+}
+
+pub extern "C" fn NvAPI_DRS_EnumSettings(hSession: NvDRSSessionHandle, hProfile: NvDRSProfileHandle, startIndex: u32, settingsCount: *mut u32, pSetting: *mut NVDRS_SETTING) -> NvAPI_Status {
+    call(Api::NvAPI_DRS_EnumSettings, |state| {
+        let count = out(settingsCount)?;
+        out(pSetting)?;
+        let (session, index) = profile(state, hSession, hProfile)?;
+        let settings = session.profiles[index].1.settings.get(startIndex as usize..)
+            .filter(|settings| !settings.is_empty())
+            .ok_or(Status::EndEnumeration)?;
+
+        let settings = &settings[..settings.len().min(*count as usize)];
+        let raw = unsafe { std::slice::from_raw_parts_mut(pSetting, settings.len()) };
+        for (raw, setting) in raw.iter_mut().zip(settings) {
+            write_setting(raw, setting, SettingLocation::CurrentProfile)?;
+        }
+        *count = settings.len() as _;
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_DRS_EnumAvailableSettingIds(pSettingIds: *mut u32, pMaxCount: *mut u32) -> NvAPI_Status {
+    call(Api::NvAPI_DRS_EnumAvailableSettingIds, |_| {
+        let count = out(pMaxCount)?;
+        out(pSettingIds)?;
+        let ids = unsafe { std::slice::from_raw_parts_mut(pSettingIds, *count as usize) };
+        for (id, &(known, _)) in ids.iter_mut().zip(SETTING_NAMES) {
+            *id = known;
+        }
+        *count = SETTING_NAMES.len().min(ids.len()) as _;
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_DRS_GetSettingIdFromName(settingName: *const NvAPI_UnicodeString, pSettingId: *mut u32) -> NvAPI_Status {
+    call(Api::NvAPI_DRS_GetSettingIdFromName, |_| {
+        let name = read_name(settingName)?;
+        let id = out(pSettingId)?;
+        *id = SETTING_NAMES.iter().find(|&&(_, known)| known == name)
+            .map(|&(id, _)| id)
+            .ok_or(Status::SettingNotFound)?;
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_DRS_GetSettingNameFromId(settingId: u32, pSettingName: *mut NvAPI_UnicodeString) -> NvAPI_Status {
+    call(Api::NvAPI_DRS_GetSettingNameFromId, |_| {
+        let name = out(pSettingName)?;
+        write_unicode(name, setting_name(settingId).ok_or(Status::SettingNotFound)?);
+        Ok(())
+    })
+}
+
+/// Predefined settings are restored rather than removed.
+fn restore_setting(state: &mut State, hSession: NvDRSSessionHandle, hProfile: NvDRSProfileHandle, settingId: u32) -> Result<()> {
+    let (session, index) = profile(state, hSession, hProfile)?;
+    let settings = &mut session.profiles[index].1.settings;
+    let setting = settings.iter().position(|setting| setting.id == settingId)
+        .ok_or(Status::SettingNotFound)?;
+    match settings[setting].predefined.clone() {
+        Some(predefined) => settings[setting].value = predefined,
+        None => drop(settings.remove(setting)),
+    }
+    Ok(())
+}
+
+pub extern "C" fn NvAPI_DRS_DeleteProfileSetting(hSession: NvDRSSessionHandle, hProfile: NvDRSProfileHandle, settingId: u32) -> NvAPI_Status {
+    call(Api::NvAPI_DRS_DeleteProfileSetting, |state| {
+        restore_setting(state, hSession, hProfile, settingId)
+    })
+}
+
+pub extern "C" fn NvAPI_DRS_RestoreAllDefaults(hSession: NvDRSSessionHandle) -> NvAPI_Status {
+    call(Api::NvAPI_DRS_RestoreAllDefaults, |state| {
+        let session = session(state, hSession)?;
+        session.profiles.retain(|(_, profile)| profile.predefined);
+        for (_, profile) in &mut session.profiles {
+            restore(profile);
+        }
+        session.global = DRS_BASE_PROFILE.into();
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_DRS_RestoreProfileDefault(hSession: NvDRSSessionHandle, hProfile: NvDRSProfileHandle) -> NvAPI_Status {
+    call(Api::NvAPI_DRS_RestoreProfileDefault, |state| {
+        let (session, index) = profile(state, hSession, hProfile)?;
+        if !session.profiles[index].1.predefined {
+            session.profiles.remove(index);
+            return Err(Status::ProfileRemoved)
+        }
+        restore(&mut session.profiles[index].1);
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_DRS_RestoreProfileDefaultSetting(hSession: NvDRSSessionHandle, hProfile: NvDRSProfileHandle, settingId: u32) -> NvAPI_Status {
+    call(Api::NvAPI_DRS_RestoreProfileDefaultSetting, |state| {
+        restore_setting(state, hSession, hProfile, settingId)
+    })
+}
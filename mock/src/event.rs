@@ -0,0 +1,47 @@
+use nvapi_sys::{Api, Status, NvAPI_Status};
+use nvapi_sys::handles::NvEventHandle;
+use nvapi_sys::event::{self, NV_EVENT_REGISTER_CALLBACK, NVAPI_CALLBACK_QSYNCEVENT, EventType};
+use crate::call;
+use crate::util::{out, input, check_version};
+
+/// The handle shared by every callback in the process, as with the driver.
+const EVENT_HANDLE: usize = 0x50000;
+
+/// A registered callback, along with the parameter it is handed back.
+#[derive(Debug, Copy, Clone)]
+pub struct Registration {
+    pub callback: NVAPI_CALLBACK_QSYNCEVENT,
+    pub param: usize,
+}
+
+pub extern "C" fn NvAPI_Event_RegisterCallback(eventCallback: *mut NV_EVENT_REGISTER_CALLBACK, phClient: *mut NvEventHandle) -> NvAPI_Status {
+    call(Api::NvAPI_Event_RegisterCallback, |state| {
+        let raw = input(eventCallback)?;
+        check_version(raw.version, &[event::NV_EVENT_REGISTER_CALLBACK_VERSION])?;
+        let handle = out(phClient)?;
+        // only sync events come with a callback signature
+        match EventType::from_raw(raw.eventId) {
+            Ok(EventType::QSync) => (),
+            _ => return Err(Status::InvalidArgument),
+        }
+        let callback = raw.nvCallBackFunc.ok_or(Status::InvalidArgument)?;
+
+        state.event_callbacks.push(Registration {
+            callback,
+            param: raw.callbackParam as usize,
+        });
+        *handle = NvEventHandle::from_raw(EVENT_HANDLE as *const _);
+        Ok(())
+    })
+}
+
+/// Removes every callback registered under the handle.
+pub extern "C" fn NvAPI_Event_UnregisterCallback(hClient: NvEventHandle) -> NvAPI_Status {
+    call(Api::NvAPI_Event_UnregisterCallback, |state| {
+        if hClient.as_ptr() as usize != EVENT_HANDLE || state.event_callbacks.is_empty() {
+            return Err(Status::InvalidArgument)
+        }
+        state.event_callbacks.clear();
+        Ok(())
+    })
+}
@@ -0,0 +1,1179 @@
+use nvapi_sys::{Api, Status, NvAPI_Status};
+use nvapi_sys::handles::{NvPhysicalGpuHandle, NvLogicalGpuHandle};
+use nvapi_sys::types::{self, BoolU32, NvAPI_ShortString};
+use nvapi_sys::gpu::{self, NV_SYSTEM_TYPE, NVAPI_GPU_PERF_DECREASE};
+use nvapi_sys::gpu::private::{NV_GPU_RAM_TYPE, NV_GPU_RAM_MAKER, NV_GPU_FOUNDRY};
+use nvapi_sys::gpu::clock::{self, PublicClockId, ClockFrequencyType};
+use nvapi_sys::gpu::clock::private::{
+    NV_USAGES_INFO, NV_CLOCKS_INFO,
+    NV_GPU_CLOCK_CLIENT_CLK_VF_POINTS_INFO, NV_GPU_CLOCK_CLIENT_CLK_VF_POINTS_CONTROL,
+    NV_GPU_CLOCK_CLIENT_CLK_DOMAINS_INFO, NV_GPU_PERF_CLIENT_LIMITS, ClockLockMode,
+};
+use nvapi_sys::gpu::pstate::{self, NV_GPU_PERF_PSTATE_ID, PstateId, PstateClockType, VoltageInfoDomain};
+use nvapi_sys::gpu::power::private::{
+    NV_GPU_CLIENT_VOLT_RAILS_STATUS, NV_GPU_CLIENT_VOLT_RAILS_CONTROL, NV_GPU_CLOCK_CLIENT_CLK_VF_POINTS_STATUS,
+    NV_GPU_POWER_INFO, NV_GPU_POWER_STATUS, NV_GPU_POWER_TOPO, NV_GPU_PERF_INFO, NV_GPU_PERF_STATUS,
+    NV_VOLT_STATUS, NV_VOLT_TABLE,
+};
+use nvapi_sys::gpu::thermal::{self, NV_GPU_THERMAL_SETTINGS};
+use nvapi_sys::gpu::ecc;
+use nvapi_sys::gpu::thermal::private::{NV_GPU_THERMAL_INFO, NV_GPU_CLIENT_THERMAL_POLICIES_STATUS};
+use nvapi_sys::gpu::cooler::private::{
+    self as cooler, NV_GPU_COOLER_SETTINGS, NV_GPU_SETCOOLER_LEVEL, NV_GPU_COOLER_POLICY_TABLE,
+    NV_COOLER_POLICY, CoolerPolicy, CoolerActivityLevel,
+};
+use nvapi_sys::driverapi::{self, NV_DISPLAY_DRIVER_MEMORY_INFO, NV_GPU_MEMORY_INFO_EX_V1};
+use crate::{call, model, Result};
+use crate::util::{gpu, gpu_index, gpu_handle, logical_gpu, logical_handle, logical_handle_of, out, input, check_version, write_str, set_bit, get_bit};
+
+/// Graphics VF points occupy the first 80 mask bits, memory points follow.
+const VFP_GRAPHICS_MAX: usize = 80;
+const VFP_MEMORY_MAX: usize = 23;
+
+fn with_gpu<F: FnOnce(&mut model::VirtualGpu) -> Result<()>>(api: Api, handle: NvPhysicalGpuHandle, f: F) -> NvAPI_Status {
+    call(api, |state| gpu(state, handle).and_then(f))
+}
+
+fn write_u32(api: Api, handle: NvPhysicalGpuHandle, ptr: *mut u32, f: fn(&model::VirtualGpu) -> u32) -> NvAPI_Status {
+    with_gpu(api, handle, |gpu| {
+        *out(ptr)? = f(gpu);
+        Ok(())
+    })
+}
+
+fn write_string(api: Api, handle: NvPhysicalGpuHandle, ptr: *mut NvAPI_ShortString, f: fn(&model::VirtualGpu) -> &str) -> NvAPI_Status {
+    with_gpu(api, handle, |gpu| {
+        write_str(out(ptr)?, f(gpu));
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_EnumPhysicalGPUs(nvGPUHandle: *mut [NvPhysicalGpuHandle; types::NVAPI_MAX_PHYSICAL_GPUS], pGpuCount: *mut u32) -> NvAPI_Status {
+    call(Api::NvAPI_EnumPhysicalGPUs, |state| {
+        let handles = out(nvGPUHandle)?;
+        let count = out(pGpuCount)?;
+        let gpus = state.system.gpus.len().min(types::NVAPI_MAX_PHYSICAL_GPUS);
+        if gpus == 0 {
+            return Err(Status::NvidiaDeviceNotFound)
+        }
+
+        for (i, handle) in handles[..gpus].iter_mut().enumerate() {
+            *handle = gpu_handle(state, i);
+        }
+        *count = gpus as u32;
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_EnumLogicalGPUs(nvGPUHandle: *mut [NvLogicalGpuHandle; types::NVAPI_MAX_LOGICAL_GPUS], pGpuCount: *mut u32) -> NvAPI_Status {
+    call(Api::NvAPI_EnumLogicalGPUs, |state| {
+        let handles = out(nvGPUHandle)?;
+        let count = out(pGpuCount)?;
+        let logical = state.system.logical_gpus().len().min(types::NVAPI_MAX_LOGICAL_GPUS);
+        if logical == 0 {
+            return Err(Status::NvidiaDeviceNotFound)
+        }
+
+        for (i, handle) in handles[..logical].iter_mut().enumerate() {
+            *handle = logical_handle(state, i);
+        }
+        *count = logical as u32;
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GetPhysicalGPUsFromLogicalGPU(hLogicalGPU: NvLogicalGpuHandle, hPhysicalGPU: *mut [NvPhysicalGpuHandle; types::NVAPI_MAX_PHYSICAL_GPUS], pGpuCount: *mut u32) -> NvAPI_Status {
+    call(Api::NvAPI_GetPhysicalGPUsFromLogicalGPU, |state| {
+        let gpus = logical_gpu(state, hLogicalGPU)?;
+        let handles = out(hPhysicalGPU)?;
+        let count = out(pGpuCount)?;
+        for (handle, &index) in handles.iter_mut().zip(&gpus) {
+            *handle = gpu_handle(state, index);
+        }
+        *count = gpus.len().min(types::NVAPI_MAX_PHYSICAL_GPUS) as u32;
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GetLogicalGPUFromPhysicalGPU(hPhysicalGPU: NvPhysicalGpuHandle, pLogicalGPU: *mut NvLogicalGpuHandle) -> NvAPI_Status {
+    call(Api::NvAPI_GetLogicalGPUFromPhysicalGPU, |state| {
+        let index = gpu_index(state, hPhysicalGPU)?;
+        *out(pLogicalGPU)? = logical_handle_of(state, index)?;
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GPU_GetFullName(hPhysicalGPU: NvPhysicalGpuHandle, szName: *mut NvAPI_ShortString) -> NvAPI_Status {
+    write_string(Api::NvAPI_GPU_GetFullName, hPhysicalGPU, szName, |gpu| &gpu.full_name)
+}
+
+pub extern "C" fn NvAPI_GPU_GetShortName(hPhysicalGPU: NvPhysicalGpuHandle, szName: *mut NvAPI_ShortString) -> NvAPI_Status {
+    write_string(Api::NvAPI_GPU_GetShortName, hPhysicalGPU, szName, |gpu| &gpu.short_name)
+}
+
+pub extern "C" fn NvAPI_GPU_GetVbiosVersionString(hPhysicalGPU: NvPhysicalGpuHandle, szBiosRevision: *mut NvAPI_ShortString) -> NvAPI_Status {
+    write_string(Api::NvAPI_GPU_GetVbiosVersionString, hPhysicalGPU, szBiosRevision, |gpu| &gpu.vbios_version)
+}
+
+pub extern "C" fn NvAPI_GPU_GetPhysicalFrameBufferSize(hPhysicalGPU: NvPhysicalGpuHandle, pSize: *mut u32) -> NvAPI_Status {
+    write_u32(Api::NvAPI_GPU_GetPhysicalFrameBufferSize, hPhysicalGPU, pSize, |gpu| gpu.memory.dedicated)
+}
+
+pub extern "C" fn NvAPI_GPU_GetPCIIdentifiers(hPhysicalGPU: NvPhysicalGpuHandle, pDeviceId: *mut u32, pSubSystemId: *mut u32, pRevisionId: *mut u32, pExtDeviceId: *mut u32) -> NvAPI_Status {
+    with_gpu(Api::NvAPI_GPU_GetPCIIdentifiers, hPhysicalGPU, |gpu| {
+        *out(pDeviceId)? = gpu.pci.device_id;
+        *out(pSubSystemId)? = gpu.pci.subsystem_id;
+        *out(pRevisionId)? = gpu.pci.revision_id;
+        *out(pExtDeviceId)? = gpu.pci.ext_device_id;
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GPU_GetBusId(hPhysicalGpu: NvPhysicalGpuHandle, pBusId: *mut u32) -> NvAPI_Status {
+    write_u32(Api::NvAPI_GPU_GetBusId, hPhysicalGpu, pBusId, |gpu| gpu.bus_id)
+}
+
+pub extern "C" fn NvAPI_GPU_GetBusSlotId(hPhysicalGpu: NvPhysicalGpuHandle, pBusSlotId: *mut u32) -> NvAPI_Status {
+    write_u32(Api::NvAPI_GPU_GetBusSlotId, hPhysicalGpu, pBusSlotId, |gpu| gpu.bus_slot_id)
+}
+
+pub extern "C" fn NvAPI_GPU_GetBusType(hPhysicalGpu: NvPhysicalGpuHandle, pBusType: *mut gpu::NV_GPU_BUS_TYPE) -> NvAPI_Status {
+    with_gpu(Api::NvAPI_GPU_GetBusType, hPhysicalGpu, |gpu| {
+        *out(pBusType)? = gpu.bus_type.raw();
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GPU_GetIRQ(hPhysicalGpu: NvPhysicalGpuHandle, pIRQ: *mut u32) -> NvAPI_Status {
+    write_u32(Api::NvAPI_GPU_GetIRQ, hPhysicalGpu, pIRQ, |gpu| gpu.irq)
+}
+
+fn pcie(gpu: &model::VirtualGpu) -> Result<&model::Pcie> {
+    match gpu.bus_type {
+        gpu::BusType::PciExpress => Ok(&gpu.pcie),
+        _ => Err(Status::NotSupported),
+    }
+}
+
+pub extern "C" fn NvAPI_GPU_GetCurrentPCIEDownstreamWidth(hPhysicalGpu: NvPhysicalGpuHandle, pWidth: *mut u32) -> NvAPI_Status {
+    with_gpu(Api::NvAPI_GPU_GetCurrentPCIEDownstreamWidth, hPhysicalGpu, |gpu| {
+        let width = out(pWidth)?;
+        *width = pcie(gpu)?.current.1;
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GPU_GetPCIEInfo(hPhysicalGpu: NvPhysicalGpuHandle, pPciInfo: *mut gpu::private::NV_GPU_PCIE_INFO) -> NvAPI_Status {
+    with_gpu(Api::NvAPI_GPU_GetPCIEInfo, hPhysicalGpu, |gpu| {
+        let info = out(pPciInfo)?;
+        check_version(info.version, &[gpu::private::NV_GPU_PCIE_INFO_VER_2])?;
+        let pcie = pcie(gpu)?;
+        let links = std::iter::once(&pcie.current).chain(&pcie.levels);
+        for (entry, &(rate, lanes)) in info.entries.iter_mut().zip(links) {
+            entry.transferRate = rate;
+            entry.lanes = lanes;
+        }
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GPU_GetSystemType(hPhysicalGPU: NvPhysicalGpuHandle, pSystemType: *mut NV_SYSTEM_TYPE) -> NvAPI_Status {
+    with_gpu(Api::NvAPI_GPU_GetSystemType, hPhysicalGPU, |gpu| {
+        *out(pSystemType)? = gpu.system_type.raw();
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GPU_GetShaderSubPipeCount(hPhysicalGPU: NvPhysicalGpuHandle, pCount: *mut u32) -> NvAPI_Status {
+    write_u32(Api::NvAPI_GPU_GetShaderSubPipeCount, hPhysicalGPU, pCount, |gpu| gpu.shader_sub_pipe_count)
+}
+
+pub extern "C" fn NvAPI_GPU_GetShaderPipeCount(hPhysicalGPU: NvPhysicalGpuHandle, pCount: *mut u32) -> NvAPI_Status {
+    write_u32(Api::NvAPI_GPU_GetShaderPipeCount, hPhysicalGPU, pCount, |gpu| gpu.shader_pipe_count)
+}
+
+pub extern "C" fn NvAPI_GPU_GetGpuCoreCount(hPhysicalGPU: NvPhysicalGpuHandle, pCount: *mut u32) -> NvAPI_Status {
+    write_u32(Api::NvAPI_GPU_GetGpuCoreCount, hPhysicalGPU, pCount, |gpu| gpu.core_count)
+}
+
+pub extern "C" fn NvAPI_GPU_GetBoardInfo(hPhysicalGpu: NvPhysicalGpuHandle, pBoardInfo: *mut gpu::NV_BOARD_INFO) -> NvAPI_Status {
+    with_gpu(Api::NvAPI_GPU_GetBoardInfo, hPhysicalGpu, |gpu| {
+        let info = out(pBoardInfo)?;
+        check_version(info.version, &[gpu::NV_BOARD_INFO_VER1])?;
+        info.BoardNum = gpu.board_number;
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GPU_GetPerfDecreaseInfo(hPhysicalGpu: NvPhysicalGpuHandle, pPerfDecrInfo: *mut NVAPI_GPU_PERF_DECREASE) -> NvAPI_Status {
+    write_u32(Api::NvAPI_GPU_GetPerfDecreaseInfo, hPhysicalGpu, pPerfDecrInfo, |gpu| gpu.perf_decrease.bits())
+}
+
+pub extern "C" fn NvAPI_GPU_GetRamType(hPhysicalGPU: NvPhysicalGpuHandle, pMemType: *mut NV_GPU_RAM_TYPE) -> NvAPI_Status {
+    with_gpu(Api::NvAPI_GPU_GetRamType, hPhysicalGPU, |gpu| {
+        *out(pMemType)? = gpu.ram_type.raw();
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GPU_GetRamMaker(hPhysicalGPU: NvPhysicalGpuHandle, pRamMaker: *mut NV_GPU_RAM_MAKER) -> NvAPI_Status {
+    with_gpu(Api::NvAPI_GPU_GetRamMaker, hPhysicalGPU, |gpu| {
+        *out(pRamMaker)? = gpu.ram_maker.raw();
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GPU_GetRamBusWidth(hPhysicalGPU: NvPhysicalGpuHandle, pRamBusWidth: *mut u32) -> NvAPI_Status {
+    write_u32(Api::NvAPI_GPU_GetRamBusWidth, hPhysicalGPU, pRamBusWidth, |gpu| gpu.ram_bus_width)
+}
+
+pub extern "C" fn NvAPI_GPU_GetRamBankCount(hPhysicalGPU: NvPhysicalGpuHandle, pRamBankCount: *mut u32) -> NvAPI_Status {
+    write_u32(Api::NvAPI_GPU_GetRamBankCount, hPhysicalGPU, pRamBankCount, |gpu| gpu.ram_bank_count)
+}
+
+pub extern "C" fn NvAPI_GPU_GetPartitionCount(hPhysicalGpu: NvPhysicalGpuHandle, pPartitionCount: *mut u32) -> NvAPI_Status {
+    write_u32(Api::NvAPI_GPU_GetPartitionCount, hPhysicalGpu, pPartitionCount, |gpu| gpu.ram_partition_count)
+}
+
+pub extern "C" fn NvAPI_GPU_GetFoundry(hPhysicalGPU: NvPhysicalGpuHandle, pFoundry: *mut NV_GPU_FOUNDRY) -> NvAPI_Status {
+    with_gpu(Api::NvAPI_GPU_GetFoundry, hPhysicalGPU, |gpu| {
+        *out(pFoundry)? = gpu.foundry.raw();
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GPU_GetFBWidthAndLocation(hPhysicalGpu: NvPhysicalGpuHandle, pWidth: *mut u32, pLocation: *mut u32) -> NvAPI_Status {
+    with_gpu(Api::NvAPI_GPU_GetFBWidthAndLocation, hPhysicalGpu, |gpu| {
+        *out(pWidth)? = gpu.frame_buffer_width;
+        *out(pLocation)? = gpu.frame_buffer_location;
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GetGPUIDFromPhysicalGPU(hPhysicalGpu: NvPhysicalGpuHandle, gpuid: *mut u32) -> NvAPI_Status {
+    write_u32(Api::NvAPI_GetGPUIDFromPhysicalGPU, hPhysicalGpu, gpuid, |gpu| gpu.gpu_id)
+}
+
+pub extern "C" fn NvAPI_GetDriverModel(hPhysicalGpu: NvPhysicalGpuHandle, pDriverModel: *mut u32) -> NvAPI_Status {
+    write_u32(Api::NvAPI_GetDriverModel, hPhysicalGpu, pDriverModel, |gpu| gpu.driver_model)
+}
+
+fn memory_info(gpu: &model::VirtualGpu, info: &mut NV_DISPLAY_DRIVER_MEMORY_INFO) -> Result<()> {
+    check_version(info.version, &[
+        driverapi::NV_DISPLAY_DRIVER_MEMORY_INFO_VER_1,
+        driverapi::NV_DISPLAY_DRIVER_MEMORY_INFO_VER_2,
+        driverapi::NV_DISPLAY_DRIVER_MEMORY_INFO_VER_3,
+    ])?;
+
+    let memory = &gpu.memory;
+    info.dedicatedVideoMemory = memory.dedicated;
+    info.availableDedicatedVideoMemory = memory.dedicated_available;
+    info.systemVideoMemory = memory.system;
+    info.sharedSystemMemory = memory.shared;
+    if info.version != driverapi::NV_DISPLAY_DRIVER_MEMORY_INFO_VER_1 {
+        info.curAvailableDedicatedVideoMemory = memory.dedicated_available_current;
+    }
+    if info.version == driverapi::NV_DISPLAY_DRIVER_MEMORY_INFO_VER_3 {
+        info.dedicatedVideoMemoryEvictionsSize = memory.dedicated_evictions_size;
+        info.dedicatedVideoMemoryEvictionCount = memory.dedicated_evictions;
+    }
+    Ok(())
+}
+
+pub extern "C" fn NvAPI_GPU_GetMemoryInfo(hPhysicalGpu: NvPhysicalGpuHandle, pMemoryInfo: *mut NV_DISPLAY_DRIVER_MEMORY_INFO) -> NvAPI_Status {
+    with_gpu(Api::NvAPI_GPU_GetMemoryInfo, hPhysicalGpu, |gpu| memory_info(gpu, out(pMemoryInfo)?))
+}
+
+pub extern "C" fn NvAPI_GetDisplayDriverMemoryInfo(hPhysicalGpu: NvPhysicalGpuHandle, pMemoryInfo: *mut NV_DISPLAY_DRIVER_MEMORY_INFO) -> NvAPI_Status {
+    with_gpu(Api::NvAPI_GetDisplayDriverMemoryInfo, hPhysicalGpu, |gpu| memory_info(gpu, out(pMemoryInfo)?))
+}
+
+pub extern "C" fn NvAPI_GPU_GetMemoryInfoEx(hPhysicalGpu: NvPhysicalGpuHandle, pMemoryInfo: *mut NV_GPU_MEMORY_INFO_EX_V1) -> NvAPI_Status {
+    with_gpu(Api::NvAPI_GPU_GetMemoryInfoEx, hPhysicalGpu, |gpu| {
+        let info = out(pMemoryInfo)?;
+        check_version(info.version, &[driverapi::NV_DISPLAY_DRIVER_MEMORY_INFO_EX_VER_1])?;
+
+        // The extended variant reports bytes rather than KiB
+        let bytes = |kib: u32| kib.saturating_mul(1024);
+        let memory = &gpu.memory;
+        info.dedicatedVideoMemory = bytes(memory.dedicated);
+        info.availableDedicatedVideoMemory = bytes(memory.dedicated_available);
+        info.systemVideoMemory = bytes(memory.system);
+        info.curAvailableDedicatedVideoMemory = bytes(memory.dedicated_available_current);
+        info.dedicatedVideoMemoryEvictionsSize = bytes(memory.dedicated_evictions_size);
+        info.dedicatedVideoMemoryEvictionCount = memory.dedicated_evictions;
+        info.dedicatedVideoMemoryPromotionsSize = bytes(memory.dedicated_promotions_size);
+        info.dedicatedVideoMemoryPromotionCount = memory.dedicated_promotions;
+        Ok(())
+    })
+}
+
+fn ecc(gpu: &mut model::VirtualGpu) -> Result<&mut model::Ecc> {
+    gpu.ecc.as_mut().ok_or(Status::NotSupported)
+}
+
+pub extern "C" fn NvAPI_GPU_GetECCStatusInfo(hPhysicalGpu: NvPhysicalGpuHandle, pECCStatusInfo: *mut ecc::NV_GPU_ECC_STATUS_INFO) -> NvAPI_Status {
+    with_gpu(Api::NvAPI_GPU_GetECCStatusInfo, hPhysicalGpu, |gpu| {
+        let info = out(pECCStatusInfo)?;
+        check_version(info.version, &[ecc::NV_GPU_ECC_STATUS_INFO_VER1])?;
+        match gpu.ecc {
+            Some(ecc) => {
+                info.flags = ecc::NV_GPU_ECC_STATUS_IS_SUPPORTED;
+                info.configurationOptions = ecc.configuration.raw();
+                info.enabledFlags = if ecc.enabled { ecc::NV_GPU_ECC_STATUS_IS_ENABLED } else { 0 };
+            },
+            None => {
+                info.flags = 0;
+                info.configurationOptions = ecc::NV_ECC_CONFIGURATION_NOT_SUPPORTED;
+                info.enabledFlags = 0;
+            },
+        }
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GPU_GetECCErrorInfo(hPhysicalGpu: NvPhysicalGpuHandle, pECCErrorInfo: *mut ecc::NV_GPU_ECC_ERROR_INFO) -> NvAPI_Status {
+    with_gpu(Api::NvAPI_GPU_GetECCErrorInfo, hPhysicalGpu, |gpu| {
+        let info = out(pECCErrorInfo)?;
+        check_version(info.version, &[ecc::NV_GPU_ECC_ERROR_INFO_VER1])?;
+        let ecc = ecc(gpu)?;
+        let counts = |(single, double)| ecc::NV_GPU_ECC_ERROR_COUNTS {
+            singleBitErrors: single,
+            doubleBitErrors: double,
+        };
+        info.current = counts(ecc.current);
+        info.aggregate = counts(ecc.aggregate);
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GPU_ResetECCErrorInfo(hPhysicalGpu: NvPhysicalGpuHandle, bResetCurrent: u8, bResetAggregate: u8) -> NvAPI_Status {
+    with_gpu(Api::NvAPI_GPU_ResetECCErrorInfo, hPhysicalGpu, |gpu| {
+        let ecc = ecc(gpu)?;
+        if bResetCurrent != 0 {
+            ecc.current = (0, 0);
+        }
+        if bResetAggregate != 0 {
+            ecc.aggregate = (0, 0);
+        }
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GPU_GetECCConfigurationInfo(hPhysicalGpu: NvPhysicalGpuHandle, pECCConfigurationInfo: *mut ecc::NV_GPU_ECC_CONFIGURATION_INFO) -> NvAPI_Status {
+    with_gpu(Api::NvAPI_GPU_GetECCConfigurationInfo, hPhysicalGpu, |gpu| {
+        let info = out(pECCConfigurationInfo)?;
+        check_version(info.version, &[ecc::NV_GPU_ECC_CONFIGURATION_INFO_VER1])?;
+        let ecc = ecc(gpu)?;
+        let mut flags = ecc::EccConfigurationFlags::empty();
+        flags.set(ecc::EccConfigurationFlags::IS_ENABLED, ecc.pending);
+        flags.set(ecc::EccConfigurationFlags::IS_ENABLED_BY_DEFAULT, ecc.enabled_by_default);
+        info.flags = flags.bits();
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GPU_SetECCConfiguration(hPhysicalGpu: NvPhysicalGpuHandle, bEnable: u8, bEnableImmediately: u8) -> NvAPI_Status {
+    with_gpu(Api::NvAPI_GPU_SetECCConfiguration, hPhysicalGpu, |gpu| {
+        let ecc = ecc(gpu)?;
+        ecc.pending = bEnable != 0;
+        if bEnableImmediately != 0 && ecc.configuration == ecc::EccConfiguration::Immediate {
+            ecc.enabled = ecc.pending;
+        }
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GPU_GetAllClockFrequencies(hPhysicalGPU: NvPhysicalGpuHandle, pClkFreqs: *mut clock::NV_GPU_CLOCK_FREQUENCIES) -> NvAPI_Status {
+    with_gpu(Api::NvAPI_GPU_GetAllClockFrequencies, hPhysicalGPU, |gpu| {
+        let freqs = out(pClkFreqs)?;
+        check_version(freqs.version, &[
+            clock::NV_GPU_CLOCK_FREQUENCIES_VER_1,
+            clock::NV_GPU_CLOCK_FREQUENCIES_VER_2,
+            clock::NV_GPU_CLOCK_FREQUENCIES_VER_3,
+        ])?;
+
+        let clocks = match ClockFrequencyType::from_raw(freqs.ClockType())? {
+            ClockFrequencyType::Current => &gpu.clocks.current,
+            ClockFrequencyType::Base => &gpu.clocks.base,
+            ClockFrequencyType::Boost => &gpu.clocks.boost,
+            ClockFrequencyType::Count | ClockFrequencyType::Unrecognized(..) => return Err(Status::InvalidArgument),
+        };
+        for domain in &mut freqs.domain[..] {
+            *domain = clock::NV_GPU_CLOCK_FREQUENCIES_DOMAIN::zeroed();
+        }
+        for (&id, &frequency) in clocks {
+            let domain = freqs.domain.get_mut(id.raw() as usize).ok_or(Status::InvalidArgument)?;
+            domain.bIsPresent = BoolU32(1);
+            domain.frequency = frequency;
+        }
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GPU_GetAllClocks(hPhysicalGPU: NvPhysicalGpuHandle, pClocksInfo: *mut NV_CLOCKS_INFO) -> NvAPI_Status {
+    with_gpu(Api::NvAPI_GPU_GetAllClocks, hPhysicalGPU, |gpu| {
+        let info = out(pClocksInfo)?;
+        check_version(info.version, &[clock::private::NV_CLOCKS_INFO_VER_1])?;
+
+        // The layout of this table is largely unknown, so only the public domains are filled in
+        for (&id, &frequency) in &gpu.clocks.current {
+            if let Some(clock) = info.clocks.get_mut(id.raw() as usize) {
+                *clock = frequency;
+            }
+        }
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GPU_GetUsages(hPhysicalGPU: NvPhysicalGpuHandle, pUsagesInfo: *mut NV_USAGES_INFO) -> NvAPI_Status {
+    with_gpu(Api::NvAPI_GPU_GetUsages, hPhysicalGPU, |gpu| {
+        let info = out(pUsagesInfo)?;
+        check_version(info.version, &[clock::private::NV_USAGES_INFO_VER_1])?;
+
+        for (&domain, &percentage) in &gpu.utilization {
+            let usage = info.usages.get_mut(domain.raw() as usize).ok_or(Status::InvalidArgument)?;
+            usage.bIsPresent = BoolU32(1);
+            usage.percentage = percentage;
+        }
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GPU_GetDynamicPstatesInfoEx(hPhysicalGPU: NvPhysicalGpuHandle, pDynamicPstatesInfoEx: *mut pstate::NV_GPU_DYNAMIC_PSTATES_INFO_EX) -> NvAPI_Status {
+    with_gpu(Api::NvAPI_GPU_GetDynamicPstatesInfoEx, hPhysicalGPU, |gpu| {
+        let info = out(pDynamicPstatesInfoEx)?;
+        check_version(info.version, &[pstate::NV_GPU_DYNAMIC_PSTATES_INFO_EX_VER])?;
+
+        info.flags = if gpu.dynamic_pstates { 1 } else { 0 };
+        for (&domain, &percentage) in &gpu.utilization {
+            let util = info.utilization.get_mut(domain.raw() as usize).ok_or(Status::InvalidArgument)?;
+            util.bIsPresent = BoolU32(1);
+            util.percentage = percentage;
+        }
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GPU_GetCurrentPstate(hPhysicalGPU: NvPhysicalGpuHandle, pCurrentPstate: *mut NV_GPU_PERF_PSTATE_ID) -> NvAPI_Status {
+    with_gpu(Api::NvAPI_GPU_GetCurrentPstate, hPhysicalGPU, |gpu| {
+        *out(pCurrentPstate)? = gpu.current_pstate.raw();
+        Ok(())
+    })
+}
+
+fn raw_delta(delta: &model::Delta) -> pstate::NV_GPU_PERF_PSTATES20_PARAM_DELTA {
+    pstate::NV_GPU_PERF_PSTATES20_PARAM_DELTA {
+        value: delta.value,
+        min: delta.min,
+        max: delta.max,
+    }
+}
+
+fn raw_base_voltage(voltage: &model::BaseVoltage) -> pstate::NV_GPU_PERF_PSTATE20_BASE_VOLTAGE_ENTRY_V1 {
+    pstate::NV_GPU_PERF_PSTATE20_BASE_VOLTAGE_ENTRY_V1 {
+        domainId: voltage.domain.raw(),
+        bIsEditable: BoolU32(voltage.editable as _),
+        volt_uV: voltage.voltage,
+        voltDelta_uV: raw_delta(&voltage.delta),
+    }
+}
+
+pub extern "C" fn NvAPI_GPU_GetPstates20(hPhysicalGPU: NvPhysicalGpuHandle, pPstatesInfo: *mut pstate::NV_GPU_PERF_PSTATES20_INFO) -> NvAPI_Status {
+    with_gpu(Api::NvAPI_GPU_GetPstates20, hPhysicalGPU, |gpu| {
+        let info = out(pPstatesInfo)?;
+        check_version(info.version, &[
+            pstate::NV_GPU_PERF_PSTATES20_INFO_VER1,
+            pstate::NV_GPU_PERF_PSTATES20_INFO_VER2,
+            pstate::NV_GPU_PERF_PSTATES20_INFO_VER3,
+        ])?;
+
+        let pstates = &gpu.pstates[..gpu.pstates.len().min(pstate::NVAPI_MAX_GPU_PSTATE20_PSTATES)];
+        info.bIsEditable = BoolU32(gpu.pstates_editable as _);
+        info.numPstates = pstates.len() as _;
+        // The clock and voltage counts are shared by every pstate
+        info.numClocks = pstates.iter().map(|p| p.clocks.len()).max().unwrap_or(0).min(pstate::NVAPI_MAX_GPU_PSTATE20_CLOCKS) as _;
+        info.numBaseVoltages = pstates.iter().map(|p| p.base_voltages.len()).max().unwrap_or(0).min(pstate::NVAPI_MAX_GPU_PSTATE20_BASE_VOLTAGES) as _;
+        for (raw, pstate) in info.pstates.iter_mut().zip(pstates) {
+            raw.pstateId = pstate.id.raw();
+            raw.bIsEditable = BoolU32(pstate.editable as _);
+            for (raw, clock) in raw.clocks.iter_mut().zip(&pstate.clocks) {
+                raw.domainId = clock.domain.raw();
+                raw.bIsEditable = BoolU32(clock.editable as _);
+                raw.freqDelta_kHz = raw_delta(&clock.delta);
+                match clock.frequency {
+                    model::PStateFrequency::Single(freq) => {
+                        raw.typeId = PstateClockType::Single.raw();
+                        raw.data.set_single(pstate::NV_GPU_PSTATE20_CLOCK_ENTRY_SINGLE {
+                            freq_kHz: freq,
+                        });
+                    },
+                    model::PStateFrequency::Range { min, max, voltage_domain, min_voltage, max_voltage } => {
+                        raw.typeId = PstateClockType::Range.raw();
+                        raw.data.set_range(pstate::NV_GPU_PSTATE20_CLOCK_ENTRY_RANGE {
+                            minFreq_kHz: min,
+                            maxFreq_kHz: max,
+                            domainId: voltage_domain.raw(),
+                            minVoltage_uV: min_voltage,
+                            maxVoltage_uV: max_voltage,
+                        });
+                    },
+                }
+            }
+            for (raw, voltage) in raw.baseVoltages.iter_mut().zip(&pstate.base_voltages) {
+                *raw = raw_base_voltage(voltage);
+            }
+        }
+
+        if info.version != pstate::NV_GPU_PERF_PSTATES20_INFO_VER1 {
+            let overvolt = &gpu.overvolt[..gpu.overvolt.len().min(pstate::NVAPI_MAX_GPU_PSTATE20_BASE_VOLTAGES)];
+            info.numVoltages = overvolt.len() as _;
+            for (raw, voltage) in info.voltages.iter_mut().zip(overvolt) {
+                *raw = raw_base_voltage(voltage);
+            }
+        }
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GPU_SetPstates20(hPhysicalGPU: NvPhysicalGpuHandle, pPstatesInfo: *const pstate::NV_GPU_PERF_PSTATES20_INFO) -> NvAPI_Status {
+    with_gpu(Api::NvAPI_GPU_SetPstates20, hPhysicalGPU, |gpu| {
+        let info = input(pPstatesInfo)?;
+        check_version(info.version, &[
+            pstate::NV_GPU_PERF_PSTATES20_INFO_VER1,
+            pstate::NV_GPU_PERF_PSTATES20_INFO_VER2,
+            pstate::NV_GPU_PERF_PSTATES20_INFO_VER3,
+        ])?;
+        if !gpu.pstates_editable {
+            return Err(Status::NotSupported)
+        }
+
+        let num_pstates = info.numPstates as usize;
+        let num_clocks = info.numClocks as usize;
+        let num_voltages = info.numBaseVoltages as usize;
+        if num_pstates > pstate::NVAPI_MAX_GPU_PSTATE20_PSTATES || num_clocks > pstate::NVAPI_MAX_GPU_PSTATE20_CLOCKS || num_voltages > pstate::NVAPI_MAX_GPU_PSTATE20_BASE_VOLTAGES {
+            return Err(Status::InvalidArgument)
+        }
+
+        // Validate everything before applying anything
+        let mut changes = Vec::new();
+        for raw in &info.pstates[..num_pstates] {
+            let id = PstateId::from_raw(raw.pstateId)?;
+            let (pi, pstate) = gpu.pstates.iter().enumerate().find(|&(_, p)| p.id == id).ok_or(Status::InvalidArgument)?;
+            if !pstate.editable {
+                return Err(Status::NotSupported)
+            }
+            for raw in &raw.clocks[..num_clocks] {
+                let domain = PublicClockId::from_raw(raw.domainId)?;
+                let (ci, clock) = pstate.clocks.iter().enumerate().find(|&(_, c)| c.domain == domain).ok_or(Status::InvalidArgument)?;
+                let delta = raw.freqDelta_kHz.value;
+                if !clock.editable || delta < clock.delta.min || delta > clock.delta.max {
+                    return Err(Status::InvalidArgument)
+                }
+                changes.push((pi, Ok(ci), delta));
+            }
+            for raw in &raw.baseVoltages[..num_voltages] {
+                let domain = VoltageInfoDomain::from_raw(raw.domainId)?;
+                let (vi, voltage) = pstate.base_voltages.iter().enumerate().find(|&(_, v)| v.domain == domain).ok_or(Status::InvalidArgument)?;
+                let delta = raw.voltDelta_uV.value;
+                if !voltage.editable || delta < voltage.delta.min || delta > voltage.delta.max {
+                    return Err(Status::InvalidArgument)
+                }
+                changes.push((pi, Err(vi), delta));
+            }
+        }
+
+        for (pi, index, delta) in changes {
+            let pstate = &mut gpu.pstates[pi];
+            match index {
+                Ok(ci) => pstate.clocks[ci].delta.value = delta,
+                Err(vi) => pstate.base_voltages[vi].delta.value = delta,
+            }
+        }
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GPU_ClockClientClkVfPointsGetInfo(hPhysicalGPU: NvPhysicalGpuHandle, pClockMasks: *mut NV_GPU_CLOCK_CLIENT_CLK_VF_POINTS_INFO) -> NvAPI_Status {
+    with_gpu(Api::NvAPI_GPU_ClockClientClkVfPointsGetInfo, hPhysicalGPU, |gpu| {
+        let info = out(pClockMasks)?;
+        check_version(info.version, &[clock::private::NV_GPU_CLOCK_CLIENT_CLK_VF_POINTS_INFO_VER_1])?;
+        if gpu.vfp.is_empty() && gpu.vfp_memory.is_empty() {
+            return Err(Status::NotSupported)
+        }
+
+        info.mask = [0; 4];
+        for i in 0..gpu.vfp.len().min(VFP_GRAPHICS_MAX) {
+            set_bit(&mut info.mask, i);
+            info.clocks[i].gpuDelta = 1;
+        }
+        for i in 0..gpu.vfp_memory.len().min(VFP_MEMORY_MAX) {
+            set_bit(&mut info.mask, VFP_GRAPHICS_MAX + i);
+            info.clocks[VFP_GRAPHICS_MAX + i].memDelta = 1;
+        }
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GPU_ClockClientClkVfPointsGetControl(hPhysicalGPU: NvPhysicalGpuHandle, pClockTable: *mut NV_GPU_CLOCK_CLIENT_CLK_VF_POINTS_CONTROL) -> NvAPI_Status {
+    with_gpu(Api::NvAPI_GPU_ClockClientClkVfPointsGetControl, hPhysicalGPU, |gpu| {
+        let table = out(pClockTable)?;
+        check_version(table.version, &[clock::private::NV_GPU_CLOCK_CLIENT_CLK_VF_POINTS_CONTROL_VER_1])?;
+        if gpu.vfp.is_empty() && gpu.vfp_memory.is_empty() {
+            return Err(Status::NotSupported)
+        }
+
+        for (i, point) in gpu.vfp.iter().enumerate().take(VFP_GRAPHICS_MAX) {
+            if get_bit(&table.mask, i) {
+                // Graphics deltas are reported in units of 0.5 kHz
+                table.gpuDeltas[i].freqDeltaKHz = point.delta * 2;
+            }
+        }
+        for (i, point) in gpu.vfp_memory.iter().enumerate().take(VFP_MEMORY_MAX) {
+            if get_bit(&table.mask, VFP_GRAPHICS_MAX + i) {
+                table.memFilled[i] = 1;
+                table.memDeltas[i] = point.delta;
+            }
+        }
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GPU_ClockClientClkVfPointsSetControl(hPhysicalGPU: NvPhysicalGpuHandle, pClockTable: *const NV_GPU_CLOCK_CLIENT_CLK_VF_POINTS_CONTROL) -> NvAPI_Status {
+    with_gpu(Api::NvAPI_GPU_ClockClientClkVfPointsSetControl, hPhysicalGPU, |gpu| {
+        let table = input(pClockTable)?;
+        check_version(table.version, &[clock::private::NV_GPU_CLOCK_CLIENT_CLK_VF_POINTS_CONTROL_VER_1])?;
+        if gpu.vfp.is_empty() && gpu.vfp_memory.is_empty() {
+            return Err(Status::NotSupported)
+        }
+
+        let range = |domain| gpu.vfp_ranges.iter().find(|r| r.domain == domain).map(|r| (r.min, r.max));
+        let (gpu_range, mem_range) = (range(PublicClockId::Graphics), range(PublicClockId::Memory));
+        let in_range = |range: Option<(i32, i32)>, delta: i32| range.map(|(min, max)| delta >= min && delta <= max).unwrap_or(true);
+
+        let mut gpu_deltas = Vec::new();
+        for i in 0..VFP_GRAPHICS_MAX {
+            if get_bit(&table.mask, i) {
+                let delta = table.gpuDeltas[i].freqDeltaKHz / 2;
+                if i >= gpu.vfp.len() || !in_range(gpu_range, delta) {
+                    return Err(Status::InvalidArgument)
+                }
+                gpu_deltas.push((i, delta));
+            }
+        }
+        let mut mem_deltas = Vec::new();
+        for (i, &filled) in table.memFilled.iter().enumerate() {
+            match filled {
+                0 => (),
+                1 if i < gpu.vfp_memory.len() && in_range(mem_range, table.memDeltas[i]) => mem_deltas.push((i, table.memDeltas[i])),
+                _ => return Err(Status::InvalidArgument),
+            }
+        }
+
+        for (i, delta) in gpu_deltas {
+            gpu.vfp[i].delta = delta;
+        }
+        for (i, delta) in mem_deltas {
+            gpu.vfp_memory[i].delta = delta;
+        }
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GPU_ClockClientClkVfPointsGetStatus(hPhysicalGPU: NvPhysicalGpuHandle, pVfpCurve: *mut NV_GPU_CLOCK_CLIENT_CLK_VF_POINTS_STATUS) -> NvAPI_Status {
+    with_gpu(Api::NvAPI_GPU_ClockClientClkVfPointsGetStatus, hPhysicalGPU, |gpu| {
+        let curve = out(pVfpCurve)?;
+        check_version(curve.version, &[nvapi_sys::gpu::power::private::NV_GPU_CLOCK_CLIENT_CLK_VF_POINTS_STATUS_VER_1])?;
+        if gpu.vfp.is_empty() && gpu.vfp_memory.is_empty() {
+            return Err(Status::NotSupported)
+        }
+
+        let offset = |freq: u32, delta: i32| (freq as i64 + delta as i64).max(0) as u32;
+        for (i, point) in gpu.vfp.iter().enumerate().take(VFP_GRAPHICS_MAX) {
+            if get_bit(&curve.mask, i) {
+                let entry = &mut curve.gpuEntries[i];
+                entry.freq_kHz = offset(point.frequency, point.delta) * 2;
+                entry.voltage_uV = point.voltage;
+            }
+        }
+        for (i, point) in gpu.vfp_memory.iter().enumerate().take(VFP_MEMORY_MAX) {
+            if get_bit(&curve.mask, VFP_GRAPHICS_MAX + i) {
+                let entry = &mut curve.memEntries[i];
+                entry.freq_kHz = offset(point.frequency, point.delta);
+                entry.voltage_uV = point.voltage;
+            }
+        }
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GPU_ClockClientClkDomainsGetInfo(hPhysicalGPU: NvPhysicalGpuHandle, pClockRanges: *mut NV_GPU_CLOCK_CLIENT_CLK_DOMAINS_INFO) -> NvAPI_Status {
+    with_gpu(Api::NvAPI_GPU_ClockClientClkDomainsGetInfo, hPhysicalGPU, |gpu| {
+        let info = out(pClockRanges)?;
+        check_version(info.version, &[clock::private::NV_GPU_CLOCK_CLIENT_CLK_DOMAINS_INFO_VER_1])?;
+        if gpu.vfp_ranges.is_empty() {
+            return Err(Status::NotSupported)
+        }
+
+        let ranges = &gpu.vfp_ranges[..gpu.vfp_ranges.len().min(info.entries.len() - 1)];
+        // The driver appears to count an additional trailing entry
+        info.numClocks = ranges.len() as u32 + 1;
+        for (entry, range) in info.entries.iter_mut().zip(ranges) {
+            entry.clockType = range.domain.raw();
+            entry.rangeMin = range.min * 2;
+            entry.rangeMax = range.max * 2;
+            entry.tempMax = range.temp_max << 8;
+        }
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GPU_PerfClientLimitsGetStatus(hPhysicalGPU: NvPhysicalGpuHandle, pClockLocks: *mut NV_GPU_PERF_CLIENT_LIMITS) -> NvAPI_Status {
+    with_gpu(Api::NvAPI_GPU_PerfClientLimitsGetStatus, hPhysicalGPU, |gpu| {
+        let limits = out(pClockLocks)?;
+        check_version(limits.version, &[clock::private::NV_GPU_PERF_CLIENT_LIMITS_VER_2])?;
+        if gpu.vfp_locks.is_empty() {
+            return Err(Status::NotSupported)
+        }
+
+        let locks = &gpu.vfp_locks[..gpu.vfp_locks.len().min(limits.entries.len())];
+        limits.count = locks.len() as _;
+        for (i, (entry, lock)) in limits.entries.iter_mut().zip(locks).enumerate() {
+            entry.id = i as _;
+            entry.mode = lock.mode.raw();
+            entry.voltage_uV = lock.voltage;
+        }
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GPU_PerfClientLimitsSetStatus(hPhysicalGPU: NvPhysicalGpuHandle, pClockLocks: *const NV_GPU_PERF_CLIENT_LIMITS) -> NvAPI_Status {
+    with_gpu(Api::NvAPI_GPU_PerfClientLimitsSetStatus, hPhysicalGPU, |gpu| {
+        let limits = input(pClockLocks)?;
+        check_version(limits.version, &[clock::private::NV_GPU_PERF_CLIENT_LIMITS_VER_2])?;
+        if gpu.vfp_locks.is_empty() {
+            return Err(Status::NotSupported)
+        }
+
+        let entries = limits.entries.get(..limits.count as usize).ok_or(Status::InvalidArgument)?;
+        let mut locks = Vec::with_capacity(entries.len());
+        for entry in entries {
+            let id = entry.id as usize;
+            if id >= gpu.vfp_locks.len() {
+                return Err(Status::InvalidArgument)
+            }
+            let lock = match ClockLockMode::from_raw(entry.mode)? {
+                ClockLockMode::None => model::VfpLock::default(),
+                mode @ ClockLockMode::Manual => model::VfpLock {
+                    mode,
+                    voltage: entry.voltage_uV,
+                },
+                ClockLockMode::Unrecognized(..) => return Err(Status::InvalidArgument),
+            };
+            locks.push((id, lock));
+        }
+
+        for (id, lock) in locks {
+            gpu.vfp_locks[id] = lock;
+        }
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GPU_ClientVoltRailsGetStatus(hPhysicalGPU: NvPhysicalGpuHandle, pVoltageStatus: *mut NV_GPU_CLIENT_VOLT_RAILS_STATUS) -> NvAPI_Status {
+    with_gpu(Api::NvAPI_GPU_ClientVoltRailsGetStatus, hPhysicalGPU, |gpu| {
+        let status = out(pVoltageStatus)?;
+        check_version(status.version, &[nvapi_sys::gpu::power::private::NV_GPU_CLIENT_VOLT_RAILS_STATUS_VER_1])?;
+        status.value_uV = gpu.core_voltage;
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GPU_ClientVoltRailsGetControl(hPhysicalGPU: NvPhysicalGpuHandle, pVoltboostPercent: *mut NV_GPU_CLIENT_VOLT_RAILS_CONTROL) -> NvAPI_Status {
+    with_gpu(Api::NvAPI_GPU_ClientVoltRailsGetControl, hPhysicalGPU, |gpu| {
+        let control = out(pVoltboostPercent)?;
+        check_version(control.version, &[nvapi_sys::gpu::power::private::NV_GPU_CLIENT_VOLT_RAILS_CONTROL_VER_1])?;
+        control.percent = gpu.core_voltage_boost;
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GPU_ClientVoltRailsSetControl(hPhysicalGPU: NvPhysicalGpuHandle, pVoltboostPercent: *const NV_GPU_CLIENT_VOLT_RAILS_CONTROL) -> NvAPI_Status {
+    with_gpu(Api::NvAPI_GPU_ClientVoltRailsSetControl, hPhysicalGPU, |gpu| {
+        let control = input(pVoltboostPercent)?;
+        check_version(control.version, &[nvapi_sys::gpu::power::private::NV_GPU_CLIENT_VOLT_RAILS_CONTROL_VER_1])?;
+        if control.percent > 100 {
+            return Err(Status::InvalidArgument)
+        }
+        gpu.core_voltage_boost = control.percent;
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GPU_ClientPowerPoliciesGetInfo(hPhysicalGPU: NvPhysicalGpuHandle, pPowerInfo: *mut NV_GPU_POWER_INFO) -> NvAPI_Status {
+    with_gpu(Api::NvAPI_GPU_ClientPowerPoliciesGetInfo, hPhysicalGPU, |gpu| {
+        let info = out(pPowerInfo)?;
+        check_version(info.version, &[nvapi_sys::gpu::power::private::NV_GPU_POWER_INFO_VER_1])?;
+
+        let limits = &gpu.power_limits[..gpu.power_limits.len().min(info.entries.len())];
+        info.valid = 1;
+        info.count = limits.len() as _;
+        for (entry, limit) in info.entries.iter_mut().zip(limits) {
+            entry.pstate = limit.pstate.raw() as _;
+            entry.min_power = limit.min;
+            entry.def_power = limit.default;
+            entry.max_power = limit.max;
+        }
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GPU_ClientPowerPoliciesGetStatus(hPhysicalGPU: NvPhysicalGpuHandle, pPowerStatus: *mut NV_GPU_POWER_STATUS) -> NvAPI_Status {
+    with_gpu(Api::NvAPI_GPU_ClientPowerPoliciesGetStatus, hPhysicalGPU, |gpu| {
+        let status = out(pPowerStatus)?;
+        check_version(status.version, &[nvapi_sys::gpu::power::private::NV_GPU_POWER_STATUS_VER_1])?;
+
+        let limits = &gpu.power_limits[..gpu.power_limits.len().min(status.entries.len())];
+        status.count = limits.len() as _;
+        for (entry, limit) in status.entries.iter_mut().zip(limits) {
+            entry.power = limit.current;
+        }
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GPU_ClientPowerPoliciesSetStatus(hPhysicalGPU: NvPhysicalGpuHandle, pPowerStatus: *const NV_GPU_POWER_STATUS) -> NvAPI_Status {
+    with_gpu(Api::NvAPI_GPU_ClientPowerPoliciesSetStatus, hPhysicalGPU, |gpu| {
+        let status = input(pPowerStatus)?;
+        check_version(status.version, &[nvapi_sys::gpu::power::private::NV_GPU_POWER_STATUS_VER_1])?;
+
+        let entries = status.entries.get(..status.count as usize).ok_or(Status::InvalidArgument)?;
+        if entries.len() > gpu.power_limits.len() {
+            return Err(Status::InvalidArgument)
+        }
+        if entries.iter().zip(&gpu.power_limits).any(|(entry, limit)| entry.power < limit.min || entry.power > limit.max) {
+            return Err(Status::InvalidArgument)
+        }
+        for (entry, limit) in entries.iter().zip(&mut gpu.power_limits) {
+            limit.current = entry.power;
+        }
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GPU_ClientPowerTopologyGetStatus(hPhysicalGPU: NvPhysicalGpuHandle, pPowerTopo: *mut NV_GPU_POWER_TOPO) -> NvAPI_Status {
+    with_gpu(Api::NvAPI_GPU_ClientPowerTopologyGetStatus, hPhysicalGPU, |gpu| {
+        let topo = out(pPowerTopo)?;
+        check_version(topo.version, &[nvapi_sys::gpu::power::private::NV_GPU_POWER_TOPO_VER_1])?;
+
+        let usage = &gpu.power_usage[..gpu.power_usage.len().min(topo.entries.len())];
+        topo.count = usage.len() as _;
+        for (entry, &power) in topo.entries.iter_mut().zip(usage) {
+            entry.power = power;
+        }
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GPU_PerfPoliciesGetInfo(hPhysicalGPU: NvPhysicalGpuHandle, pPerfInfo: *mut NV_GPU_PERF_INFO) -> NvAPI_Status {
+    with_gpu(Api::NvAPI_GPU_PerfPoliciesGetInfo, hPhysicalGPU, |gpu| {
+        let info = out(pPerfInfo)?;
+        check_version(info.version, &[nvapi_sys::gpu::power::private::NV_GPU_PERF_INFO_VER_1])?;
+        info.maxUnknown = gpu.perf_status_unknown;
+        info.limitSupport = gpu.perf_limit_support.bits();
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GPU_PerfPoliciesGetStatus(hPhysicalGPU: NvPhysicalGpuHandle, pPerfStatus: *mut NV_GPU_PERF_STATUS) -> NvAPI_Status {
+    with_gpu(Api::NvAPI_GPU_PerfPoliciesGetStatus, hPhysicalGPU, |gpu| {
+        let status = out(pPerfStatus)?;
+        check_version(status.version, &[nvapi_sys::gpu::power::private::NV_GPU_PERF_STATUS_VER_1])?;
+        status.limits = gpu.perf_limits.bits();
+        status.unknown = gpu.perf_status_unknown;
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GPU_GetVoltageDomainsStatus(hPhysicalGPU: NvPhysicalGpuHandle, pVoltStatus: *mut NV_VOLT_STATUS) -> NvAPI_Status {
+    with_gpu(Api::NvAPI_GPU_GetVoltageDomainsStatus, hPhysicalGPU, |gpu| {
+        let status = out(pVoltStatus)?;
+        check_version(status.version, &[nvapi_sys::gpu::power::private::NV_VOLT_STATUS_VER_1])?;
+        status.count = 1;
+        status.value_uV = gpu.core_voltage;
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GPU_GetVoltageStep(hPhysicalGPU: NvPhysicalGpuHandle, pVoltStep: *mut NV_VOLT_STATUS) -> NvAPI_Status {
+    with_gpu(Api::NvAPI_GPU_GetVoltageStep, hPhysicalGPU, |gpu| {
+        let status = out(pVoltStep)?;
+        check_version(status.version, &[nvapi_sys::gpu::power::private::NV_VOLT_STATUS_VER_1])?;
+        let mut table = gpu.voltage_table.iter();
+        let step = match (table.next(), table.next()) {
+            (Some(&a), Some(&b)) => b.saturating_sub(a),
+            _ => 0,
+        };
+        status.count = 1;
+        status.value_uV = step;
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GPU_GetVoltages(hPhysicalGPU: NvPhysicalGpuHandle, pVolts: *mut NV_VOLT_TABLE) -> NvAPI_Status {
+    with_gpu(Api::NvAPI_GPU_GetVoltages, hPhysicalGPU, |gpu| {
+        let table = out(pVolts)?;
+        check_version(table.version, &[nvapi_sys::gpu::power::private::NV_VOLT_TABLE_VER_1])?;
+        if gpu.voltage_table.is_empty() {
+            return Err(Status::NotSupported)
+        }
+
+        let voltages = &gpu.voltage_table[..gpu.voltage_table.len().min(table.entries.len())];
+        table.filled = voltages.len() as _;
+        for (entry, &voltage) in table.entries.iter_mut().zip(voltages) {
+            entry.voltage_uV = voltage;
+        }
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GPU_GetThermalSettings(hPhysicalGPU: NvPhysicalGpuHandle, sensorIndex: u32, pThermalSettings: *mut NV_GPU_THERMAL_SETTINGS) -> NvAPI_Status {
+    with_gpu(Api::NvAPI_GPU_GetThermalSettings, hPhysicalGPU, |gpu| {
+        let settings = out(pThermalSettings)?;
+        check_version(settings.version, &[thermal::NV_GPU_THERMAL_SETTINGS_VER_1, thermal::NV_GPU_THERMAL_SETTINGS_VER_2])?;
+
+        let sensors = &gpu.sensors[..gpu.sensors.len().min(thermal::NVAPI_MAX_THERMAL_SENSORS_PER_GPU)];
+        let sensors = if sensorIndex == thermal::NVAPI_THERMAL_TARGET_ALL as u32 {
+            sensors
+        } else {
+            let index = sensorIndex as usize;
+            sensors.get(index..index + 1).ok_or(Status::InvalidArgument)?
+        };
+        settings.count = sensors.len() as _;
+        for (raw, sensor) in settings.sensor.iter_mut().zip(sensors) {
+            raw.controller = sensor.controller.raw();
+            raw.defaultMinTemp = sensor.default_range.0;
+            raw.defaultMaxTemp = sensor.default_range.1;
+            raw.currentTemp = sensor.current;
+            raw.target = sensor.target.raw();
+        }
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GPU_ClientThermalPoliciesGetInfo(hPhysicalGPU: NvPhysicalGpuHandle, pThermalInfo: *mut NV_GPU_THERMAL_INFO) -> NvAPI_Status {
+    with_gpu(Api::NvAPI_GPU_ClientThermalPoliciesGetInfo, hPhysicalGPU, |gpu| {
+        let info = out(pThermalInfo)?;
+        check_version(info.version, &[thermal::private::NV_GPU_THERMAL_INFO_VER_2])?;
+
+        let limits = &gpu.thermal_limits[..gpu.thermal_limits.len().min(info.entries.len())];
+        info.count = limits.len() as _;
+        info.flags = 1;
+        for (entry, limit) in info.entries.iter_mut().zip(limits) {
+            entry.controller = limit.controller.raw();
+            entry.minTemp = limit.min << 8;
+            entry.defaultTemp = limit.default << 8;
+            entry.maxTemp = limit.max << 8;
+            entry.defaultFlags = limit.flags;
+        }
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GPU_ClientThermalPoliciesGetStatus(hPhysicalGPU: NvPhysicalGpuHandle, pThermalLimit: *mut NV_GPU_CLIENT_THERMAL_POLICIES_STATUS) -> NvAPI_Status {
+    with_gpu(Api::NvAPI_GPU_ClientThermalPoliciesGetStatus, hPhysicalGPU, |gpu| {
+        let status = out(pThermalLimit)?;
+        check_version(status.version, &[thermal::private::NV_GPU_CLIENT_THERMAL_POLICIES_STATUS_VER_2])?;
+
+        let limits = &gpu.thermal_limits[..gpu.thermal_limits.len().min(status.entries.len())];
+        // `flags` doubles as the entry count
+        status.flags = limits.len() as _;
+        for (entry, limit) in status.entries.iter_mut().zip(limits) {
+            entry.controller = limit.controller.raw();
+            entry.value = (limit.current << 8) as u32;
+            entry.flags = limit.flags;
+        }
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GPU_ClientThermalPoliciesSetStatus(hPhysicalGPU: NvPhysicalGpuHandle, pThermalLimit: *const NV_GPU_CLIENT_THERMAL_POLICIES_STATUS) -> NvAPI_Status {
+    with_gpu(Api::NvAPI_GPU_ClientThermalPoliciesSetStatus, hPhysicalGPU, |gpu| {
+        let status = input(pThermalLimit)?;
+        check_version(status.version, &[thermal::private::NV_GPU_CLIENT_THERMAL_POLICIES_STATUS_VER_2])?;
+
+        let entries = status.entries.get(..status.flags as usize).ok_or(Status::InvalidArgument)?;
+        let mut values = Vec::with_capacity(entries.len());
+        for entry in entries {
+            let index = gpu.thermal_limits.iter().position(|l| l.controller.raw() == entry.controller).ok_or(Status::InvalidArgument)?;
+            let limit = &gpu.thermal_limits[index];
+            let value = (entry.value as i32) >> 8;
+            if value < limit.min || value > limit.max {
+                return Err(Status::InvalidArgument)
+            }
+            values.push((index, value));
+        }
+        for (index, value) in values {
+            gpu.thermal_limits[index].current = value;
+        }
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GPU_GetTachReading(hPhysicalGPU: NvPhysicalGpuHandle, pValue: *mut u32) -> NvAPI_Status {
+    with_gpu(Api::NvAPI_GPU_GetTachReading, hPhysicalGPU, |gpu| {
+        if gpu.coolers.is_empty() {
+            return Err(Status::NotSupported)
+        }
+        *out(pValue)? = gpu.tachometer;
+        Ok(())
+    })
+}
+
+/// Resolves a cooler index argument, where `NVAPI_COOLER_TARGET_ALL` selects every cooler.
+fn coolers(gpu: &model::VirtualGpu, index: u32) -> Result<std::ops::Range<usize>> {
+    let count = gpu.coolers.len().min(cooler::NVAPI_MAX_COOLERS_PER_GPU);
+    if index == cooler::NVAPI_COOLER_TARGET_ALL as u32 {
+        Ok(0..count)
+    } else if (index as usize) < count {
+        Ok(index as usize..index as usize + 1)
+    } else {
+        Err(Status::InvalidArgument)
+    }
+}
+
+fn cooler_indices(gpu: &model::VirtualGpu, coolerIndex: *const u32, coolerCount: u32) -> Result<Vec<usize>> {
+    if coolerIndex.is_null() || coolerCount == 0 {
+        Ok((0..gpu.coolers.len()).collect())
+    } else {
+        let indices = unsafe { std::slice::from_raw_parts(coolerIndex, coolerCount as usize) };
+        indices.iter().map(|&i| match i as usize {
+            i if i < gpu.coolers.len() => Ok(i),
+            _ => Err(Status::InvalidArgument),
+        }).collect()
+    }
+}
+
+pub extern "C" fn NvAPI_GPU_GetCoolerSettings(hPhysicalGPU: NvPhysicalGpuHandle, coolerIndex: u32, pCoolerInfo: *mut NV_GPU_COOLER_SETTINGS) -> NvAPI_Status {
+    with_gpu(Api::NvAPI_GPU_GetCoolerSettings, hPhysicalGPU, |gpu| {
+        let settings = out(pCoolerInfo)?;
+        check_version(settings.version, &[cooler::NV_GPU_COOLER_SETTINGS_VER_1])?;
+        if gpu.coolers.is_empty() {
+            return Err(Status::NotSupported)
+        }
+
+        let coolers = &gpu.coolers[coolers(gpu, coolerIndex)?];
+        settings.count = coolers.len() as _;
+        for (raw, cooler) in settings.cooler.iter_mut().zip(coolers) {
+            raw.type_ = cooler.kind.raw();
+            raw.controller = cooler.controller.raw();
+            raw.defaultMinLevel = cooler.default_range.0;
+            raw.defaultMaxLevel = cooler.default_range.1;
+            raw.currentMinLevel = cooler.current_range.0;
+            raw.currentMaxLevel = cooler.current_range.1;
+            raw.currentLevel = cooler.level;
+            raw.defaultPolicy = cooler.default_policy.raw();
+            raw.currentPolicy = cooler.policy.raw();
+            raw.target = cooler.target.raw();
+            raw.controlType = cooler.control.raw();
+            raw.active = if cooler.active { CoolerActivityLevel::Active } else { CoolerActivityLevel::Inactive }.raw();
+        }
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GPU_SetCoolerLevels(hPhysicalGPU: NvPhysicalGpuHandle, coolerIndex: u32, pCoolerLevels: *const NV_GPU_SETCOOLER_LEVEL) -> NvAPI_Status {
+    with_gpu(Api::NvAPI_GPU_SetCoolerLevels, hPhysicalGPU, |gpu| {
+        let levels = input(pCoolerLevels)?;
+        check_version(levels.version, &[cooler::NV_GPU_SETCOOLER_LEVEL_VER_1])?;
+        if gpu.coolers.is_empty() {
+            return Err(Status::NotSupported)
+        }
+
+        let range = coolers(gpu, coolerIndex)?;
+        let mut values = Vec::with_capacity(range.len());
+        for (raw, index) in levels.cooler.iter().zip(range) {
+            let cooler = &gpu.coolers[index];
+            let policy = CoolerPolicy::from_raw(raw.currentPolicy)?;
+            if raw.currentLevel < cooler.current_range.0 || raw.currentLevel > cooler.current_range.1 {
+                return Err(Status::InvalidArgument)
+            }
+            values.push((index, raw.currentLevel, policy));
+        }
+        for (index, level, policy) in values {
+            let cooler = &mut gpu.coolers[index];
+            cooler.level = level;
+            cooler.policy = policy;
+        }
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GPU_RestoreCoolerSettings(hPhysicalGPU: NvPhysicalGpuHandle, coolerIndex: *const u32, coolerCount: u32) -> NvAPI_Status {
+    with_gpu(Api::NvAPI_GPU_RestoreCoolerSettings, hPhysicalGPU, |gpu| {
+        for index in cooler_indices(gpu, coolerIndex, coolerCount)? {
+            let cooler = &mut gpu.coolers[index];
+            cooler.level = cooler.default_level;
+            cooler.policy = cooler.default_policy;
+        }
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GPU_GetCoolerPolicyTable(hPhysicalGPU: NvPhysicalGpuHandle, coolerIndex: u32, pCoolerTable: *mut NV_GPU_COOLER_POLICY_TABLE, count: *mut u32) -> NvAPI_Status {
+    with_gpu(Api::NvAPI_GPU_GetCoolerPolicyTable, hPhysicalGPU, |gpu| {
+        let table = out(pCoolerTable)?;
+        let count = out(count)?;
+        check_version(table.version, &[cooler::NV_GPU_COOLER_POLICY_TABLE_VER_1])?;
+        let cooler = gpu.coolers.get(coolerIndex as usize).ok_or(Status::InvalidArgument)?;
+        if CoolerPolicy::from_raw(table.policy)? != CoolerPolicy::Performance {
+            return Err(Status::NotSupported)
+        }
+
+        let levels = &cooler.policy_levels[..cooler.policy_levels.len().min(cooler::NVAPI_MAX_COOLER_LEVELS)];
+        *count = levels.len() as _;
+        for (raw, level) in table.policyCoolerLevel.iter_mut().zip(levels) {
+            raw.levelId = level.id;
+            raw.currentLevel = level.level;
+            raw.defaultLevel = level.default_level;
+        }
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GPU_SetCoolerPolicyTable(hPhysicalGPU: NvPhysicalGpuHandle, coolerIndex: u32, pCoolerTable: *const NV_GPU_COOLER_POLICY_TABLE, count: u32) -> NvAPI_Status {
+    with_gpu(Api::NvAPI_GPU_SetCoolerPolicyTable, hPhysicalGPU, |gpu| {
+        let table = input(pCoolerTable)?;
+        check_version(table.version, &[cooler::NV_GPU_COOLER_POLICY_TABLE_VER_1])?;
+        let cooler = gpu.coolers.get_mut(coolerIndex as usize).ok_or(Status::InvalidArgument)?;
+        if CoolerPolicy::from_raw(table.policy)? != CoolerPolicy::Performance {
+            return Err(Status::NotSupported)
+        }
+
+        let raw = table.policyCoolerLevel.get(..count as usize).ok_or(Status::InvalidArgument)?;
+        let mut values = Vec::with_capacity(raw.len());
+        for raw in raw {
+            let index = cooler.policy_levels.iter().position(|l| l.id == raw.levelId).ok_or(Status::InvalidArgument)?;
+            values.push((index, raw.currentLevel));
+        }
+        for (index, level) in values {
+            cooler.policy_levels[index].level = level;
+        }
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GPU_RestoreCoolerPolicyTable(hPhysicalGPU: NvPhysicalGpuHandle, coolerIndex: *const u32, coolerCount: u32, policy: NV_COOLER_POLICY) -> NvAPI_Status {
+    with_gpu(Api::NvAPI_GPU_RestoreCoolerPolicyTable, hPhysicalGPU, |gpu| {
+        if CoolerPolicy::from_raw(policy)? != CoolerPolicy::Performance {
+            return Err(Status::NotSupported)
+        }
+
+        for index in cooler_indices(gpu, coolerIndex, coolerCount)? {
+            for level in &mut gpu.coolers[index].policy_levels {
+                level.level = level.default_level;
+            }
+        }
+        Ok(())
+    })
+}
@@ -0,0 +1,293 @@
+use std::collections::BTreeMap;
+use nvapi_sys::{Api, Status, NvAPI_Status};
+use nvapi_sys::handles::{NvGSyncDeviceHandle, NvPhysicalGpuHandle};
+use nvapi_sys::gsync::{self, NV_GSYNC_CAPABILITIES, NV_GSYNC_GPU, NV_GSYNC_DISPLAY, NV_GSYNC_CONTROL_PARAMS, NV_GSYNC_DELAY, NV_GSYNC_STATUS, NV_GSYNC_STATUS_PARAMS, NVAPI_MAX_GSYNC_DEVICES};
+use nvapi_sys::gsync::{DisplaySyncState, GSyncDisplayFlags, GSyncGpuFlags, GSyncCapabilitiesFlags, GSyncControlFlags, GSyncPolarity, GSyncVideoMode, GSyncSource, GSyncMultiplyDivideMode};
+use crate::{call, Result, System, GSyncDevice, GSyncControl};
+use crate::util::{out, input, check_version, gpu_handle, gpu_index, gsync_handle, gsync_index};
+
+/// The most lines the sync signal can be delayed by, a frame of 1080p.
+const MAX_DELAY_LINES: u32 = 1125;
+/// The most the sync signal can be multiplied or divided by.
+const MAX_MUL_DIV: u8 = 8;
+
+/// The displays connected to the device's GPUs, and whether each can be the master.
+fn displays(system: &System, device: &GSyncDevice) -> Vec<(u32, bool)> {
+    device.gpus.iter().filter_map(|&(gpu, _)| system.gpus.get(gpu))
+        .flat_map(|gpu| &gpu.displays)
+        .filter(|display| display.connected)
+        .map(|display| (display.id, display.active()))
+        .collect()
+}
+
+fn sync_state(device: &GSyncDevice, display_id: u32) -> DisplaySyncState {
+    device.display_states.get(&display_id).cloned().unwrap_or(DisplaySyncState::Unsynced)
+}
+
+/// The refresh rate of an active display, in 0.0001 Hz.
+fn refresh_rate(system: &System, display_id: u32) -> Option<u32> {
+    system.display_config.iter().flat_map(|path| &path.targets)
+        .find(|target| target.display_id == display_id)
+        .map(|target| target.refresh_rate.saturating_mul(10))
+}
+
+/// The rate of the signal the device locks to, in 0.0001 Hz.
+fn signal_rate(system: &System, device: &GSyncDevice) -> Option<u32> {
+    match device.control.source {
+        GSyncSource::HouseSync => Some(device.house_sync).filter(|&rate| rate != 0),
+        _ => displays(system, device).into_iter()
+            .find(|&(id, _)| sync_state(device, id) == DisplaySyncState::Master)
+            .and_then(|(id, _)| refresh_rate(system, id)),
+    }
+}
+
+/// Whether the rates are within 0.1% of each other.
+fn matches(rate: u32, signal: u32) -> bool {
+    (rate as u64).abs_diff(signal as u64) * 1000 <= signal as u64
+}
+
+fn gpu_synced(system: &System, device: &GSyncDevice, gpu: usize) -> bool {
+    let signal = match signal_rate(system, device) {
+        Some(signal) => signal,
+        None => return false,
+    };
+    let mut synced = system.gpus[gpu].displays.iter()
+        .filter(|display| display.connected && sync_state(device, display.id) != DisplaySyncState::Unsynced)
+        .map(|display| refresh_rate(system, display.id))
+        .peekable();
+    synced.peek().is_some() && synced.all(|rate| rate.is_some_and(|rate| matches(rate, signal)))
+}
+
+fn write_delay(raw: &mut NV_GSYNC_DELAY, (lines, pixels): (u32, u32)) -> Result<()> {
+    check_version(raw.version, &[gsync::NV_GSYNC_DELAY_VER1])?;
+    raw.numLines = lines;
+    raw.numPixels = pixels;
+    raw.maxLines = MAX_DELAY_LINES;
+    raw.minPixels = 0;
+    Ok(())
+}
+
+fn read_delay(raw: &NV_GSYNC_DELAY) -> Result<(u32, u32)> {
+    check_version(raw.version, &[gsync::NV_GSYNC_DELAY_VER1])?;
+    match raw.numLines <= MAX_DELAY_LINES {
+        true => Ok((raw.numLines, raw.numPixels)),
+        false => Err(Status::InvalidArgument),
+    }
+}
+
+pub extern "C" fn NvAPI_GSync_EnumSyncDevices(nvGSyncHandles: *mut [NvGSyncDeviceHandle; NVAPI_MAX_GSYNC_DEVICES], gsyncCount: *mut u32) -> NvAPI_Status {
+    call(Api::NvAPI_GSync_EnumSyncDevices, |state| {
+        let handles = out(nvGSyncHandles)?;
+        let count = out(gsyncCount)?;
+        let len = state.system.gsync_devices.len().min(NVAPI_MAX_GSYNC_DEVICES);
+        if len == 0 {
+            return Err(Status::NvidiaDeviceNotFound)
+        }
+        for (i, handle) in handles[..len].iter_mut().enumerate() {
+            *handle = gsync_handle(state, i);
+        }
+        *count = len as _;
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GSync_QueryCapabilities(hNvGSyncDevice: NvGSyncDeviceHandle, pNvGSyncCapabilities: *mut NV_GSYNC_CAPABILITIES) -> NvAPI_Status {
+    call(Api::NvAPI_GSync_QueryCapabilities, |state| {
+        let device = &state.system.gsync_devices[gsync_index(state, hNvGSyncDevice)?];
+        let caps = out(pNvGSyncCapabilities)?;
+        check_version(caps.version, &[gsync::NV_GSYNC_CAPABILITIES_VER1, gsync::NV_GSYNC_CAPABILITIES_VER2, gsync::NV_GSYNC_CAPABILITIES_VER3])?;
+        caps.boardId = device.board_id;
+        caps.revision = device.revision.0;
+        caps.capFlags = 0;
+        if caps.version != gsync::NV_GSYNC_CAPABILITIES_VER1 {
+            caps.extendedRevision = device.revision.1;
+        }
+        if caps.version == gsync::NV_GSYNC_CAPABILITIES_VER3 {
+            caps.flags = GSyncCapabilitiesFlags::IS_MUL_DIV_SUPPORTED.bits();
+            caps.maxMulDivValue = MAX_MUL_DIV as _;
+        }
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GSync_GetTopology(hNvGSyncDevice: NvGSyncDeviceHandle, gsyncGpuCount: *mut u32, gsyncGPUs: *mut NV_GSYNC_GPU, gsyncDisplayCount: *mut u32, gsyncDisplays: *mut NV_GSYNC_DISPLAY) -> NvAPI_Status {
+    call(Api::NvAPI_GSync_GetTopology, |state| {
+        let index = gsync_index(state, hNvGSyncDevice)?;
+        let device = &state.system.gsync_devices[index];
+
+        if !gsyncGpuCount.is_null() {
+            let count = out(gsyncGpuCount)?;
+            if !gsyncGPUs.is_null() {
+                let len = device.gpus.len().min(*count as usize);
+                let gpus = unsafe { std::slice::from_raw_parts_mut(gsyncGPUs, len) };
+                for (raw, &(gpu, connector)) in gpus.iter_mut().zip(&device.gpus) {
+                    check_version(raw.version, &[gsync::NV_GSYNC_GPU_VER1])?;
+                    raw.hPhysicalGpu = gpu_handle(state, gpu);
+                    raw.connector = connector.raw();
+                    raw.hProxyPhysicalGpu = Default::default();
+                    raw.flags = match gpu_synced(&state.system, device, gpu) {
+                        true => GSyncGpuFlags::IS_SYNCED.bits(),
+                        false => 0,
+                    };
+                }
+            }
+            *count = device.gpus.len() as _;
+        }
+
+        if !gsyncDisplayCount.is_null() {
+            let displays = displays(&state.system, device);
+            let count = out(gsyncDisplayCount)?;
+            if !gsyncDisplays.is_null() {
+                let len = displays.len().min(*count as usize);
+                let raw = unsafe { std::slice::from_raw_parts_mut(gsyncDisplays, len) };
+                for (raw, &(display_id, masterable)) in raw.iter_mut().zip(&displays) {
+                    check_version(raw.version, &[gsync::NV_GSYNC_DISPLAY_VER1])?;
+                    raw.displayId = display_id;
+                    raw.flags = match masterable {
+                        true => GSyncDisplayFlags::IS_MASTERABLE.bits(),
+                        false => 0,
+                    };
+                    raw.syncState = sync_state(device, display_id).raw();
+                }
+            }
+            *count = displays.len() as _;
+        }
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GSync_SetSyncStateSettings(gsyncDisplayCount: u32, pGsyncDisplays: *mut NV_GSYNC_DISPLAY, flags: u32) -> NvAPI_Status {
+    call(Api::NvAPI_GSync_SetSyncStateSettings, |state| {
+        if flags != 0 {
+            return Err(Status::InvalidArgument)
+        }
+        if state.system.gsync_devices.is_empty() {
+            return Err(Status::NvidiaDeviceNotFound)
+        }
+        let raw: &[NV_GSYNC_DISPLAY] = match gsyncDisplayCount {
+            0 => &[],
+            count => unsafe { std::slice::from_raw_parts(input(pGsyncDisplays)? as *const _, count as usize) },
+        };
+
+        let mut states = vec![BTreeMap::new(); state.system.gsync_devices.len()];
+        let mut master = false;
+        for raw in raw {
+            check_version(raw.version, &[gsync::NV_GSYNC_DISPLAY_VER1])?;
+            let sync_state = DisplaySyncState::from_raw(raw.syncState).map_err(|_| Status::InvalidArgument)?;
+            let (device, masterable) = state.system.gsync_devices.iter().enumerate()
+                .find_map(|(i, device)| displays(&state.system, device).into_iter()
+                    .find(|&(id, _)| id == raw.displayId)
+                    .map(|(_, masterable)| (i, masterable))
+                ).ok_or(Status::InvalidSyncTopology)?;
+            match sync_state {
+                DisplaySyncState::Master if master => return Err(Status::InvalidArgument),
+                DisplaySyncState::Master if !masterable => return Err(Status::InvalidSyncTopology),
+                DisplaySyncState::Master => master = true,
+                DisplaySyncState::Unsynced => continue,
+                _ => (),
+            }
+            states[device].insert(raw.displayId, sync_state);
+        }
+
+        for (device, states) in state.system.gsync_devices.iter_mut().zip(states) {
+            device.display_states = states;
+        }
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GSync_GetControlParameters(hNvGSyncDevice: NvGSyncDeviceHandle, pGsyncControls: *mut NV_GSYNC_CONTROL_PARAMS) -> NvAPI_Status {
+    call(Api::NvAPI_GSync_GetControlParameters, |state| {
+        let control = &state.system.gsync_devices[gsync_index(state, hNvGSyncDevice)?].control;
+        let raw = out(pGsyncControls)?;
+        check_version(raw.version, &[gsync::NV_GSYNC_CONTROL_PARAMS_VER1, gsync::NV_GSYNC_CONTROL_PARAMS_VER2])?;
+        raw.polarity = control.polarity.raw();
+        raw.vmode = control.video_mode.raw();
+        raw.interval = control.interval;
+        raw.source = control.source.raw();
+        let mut flags = GSyncControlFlags::empty();
+        flags.set(GSyncControlFlags::INTERLACE_MODE, control.interlace);
+        flags.set(GSyncControlFlags::SYNC_SOURCE_IS_OUTPUT, control.source_is_output);
+        raw.flags = flags.bits();
+        write_delay(&mut raw.syncSkew, control.skew)?;
+        write_delay(&mut raw.startupDelay, control.startup_delay)?;
+        if raw.version == gsync::NV_GSYNC_CONTROL_PARAMS_VER2 {
+            raw.multiplyDivideMode = control.multiply_divide.0.raw();
+            raw.multiplyDivideValue = control.multiply_divide.1;
+        }
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GSync_SetControlParameters(hNvGSyncDevice: NvGSyncDeviceHandle, pGsyncControls: *mut NV_GSYNC_CONTROL_PARAMS) -> NvAPI_Status {
+    call(Api::NvAPI_GSync_SetControlParameters, |state| {
+        let index = gsync_index(state, hNvGSyncDevice)?;
+        let raw = input(pGsyncControls)?;
+        check_version(raw.version, &[gsync::NV_GSYNC_CONTROL_PARAMS_VER1, gsync::NV_GSYNC_CONTROL_PARAMS_VER2])?;
+        let device = &state.system.gsync_devices[index];
+        let flags = GSyncControlFlags::from_bits(raw.flags).ok_or(Status::InvalidArgument)?;
+        let control = GSyncControl {
+            polarity: GSyncPolarity::from_raw(raw.polarity).map_err(|_| Status::InvalidArgument)?,
+            video_mode: GSyncVideoMode::from_raw(raw.vmode).map_err(|_| Status::InvalidArgument)?,
+            interval: raw.interval,
+            source: GSyncSource::from_raw(raw.source).map_err(|_| Status::InvalidArgument)?,
+            interlace: flags.contains(GSyncControlFlags::INTERLACE_MODE),
+            source_is_output: flags.contains(GSyncControlFlags::SYNC_SOURCE_IS_OUTPUT),
+            skew: read_delay(&raw.syncSkew)?,
+            startup_delay: read_delay(&raw.startupDelay)?,
+            multiply_divide: match raw.version {
+                gsync::NV_GSYNC_CONTROL_PARAMS_VER2 => (
+                    GSyncMultiplyDivideMode::from_raw(raw.multiplyDivideMode).map_err(|_| Status::InvalidArgument)?,
+                    raw.multiplyDivideValue,
+                ),
+                _ => device.control.multiply_divide,
+            },
+        };
+        if control.source_is_output && control.source != GSyncSource::Vsync {
+            return Err(Status::InvalidArgument)
+        }
+        if control.multiply_divide.1 > MAX_MUL_DIV {
+            return Err(Status::InvalidArgument)
+        }
+        if !device.display_states.values().any(|&state| state == DisplaySyncState::Master) {
+            return Err(Status::SyncMasterNotFound)
+        }
+
+        state.system.gsync_devices[index].control = control;
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GSync_GetSyncStatus(hNvGSyncDevice: NvGSyncDeviceHandle, hPhysicalGpu: NvPhysicalGpuHandle, status: *mut NV_GSYNC_STATUS) -> NvAPI_Status {
+    call(Api::NvAPI_GSync_GetSyncStatus, |state| {
+        let device = &state.system.gsync_devices[gsync_index(state, hNvGSyncDevice)?];
+        let gpu = gpu_index(state, hPhysicalGpu)?;
+        if !device.gpus.iter().any(|&(i, _)| i == gpu) {
+            return Err(Status::ExpectedPhysicalGpuHandle)
+        }
+        let status = out(status)?;
+        check_version(status.version, &[gsync::NV_GSYNC_STATUS_VER1])?;
+        let synced = gpu_synced(&state.system, device, gpu) as u32;
+        status.bIsSynced = synced;
+        status.bIsStereoSynced = synced;
+        status.bIsSyncSignalAvailable = signal_rate(&state.system, device).is_some() as u32;
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_GSync_GetStatusParameters(hNvGSyncDevice: NvGSyncDeviceHandle, pStatusParams: *mut NV_GSYNC_STATUS_PARAMS) -> NvAPI_Status {
+    call(Api::NvAPI_GSync_GetStatusParameters, |state| {
+        let device = &state.system.gsync_devices[gsync_index(state, hNvGSyncDevice)?];
+        let params = out(pStatusParams)?;
+        check_version(params.version, &[gsync::NV_GSYNC_STATUS_PARAMS_VER1, gsync::NV_GSYNC_STATUS_PARAMS_VER2])?;
+        params.refreshRate = signal_rate(&state.system, device).unwrap_or(0);
+        params.RJ45_IO = [device.rj45[0].raw(), device.rj45[1].raw()];
+        params.RJ45_Ethernet = [0; gsync::NVAPI_MAX_RJ45_PER_GSYNC];
+        params.houseSyncIncoming = device.house_sync;
+        params.bHouseSync = (device.house_sync != 0) as u32;
+        if params.version == gsync::NV_GSYNC_STATUS_PARAMS_VER2 {
+            params.flags = 0;
+        }
+        Ok(())
+    })
+}
@@ -0,0 +1,120 @@
+use nvapi_sys::{Api, Status, NvAPI_Status};
+use nvapi_sys::handles::NvPhysicalGpuHandle;
+use nvapi_sys::i2c::{self, NV_I2C_INFO};
+use nvapi_sys::i2c::private::{self as i2c_ex, NV_I2C_INFO_EX};
+use crate::{call, Result, I2cDevice, VirtualGpu};
+use crate::util::{gpu, out, check_version};
+
+/// The subset of an I2C request that the emulated bus cares about.
+struct Transaction {
+    address: u8,
+    port: Option<u8>,
+    register: usize,
+    data: *mut u8,
+    len: usize,
+}
+
+impl Transaction {
+    fn new(address: u8, port: Option<u8>, reg: *mut u8, reg_size: u32, data: *mut u8, len: u32) -> Result<Self> {
+        let reg_size = reg_size as usize;
+        if reg_size > i2c::NVAPI_MAX_SIZEOF_I2C_REG_ADDRESS || (reg.is_null() && reg_size != 0) {
+            return Err(Status::InvalidArgument)
+        }
+        let len = len as usize;
+        if len > i2c::NVAPI_MAX_SIZEOF_I2C_DATA_BUFFER {
+            return Err(Status::ArgumentExceedMaxSize)
+        }
+        if data.is_null() && len != 0 {
+            return Err(Status::InvalidArgument)
+        }
+
+        let register = match reg_size {
+            0 => 0,
+            _ => unsafe { std::slice::from_raw_parts(reg, reg_size) }
+                .iter().fold(0usize, |acc, &b| (acc << 8) | b as usize),
+        };
+
+        Ok(Transaction {
+            address: address >> 1,
+            port,
+            register,
+            data,
+            len,
+        })
+    }
+
+    fn device<'a>(&self, gpu: &'a mut VirtualGpu) -> Result<&'a mut I2cDevice> {
+        let port = self.port;
+        let address = self.address;
+        gpu.i2c.iter_mut()
+            .find(|d| d.address == address && (port.is_none() || d.port.is_none() || d.port == port))
+            .ok_or(Status::Error)
+    }
+
+    fn range(&self, device: &I2cDevice) -> Result<std::ops::Range<usize>> {
+        match self.register.checked_add(self.len) {
+            Some(end) if end <= device.registers.len() => Ok(self.register..end),
+            _ => Err(Status::Error),
+        }
+    }
+
+    fn read(&self, gpu: &mut VirtualGpu) -> Result<()> {
+        let device = self.device(gpu)?;
+        let range = self.range(device)?;
+        if self.len > 0 {
+            let data = unsafe { std::slice::from_raw_parts_mut(self.data, self.len) };
+            data.copy_from_slice(&device.registers[range]);
+        }
+        Ok(())
+    }
+
+    fn write(&self, gpu: &mut VirtualGpu) -> Result<()> {
+        let device = self.device(gpu)?;
+        let range = self.range(device)?;
+        if self.len > 0 {
+            let data = unsafe { std::slice::from_raw_parts(self.data, self.len) };
+            device.registers[range].copy_from_slice(data);
+        }
+        Ok(())
+    }
+}
+
+fn transaction(info: &NV_I2C_INFO) -> Result<Transaction> {
+    check_version(info.version, &[i2c::NV_I2C_INFO_VER1, i2c::NV_I2C_INFO_VER2, i2c::NV_I2C_INFO_VER3])?;
+    let port = if info.version == i2c::NV_I2C_INFO_VER3 && info.bIsPortIdSet != 0 { Some(info.portId) } else { None };
+    Transaction::new(info.i2cDevAddress, port, info.pbI2cRegAddress, info.regAddrSize, info.pbData, info.cbSize)
+}
+
+fn transaction_ex(info: &NV_I2C_INFO_EX) -> Result<Transaction> {
+    check_version(info.version, &[i2c_ex::NV_I2C_INFO_EX_VER3])?;
+    let port = if info.bIsPortIdSet != 0 { Some(info.portId) } else { None };
+    Transaction::new(info.i2cDevAddress, port, info.pbI2cRegAddress, info.regAddrSize, info.pbData, info.cbSize)
+}
+
+pub extern "C" fn NvAPI_I2CRead(hPhysicalGpu: NvPhysicalGpuHandle, pI2cInfo: *mut NV_I2C_INFO) -> NvAPI_Status {
+    call(Api::NvAPI_I2CRead, |state| {
+        let transaction = transaction(out(pI2cInfo)?)?;
+        transaction.read(gpu(state, hPhysicalGpu)?)
+    })
+}
+
+pub extern "C" fn NvAPI_I2CWrite(hPhysicalGpu: NvPhysicalGpuHandle, pI2cInfo: *mut NV_I2C_INFO) -> NvAPI_Status {
+    call(Api::NvAPI_I2CWrite, |state| {
+        let transaction = transaction(out(pI2cInfo)?)?;
+        transaction.write(gpu(state, hPhysicalGpu)?)
+    })
+}
+
+pub extern "C" fn NvAPI_I2CReadEx(hPhysicalGpu: NvPhysicalGpuHandle, pI2cInfo: *mut NV_I2C_INFO_EX, _pData: *mut u32) -> NvAPI_Status {
+    call(Api::NvAPI_I2CReadEx, |state| {
+        let transaction = transaction_ex(out(pI2cInfo)?)?;
+        transaction.read(gpu(state, hPhysicalGpu)?)
+    })
+}
+
+pub extern "C" fn NvAPI_I2CWriteEx(hPhysicalGpu: NvPhysicalGpuHandle, pI2cInfo: *mut NV_I2C_INFO_EX, _pData: *mut u32) -> NvAPI_Status {
+    call(Api::NvAPI_I2CWriteEx, |state| {
+        let transaction = transaction_ex(out(pI2cInfo)?)?;
+        transaction.write(gpu(state, hPhysicalGpu)?)
+    })
+}
@@ -0,0 +1,169 @@
+use std::os::raw::c_void;
+use nvapi_sys::Api;
+use crate::{driver, gpu, display, i2c, drs, mosaic, gsync, event};
+
+/// Resolves an interface id to an emulated function.
+///
+/// Functions that aren't emulated resolve to null, just as they would against a
+/// driver that doesn't export them.
+pub extern "C" fn nvapi_QueryInterface(id: u32) -> *const c_void {
+    macro_rules! interface {
+        ($($module:ident::$fn:ident,)*) => {
+            match Api::from_id(id) {
+                $(
+                    Ok(Api::$fn) => $module::$fn as *const c_void,
+                )*
+                _ => std::ptr::null(),
+            }
+        };
+    }
+
+    interface! {
+        driver::NvAPI_Initialize,
+        driver::NvAPI_Unload,
+        driver::NvAPI_GetErrorMessage,
+        driver::NvAPI_GetInterfaceVersionString,
+        driver::NvAPI_SYS_GetDriverAndBranchVersion,
+        gpu::NvAPI_EnumPhysicalGPUs,
+        gpu::NvAPI_EnumLogicalGPUs,
+        gpu::NvAPI_GetPhysicalGPUsFromLogicalGPU,
+        gpu::NvAPI_GetLogicalGPUFromPhysicalGPU,
+        gpu::NvAPI_GPU_GetFullName,
+        gpu::NvAPI_GPU_GetShortName,
+        gpu::NvAPI_GPU_GetVbiosVersionString,
+        gpu::NvAPI_GPU_GetPhysicalFrameBufferSize,
+        gpu::NvAPI_GPU_GetPCIIdentifiers,
+        gpu::NvAPI_GPU_GetBusId,
+        gpu::NvAPI_GPU_GetBusSlotId,
+        gpu::NvAPI_GPU_GetBusType,
+        gpu::NvAPI_GPU_GetIRQ,
+        gpu::NvAPI_GPU_GetCurrentPCIEDownstreamWidth,
+        gpu::NvAPI_GPU_GetPCIEInfo,
+        gpu::NvAPI_GPU_GetSystemType,
+        gpu::NvAPI_GPU_GetShaderSubPipeCount,
+        gpu::NvAPI_GPU_GetShaderPipeCount,
+        gpu::NvAPI_GPU_GetGpuCoreCount,
+        gpu::NvAPI_GPU_GetBoardInfo,
+        gpu::NvAPI_GPU_GetPerfDecreaseInfo,
+        gpu::NvAPI_GPU_GetRamType,
+        gpu::NvAPI_GPU_GetRamMaker,
+        gpu::NvAPI_GPU_GetRamBusWidth,
+        gpu::NvAPI_GPU_GetRamBankCount,
+        gpu::NvAPI_GPU_GetPartitionCount,
+        gpu::NvAPI_GPU_GetFoundry,
+        gpu::NvAPI_GPU_GetFBWidthAndLocation,
+        gpu::NvAPI_GetGPUIDFromPhysicalGPU,
+        gpu::NvAPI_GetDriverModel,
+        gpu::NvAPI_GPU_GetMemoryInfo,
+        gpu::NvAPI_GetDisplayDriverMemoryInfo,
+        gpu::NvAPI_GPU_GetMemoryInfoEx,
+        gpu::NvAPI_GPU_GetECCStatusInfo,
+        gpu::NvAPI_GPU_GetECCErrorInfo,
+        gpu::NvAPI_GPU_ResetECCErrorInfo,
+        gpu::NvAPI_GPU_GetECCConfigurationInfo,
+        gpu::NvAPI_GPU_SetECCConfiguration,
+        gpu::NvAPI_GPU_GetAllClockFrequencies,
+        gpu::NvAPI_GPU_GetAllClocks,
+        gpu::NvAPI_GPU_GetUsages,
+        gpu::NvAPI_GPU_GetDynamicPstatesInfoEx,
+        gpu::NvAPI_GPU_GetCurrentPstate,
+        gpu::NvAPI_GPU_GetPstates20,
+        gpu::NvAPI_GPU_SetPstates20,
+        gpu::NvAPI_GPU_ClockClientClkVfPointsGetInfo,
+        gpu::NvAPI_GPU_ClockClientClkVfPointsGetControl,
+        gpu::NvAPI_GPU_ClockClientClkVfPointsSetControl,
+        gpu::NvAPI_GPU_ClockClientClkVfPointsGetStatus,
+        gpu::NvAPI_GPU_ClockClientClkDomainsGetInfo,
+        gpu::NvAPI_GPU_PerfClientLimitsGetStatus,
+        gpu::NvAPI_GPU_PerfClientLimitsSetStatus,
+        gpu::NvAPI_GPU_ClientVoltRailsGetStatus,
+        gpu::NvAPI_GPU_ClientVoltRailsGetControl,
+        gpu::NvAPI_GPU_ClientVoltRailsSetControl,
+        gpu::NvAPI_GPU_ClientPowerPoliciesGetInfo,
+        gpu::NvAPI_GPU_ClientPowerPoliciesGetStatus,
+        gpu::NvAPI_GPU_ClientPowerPoliciesSetStatus,
+        gpu::NvAPI_GPU_ClientPowerTopologyGetStatus,
+        gpu::NvAPI_GPU_PerfPoliciesGetInfo,
+        gpu::NvAPI_GPU_PerfPoliciesGetStatus,
+        gpu::NvAPI_GPU_GetVoltageDomainsStatus,
+        gpu::NvAPI_GPU_GetVoltageStep,
+        gpu::NvAPI_GPU_GetVoltages,
+        gpu::NvAPI_GPU_GetThermalSettings,
+        gpu::NvAPI_GPU_ClientThermalPoliciesGetInfo,
+        gpu::NvAPI_GPU_ClientThermalPoliciesGetStatus,
+        gpu::NvAPI_GPU_ClientThermalPoliciesSetStatus,
+        gpu::NvAPI_GPU_GetTachReading,
+        gpu::NvAPI_GPU_GetCoolerSettings,
+        gpu::NvAPI_GPU_SetCoolerLevels,
+        gpu::NvAPI_GPU_RestoreCoolerSettings,
+        gpu::NvAPI_GPU_GetCoolerPolicyTable,
+        gpu::NvAPI_GPU_SetCoolerPolicyTable,
+        gpu::NvAPI_GPU_RestoreCoolerPolicyTable,
+        display::NvAPI_GPU_GetConnectedDisplayIds,
+        display::NvAPI_GPU_GetAllDisplayIds,
+        display::NvAPI_EnumNvidiaDisplayHandle,
+        display::NvAPI_EnumNvidiaUnAttachedDisplayHandle,
+        display::NvAPI_GetAssociatedNvidiaDisplayHandle,
+        display::NvAPI_DISP_GetAssociatedUnAttachedNvidiaDisplayHandle,
+        display::NvAPI_GetLogicalGPUFromDisplay,
+        display::NvAPI_GetAssociatedNvidiaDisplayName,
+        display::NvAPI_GetUnAttachedAssociatedDisplayName,
+        display::NvAPI_GetPhysicalGPUsFromDisplay,
+        display::NvAPI_GetPhysicalGPUFromUnAttachedDisplay,
+        display::NvAPI_DISP_GetDisplayIdByDisplayName,
+        display::NvAPI_GPU_GetEDID,
+        display::NvAPI_GPU_SetEDID,
+        display::NvAPI_DISP_GetDisplayConfig,
+        display::NvAPI_DISP_SetDisplayConfig,
+        i2c::NvAPI_I2CRead,
+        i2c::NvAPI_I2CWrite,
+        i2c::NvAPI_I2CReadEx,
+        i2c::NvAPI_I2CWriteEx,
+        drs::NvAPI_DRS_CreateSession,
+        drs::NvAPI_DRS_DestroySession,
+        drs::NvAPI_DRS_LoadSettings,
+        drs::NvAPI_DRS_SaveSettings,
+        drs::NvAPI_DRS_CreateProfile,
+        drs::NvAPI_DRS_DeleteProfile,
+        drs::NvAPI_DRS_SetCurrentGlobalProfile,
+        drs::NvAPI_DRS_GetCurrentGlobalProfile,
+        drs::NvAPI_DRS_GetProfileInfo,
+        drs::NvAPI_DRS_SetProfileInfo,
+        drs::NvAPI_DRS_FindProfileByName,
+        drs::NvAPI_DRS_EnumProfiles,
+        drs::NvAPI_DRS_GetNumProfiles,
+        drs::NvAPI_DRS_CreateApplication,
+        drs::NvAPI_DRS_DeleteApplicationEx,
+        drs::NvAPI_DRS_DeleteApplication,
+        drs::NvAPI_DRS_GetApplicationInfo,
+        drs::NvAPI_DRS_EnumApplications,
+        drs::NvAPI_DRS_FindApplicationByName,
+        drs::NvAPI_DRS_SetSetting,
+        drs::NvAPI_DRS_GetSetting,
+        drs::NvAPI_DRS_EnumSettings,
+        drs::NvAPI_DRS_EnumAvailableSettingIds,
+        drs::NvAPI_DRS_GetSettingIdFromName,
+        drs::NvAPI_DRS_GetSettingNameFromId,
+        drs::NvAPI_DRS_DeleteProfileSetting,
+        drs::NvAPI_DRS_RestoreAllDefaults,
+        drs::NvAPI_DRS_RestoreProfileDefault,
+        drs::NvAPI_DRS_RestoreProfileDefaultSetting,
+        drs::NvAPI_DRS_GetBaseProfile,
+        mosaic::NvAPI_Mosaic_GetOverlapLimits,
+        mosaic::NvAPI_Mosaic_EnumDisplayModes,
+        mosaic::NvAPI_Mosaic_EnumDisplayGrids,
+        mosaic::NvAPI_Mosaic_SetDisplayGrids,
+        mosaic::NvAPI_Mosaic_ValidateDisplayGrids,
+        mosaic::NvAPI_Mosaic_GetDisplayViewportsByResolution,
+        gsync::NvAPI_GSync_EnumSyncDevices,
+        gsync::NvAPI_GSync_QueryCapabilities,
+        gsync::NvAPI_GSync_GetTopology,
+        gsync::NvAPI_GSync_SetSyncStateSettings,
+        gsync::NvAPI_GSync_GetControlParameters,
+        gsync::NvAPI_GSync_SetControlParameters,
+        gsync::NvAPI_GSync_GetSyncStatus,
+        gsync::NvAPI_GSync_GetStatusParameters,
+        event::NvAPI_Event_RegisterCallback,
+        event::NvAPI_Event_UnregisterCallback,
+    }
+}
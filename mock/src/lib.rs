@@ -0,0 +1,215 @@
+#![allow(non_snake_case)]
+#![doc(html_root_url = "http://docs.rs/nvapi-mock/0.2.0")]
+
+//! A software NVAPI implementation that drives one or more configurable virtual GPUs.
+//!
+//! Calling [`install`] points `nvapi_sys` at this crate's `nvapi_QueryInterface`,
+//! after which the regular `nvapi` and `nvapi-hi` APIs can be exercised on machines
+//! without NVIDIA hardware (or on platforms the driver doesn't support at all).
+//!
+//! The emulated driver state is global, so only one [`Mock`] may exist at a time;
+//! `install` blocks until any previous instance has been dropped.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::{Mutex, MutexGuard};
+use std::panic::{self, AssertUnwindSafe};
+use std::thread;
+use nvapi_sys::{self as sys, Api, Status, NvAPI_Status};
+use nvapi_sys::event::{QSyncEvent, NV_QSYNC_EVENT_DATA};
+
+pub mod model;
+pub use model::*;
+
+mod interface;
+mod util;
+mod driver;
+mod gpu;
+mod display;
+mod i2c;
+mod drs;
+mod mosaic;
+mod gsync;
+mod event;
+
+/// Emulated functions deal in plain status codes.
+type Result<T> = std::result::Result<T, Status>;
+
+/// Per-installation driver state.
+struct State {
+    system: System,
+    refcount: u32,
+    /// Bumped whenever handles are invalidated.
+    generation: usize,
+    overrides: BTreeMap<Api, NvAPI_Status>,
+    rejected_versions: BTreeMap<Api, BTreeSet<u32>>,
+    calls: Vec<Api>,
+    /// Open driver settings sessions, indexed by handle; closed ones are left as `None`.
+    drs_sessions: Vec<Option<drs::Session>>,
+    /// Registered event callbacks, indexed by handle; unregistered ones are left as `None`.
+    event_callbacks: Vec<event::Registration>,
+}
+
+impl State {
+    fn new(system: System) -> Self {
+        State {
+            system,
+            refcount: 0,
+            generation: 0,
+            overrides: Default::default(),
+            rejected_versions: Default::default(),
+            calls: Default::default(),
+            drs_sessions: Default::default(),
+            event_callbacks: Default::default(),
+        }
+    }
+}
+
+static STATE: Mutex<Option<State>> = Mutex::new(None);
+static INSTALL: Mutex<()> = Mutex::new(());
+
+fn lock<T>(m: &'static Mutex<T>) -> MutexGuard<'static, T> {
+    m.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Dispatches an emulated API call against the installed state.
+///
+/// Never unwinds across the FFI boundary; a panicking implementation reports `NVAPI_ERROR`.
+fn call<F: FnOnce(&mut State) -> Result<()>>(api: Api, f: F) -> NvAPI_Status {
+    let mut state = lock(&STATE);
+    let state = match *state {
+        Some(ref mut state) => state,
+        None => return sys::status::NVAPI_LIBRARY_NOT_FOUND,
+    };
+
+    state.calls.push(api);
+    if let Some(&status) = state.overrides.get(&api) {
+        return status
+    }
+
+    match api {
+        Api::NvAPI_Initialize | Api::NvAPI_GetErrorMessage => (),
+        _ if state.refcount == 0 => return sys::status::NVAPI_API_NOT_INITIALIZED,
+        _ => (),
+    }
+
+    let rejected = state.rejected_versions.get(&api).cloned().unwrap_or_default();
+    let res = util::with_rejected_versions(rejected, || panic::catch_unwind(AssertUnwindSafe(|| f(state))));
+    match res {
+        Ok(Ok(())) => sys::status::NVAPI_OK,
+        Ok(Err(e)) => e.raw(),
+        Err(..) => sys::status::NVAPI_ERROR,
+    }
+}
+
+/// Installs the emulated driver, returning a guard that owns it.
+///
+/// Functions already resolved through the library or a previous interface are
+/// resolved again through the mock on their next call.
+pub fn install(system: System) -> Mock {
+    let guard = lock(&INSTALL);
+
+    *lock(&STATE) = Some(State::new(system));
+    unsafe {
+        sys::nvapi::set_query_interface(interface::nvapi_QueryInterface);
+    }
+
+    Mock {
+        _guard: guard,
+    }
+}
+
+/// An installed emulated driver.
+///
+/// The driver is torn down when this is dropped, after which every NVAPI call
+/// fails with `NVAPI_LIBRARY_NOT_FOUND`.
+pub struct Mock {
+    _guard: MutexGuard<'static, ()>,
+}
+
+impl Mock {
+    fn state<R, F: FnOnce(&mut State) -> R>(&self, f: F) -> R {
+        f(lock(&STATE).as_mut().expect("mock state missing"))
+    }
+
+    /// Inspects or modifies the emulated system.
+    pub fn with<R, F: FnOnce(&mut System) -> R>(&self, f: F) -> R {
+        self.state(|state| f(&mut state.system))
+    }
+
+    /// A snapshot of the emulated system.
+    pub fn system(&self) -> System {
+        self.with(|system| system.clone())
+    }
+
+    /// Forces every call to `api` to fail with `status`.
+    pub fn fail(&self, api: Api, status: Status) {
+        self.fail_raw(api, status.raw())
+    }
+
+    /// Forces every call to `api` to return `status`, which need not be a known status code.
+    pub fn fail_raw(&self, api: Api, status: NvAPI_Status) {
+        self.state(|state| state.overrides.insert(api, status));
+    }
+
+    /// Makes `api` refuse structures tagged with `version`, as a driver that predates it would.
+    pub fn reject_version(&self, api: Api, version: u32) {
+        self.state(|state| state.rejected_versions.entry(api).or_default().insert(version));
+    }
+
+    /// Removes all injected failures and rejected versions.
+    pub fn clear_failures(&self) {
+        self.state(|state| {
+            state.overrides.clear();
+            state.rejected_versions.clear();
+        })
+    }
+
+    /// Simulates a mode set or topology change.
+    ///
+    /// Every GPU handle returned so far fails with `NVAPI_HANDLE_INVALIDATED`, and
+    /// enumeration hands out new ones.
+    pub fn invalidate_handles(&self) {
+        self.state(|state| state.generation += 1)
+    }
+
+    /// Every emulated function called so far, in order.
+    pub fn calls(&self) -> Vec<Api> {
+        self.state(|state| state.calls.clone())
+    }
+
+    pub fn clear_calls(&self) {
+        self.state(|state| state.calls.clear())
+    }
+
+    /// The number of driver settings sessions that haven't been destroyed.
+    pub fn drs_sessions(&self) -> usize {
+        self.state(|state| state.drs_sessions.iter().filter(|session| session.is_some()).count())
+    }
+
+    /// The number of event callbacks that haven't been unregistered.
+    pub fn event_callbacks(&self) -> usize {
+        self.state(|state| state.event_callbacks.len())
+    }
+
+    /// Delivers a sync event to every registered callback, from a thread of its own as
+    /// the driver does, and waits for them to return.
+    pub fn fire_sync_event(&self, event: QSyncEvent) {
+        let registrations: Vec<_> = self.state(|state| state.event_callbacks.clone());
+        let mut data = NV_QSYNC_EVENT_DATA::zeroed();
+        data.eventType = event.raw();
+        thread::spawn(move || for registration in registrations {
+            (registration.callback)(data, registration.param as *mut _);
+        }).join().expect("event callback panicked")
+    }
+
+    /// The number of outstanding `NvAPI_Initialize` calls.
+    pub fn refcount(&self) -> u32 {
+        self.state(|state| state.refcount)
+    }
+}
+
+impl Drop for Mock {
+    fn drop(&mut self) {
+        *lock(&STATE) = None;
+    }
+}
@@ -0,0 +1,914 @@
+use std::collections::BTreeMap;
+use nvapi_sys::gpu::{SystemType, PerformanceDecreaseReason, BusType};
+use nvapi_sys::gpu::private::{RamType, RamMaker, Foundry};
+use nvapi_sys::gpu::clock::PublicClockId;
+use nvapi_sys::gpu::clock::private::ClockLockMode;
+use nvapi_sys::gpu::pstate::{PstateId, UtilizationDomain, VoltageInfoDomain};
+use nvapi_sys::gpu::power::private::PerfFlags;
+use nvapi_sys::gpu::thermal::{ThermalController, ThermalTarget};
+use nvapi_sys::gpu::cooler::private::{CoolerType, CoolerController, CoolerPolicy, CoolerTarget, CoolerControl};
+use nvapi_sys::gpu::display::{MonitorConnectorType, DisplayIdsFlags};
+use nvapi_sys::gpu::ecc::EccConfiguration;
+use nvapi_sys::dispcontrol::{Rotation, Scaling, TimingOverride, NV_TIMING};
+use nvapi_sys::mosaic::{GridTopologyFlags, PixelShiftType};
+use nvapi_sys::gsync::{DisplaySyncState, GSyncConnector, GSyncPolarity, GSyncVideoMode, GSyncSource, GSyncMultiplyDivideMode, GSyncRj45Io};
+use nvapi_sys::drs::GpuSupport;
+use nvapi_sys::driversettings::{VSYNCMODE_ID, VSYNCMODE_PASSIVE, PREFERRED_PSTATE_ID, PREFERRED_PSTATE_PREFER_MAX};
+
+/// The EDID of the default display: a 1920x1080 panel with a CTA-861 extension
+/// advertising audio, HDR static metadata and a 48-144 Hz VRR range.
+pub const MOCK_EDID: [u8; 256] = [
+    0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00, 0x3a, 0xcd, 0x34, 0x12, 0x04, 0x03, 0x02, 0x01,
+    0x0c, 0x1c, 0x01, 0x04, 0xa5, 0x3c, 0x22, 0x78, 0x3a, 0xee, 0x91, 0xa3, 0x54, 0x4c, 0x99, 0x26,
+    0x0f, 0x50, 0x54, 0x21, 0x08, 0x00, 0xd1, 0xc0, 0x81, 0xc0, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01,
+    0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x02, 0x3a, 0x80, 0x18, 0x71, 0x38, 0x2d, 0x40, 0x58, 0x2c,
+    0x45, 0x00, 0x58, 0x54, 0x21, 0x00, 0x00, 0x1e, 0x00, 0x00, 0x00, 0xfd, 0x00, 0x30, 0x90, 0x1e,
+    0xa0, 0x3c, 0x00, 0x0a, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20, 0x00, 0x00, 0x00, 0xfc, 0x00, 0x4d,
+    0x6f, 0x63, 0x6b, 0x20, 0x44, 0x69, 0x73, 0x70, 0x6c, 0x61, 0x79, 0x0a, 0x00, 0x00, 0x00, 0xff,
+    0x00, 0x4d, 0x4f, 0x43, 0x4b, 0x30, 0x30, 0x30, 0x31, 0x0a, 0x20, 0x20, 0x20, 0x20, 0x01, 0x78,
+    0x02, 0x03, 0x23, 0x71, 0x44, 0x90, 0x04, 0x03, 0x61, 0x23, 0x09, 0x07, 0x07, 0x83, 0x01, 0x00,
+    0x00, 0x6a, 0xd8, 0x5d, 0xc4, 0x01, 0x78, 0x80, 0x00, 0x00, 0x30, 0x90, 0xe6, 0x06, 0x05, 0x01,
+    0x60, 0x50, 0x20, 0x01, 0x1d, 0x00, 0x72, 0x51, 0xd0, 0x1e, 0x20, 0x6e, 0x28, 0x55, 0x00, 0x58,
+    0x54, 0x21, 0x00, 0x00, 0x1e, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xca,
+];
+
+/// The state of the whole emulated driver installation.
+#[derive(Debug, Clone)]
+pub struct System {
+    pub driver_version: u32,
+    pub driver_branch: String,
+    pub interface_version: String,
+    pub gpus: Vec<VirtualGpu>,
+    /// Indices into `gpus` that are linked into a single logical GPU, as with SLI.
+    ///
+    /// Any GPU not listed here is a logical GPU of its own.
+    pub sli_groups: Vec<Vec<usize>>,
+    /// The desktop layout reported by `NvAPI_DISP_GetDisplayConfig`.
+    pub display_config: Vec<DisplayPath>,
+    /// The Mosaic grids spanning a path across more than one display.
+    ///
+    /// A grid only applies while a path shows exactly its displays; every other
+    /// display is reported as a 1x1 grid of its own.
+    pub mosaic_grids: Vec<MosaicGrid>,
+    /// G-SYNC and Quadro Sync boards, none by default.
+    pub gsync_devices: Vec<GSyncDevice>,
+    /// The saved driver settings, starting with the base profile.
+    pub drs_profiles: Vec<DrsProfile>,
+    /// The name of the profile applied to applications without one of their own.
+    pub drs_global_profile: String,
+}
+
+impl Default for System {
+    fn default() -> Self {
+        System {
+            driver_version: 41109,
+            driver_branch: "r410_00".into(),
+            interface_version: "NVAPI Open Source Interface".into(),
+            gpus: vec![VirtualGpu::default()],
+            sli_groups: Vec::new(),
+            display_config: vec![DisplayPath::default()],
+            mosaic_grids: Vec::new(),
+            gsync_devices: Vec::new(),
+            drs_profiles: DrsProfile::defaults(),
+            drs_global_profile: DRS_BASE_PROFILE.into(),
+        }
+    }
+}
+
+impl System {
+    pub fn new(gpus: Vec<VirtualGpu>) -> Self {
+        System {
+            gpus,
+            .. Default::default()
+        }
+    }
+
+    /// The physical GPU indices making up each logical GPU, ordered by their first member.
+    pub fn logical_gpus(&self) -> Vec<Vec<usize>> {
+        let mut logical: Vec<Vec<usize>> = Vec::new();
+        for gpu in 0..self.gpus.len() {
+            if logical.iter().any(|group| group.contains(&gpu)) {
+                continue
+            }
+            logical.push(match self.sli_groups.iter().find(|group| group.contains(&gpu)) {
+                Some(group) => group.iter().cloned().filter(|&i| i < self.gpus.len()).collect(),
+                None => vec![gpu],
+            });
+        }
+        logical
+    }
+}
+
+/// A configurable virtual GPU.
+///
+/// Values are stored in the units NVAPI itself reports (kHz, uV, KiB, percent),
+/// the emulated functions take care of any driver-specific encoding.
+#[derive(Debug, Clone)]
+pub struct VirtualGpu {
+    pub full_name: String,
+    pub short_name: String,
+    pub vbios_version: String,
+    pub gpu_id: u32,
+    pub driver_model: u32,
+    pub pci: PciIdentifiers,
+    pub bus_id: u32,
+    pub bus_slot_id: u32,
+    pub bus_type: BusType,
+    pub irq: u32,
+    pub pcie: Pcie,
+    pub board_number: [u8; 0x10],
+    pub system_type: SystemType,
+    pub core_count: u32,
+    pub shader_pipe_count: u32,
+    pub shader_sub_pipe_count: u32,
+    pub ram_type: RamType,
+    pub ram_maker: RamMaker,
+    pub ram_bus_width: u32,
+    pub ram_bank_count: u32,
+    pub ram_partition_count: u32,
+    pub foundry: Foundry,
+    pub frame_buffer_width: u32,
+    pub frame_buffer_location: u32,
+    pub memory: Memory,
+    pub clocks: Clocks,
+    pub current_pstate: PstateId,
+    pub pstates_editable: bool,
+    pub pstates: Vec<PState>,
+    pub overvolt: Vec<BaseVoltage>,
+    /// Reported as bit 0 of `NV_GPU_DYNAMIC_PSTATES_INFO_EX::flags`
+    pub dynamic_pstates: bool,
+    pub utilization: BTreeMap<UtilizationDomain, u32>,
+    pub perf_decrease: PerformanceDecreaseReason,
+    pub sensors: Vec<Sensor>,
+    pub thermal_limits: Vec<ThermalLimit>,
+    pub tachometer: u32,
+    pub coolers: Vec<Cooler>,
+    pub power_limits: Vec<PowerLimit>,
+    /// Current power draw per rail, in `1/1000`ths of a percent of TDP
+    pub power_usage: Vec<u32>,
+    pub perf_limit_support: PerfFlags,
+    pub perf_limits: PerfFlags,
+    pub perf_status_unknown: u32,
+    pub core_voltage: u32,
+    pub core_voltage_boost: u32,
+    pub voltage_table: Vec<u32>,
+    pub vfp: Vec<VfPoint>,
+    pub vfp_memory: Vec<VfPoint>,
+    pub vfp_ranges: Vec<VfpRange>,
+    pub vfp_locks: Vec<VfpLock>,
+    pub displays: Vec<Display>,
+    pub i2c: Vec<I2cDevice>,
+    /// `None` for boards without ECC memory
+    pub ecc: Option<Ecc>,
+}
+
+impl Default for VirtualGpu {
+    /// A GeForce GTX 1080 Founders Edition, more or less.
+    fn default() -> Self {
+        let vfp: Vec<_> = (0..80u32).map(|i| VfPoint {
+            frequency: 139000 + i * 25000,
+            voltage: 700000 + i * 6250,
+            delta: 0,
+        }).collect();
+
+        VirtualGpu {
+            full_name: "GeForce GTX 1080".into(),
+            short_name: "GP104".into(),
+            vbios_version: "86.04.17.00.01".into(),
+            gpu_id: 0x100,
+            driver_model: 0x2000,
+            pci: PciIdentifiers {
+                device_id: 0x1b8010de,
+                subsystem_id: 0x119e10de,
+                revision_id: 0xa1,
+                ext_device_id: 0x1b80,
+            },
+            bus_id: 1,
+            bus_slot_id: 0,
+            bus_type: BusType::PciExpress,
+            irq: 16,
+            pcie: Pcie {
+                current: (8000, 16),
+                levels: vec![(8000, 16), (2500, 16)],
+            },
+            board_number: *b"0324416012345\0\0\0",
+            system_type: SystemType::Desktop,
+            core_count: 2560,
+            shader_pipe_count: 2560,
+            shader_sub_pipe_count: 20,
+            ram_type: RamType::GDDR5X,
+            ram_maker: RamMaker::Micron,
+            ram_bus_width: 256,
+            ram_bank_count: 8,
+            ram_partition_count: 8,
+            foundry: Foundry::TSMC,
+            frame_buffer_width: 256,
+            frame_buffer_location: 0,
+            memory: Memory {
+                dedicated: 8 * 1024 * 1024,
+                dedicated_available: 8 * 1024 * 1024 - 65536,
+                system: 0,
+                shared: 8 * 1024 * 1024,
+                dedicated_available_current: 7 * 1024 * 1024,
+                dedicated_evictions_size: 0,
+                dedicated_evictions: 0,
+                dedicated_promotions_size: 0,
+                dedicated_promotions: 0,
+            },
+            clocks: Clocks {
+                current: vec![
+                    (PublicClockId::Graphics, 1733500),
+                    (PublicClockId::Memory, 5005000),
+                    (PublicClockId::Video, 1620500),
+                ].into_iter().collect(),
+                base: vec![
+                    (PublicClockId::Graphics, 1607000),
+                    (PublicClockId::Memory, 5005000),
+                ].into_iter().collect(),
+                boost: vec![
+                    (PublicClockId::Graphics, 1733000),
+                    (PublicClockId::Memory, 5005000),
+                ].into_iter().collect(),
+            },
+            current_pstate: PstateId::P0,
+            pstates_editable: true,
+            pstates: vec![
+                PState {
+                    id: PstateId::P0,
+                    editable: true,
+                    clocks: vec![
+                        PStateClock {
+                            domain: PublicClockId::Graphics,
+                            editable: true,
+                            delta: Delta { value: 0, min: -1000000, max: 1000000 },
+                            frequency: PStateFrequency::Range {
+                                min: 139000,
+                                max: 2100000,
+                                voltage_domain: VoltageInfoDomain::Core,
+                                min_voltage: 600000,
+                                max_voltage: 1200000,
+                            },
+                        },
+                        PStateClock {
+                            domain: PublicClockId::Memory,
+                            editable: true,
+                            delta: Delta { value: 0, min: -1000000, max: 1000000 },
+                            frequency: PStateFrequency::Single(5005000),
+                        },
+                    ],
+                    base_voltages: Vec::new(),
+                },
+                PState {
+                    id: PstateId::P8,
+                    editable: false,
+                    clocks: vec![
+                        PStateClock {
+                            domain: PublicClockId::Graphics,
+                            editable: false,
+                            delta: Delta::default(),
+                            frequency: PStateFrequency::Range {
+                                min: 139000,
+                                max: 1000000,
+                                voltage_domain: VoltageInfoDomain::Core,
+                                min_voltage: 600000,
+                                max_voltage: 800000,
+                            },
+                        },
+                        PStateClock {
+                            domain: PublicClockId::Memory,
+                            editable: false,
+                            delta: Delta::default(),
+                            frequency: PStateFrequency::Single(405000),
+                        },
+                    ],
+                    base_voltages: Vec::new(),
+                },
+            ],
+            overvolt: vec![
+                BaseVoltage {
+                    domain: VoltageInfoDomain::Core,
+                    editable: true,
+                    voltage: 1050000,
+                    delta: Delta { value: 0, min: 0, max: 100000 },
+                },
+            ],
+            dynamic_pstates: false,
+            utilization: vec![
+                (UtilizationDomain::Graphics, 12),
+                (UtilizationDomain::FrameBuffer, 5),
+                (UtilizationDomain::VideoEngine, 0),
+                (UtilizationDomain::BusInterface, 1),
+            ].into_iter().collect(),
+            perf_decrease: PerformanceDecreaseReason::NONE,
+            sensors: vec![
+                Sensor {
+                    controller: ThermalController::GpuInternal,
+                    target: ThermalTarget::Gpu,
+                    default_range: (0, 127),
+                    current: 45,
+                },
+            ],
+            thermal_limits: vec![
+                ThermalLimit {
+                    controller: ThermalController::GpuInternal,
+                    min: 60,
+                    default: 83,
+                    max: 92,
+                    current: 83,
+                    flags: 1,
+                },
+            ],
+            tachometer: 1100,
+            coolers: vec![
+                Cooler {
+                    kind: CoolerType::Fan,
+                    controller: CoolerController::Internal,
+                    default_range: (0, 100),
+                    current_range: (0, 100),
+                    default_level: 30,
+                    level: 30,
+                    default_policy: CoolerPolicy::TemperatureContinuous,
+                    policy: CoolerPolicy::TemperatureContinuous,
+                    target: CoolerTarget::All,
+                    control: CoolerControl::Variable,
+                    active: true,
+                    policy_levels: (0..4).map(|i| PolicyLevel {
+                        id: i,
+                        default_level: 30 + i * 20,
+                        level: 30 + i * 20,
+                    }).collect(),
+                },
+            ],
+            power_limits: vec![
+                PowerLimit {
+                    pstate: PstateId::P0,
+                    min: 50000,
+                    default: 100000,
+                    max: 120000,
+                    current: 100000,
+                },
+            ],
+            power_usage: vec![35000],
+            perf_limit_support: PerfFlags::POWER_LIMIT | PerfFlags::THERMAL_LIMIT | PerfFlags::VOLTAGE_REL_LIMIT | PerfFlags::VOLTAGE_OP_LIMIT | PerfFlags::NO_LOAD_LIMIT,
+            perf_limits: PerfFlags::NO_LOAD_LIMIT,
+            perf_status_unknown: 7,
+            core_voltage: 800000,
+            core_voltage_boost: 0,
+            voltage_table: vfp.iter().map(|p| p.voltage).collect(),
+            vfp,
+            vfp_memory: vec![
+                VfPoint { frequency: 405000, voltage: 800000, delta: 0 },
+                VfPoint { frequency: 810000, voltage: 800000, delta: 0 },
+                VfPoint { frequency: 5005000, voltage: 800000, delta: 0 },
+            ],
+            vfp_ranges: vec![
+                VfpRange { domain: PublicClockId::Graphics, min: -1000000, max: 1000000, temp_max: 0 },
+                VfpRange { domain: PublicClockId::Memory, min: -1000000, max: 1000000, temp_max: 0 },
+            ],
+            vfp_locks: vec![
+                VfpLock::default(),
+                VfpLock::default(),
+            ],
+            displays: vec![
+                Display {
+                    id: 0x80061086,
+                    connector: MonitorConnectorType::DisplayPort,
+                    flags: DisplayIdsFlags::ACTIVE | DisplayIdsFlags::OS_VISIBLE | DisplayIdsFlags::CONNECTED | DisplayIdsFlags::PHYSICALLY_CONNECTED,
+                    connected: true,
+                    name: "\\\\.\\DISPLAY1".into(),
+                    edid: MOCK_EDID.to_vec(),
+                    edid_id: 1,
+                },
+                Display {
+                    id: 0x80061087,
+                    connector: MonitorConnectorType::Hdmi,
+                    flags: DisplayIdsFlags::empty(),
+                    connected: false,
+                    name: "\\\\.\\DISPLAY2".into(),
+                    edid: Vec::new(),
+                    edid_id: 0,
+                },
+            ],
+            i2c: Vec::new(),
+            ecc: None,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct PciIdentifiers {
+    pub device_id: u32,
+    pub subsystem_id: u32,
+    pub revision_id: u32,
+    pub ext_device_id: u32,
+}
+
+/// The PCIe link, as `(transfer rate in MT/s, lanes)`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Pcie {
+    /// The link as it is currently trained
+    pub current: (u32, u32),
+    /// The fastest link each performance level may use
+    pub levels: Vec<(u32, u32)>,
+}
+
+/// Memory sizes, in KiB
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct Memory {
+    pub dedicated: u32,
+    pub dedicated_available: u32,
+    pub system: u32,
+    pub shared: u32,
+    pub dedicated_available_current: u32,
+    pub dedicated_evictions_size: u32,
+    pub dedicated_evictions: u32,
+    pub dedicated_promotions_size: u32,
+    pub dedicated_promotions: u32,
+}
+
+/// Clock frequencies in kHz, indexed by domain
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Clocks {
+    pub current: BTreeMap<PublicClockId, u32>,
+    pub base: BTreeMap<PublicClockId, u32>,
+    pub boost: BTreeMap<PublicClockId, u32>,
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct Delta {
+    pub value: i32,
+    pub min: i32,
+    pub max: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PState {
+    pub id: PstateId,
+    pub editable: bool,
+    pub clocks: Vec<PStateClock>,
+    pub base_voltages: Vec<BaseVoltage>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PStateClock {
+    pub domain: PublicClockId,
+    pub editable: bool,
+    /// Frequency delta in kHz
+    pub delta: Delta,
+    pub frequency: PStateFrequency,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PStateFrequency {
+    Single(u32),
+    Range {
+        min: u32,
+        max: u32,
+        voltage_domain: VoltageInfoDomain,
+        min_voltage: u32,
+        max_voltage: u32,
+    },
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct BaseVoltage {
+    pub domain: VoltageInfoDomain,
+    pub editable: bool,
+    /// Voltage in uV
+    pub voltage: u32,
+    /// Voltage delta in uV
+    pub delta: Delta,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Sensor {
+    pub controller: ThermalController,
+    pub target: ThermalTarget,
+    pub default_range: (i32, i32),
+    pub current: i32,
+}
+
+/// Temperatures in degrees Celsius
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ThermalLimit {
+    pub controller: ThermalController,
+    pub min: i32,
+    pub default: i32,
+    pub max: i32,
+    pub current: i32,
+    pub flags: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cooler {
+    pub kind: CoolerType,
+    pub controller: CoolerController,
+    pub default_range: (u32, u32),
+    pub current_range: (u32, u32),
+    pub default_level: u32,
+    pub level: u32,
+    pub default_policy: CoolerPolicy,
+    pub policy: CoolerPolicy,
+    pub target: CoolerTarget,
+    pub control: CoolerControl,
+    pub active: bool,
+    /// The `CoolerPolicy::Performance` table
+    pub policy_levels: Vec<PolicyLevel>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PolicyLevel {
+    pub id: u32,
+    pub default_level: u32,
+    pub level: u32,
+}
+
+/// Power limits in `1/1000`ths of a percent of TDP
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PowerLimit {
+    pub pstate: PstateId,
+    pub min: u32,
+    pub default: u32,
+    pub max: u32,
+    pub current: u32,
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct VfPoint {
+    /// Frequency in kHz
+    pub frequency: u32,
+    /// Voltage in uV
+    pub voltage: u32,
+    /// Frequency delta in kHz
+    pub delta: i32,
+}
+
+/// Frequency deltas in kHz, temperature in degrees Celsius
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct VfpRange {
+    pub domain: PublicClockId,
+    pub min: i32,
+    pub max: i32,
+    pub temp_max: i32,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct VfpLock {
+    pub mode: ClockLockMode,
+    /// Voltage in uV
+    pub voltage: u32,
+}
+
+impl Default for VfpLock {
+    fn default() -> Self {
+        VfpLock {
+            mode: ClockLockMode::None,
+            voltage: 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Display {
+    pub id: u32,
+    pub connector: MonitorConnectorType,
+    pub flags: DisplayIdsFlags,
+    pub connected: bool,
+    /// The GDI name, such as `\\.\DISPLAY1`
+    pub name: String,
+    /// The raw EDID, empty if the display doesn't provide one.
+    pub edid: Vec<u8>,
+    /// Reported alongside the EDID; bump it whenever `edid` changes.
+    pub edid_id: u32,
+}
+
+impl Display {
+    pub fn active(&self) -> bool {
+        self.connected && self.flags.contains(DisplayIdsFlags::ACTIVE)
+    }
+}
+
+/// A device on an I2C bus, exposed as a flat register file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct I2cDevice {
+    /// Only matched if the caller specifies a port.
+    pub port: Option<u8>,
+    /// The 7-bit device address.
+    pub address: u8,
+    pub registers: Vec<u8>,
+}
+
+/// ECC memory state and error counters.
+///
+/// Changing the configuration only sets `pending` unless it can be applied
+/// immediately; `enabled` is what the memory is actually running with.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Ecc {
+    pub configuration: EccConfiguration,
+    pub enabled: bool,
+    pub pending: bool,
+    pub enabled_by_default: bool,
+    /// `(single_bit, double_bit)` errors since boot
+    pub current: (u64, u64),
+    /// `(single_bit, double_bit)` errors since the counters were last reset
+    pub aggregate: (u64, u64),
+}
+
+impl Default for Ecc {
+    fn default() -> Self {
+        Ecc {
+            configuration: EccConfiguration::Deferred,
+            enabled: true,
+            pending: true,
+            enabled_by_default: true,
+            current: (0, 0),
+            aggregate: (0, 0),
+        }
+    }
+}
+
+/// A desktop source and the displays it is shown on.
+#[derive(Debug, Clone)]
+pub struct DisplayPath {
+    pub source_id: u32,
+    pub width: u32,
+    pub height: u32,
+    pub color_depth: u32,
+    pub position: (i32, i32),
+    pub gdi_primary: bool,
+    pub targets: Vec<DisplayTarget>,
+}
+
+impl Default for DisplayPath {
+    fn default() -> Self {
+        DisplayPath {
+            source_id: 0,
+            width: 1920,
+            height: 1080,
+            color_depth: 32,
+            position: (0, 0),
+            gdi_primary: true,
+            targets: vec![DisplayTarget::default()],
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DisplayTarget {
+    pub display_id: u32,
+    pub rotation: Rotation,
+    pub scaling: Scaling,
+    /// In millihertz
+    pub refresh_rate: u32,
+    pub interlaced: bool,
+    pub primary: bool,
+    pub timing_override: TimingOverride,
+    pub timing: NV_TIMING,
+}
+
+impl Default for DisplayTarget {
+    fn default() -> Self {
+        // CTA-861 1920x1080@60
+        let mut timing = NV_TIMING::zeroed();
+        timing.HVisible = 1920;
+        timing.HFrontPorch = 88;
+        timing.HSyncWidth = 44;
+        timing.HTotal = 2200;
+        timing.VVisible = 1080;
+        timing.VFrontPorch = 4;
+        timing.VSyncWidth = 5;
+        timing.VTotal = 1125;
+        timing.pclk = 14850;
+        timing.etc.rr = 60;
+        timing.etc.rrx1k = 60000;
+
+        DisplayTarget {
+            display_id: 0x80061086,
+            rotation: Rotation::Degrees0,
+            scaling: Scaling::Default,
+            refresh_rate: 60000,
+            interlaced: false,
+            primary: true,
+            timing_override: TimingOverride::Current,
+            timing,
+        }
+    }
+}
+
+/// Displays arranged in rows and columns, all running the same mode.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MosaicGrid {
+    pub rows: u32,
+    pub columns: u32,
+    /// Row by row.
+    pub displays: Vec<MosaicDisplay>,
+    pub flags: GridTopologyFlags,
+    /// The size of each display, as oriented on the desktop.
+    pub width: u32,
+    pub height: u32,
+    pub color_depth: u32,
+    /// In millihertz
+    pub refresh_rate: u32,
+}
+
+impl MosaicGrid {
+    /// Gaps between displays are only kept with bezel correction.
+    fn overlap(&self, overlap: i32) -> i32 {
+        match self.flags.contains(GridTopologyFlags::APPLY_WITH_BEZEL_CORRECT) {
+            true => overlap,
+            false => overlap.max(0),
+        }
+    }
+
+    /// The desktop area shown on each display, as `(left, top, width, height)`.
+    pub fn viewports(&self) -> Vec<(i32, i32, u32, u32)> {
+        let columns = self.columns.max(1) as usize;
+        (0..self.displays.len()).map(|i| {
+            let (row, column) = (i / columns, i % columns);
+            let left = (1..=column).map(|c| self.width as i32 - self.overlap(self.displays[row * columns + c].overlap.0)).sum();
+            let top = (1..=row).map(|r| self.height as i32 - self.overlap(self.displays[r * columns + column].overlap.1)).sum();
+            (left, top, self.width, self.height)
+        }).collect()
+    }
+
+    /// The size of the desktop spanning the grid.
+    pub fn size(&self) -> (u32, u32) {
+        self.viewports().iter().fold((0, 0), |(width, height), &(left, top, w, h)| {
+            (width.max((left + w as i32).max(0) as u32), height.max((top + h as i32).max(0) as u32))
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MosaicDisplay {
+    pub display_id: u32,
+    /// Pixels shared with the display to the left and above; negative for a gap.
+    pub overlap: (i32, i32),
+    pub rotation: Rotation,
+    pub pixel_shift: PixelShiftType,
+}
+
+/// A sync board frame locking the GPUs plugged into it.
+///
+/// Displays on those GPUs are part of its topology while connected, and may be the
+/// master while active. A GPU is in sync once the device has a signal and every synced
+/// display on it runs at the signal's rate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GSyncDevice {
+    pub board_id: u32,
+    /// FPGA major and minor revision
+    pub revision: (u32, u32),
+    /// Indices into `System::gpus`, with the connector each is plugged into.
+    pub gpus: Vec<(usize, GSyncConnector)>,
+    /// Displays that are not listed are unsynced.
+    pub display_states: BTreeMap<u32, DisplaySyncState>,
+    pub control: GSyncControl,
+    /// The frequency arriving on the house sync input in 0.0001 Hz, or 0 without a signal.
+    pub house_sync: u32,
+    pub rj45: [GSyncRj45Io; 2],
+}
+
+impl Default for GSyncDevice {
+    fn default() -> Self {
+        GSyncDevice {
+            board_id: 0x358,
+            revision: (1, 6),
+            gpus: vec![(0, GSyncConnector::Primary)],
+            display_states: Default::default(),
+            control: Default::default(),
+            house_sync: 0,
+            rj45: [GSyncRj45Io::Output, GSyncRj45Io::Unused],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GSyncControl {
+    pub polarity: GSyncPolarity,
+    pub video_mode: GSyncVideoMode,
+    pub interval: u32,
+    pub source: GSyncSource,
+    pub interlace: bool,
+    pub source_is_output: bool,
+    /// In `(lines, pixels)`
+    pub skew: (u32, u32),
+    pub startup_delay: (u32, u32),
+    pub multiply_divide: (GSyncMultiplyDivideMode, u8),
+}
+
+impl Default for GSyncControl {
+    fn default() -> Self {
+        GSyncControl {
+            polarity: GSyncPolarity::RisingEdge,
+            video_mode: GSyncVideoMode::None,
+            interval: 0,
+            source: GSyncSource::Vsync,
+            interlace: false,
+            source_is_output: false,
+            skew: (0, 0),
+            startup_delay: (0, 0),
+            multiply_divide: (GSyncMultiplyDivideMode::None, 0),
+        }
+    }
+}
+
+pub const DRS_BASE_PROFILE: &str = "Base Profile";
+
+/// A driver settings profile.
+///
+/// Restoring defaults drops everything not marked as predefined, and returns settings
+/// to their predefined values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DrsProfile {
+    pub name: String,
+    pub gpu_support: GpuSupport,
+    pub predefined: bool,
+    pub applications: Vec<DrsApplication>,
+    pub settings: Vec<DrsSetting>,
+}
+
+impl DrsProfile {
+    pub fn new(name: &str) -> Self {
+        DrsProfile {
+            name: name.into(),
+            gpu_support: GpuSupport::GEFORCE | GpuSupport::QUADRO | GpuSupport::NVS,
+            predefined: false,
+            applications: Vec::new(),
+            settings: Vec::new(),
+        }
+    }
+
+    /// The base profile, and a predefined profile for `mockgame.exe`.
+    pub fn defaults() -> Vec<Self> {
+        vec![
+            DrsProfile {
+                predefined: true,
+                settings: vec![
+                    DrsSetting::predefined(VSYNCMODE_ID, DrsValue::Dword(VSYNCMODE_PASSIVE as _)),
+                ],
+                .. DrsProfile::new(DRS_BASE_PROFILE)
+            },
+            DrsProfile {
+                predefined: true,
+                applications: vec![DrsApplication {
+                    predefined: true,
+                    .. DrsApplication::new("mockgame.exe", "Mock Game")
+                }],
+                settings: vec![
+                    DrsSetting::predefined(PREFERRED_PSTATE_ID, DrsValue::Dword(PREFERRED_PSTATE_PREFER_MAX as _)),
+                ],
+                .. DrsProfile::new("Mock Game")
+            },
+        ]
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct DrsApplication {
+    pub name: String,
+    pub friendly_name: String,
+    pub launcher: String,
+    /// Files that must be present next to the executable, separated by `:`
+    pub file_in_folder: String,
+    pub command_line: Option<String>,
+    pub metro: bool,
+    pub predefined: bool,
+}
+
+impl DrsApplication {
+    pub fn new(name: &str, friendly_name: &str) -> Self {
+        DrsApplication {
+            name: name.into(),
+            friendly_name: friendly_name.into(),
+            .. Default::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DrsSetting {
+    pub id: u32,
+    pub value: DrsValue,
+    pub predefined: Option<DrsValue>,
+}
+
+impl DrsSetting {
+    pub fn predefined(id: u32, value: DrsValue) -> Self {
+        DrsSetting {
+            id,
+            predefined: Some(value.clone()),
+            value,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DrsValue {
+    Dword(u32),
+    Binary(Vec<u8>),
+    String(String),
+}
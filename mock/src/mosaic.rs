@@ -0,0 +1,404 @@
+use std::collections::BTreeSet;
+use nvapi_sys::{Api, Status, NvAPI_Status};
+use nvapi_sys::types::NV_RECT;
+use nvapi_sys::dispcontrol::Rotation;
+use nvapi_sys::gpu::display::DisplayIdsFlags;
+use nvapi_sys::mosaic::{self, NV_MOSAIC_GRID_TOPO, NV_MOSAIC_DISPLAY_TOPO_STATUS, NV_MOSAIC_DISPLAY_SETTING, NV_MOSAIC_TOPO_BRIEF, NV_MOSAIC_SETDISPLAYTOPO_FLAGS, NV_MOSAIC_MAX_DISPLAYS};
+use nvapi_sys::mosaic::{GridTopologyFlags, SetDisplayTopoFlags, DisplayCapsProblem, DisplayTopoWarning, DisplayTopoStatusFlags, PixelShiftType, Topology};
+use crate::{call, Result, System, DisplayPath, DisplayTarget, MosaicGrid, MosaicDisplay};
+use crate::util::{out, input, check_version};
+
+/// The modes every display supports, as `(width, height, refresh rate in Hz)`.
+const MODES: &[(u32, u32, u32)] = &[
+    (3840, 2160, 60),
+    (2560, 1440, 60),
+    (1920, 1080, 60),
+    (1920, 1080, 50),
+    (1280, 720, 60),
+];
+
+/// Displays may overlap or leave a gap of up to a quarter of their size.
+fn overlap_limits(size: u32) -> (i32, i32) {
+    let limit = (size / 4) as i32;
+    (-limit, limit)
+}
+
+/// The modes available to a grid, as oriented on the desktop.
+fn modes(rotation: Rotation) -> impl Iterator<Item=(u32, u32, u32)> {
+    let portrait = matches!(rotation, Rotation::Degrees90 | Rotation::Degrees270);
+    MODES.iter().map(move |&(width, height, refresh_rate)| match portrait {
+        true => (height, width, refresh_rate),
+        false => (width, height, refresh_rate),
+    })
+}
+
+fn connected(system: &System, display_id: u32) -> bool {
+    system.gpus.iter().flat_map(|gpu| &gpu.displays)
+        .any(|display| display.id == display_id && display.connected)
+}
+
+/// The grid as the driver remembers it, without the flags that only affect how it's applied.
+fn persisted(grid: &MosaicGrid) -> MosaicGrid {
+    MosaicGrid {
+        flags: grid.flags - GridTopologyFlags::DRIVER_RELOAD_ALLOWED - GridTopologyFlags::ACCELERATE_PRIMARY_DISPLAY,
+        .. grid.clone()
+    }
+}
+
+/// Every grid on the desktop, in path order.
+fn current_grids(system: &System) -> Vec<MosaicGrid> {
+    let mut grids = Vec::new();
+    for path in &system.display_config {
+        let spanned = system.mosaic_grids.iter().find(|grid| grid.displays.iter().map(|display| display.display_id)
+            .eq(path.targets.iter().map(|target| target.display_id)));
+        match spanned {
+            Some(grid) => grids.push(grid.clone()),
+            None => grids.extend(path.targets.iter().map(|target| MosaicGrid {
+                rows: 1,
+                columns: 1,
+                displays: vec![MosaicDisplay {
+                    display_id: target.display_id,
+                    overlap: (0, 0),
+                    rotation: target.rotation,
+                    pixel_shift: PixelShiftType::None,
+                }],
+                flags: GridTopologyFlags::empty(),
+                width: path.width,
+                height: path.height,
+                color_depth: path.color_depth,
+                refresh_rate: target.refresh_rate,
+            })),
+        }
+    }
+    grids
+}
+
+fn read_grid(raw: &NV_MOSAIC_GRID_TOPO) -> Result<MosaicGrid> {
+    check_version(raw.version, &[mosaic::NV_MOSAIC_GRID_TOPO_VER2])?;
+    let count = raw.displayCount as usize;
+    match raw.rows.checked_mul(raw.columns) {
+        Some(cells) if cells > 0 && cells as usize == count && count <= NV_MOSAIC_MAX_DISPLAYS => (),
+        _ => return Err(Status::InvalidArgument),
+    }
+
+    let displays = raw.displays[..count].iter().map(|display| {
+        check_version(display.version, &[mosaic::NV_MOSAIC_GRID_TOPO_DISPLAY_VER2])?;
+        Ok(MosaicDisplay {
+            display_id: display.displayId,
+            overlap: (display.overlapX, display.overlapY),
+            rotation: Rotation::from_raw(display.rotation).ok()
+                .filter(|&rotation| rotation != Rotation::Ignored)
+                .ok_or(Status::InvalidArgument)?,
+            pixel_shift: PixelShiftType::from_raw(display.pixelShiftType).map_err(|_| Status::InvalidArgument)?,
+        })
+    }).collect::<Result<_>>()?;
+
+    let settings = &raw.displaySettings;
+    check_version(settings.version, &[mosaic::NVAPI_MOSAIC_DISPLAY_SETTING_VER1])?;
+    Ok(MosaicGrid {
+        rows: raw.rows,
+        columns: raw.columns,
+        displays,
+        flags: GridTopologyFlags::from_bits(raw.flags).ok_or(Status::InvalidArgument)?,
+        width: settings.width,
+        height: settings.height,
+        color_depth: settings.bpp,
+        refresh_rate: settings.freq.saturating_mul(1000),
+    })
+}
+
+fn read_grids(grids: *const NV_MOSAIC_GRID_TOPO, count: u32) -> Result<Vec<MosaicGrid>> {
+    input(grids)?;
+    if count == 0 {
+        return Err(Status::InvalidArgument)
+    }
+    unsafe { std::slice::from_raw_parts(grids, count as usize) }.iter()
+        .map(read_grid).collect()
+}
+
+fn write_grid(raw: &mut NV_MOSAIC_GRID_TOPO, grid: &MosaicGrid) -> Result<()> {
+    check_version(raw.version, &[mosaic::NV_MOSAIC_GRID_TOPO_VER2])?;
+    raw.rows = grid.rows;
+    raw.columns = grid.columns;
+    raw.displayCount = grid.displays.len() as _;
+    raw.flags = grid.flags.bits();
+    for (raw, display) in raw.displays.iter_mut().zip(&grid.displays) {
+        raw.version = mosaic::NV_MOSAIC_GRID_TOPO_DISPLAY_VER2;
+        raw.displayId = display.display_id;
+        raw.overlapX = display.overlap.0;
+        raw.overlapY = display.overlap.1;
+        raw.rotation = display.rotation.raw();
+        raw.cloneGroup = 0;
+        raw.pixelShiftType = display.pixel_shift.raw();
+    }
+
+    let settings = &mut raw.displaySettings;
+    settings.version = mosaic::NVAPI_MOSAIC_DISPLAY_SETTING_VER1;
+    settings.width = grid.width;
+    settings.height = grid.height;
+    settings.bpp = grid.color_depth;
+    settings.freq = grid.refresh_rate.saturating_add(500) / 1000;
+    Ok(())
+}
+
+struct GridStatus {
+    /// Includes the errors of every display.
+    errors: DisplayCapsProblem,
+    warnings: DisplayTopoWarning,
+    displays: Vec<(u32, DisplayCapsProblem, DisplayTopoWarning)>,
+}
+
+/// Displays may only appear once across all of `grids`.
+fn validate(system: &System, grids: &[MosaicGrid]) -> Result<Vec<GridStatus>> {
+    let ids: BTreeSet<_> = grids.iter().flat_map(|grid| &grid.displays).map(|display| display.display_id).collect();
+    if ids.len() != grids.iter().map(|grid| grid.displays.len()).sum::<usize>() {
+        return Err(Status::InvalidArgument)
+    }
+
+    Ok(grids.iter().map(|grid| {
+        let rotation = grid.displays[0].rotation;
+        let bezel_corrected = grid.flags.contains(GridTopologyFlags::APPLY_WITH_BEZEL_CORRECT);
+        let (min_x, max_x) = overlap_limits(grid.width);
+        let (min_y, max_y) = overlap_limits(grid.height);
+
+        let mut status = GridStatus {
+            errors: DisplayCapsProblem::empty(),
+            warnings: DisplayTopoWarning::empty(),
+            displays: Vec::with_capacity(grid.displays.len()),
+        };
+        if !modes(rotation).any(|mode| mode == (grid.width, grid.height, grid.refresh_rate.saturating_add(500) / 1000)) {
+            status.errors |= DisplayCapsProblem::NO_COMMON_TIMINGS;
+        }
+        if grid.displays.len() > 1 {
+            if grid.displays.iter().any(|display| display.rotation != rotation) {
+                status.errors |= DisplayCapsProblem::NOT_SUPPORTED;
+            }
+            let current = system.mosaic_grids.contains(&persisted(grid));
+            if !current && !grid.flags.contains(GridTopologyFlags::DRIVER_RELOAD_ALLOWED) {
+                status.warnings |= DisplayTopoWarning::DRIVER_RELOAD_REQUIRED;
+            }
+        }
+
+        for display in &grid.displays {
+            let mut errors = DisplayCapsProblem::empty();
+            let mut warnings = DisplayTopoWarning::empty();
+            if !connected(system, display.display_id) {
+                errors |= DisplayCapsProblem::NO_DISPLAY_CONNECTED;
+            }
+            let (x, y) = display.overlap;
+            if x < min_x || x > max_x || y < min_y || y > max_y {
+                errors |= DisplayCapsProblem::NOT_SUPPORTED;
+            }
+            if (x < 0 || y < 0) && !bezel_corrected {
+                warnings |= DisplayTopoWarning::DISPLAY_POSITION;
+            }
+            status.errors |= errors;
+            status.displays.push((display.display_id, errors, warnings));
+        }
+        status
+    }).collect())
+}
+
+fn write_status(raw: &mut NV_MOSAIC_DISPLAY_TOPO_STATUS, status: &GridStatus) -> Result<()> {
+    check_version(raw.version, &[mosaic::NV_MOSAIC_DISPLAY_TOPO_STATUS_VER1])?;
+    raw.errorFlags = status.errors.bits();
+    raw.warningFlags = status.warnings.bits();
+    raw.displayCount = status.displays.len() as _;
+    for (raw, &(display_id, errors, warnings)) in raw.displays.iter_mut().zip(&status.displays) {
+        raw.displayId = display_id;
+        raw.errorFlags = errors.bits();
+        raw.warningFlags = warnings.bits();
+        raw.flags = DisplayTopoStatusFlags::SUPPORTS_ROTATION.bits();
+    }
+    Ok(())
+}
+
+pub extern "C" fn NvAPI_Mosaic_GetOverlapLimits(pTopoBrief: *mut NV_MOSAIC_TOPO_BRIEF, pDisplaySetting: *mut NV_MOSAIC_DISPLAY_SETTING, pMinOverlapX: *mut i32, pMaxOverlapX: *mut i32, pMinOverlapY: *mut i32, pMaxOverlapY: *mut i32) -> NvAPI_Status {
+    call(Api::NvAPI_Mosaic_GetOverlapLimits, |_state| {
+        let brief = input(pTopoBrief)?;
+        check_version(brief.version, &[mosaic::NVAPI_MOSAIC_TOPO_BRIEF_VER1])?;
+        let setting = input(pDisplaySetting)?;
+        check_version(setting.version, &[mosaic::NVAPI_MOSAIC_DISPLAY_SETTING_VER1, mosaic::NVAPI_MOSAIC_DISPLAY_SETTING_VER2])?;
+        let (rows, columns) = Topology::from_raw(brief.topo).ok()
+            .and_then(|topo| topo.shape())
+            .ok_or(Status::InvalidArgument)?;
+
+        // there's nothing to overlap along a single row or column
+        let (min_x, max_x) = if columns > 1 { overlap_limits(setting.width) } else { (0, 0) };
+        let (min_y, max_y) = if rows > 1 { overlap_limits(setting.height) } else { (0, 0) };
+        *out(pMinOverlapX)? = min_x;
+        *out(pMaxOverlapX)? = max_x;
+        *out(pMinOverlapY)? = min_y;
+        *out(pMaxOverlapY)? = max_y;
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_Mosaic_EnumDisplayModes(pGridTopology: *mut NV_MOSAIC_GRID_TOPO, pDisplaySettings: *mut NV_MOSAIC_DISPLAY_SETTING, pDisplayCount: *mut u32) -> NvAPI_Status {
+    call(Api::NvAPI_Mosaic_EnumDisplayModes, |state| {
+        let grid = read_grid(input(pGridTopology)?)?;
+        let count = out(pDisplayCount)?;
+        if grid.displays.iter().any(|display| !connected(&state.system, display.display_id)) {
+            return Err(Status::InvalidArgument)
+        }
+
+        let modes: Vec<_> = modes(grid.displays[0].rotation).collect();
+        if pDisplaySettings.is_null() {
+            *count = modes.len() as _;
+            return Ok(())
+        }
+
+        let returned = modes.len().min(*count as usize);
+        let raw_settings = unsafe { std::slice::from_raw_parts_mut(pDisplaySettings, returned) };
+        for (raw, &(width, height, refresh_rate)) in raw_settings.iter_mut().zip(&modes) {
+            check_version(raw.version, &[mosaic::NVAPI_MOSAIC_DISPLAY_SETTING_VER2])?;
+            raw.width = width;
+            raw.height = height;
+            raw.bpp = 32;
+            raw.freq = refresh_rate;
+            raw.rrx1k = refresh_rate * 1000;
+        }
+        *count = returned as _;
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_Mosaic_EnumDisplayGrids(pGridTopologies: *mut NV_MOSAIC_GRID_TOPO, pGridCount: *mut u32) -> NvAPI_Status {
+    call(Api::NvAPI_Mosaic_EnumDisplayGrids, |state| {
+        let count = out(pGridCount)?;
+        let grids = current_grids(&state.system);
+        if pGridTopologies.is_null() {
+            *count = grids.len() as _;
+            return Ok(())
+        }
+        if (*count as usize) < grids.len() {
+            *count = grids.len() as _;
+            return Err(Status::InsufficientBuffer)
+        }
+
+        let raw_grids = unsafe { std::slice::from_raw_parts_mut(pGridTopologies, grids.len()) };
+        for (raw, grid) in raw_grids.iter_mut().zip(&grids) {
+            write_grid(raw, grid)?;
+        }
+        *count = grids.len() as _;
+        Ok(())
+    })
+}
+
+/// Paths showing any of the displays in the new grids are replaced, each grid taking
+/// the place of the path its first display was on. Other grids are placed to the right
+/// of the desktop.
+pub extern "C" fn NvAPI_Mosaic_SetDisplayGrids(pGridTopologies: *mut NV_MOSAIC_GRID_TOPO, gridCount: u32, setTopoFlags: NV_MOSAIC_SETDISPLAYTOPO_FLAGS) -> NvAPI_Status {
+    call(Api::NvAPI_Mosaic_SetDisplayGrids, |state| {
+        let flags = SetDisplayTopoFlags::from_bits(setTopoFlags).ok_or(Status::InvalidArgument)?;
+        let grids = read_grids(pGridTopologies, gridCount)?;
+        if grids.iter().any(|grid| grid.width == 0 || grid.height == 0) {
+            return Err(Status::InvalidArgument)
+        }
+        let statuses = validate(&state.system, &grids)?;
+        if !flags.contains(SetDisplayTopoFlags::ALLOW_INVALID) && statuses.iter().any(|status| !status.errors.is_empty()) {
+            return Err(Status::TopoNotPossible)
+        }
+        if flags.contains(SetDisplayTopoFlags::NO_DRIVER_RELOAD) && statuses.iter().any(|status| status.warnings.contains(DisplayTopoWarning::DRIVER_RELOAD_REQUIRED)) {
+            return Err(Status::ModeChangeFailed)
+        }
+
+        let system = &mut state.system;
+        let ids: BTreeSet<_> = grids.iter().flat_map(|grid| &grid.displays).map(|display| display.display_id).collect();
+        let replaced = |path: &DisplayPath| path.targets.iter().any(|target| ids.contains(&target.display_id));
+        let mut paths: Vec<_> = system.display_config.iter().filter(|path| !replaced(path)).cloned().collect();
+        let mut claimed = BTreeSet::new();
+        for grid in &grids {
+            // a path split into several grids only keeps its place for the first of them
+            let old = system.display_config.iter().enumerate()
+                .find(|(_, path)| path.targets.iter().any(|target| target.display_id == grid.displays[0].display_id))
+                .filter(|&(i, _)| claimed.insert(i))
+                .map(|(_, path)| path);
+            let right = paths.iter().map(|path| path.position.0 + path.width as i32).max().unwrap_or(0);
+            let (width, height) = grid.size();
+            let targets = grid.displays.iter().enumerate().map(|(i, display)| {
+                let current = system.display_config.iter().flat_map(|path| &path.targets)
+                    .find(|target| target.display_id == display.display_id);
+                DisplayTarget {
+                    display_id: display.display_id,
+                    rotation: display.rotation,
+                    refresh_rate: grid.refresh_rate,
+                    primary: i == 0,
+                    .. current.cloned().unwrap_or_default()
+                }
+            }).collect();
+            paths.push(DisplayPath {
+                source_id: 0,
+                width,
+                height,
+                color_depth: grid.color_depth,
+                position: old.map(|path| path.position).unwrap_or((right, 0)),
+                gdi_primary: old.is_some_and(|path| path.gdi_primary),
+                targets,
+            });
+        }
+
+        let mut primary = false;
+        for path in &mut paths {
+            path.gdi_primary &= !primary;
+            primary |= path.gdi_primary;
+        }
+        if !primary {
+            paths[0].gdi_primary = true;
+        }
+        for (source_id, path) in paths.iter_mut().enumerate() {
+            path.source_id = source_id as _;
+        }
+
+        let active: BTreeSet<_> = paths.iter().flat_map(|path| &path.targets).map(|target| target.display_id).collect();
+        for display in system.gpus.iter_mut().flat_map(|gpu| &mut gpu.displays) {
+            display.flags.set(DisplayIdsFlags::ACTIVE, active.contains(&display.id));
+        }
+        system.mosaic_grids.retain(|grid| grid.displays.iter().all(|display| !ids.contains(&display.display_id)));
+        system.mosaic_grids.extend(grids.iter().filter(|grid| grid.displays.len() > 1).map(persisted));
+        system.display_config = paths;
+        Ok(())
+    })
+}
+
+pub extern "C" fn NvAPI_Mosaic_ValidateDisplayGrids(setTopoFlags: NV_MOSAIC_SETDISPLAYTOPO_FLAGS, pGridTopologies: *mut NV_MOSAIC_GRID_TOPO, pTopoStatus: *mut NV_MOSAIC_DISPLAY_TOPO_STATUS, gridCount: u32) -> NvAPI_Status {
+    call(Api::NvAPI_Mosaic_ValidateDisplayGrids, |state| {
+        SetDisplayTopoFlags::from_bits(setTopoFlags).ok_or(Status::InvalidArgument)?;
+        let grids = read_grids(pGridTopologies, gridCount)?;
+        input(pTopoStatus)?;
+        let raw_statuses = unsafe { std::slice::from_raw_parts_mut(pTopoStatus, grids.len()) };
+        for (raw, status) in raw_statuses.iter_mut().zip(&validate(&state.system, &grids)?) {
+            write_status(raw, status)?;
+        }
+        Ok(())
+    })
+}
+
+/// Only the current desktop size or that of another mode of the same grid is accepted.
+pub extern "C" fn NvAPI_Mosaic_GetDisplayViewportsByResolution(displayId: u32, srcWidth: u32, srcHeight: u32, viewports: *mut [NV_RECT; NV_MOSAIC_MAX_DISPLAYS], bezelCorrected: *mut u8) -> NvAPI_Status {
+    call(Api::NvAPI_Mosaic_GetDisplayViewportsByResolution, |state| {
+        let viewports = out(viewports)?;
+        let bezel_corrected = out(bezelCorrected)?;
+        let grid = current_grids(&state.system).into_iter()
+            .find(|grid| grid.displays.iter().any(|display| display.display_id == displayId))
+            .ok_or(Status::MosaicNotActive)?;
+        let grid = match (srcWidth, srcHeight) {
+            (0, 0) => grid,
+            size => modes(grid.displays[0].rotation)
+                .map(|(width, height, _)| MosaicGrid { width, height, .. grid.clone() })
+                .find(|grid| grid.size() == size)
+                .ok_or(Status::InvalidArgument)?,
+        };
+
+        *viewports = [NV_RECT::zeroed(); NV_MOSAIC_MAX_DISPLAYS];
+        for (rect, (left, top, width, height)) in viewports.iter_mut().zip(grid.viewports()) {
+            rect.left = left as u32;
+            rect.top = top as u32;
+            rect.right = rect.left + width - 1;
+            rect.bottom = rect.top + height - 1;
+        }
+        let gaps = grid.displays.iter().any(|display| display.overlap.0 < 0 || display.overlap.1 < 0);
+        *bezel_corrected = (gaps && grid.flags.contains(GridTopologyFlags::APPLY_WITH_BEZEL_CORRECT)) as u8;
+        Ok(())
+    })
+}
@@ -0,0 +1,117 @@
+use std::cell::RefCell;
+use std::collections::BTreeSet;
+use std::os::raw::c_char;
+use nvapi_sys::Status;
+use nvapi_sys::handles::{NvPhysicalGpuHandle, NvLogicalGpuHandle, NvGSyncDeviceHandle};
+use crate::{Result, State, VirtualGpu};
+
+/// Physical GPU handles are `GPU_HANDLE_BASE + (generation << 8) + index`.
+pub const GPU_HANDLE_BASE: usize = 0x100;
+
+pub fn gpu_handle(state: &State, index: usize) -> NvPhysicalGpuHandle {
+    NvPhysicalGpuHandle::from_raw((GPU_HANDLE_BASE + (state.generation << 8) + index) as *const _)
+}
+
+pub fn gpu_index(state: &State, handle: NvPhysicalGpuHandle) -> Result<usize> {
+    let offset = (handle.as_ptr() as usize).checked_sub(GPU_HANDLE_BASE).ok_or(Status::ExpectedPhysicalGpuHandle)?;
+    let (generation, index) = (offset >> 8, offset & 0xff);
+    match generation {
+        generation if generation < state.generation => Err(Status::HandleInvalidated),
+        generation if generation == state.generation && index < state.system.gpus.len() => Ok(index),
+        _ => Err(Status::ExpectedPhysicalGpuHandle),
+    }
+}
+
+pub fn gpu(state: &mut State, handle: NvPhysicalGpuHandle) -> Result<&mut VirtualGpu> {
+    gpu_index(state, handle).map(move |index| &mut state.system.gpus[index])
+}
+
+/// Logical GPU handles are `LOGICAL_HANDLE_BASE + (generation << 8) + index`.
+pub const LOGICAL_HANDLE_BASE: usize = 0x20000;
+
+pub fn logical_handle(state: &State, index: usize) -> NvLogicalGpuHandle {
+    NvLogicalGpuHandle::from_raw((LOGICAL_HANDLE_BASE + (state.generation << 8) + index) as *const _)
+}
+
+/// The physical GPU indices making up the logical GPU `handle` refers to.
+pub fn logical_gpu(state: &State, handle: NvLogicalGpuHandle) -> Result<Vec<usize>> {
+    let offset = (handle.as_ptr() as usize).checked_sub(LOGICAL_HANDLE_BASE).ok_or(Status::ExpectedLogicalGpuHandle)?;
+    let (generation, index) = (offset >> 8, offset & 0xff);
+    match generation {
+        generation if generation < state.generation => Err(Status::HandleInvalidated),
+        generation if generation == state.generation => state.system.logical_gpus().into_iter().nth(index)
+            .ok_or(Status::ExpectedLogicalGpuHandle),
+        _ => Err(Status::ExpectedLogicalGpuHandle),
+    }
+}
+
+/// The handle of the logical GPU that physical GPU `index` belongs to.
+pub fn logical_handle_of(state: &State, index: usize) -> Result<NvLogicalGpuHandle> {
+    state.system.logical_gpus().iter().position(|group| group.contains(&index))
+        .map(|logical| logical_handle(state, logical))
+        .ok_or(Status::NvidiaDeviceNotFound)
+}
+
+/// Sync device handles are `GSYNC_HANDLE_BASE + (generation << 8) + index`.
+pub const GSYNC_HANDLE_BASE: usize = 0x40000;
+
+pub fn gsync_handle(state: &State, index: usize) -> NvGSyncDeviceHandle {
+    NvGSyncDeviceHandle::from_raw((GSYNC_HANDLE_BASE + (state.generation << 8) + index) as *const _)
+}
+
+pub fn gsync_index(state: &State, handle: NvGSyncDeviceHandle) -> Result<usize> {
+    let offset = (handle.as_ptr() as usize).checked_sub(GSYNC_HANDLE_BASE).ok_or(Status::InvalidArgument)?;
+    let (generation, index) = (offset >> 8, offset & 0xff);
+    match generation {
+        generation if generation < state.generation => Err(Status::HandleInvalidated),
+        generation if generation == state.generation && index < state.system.gsync_devices.len() => Ok(index),
+        _ => Err(Status::InvalidArgument),
+    }
+}
+
+pub fn out<'a, T>(ptr: *mut T) -> Result<&'a mut T> {
+    unsafe { ptr.as_mut() }.ok_or(Status::InvalidArgument)
+}
+
+pub fn input<'a, T>(ptr: *const T) -> Result<&'a T> {
+    unsafe { ptr.as_ref() }.ok_or(Status::InvalidArgument)
+}
+
+thread_local! {
+    /// Versions the call currently being dispatched has been told to reject.
+    static REJECTED: RefCell<BTreeSet<u32>> = const { RefCell::new(BTreeSet::new()) };
+}
+
+/// Runs `f` with `check_version` refusing each of `rejected`.
+pub fn with_rejected_versions<R, F: FnOnce() -> R>(rejected: BTreeSet<u32>, f: F) -> R {
+    let previous = REJECTED.with(|r| r.replace(rejected));
+    let res = f();
+    REJECTED.with(|r| *r.borrow_mut() = previous);
+    res
+}
+
+pub fn check_version(version: u32, supported: &[u32]) -> Result<()> {
+    if supported.contains(&version) && !REJECTED.with(|r| r.borrow().contains(&version)) {
+        Ok(())
+    } else {
+        Err(Status::IncompatibleStructVersion)
+    }
+}
+
+pub fn write_str(dest: &mut [c_char], str: &str) {
+    let len = str.len().min(dest.len() - 1);
+    for (d, &s) in dest.iter_mut().zip(&str.as_bytes()[..len]) {
+        *d = s as c_char;
+    }
+    for d in &mut dest[len..] {
+        *d = 0;
+    }
+}
+
+pub fn set_bit(mask: &mut [u32; 4], bit: usize) {
+    mask[bit / 32] |= 1 << (bit % 32);
+}
+
+pub fn get_bit(mask: &[u32; 4], bit: usize) -> bool {
+    mask[bit / 32] & (1 << (bit % 32)) != 0
+}
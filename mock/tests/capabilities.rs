@@ -0,0 +1,39 @@
+extern crate nvapi;
+extern crate nvapi_mock;
+extern crate serde_json;
+
+use nvapi::{PhysicalGpu, Status, Support, Capabilities};
+use nvapi::sys::Api;
+use nvapi_mock::System;
+
+#[test]
+fn capabilities() {
+    let mock = nvapi_mock::install(System::default());
+    nvapi::initialize().unwrap();
+    let gpu = &PhysicalGpu::enumerate().unwrap()[0];
+
+    let caps = nvapi::capabilities();
+    assert_eq!(caps.support(Api::NvAPI_SYS_GetDriverAndBranchVersion), Support::Supported);
+    assert_eq!(caps.support(Api::NvAPI_GPU_GetFullName), Support::Exported);
+    assert_eq!(caps.support(Api::NvAPI_Stereo_Enable), Support::Missing);
+    assert_eq!(caps.apis.len(), Api::all().count());
+
+    mock.fail(Api::NvAPI_GPU_GetPstates20, Status::NotSupported);
+    mock.fail(Api::NvAPI_GPU_GetUsages, Status::NoImplementation);
+    mock.fail(Api::NvAPI_GPU_GetMemoryInfoEx, Status::InvalidArgument);
+    let caps = gpu.capabilities();
+    assert_eq!(caps.support(Api::NvAPI_GPU_GetFullName), Support::Supported);
+    assert_eq!(caps.support(Api::NvAPI_GPU_GetPstates20), Support::NotSupported);
+    assert_eq!(caps.support(Api::NvAPI_GPU_GetUsages), Support::NoImplementation);
+    assert_eq!(caps.support(Api::NvAPI_GPU_GetMemoryInfoEx), Support::Failed(Status::InvalidArgument));
+    assert!(caps.is_available(Api::NvAPI_GPU_SetPstates20));
+    assert!(!caps.is_available(Api::NvAPI_GPU_GetPstates20));
+    assert!(caps.with_support(Support::NotSupported).any(|api| api == Api::NvAPI_GPU_GetPstates20));
+
+    // Probing must not have changed anything
+    assert!(mock.calls().iter().map(|api| format!("{:?}", api)).all(|api| !api.contains("Set") || api.contains("Settings")));
+
+    let json = serde_json::to_string(&caps).unwrap();
+    let parsed: Capabilities = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, caps);
+}
@@ -0,0 +1,94 @@
+extern crate nvapi_hi;
+extern crate nvapi_mock;
+
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex};
+use nvapi_hi::Gpu;
+use nvapi_hi::nvapi::PhysicalGpu;
+use nvapi_hi::nvapi::sys::capture::{self, Capture};
+use nvapi_hi::nvapi::sys::i2c::I2cSpeed;
+use nvapi_mock::{System, I2cDevice};
+
+#[derive(Clone, Default)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn record<R, F: FnOnce() -> R>(f: F) -> (R, Capture) {
+    let buffer = SharedBuffer::default();
+    capture::start_recording(buffer.clone()).unwrap();
+    let result = f();
+    capture::stop_recording().unwrap();
+
+    let recorded = buffer.0.lock().unwrap().clone();
+    (result, Capture::read(&recorded[..]).unwrap())
+}
+
+#[test]
+fn record_replay() {
+    let mock = nvapi_mock::install(System::default());
+    let ((info, status), capture) = record(|| {
+        nvapi_hi::nvapi::initialize().unwrap();
+        let gpu = Gpu::enumerate().unwrap().into_iter().next().unwrap();
+        (gpu.info().unwrap(), gpu.status().unwrap())
+    });
+    assert!(!capture.calls.is_empty());
+
+    let mut rewritten = Vec::new();
+    capture.write(&mut rewritten).unwrap();
+    assert_eq!(Capture::read(&rewritten[..]).unwrap(), capture);
+
+    // The mock no longer has a GPU, so everything below is served from the capture
+    mock.with(|system| system.gpus.clear());
+    capture::start_replay(capture);
+    nvapi_hi::nvapi::initialize().unwrap();
+    let gpu = Gpu::enumerate().unwrap().into_iter().next().unwrap();
+    assert_eq!(gpu.info().unwrap(), info);
+    assert_eq!(gpu.status().unwrap(), status);
+    capture::stop_replay();
+
+    assert!(Gpu::enumerate().unwrap().is_empty());
+}
+
+#[test]
+fn counted_arrays() {
+    let mock = nvapi_mock::install(System::default());
+    let (ids, capture) = record(|| {
+        nvapi_hi::nvapi::initialize().unwrap();
+        let gpu = PhysicalGpu::enumerate().unwrap().into_iter().next().unwrap();
+        gpu.display_ids_all().unwrap()
+    });
+    assert_eq!(ids.len(), 2);
+
+    mock.with(|system| system.gpus[0].displays.clear());
+    capture::start_replay(capture);
+    let gpu = PhysicalGpu::enumerate().unwrap().into_iter().next().unwrap();
+    assert_eq!(gpu.display_ids_all().unwrap(), ids);
+    capture::stop_replay();
+}
+
+#[test]
+fn embedded_pointers() {
+    let mock = nvapi_mock::install(System::default());
+    mock.with(|system| system.gpus[0].i2c.push(I2cDevice {
+        port: None,
+        address: 0x50,
+        registers: (0..0x80).collect(),
+    }));
+    nvapi_hi::nvapi::initialize().unwrap();
+    let gpu = PhysicalGpu::enumerate().unwrap().into_iter().next().unwrap();
+
+    capture::start_recording(io::sink()).unwrap();
+    let mut data = [0u8; 4];
+    gpu.i2c_read(1, None, true, 0x50, &[0x10], &mut data, I2cSpeed::Default).unwrap();
+    assert!(capture::stop_recording().is_err());
+}
@@ -0,0 +1,75 @@
+extern crate nvapi;
+extern crate nvapi_mock;
+
+use nvapi::{Display, UnattachedDisplay, PhysicalGpu, ConnectedIdsFlags, Status};
+use nvapi_mock::{System, VirtualGpu};
+
+fn system() -> System {
+    let mut gpu = VirtualGpu::default();
+    // connect the second output without adding it to the desktop
+    gpu.displays[1].connected = true;
+    System {
+        gpus: vec![gpu, VirtualGpu {
+            bus_id: 2,
+            .. Default::default()
+        }],
+        sli_groups: vec![vec![0, 1]],
+        .. Default::default()
+    }
+}
+
+#[test]
+fn enumerate() {
+    let _mock = nvapi_mock::install(system());
+    nvapi::initialize().unwrap();
+
+    let displays = Display::enumerate().unwrap();
+    assert_eq!(displays.len(), 2);
+    assert_eq!(displays[0].name().unwrap(), "\\\\.\\DISPLAY1");
+
+    let unattached = UnattachedDisplay::enumerate().unwrap();
+    assert_eq!(unattached.len(), 1);
+    assert_eq!(unattached[0].name().unwrap(), "\\\\.\\DISPLAY2");
+    assert_eq!(unattached[0].physical_gpu().unwrap().bus_id().unwrap(), 1);
+}
+
+#[test]
+fn from_name() {
+    let _mock = nvapi_mock::install(system());
+    nvapi::initialize().unwrap();
+
+    let display = Display::from_name("\\\\.\\DISPLAY1").unwrap();
+    assert_eq!(display.handle().as_ptr(), Display::enumerate().unwrap()[0].handle().as_ptr());
+    assert_eq!(Display::from_name("\\\\.\\DISPLAY2").unwrap_err(), Status::NvidiaDeviceNotFound);
+    assert_eq!(Display::from_name("\0").unwrap_err(), Status::InvalidArgument);
+    assert!(UnattachedDisplay::from_name("\\\\.\\DISPLAY2").is_ok());
+}
+
+#[test]
+fn physical_gpus() {
+    let _mock = nvapi_mock::install(system());
+    nvapi::initialize().unwrap();
+
+    // the second GPU's display comes first, then the rest of the SLI group
+    let gpus = Display::enumerate().unwrap()[1].physical_gpus().unwrap();
+    let bus_ids: Vec<_> = gpus.iter().map(|gpu| gpu.bus_id().unwrap()).collect();
+    assert_eq!(bus_ids, [2, 1]);
+}
+
+#[test]
+fn display_ids() {
+    let _mock = nvapi_mock::install(system());
+    nvapi::initialize().unwrap();
+
+    let gpu = PhysicalGpu::enumerate().unwrap().remove(0);
+    let ids = gpu.display_ids_connected(ConnectedIdsFlags::empty()).unwrap();
+    assert_eq!(ids.len(), 2);
+
+    let display = ids[0].display().unwrap();
+    assert_eq!(display.display_id().unwrap(), ids[0].display_id);
+    assert_eq!(display.name().unwrap(), "\\\\.\\DISPLAY1");
+
+    let unattached = ids[1].unattached_display().unwrap();
+    assert_eq!(unattached.display_id().unwrap(), ids[1].display_id);
+    assert_eq!(ids[1].display().unwrap_err(), Status::NvidiaDeviceNotFound);
+}
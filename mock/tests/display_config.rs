@@ -0,0 +1,153 @@
+extern crate nvapi;
+extern crate nvapi_mock;
+
+use nvapi::{DisplayConfig, DisplayConfigChange, DisplayConfigFlags, Display, Rotation, Scaling, TimingOverride, Kilohertz, Status};
+use nvapi_mock::System;
+
+fn system() -> System {
+    let mut system = System::default();
+    // plug in the second output without adding it to the desktop
+    system.gpus[0].displays[1].connected = true;
+    system
+}
+
+#[test]
+fn get() {
+    let _mock = nvapi_mock::install(system());
+    nvapi::initialize().unwrap();
+
+    let config = DisplayConfig::get().unwrap();
+    assert_eq!(config.paths.len(), 1);
+    let path = &config.paths[0];
+    assert_eq!((path.source.resolution.width, path.source.resolution.height), (1920, 1080));
+    assert!(path.source.gdi_primary);
+    assert_eq!(path.targets.len(), 1);
+
+    let target = &path.targets[0];
+    assert_eq!(target.display_id, 0x80061086);
+    assert_eq!((target.rotation, target.scaling, target.refresh_rate), (Rotation::Degrees0, Scaling::Default, 60000));
+    let timing = target.timing.unwrap();
+    assert_eq!((timing.h_total, timing.v_total, timing.pixel_clock), (2200, 1125, Kilohertz(148500)));
+}
+
+#[test]
+fn json_round_trip() {
+    let _mock = nvapi_mock::install(system());
+    nvapi::initialize().unwrap();
+
+    let config = DisplayConfig::get().unwrap();
+    let json = serde_json::to_string(&config).unwrap();
+    let restored: DisplayConfig = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored, config);
+    assert!(restored.diff(&config).is_empty());
+    restored.apply(DisplayConfigFlags::empty()).unwrap();
+    assert_eq!(DisplayConfig::get().unwrap(), config);
+}
+
+#[test]
+fn apply() {
+    let mock = nvapi_mock::install(system());
+    nvapi::initialize().unwrap();
+
+    // extend the desktop onto the second display, rotated to portrait
+    let old = DisplayConfig::get().unwrap();
+    let mut config = old.clone();
+    let mut path = config.paths[0].clone();
+    path.source.position = (1920, 0);
+    path.source.gdi_primary = false;
+    path.targets[0].display_id = 0x80061087;
+    path.targets[0].rotation = Rotation::Degrees90;
+    path.targets[0].refresh_rate = 0;
+    config.paths.push(path);
+
+    config.validate().unwrap();
+    assert_eq!(mock.with(|system| system.display_config.len()), 1);
+    config.apply(DisplayConfigFlags::SAVE_TO_PERSISTENCE).unwrap();
+    assert_eq!(Display::enumerate().unwrap().len(), 2);
+
+    let new = DisplayConfig::get().unwrap();
+    assert_eq!(new.paths.len(), 2);
+    let (path, target) = new.find(0x80061087).unwrap();
+    assert_eq!(path.source.position, (1920, 0));
+    assert_eq!(path.source_id, 1);
+    // a zero refresh rate keeps the current one
+    assert_eq!((target.rotation, target.refresh_rate), (Rotation::Degrees90, 60000));
+
+    // and back again
+    old.apply(DisplayConfigFlags::empty()).unwrap();
+    assert_eq!(Display::enumerate().unwrap().len(), 1);
+    assert_eq!(DisplayConfig::get().unwrap(), old);
+}
+
+#[test]
+fn custom_timing() {
+    let _mock = nvapi_mock::install(system());
+    nvapi::initialize().unwrap();
+
+    let mut config = DisplayConfig::get().unwrap();
+    let target = &mut config.paths[0].targets[0];
+    let mut timing = target.timing.unwrap();
+    timing.pixel_clock = Kilohertz(297000);
+    timing.refresh_rate = 120000;
+    target.timing = Some(timing);
+    target.refresh_rate = 120000;
+    // without the override the timing is ignored
+    config.apply(DisplayConfigFlags::empty()).unwrap();
+    assert_eq!(DisplayConfig::get().unwrap().paths[0].targets[0].timing.unwrap().pixel_clock, Kilohertz(148500));
+
+    config.paths[0].targets[0].timing_override = TimingOverride::Custom;
+    config.apply(DisplayConfigFlags::empty()).unwrap();
+    assert_eq!(DisplayConfig::get().unwrap().paths[0].targets[0].timing, Some(timing));
+}
+
+#[test]
+fn validate_errors() {
+    let mock = nvapi_mock::install(System::default());
+    nvapi::initialize().unwrap();
+    let config = DisplayConfig::get().unwrap();
+
+    // the second output isn't connected
+    let mut invalid = config.clone();
+    invalid.paths[0].targets[0].display_id = 0x80061087;
+    assert_eq!(invalid.validate().unwrap_err(), Status::InvalidArgument);
+
+    // a display can only show one source
+    let mut invalid = config.clone();
+    invalid.paths.push(config.paths[0].clone());
+    assert_eq!(invalid.validate().unwrap_err(), Status::InvalidArgument);
+
+    let mut invalid = config.clone();
+    invalid.paths[0].source.resolution.width = 0;
+    assert_eq!(invalid.validate().unwrap_err(), Status::InvalidArgument);
+
+    assert_eq!(DisplayConfig::default().validate().unwrap_err(), Status::InvalidArgument);
+    assert_eq!(mock.with(|system| system.display_config[0].targets[0].display_id), 0x80061086);
+}
+
+#[test]
+fn diff() {
+    let _mock = nvapi_mock::install(system());
+    nvapi::initialize().unwrap();
+
+    let old = DisplayConfig::get().unwrap();
+    let mut new = old.clone();
+    new.paths[0].source.resolution.width = 2560;
+    new.paths[0].targets[0].scaling = Scaling::ToNative;
+    let mut clone = new.paths[0].targets[0];
+    clone.display_id = 0x80061087;
+    clone.primary = false;
+    new.paths[0].targets.push(clone);
+
+    let changes = old.diff(&new);
+    assert_eq!(changes.len(), 4, "{:#?}", changes);
+    assert!(matches!(changes[0], DisplayConfigChange::Source { display_id: 0x80061086, ref old, ref new } if (old.resolution.width, new.resolution.width) == (1920, 2560)));
+    assert_eq!(changes[1], DisplayConfigChange::Clones { display_id: 0x80061086, old: vec![], new: vec![0x80061087] });
+    assert!(matches!(changes[2], DisplayConfigChange::Target { display_id: 0x80061086, ref old, ref new } if (old.scaling, new.scaling) == (Scaling::Default, Scaling::ToNative)));
+    assert_eq!(changes[3], DisplayConfigChange::Added(0x80061087));
+    assert_eq!(new.diff(&old).last(), Some(&DisplayConfigChange::Removed(0x80061087)));
+
+    // a clone group is applied as a single source
+    new.apply(DisplayConfigFlags::empty()).unwrap();
+    let config = DisplayConfig::get().unwrap();
+    assert!(config.diff(&new).is_empty(), "{:#?}", config.diff(&new));
+}
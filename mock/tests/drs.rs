@@ -0,0 +1,284 @@
+extern crate nvapi;
+extern crate nvapi_mock;
+
+use nvapi::{DrsSession, Application, SettingValue, SettingLocation, GpuSupport, Status};
+use nvapi::nip::{self, NipProfile, NipSetting};
+use nvapi::drs_settings;
+use nvapi::sys::Api;
+use nvapi::sys::drs;
+use nvapi_mock::{System, DrsValue};
+
+const VSYNC: u32 = 0x00a879cf;
+const POWER_MANAGEMENT: u32 = 0x1057eb71;
+
+#[test]
+fn session_lifetime() {
+    let mock = nvapi_mock::install(System::default());
+    nvapi::initialize().unwrap();
+
+    let session = DrsSession::new().unwrap();
+    let other = DrsSession::new().unwrap();
+    assert_eq!(mock.drs_sessions(), 2);
+    drop(session);
+    assert_eq!(mock.drs_sessions(), 1);
+    drop(other);
+    assert_eq!(mock.drs_sessions(), 0);
+}
+
+#[test]
+fn enumerate() {
+    let _mock = nvapi_mock::install(System::default());
+    nvapi::initialize().unwrap();
+    let session = DrsSession::new().unwrap();
+
+    let names: Vec<_> = session.profiles().unwrap().iter().map(|profile| profile.info().unwrap().name).collect();
+    assert_eq!(names, ["Base Profile", "Mock Game"]);
+    assert_eq!(session.base_profile().unwrap().info().unwrap().name, "Base Profile");
+    assert_eq!(session.current_global_profile().unwrap().info().unwrap().name, "Base Profile");
+
+    let profile = session.find_profile("mock game").unwrap();
+    let info = profile.info().unwrap();
+    assert!(info.predefined);
+    assert_eq!((info.application_count, info.setting_count), (1, 1));
+    let apps = profile.applications().unwrap();
+    assert_eq!(apps.len(), 1);
+    assert_eq!((&apps[0].name[..], &apps[0].friendly_name[..], apps[0].predefined), ("mockgame.exe", "Mock Game", true));
+
+    let settings = profile.settings().unwrap();
+    assert_eq!(settings.len(), 1);
+    assert_eq!(settings[0].id, POWER_MANAGEMENT);
+    assert_eq!(settings[0].name, "Power management mode");
+    assert_eq!(settings[0].value, SettingValue::Dword(1));
+    assert_eq!(settings[0].predefined, Some(SettingValue::Dword(1)));
+
+    let (profile, app) = session.find_application(r"C:\Games\Mock\MockGame.exe").unwrap();
+    assert_eq!(profile.info().unwrap().name, "Mock Game");
+    assert_eq!(app, apps[0]);
+    assert_eq!(session.find_application("other.exe").unwrap_err(), Status::ExecutableNotFound);
+    assert_eq!(session.find_profile("Missing").unwrap_err(), Status::ProfileNotFound);
+}
+
+#[test]
+fn create_profile() {
+    let mock = nvapi_mock::install(System::default());
+    nvapi::initialize().unwrap();
+    let session = DrsSession::new().unwrap();
+
+    let profile = session.create_profile("Render Farm", GpuSupport::QUADRO).unwrap();
+    assert_eq!(session.create_profile("render farm", GpuSupport::QUADRO).unwrap_err(), Status::ProfileNameInUse);
+    let app = Application {
+        file_in_folder: "scene.blend".into(),
+        command_line: Some("--background".into()),
+        .. Application::new("blender.exe")
+    };
+    profile.add_application(&app).unwrap();
+    profile.add_application(&Application::new("cycles.exe")).unwrap();
+    assert_eq!(profile.add_application(&Application::new("mockgame.exe")).unwrap_err(), Status::ExecutableAlreadyInUse);
+    assert_eq!(profile.applications().unwrap(), [app.clone(), Application::new("cycles.exe")]);
+    assert_eq!(profile.application("BLENDER.EXE").unwrap(), app);
+
+    profile.remove_application("cycles.exe").unwrap();
+    assert_eq!(profile.remove_application("cycles.exe").unwrap_err(), Status::ExecutableNotFound);
+    profile.set_gpu_support(GpuSupport::QUADRO | GpuSupport::GEFORCE).unwrap();
+
+    // nothing reaches the driver until saved
+    assert_eq!(mock.with(|system| system.drs_profiles.len()), 2);
+    session.save().unwrap();
+    let saved = mock.with(|system| system.drs_profiles[2].clone());
+    assert_eq!(saved.name, "Render Farm");
+    assert_eq!(saved.gpu_support, GpuSupport::QUADRO | GpuSupport::GEFORCE);
+    assert_eq!(saved.applications.len(), 1);
+    assert_eq!(saved.applications[0].command_line.as_deref(), Some("--background"));
+
+    profile.delete().unwrap();
+    session.load().unwrap();
+    assert_eq!(session.find_profile("Render Farm").unwrap().info().unwrap().application_count, 1);
+}
+
+#[test]
+fn settings() {
+    let mock = nvapi_mock::install(System::default());
+    nvapi::initialize().unwrap();
+    let session = DrsSession::new().unwrap();
+    let profile = session.create_profile("Settings", GpuSupport::GEFORCE).unwrap();
+
+    let values = [
+        (0x10000001, SettingValue::Dword(0xdeadbeef)),
+        (0x10000002, SettingValue::Binary(vec![1, 2, 3, 4, 5])),
+        (0x10000003, SettingValue::String("Ünïcode".into())),
+    ];
+    for (id, value) in &values {
+        profile.set_setting(*id, value).unwrap();
+    }
+    for (id, value) in &values {
+        let setting = profile.setting(*id).unwrap();
+        assert_eq!(setting.value, *value);
+        assert_eq!((setting.location, setting.predefined), (SettingLocation::CurrentProfile, None));
+    }
+    assert_eq!(profile.settings().unwrap().len(), 3);
+    assert_eq!(profile.set_setting(0x10000001, &SettingValue::String("1".into())).unwrap_err(), Status::DatatypeMismatch);
+    assert_eq!(profile.set_setting(0x10000002, &SettingValue::Binary(vec![0; 4097])).unwrap_err(), Status::InvalidArgument);
+
+    session.save().unwrap();
+    assert_eq!(mock.with(|system| system.drs_profiles[2].settings[2].value.clone()), DrsValue::String("Ünïcode".into()));
+
+    profile.delete_setting(0x10000001).unwrap();
+    assert_eq!(profile.setting(0x10000001).unwrap_err(), Status::SettingNotFound);
+}
+
+#[test]
+fn inheritance() {
+    let _mock = nvapi_mock::install(System::default());
+    nvapi::initialize().unwrap();
+    let session = DrsSession::new().unwrap();
+    let game = session.find_profile("Mock Game").unwrap();
+
+    let vsync = game.setting(VSYNC).unwrap();
+    assert_eq!((vsync.location, vsync.value), (SettingLocation::BaseProfile, SettingValue::Dword(0x60925292)));
+
+    let global = session.create_profile("Global", GpuSupport::GEFORCE).unwrap();
+    global.set_setting(VSYNC, &SettingValue::Dword(0x08416747)).unwrap();
+    session.set_current_global_profile("Global").unwrap();
+    let vsync = game.setting(VSYNC).unwrap();
+    assert_eq!((vsync.location, vsync.value), (SettingLocation::GlobalProfile, SettingValue::Dword(0x08416747)));
+
+    game.set_setting(VSYNC, &SettingValue::Dword(0x47814940)).unwrap();
+    assert_eq!(game.setting(VSYNC).unwrap().location, SettingLocation::CurrentProfile);
+    game.delete_setting(VSYNC).unwrap();
+    assert_eq!(game.setting(VSYNC).unwrap().location, SettingLocation::GlobalProfile);
+}
+
+#[test]
+fn restore_defaults() {
+    let _mock = nvapi_mock::install(System::default());
+    nvapi::initialize().unwrap();
+    let session = DrsSession::new().unwrap();
+
+    let game = session.find_profile("Mock Game").unwrap();
+    game.set_setting(POWER_MANAGEMENT, &SettingValue::Dword(0)).unwrap();
+    game.add_application(&Application::new("mockgame-beta.exe")).unwrap();
+    game.restore_default_setting(POWER_MANAGEMENT).unwrap();
+    assert_eq!(game.setting(POWER_MANAGEMENT).unwrap().value, SettingValue::Dword(1));
+
+    // deleting a predefined profile only restores it
+    game.set_setting(POWER_MANAGEMENT, &SettingValue::Dword(0)).unwrap();
+    game.delete().unwrap();
+    let game = session.find_profile("Mock Game").unwrap();
+    assert_eq!(game.applications().unwrap().len(), 1);
+    assert_eq!(game.setting(POWER_MANAGEMENT).unwrap().value, SettingValue::Dword(1));
+
+    let user = session.create_profile("User", GpuSupport::GEFORCE).unwrap();
+    assert_eq!(user.restore_defaults().unwrap_err(), Status::ProfileRemoved);
+    assert_eq!(session.find_profile("User").unwrap_err(), Status::ProfileNotFound);
+
+    session.create_profile("User", GpuSupport::GEFORCE).unwrap();
+    session.restore_all_defaults().unwrap();
+    assert_eq!(session.profiles().unwrap().len(), 2);
+}
+
+#[test]
+fn application_versions() {
+    let mock = nvapi_mock::install(System::default());
+    nvapi::initialize().unwrap();
+    nvapi::reset_negotiated_versions();
+    mock.reject_version(Api::NvAPI_DRS_CreateApplication, drs::NVDRS_APPLICATION_VER_V4);
+    mock.reject_version(Api::NvAPI_DRS_EnumApplications, drs::NVDRS_APPLICATION_VER_V4);
+    let session = DrsSession::new().unwrap();
+    let profile = session.create_profile("Versions", GpuSupport::GEFORCE).unwrap();
+
+    let metro = Application {
+        metro: true,
+        .. Application::new("metro.exe")
+    };
+    profile.add_application(&metro).unwrap();
+    assert_eq!(nvapi::negotiated_version(Api::NvAPI_DRS_CreateApplication), Some(drs::NVDRS_APPLICATION_VER_V3));
+    assert_eq!(profile.applications().unwrap(), [metro]);
+
+    // a command line can't be expressed by older versions
+    let app = Application {
+        command_line: Some("-dx12".into()),
+        .. Application::new("game.exe")
+    };
+    assert_eq!(profile.add_application(&app).unwrap_err(), Status::IncompatibleStructVersion);
+    nvapi::reset_negotiated_versions();
+}
+
+#[test]
+fn export_import() {
+    let mock = nvapi_mock::install(System::default());
+    nvapi::initialize().unwrap();
+    let session = DrsSession::new().unwrap();
+
+    // settings at their predefined value aren't exported
+    let game = session.find_profile("Mock Game").unwrap();
+    assert_eq!(game.export().unwrap(), NipProfile {
+        name: "Mock Game".into(),
+        executables: vec!["mockgame.exe".into()],
+        settings: vec![],
+    });
+    game.set_setting(POWER_MANAGEMENT, &SettingValue::Dword(0)).unwrap();
+    game.set_setting(0x10000003, &SettingValue::String("tuned".into())).unwrap();
+    let exported = vec![game.export().unwrap()];
+    assert_eq!(exported[0].settings.len(), 2);
+
+    let data = nip::encode(&exported);
+    let json = serde_json::to_string(&exported).unwrap();
+    assert_eq!(serde_json::from_str::<Vec<NipProfile>>(&json).unwrap(), exported);
+    drop(session);
+
+    // apply to a workstation that has never seen the profile
+    let mut system = System::default();
+    system.drs_profiles.truncate(1);
+    mock.with(|s| *s = system);
+    let session = DrsSession::new().unwrap();
+    let mut imported = nip::decode(&data).unwrap();
+    imported[0].settings.push(NipSetting { id: VSYNC, name: "Vertical Sync".into(), value: SettingValue::Dword(0x08416747) });
+    session.import(&imported).unwrap();
+    // importing again only overwrites settings
+    session.import(&imported).unwrap();
+    session.save().unwrap();
+
+    let saved = mock.with(|system| system.drs_profiles[1].clone());
+    assert_eq!(saved.name, "Mock Game");
+    assert!(!saved.predefined);
+    assert_eq!(saved.applications.len(), 1);
+    assert_eq!(saved.settings.len(), 3);
+    let game = session.find_profile("Mock Game").unwrap();
+    assert_eq!(game.setting(POWER_MANAGEMENT).unwrap().value, SettingValue::Dword(0));
+    assert_eq!(game.setting(VSYNC).unwrap().location, SettingLocation::CurrentProfile);
+    assert_eq!(game.export().unwrap().settings.len(), 3);
+
+    // an executable can't join a second profile
+    let conflict = NipProfile {
+        name: "Conflict".into(),
+        executables: vec!["MockGame.exe".into()],
+        settings: vec![],
+    };
+    assert_eq!(session.import(&[conflict]).unwrap_err(), Status::ExecutableAlreadyInUse);
+}
+
+#[test]
+fn known_settings() {
+    let _mock = nvapi_mock::install(System::default());
+    nvapi::initialize().unwrap();
+
+    assert_eq!(drs_settings::setting_id_from_name("Power management mode").unwrap(), POWER_MANAGEMENT);
+    assert_eq!(drs_settings::setting_name_from_id(VSYNC).unwrap(), "Vertical Sync");
+    assert_eq!(drs_settings::setting_name_from_id(0x12345678).unwrap_err(), Status::SettingNotFound);
+    assert_eq!(drs_settings::setting_id("power_management_mode").unwrap(), POWER_MANAGEMENT);
+    assert_eq!(drs_settings::setting_id("Vertical Sync").unwrap(), VSYNC);
+    assert_eq!(drs_settings::setting_id("Nonexistent").unwrap_err(), Status::SettingNotFound);
+    let ids = drs_settings::available_setting_ids().unwrap();
+    assert!(drs_settings::KNOWN_SETTINGS.iter().all(|setting| ids.contains(&setting.id)));
+
+    let session = DrsSession::new().unwrap();
+    let game = session.find_profile("Mock Game").unwrap();
+    assert_eq!(game.known_setting("power_management_mode").unwrap(), "prefer_max_performance");
+    assert_eq!(game.known_setting("vsync").unwrap(), "use_application_setting");
+    game.set_known_setting("power_management_mode", "optimal_power").unwrap();
+    game.set_known_setting("frame_rate_limiter", "141").unwrap();
+    assert_eq!(game.setting(POWER_MANAGEMENT).unwrap().value, SettingValue::Dword(5));
+    assert_eq!(game.known_setting("frame_rate_limiter").unwrap(), "141");
+    assert_eq!(game.set_known_setting("power_management_mode", "turbo").unwrap_err(), Status::InvalidArgument);
+    assert_eq!(game.set_known_setting("turbo", "on").unwrap_err(), Status::SettingNotFound);
+}
@@ -0,0 +1,226 @@
+extern crate nvapi;
+extern crate nvapi_mock;
+
+use nvapi::{PhysicalGpu, ConnectedIdsFlags, Status, Range, Kilohertz, sys};
+use nvapi::edid::{Edid, EdidBuilder, EdidError, Extension, Descriptor, AudioFormat, Mode, BLOCK_SIZE};
+use nvapi_mock::{System, MOCK_EDID};
+
+const DISPLAY_ID: u32 = 0x80061086;
+
+/// The mock EDID with its CTA extension repeated `extensions` times.
+fn long_edid(extensions: u8) -> Vec<u8> {
+    let mut edid = MOCK_EDID[..BLOCK_SIZE].to_vec();
+    edid[126] = extensions;
+    edid[127] = 0u8.wrapping_sub(edid[..127].iter().fold(0u8, |sum, &b| sum.wrapping_add(b)));
+    for _ in 0..extensions {
+        edid.extend_from_slice(&MOCK_EDID[BLOCK_SIZE..]);
+    }
+    edid
+}
+
+#[test]
+fn read() {
+    let _mock = nvapi_mock::install(System::default());
+    nvapi::initialize().unwrap();
+
+    let gpu = &PhysicalGpu::enumerate().unwrap()[0];
+    assert_eq!(gpu.edid(DISPLAY_ID).unwrap(), &MOCK_EDID[..]);
+    // the second output isn't connected
+    assert_eq!(gpu.edid(0x80061087).unwrap_err(), Status::InvalidArgument);
+}
+
+#[test]
+fn read_missing() {
+    let mock = nvapi_mock::install(System::default());
+    nvapi::initialize().unwrap();
+    mock.with(|system| system.gpus[0].displays[0].edid.clear());
+
+    let gpu = &PhysicalGpu::enumerate().unwrap()[0];
+    assert_eq!(gpu.edid(DISPLAY_ID).unwrap_err(), Status::DataNotFound);
+}
+
+#[test]
+fn read_pages() {
+    let mock = nvapi_mock::install(System::default());
+    nvapi::initialize().unwrap();
+    let long = long_edid(3);
+    mock.with(|system| system.gpus[0].displays[0].edid = long.clone());
+
+    let gpu = &PhysicalGpu::enumerate().unwrap()[0];
+    let edid = gpu.edid(DISPLAY_ID).unwrap();
+    assert_eq!(edid, long);
+    assert_eq!(Edid::parse(&edid).unwrap().cta_extensions().count(), 3);
+}
+
+#[test]
+fn read_old_versions() {
+    let mock = nvapi_mock::install(System::default());
+    nvapi::initialize().unwrap();
+    nvapi::reset_negotiated_versions();
+    mock.with(|system| system.gpus[0].displays[0].edid = long_edid(3));
+    mock.reject_version(sys::Api::NvAPI_GPU_GetEDID, sys::gpu::display::NV_EDID_VER3);
+
+    // without paging only the first 256 bytes can be read
+    let gpu = &PhysicalGpu::enumerate().unwrap()[0];
+    assert_eq!(gpu.edid(DISPLAY_ID).unwrap(), &long_edid(3)[..256]);
+
+    mock.reject_version(sys::Api::NvAPI_GPU_GetEDID, sys::gpu::display::NV_EDID_VER2);
+    mock.with(|system| system.gpus[0].displays[0].edid = MOCK_EDID.to_vec());
+    assert_eq!(gpu.edid(DISPLAY_ID).unwrap(), &MOCK_EDID[..]);
+    nvapi::reset_negotiated_versions();
+}
+
+#[test]
+fn parse() {
+    let edid = Edid::parse(&MOCK_EDID).unwrap();
+    assert_eq!(edid.manufacturer, "NVM");
+    assert_eq!(edid.product_code, 0x1234);
+    assert_eq!(edid.serial_number, Some(0x01020304));
+    assert_eq!((edid.week, edid.year, edid.model_year), (Some(12), 2018, false));
+    assert_eq!(edid.version, (1, 4));
+    assert!(edid.digital);
+    assert_eq!(edid.screen_size, Some((60, 34)));
+    assert_eq!(edid.gamma, Some(220));
+    assert_eq!(edid.name(), Some("Mock Display"));
+    assert_eq!(edid.serial_string(), Some("MOCK0001"));
+
+    let modes = |w, h| Mode { width: w, height: h, refresh: 60, interlaced: false };
+    assert_eq!(edid.established_timings, [modes(640, 480), modes(800, 600), modes(1024, 768)]);
+    assert_eq!(edid.standard_timings, [modes(1920, 1080), modes(1280, 720)]);
+
+    let preferred = &edid.detailed_timings[0];
+    assert_eq!(preferred.pixel_clock, Kilohertz(148500));
+    assert_eq!((preferred.h_active + preferred.h_blanking, preferred.v_active + preferred.v_blanking), (2200, 1125));
+    assert_eq!(preferred.refresh_millihertz(), 60000);
+    assert_eq!(preferred.mode(), modes(1920, 1080));
+
+    let limits = edid.range_limits().unwrap();
+    assert_eq!(limits.vertical, Range { min: 48, max: 144 });
+    assert_eq!(limits.horizontal, Range { min: 30, max: 160 });
+    assert_eq!(limits.max_pixel_clock, Some(600));
+}
+
+#[test]
+fn parse_cta() {
+    let edid = Edid::parse(&MOCK_EDID).unwrap();
+    let cta = match edid.extensions[..] {
+        [Extension::Cta(ref cta)] => cta,
+        ref extensions => panic!("unexpected extensions {:?}", extensions),
+    };
+    assert_eq!(cta.revision, 3);
+    assert!(cta.basic_audio && cta.ycbcr444 && cta.ycbcr422 && !cta.underscan);
+    let vics: Vec<_> = cta.video.iter().map(|svd| (svd.vic, svd.native)).collect();
+    assert_eq!(vics, [(16, true), (4, false), (3, false), (97, false)]);
+    assert_eq!(cta.detailed_timings.len(), 1);
+    assert_eq!(cta.detailed_timings[0].mode(), Mode { width: 1280, height: 720, refresh: 60, interlaced: false });
+
+    let audio: Vec<_> = edid.audio().collect();
+    assert_eq!(audio.len(), 1);
+    assert_eq!((audio[0].format, audio[0].channels), (AudioFormat::Lpcm, 2));
+    assert_eq!(audio[0].sample_rates(), [32000, 44100, 48000]);
+    assert_eq!(audio[0].bit_depths(), [16, 20, 24]);
+    assert_eq!(cta.speaker_allocation, Some(0x01));
+
+    assert_eq!(edid.vrr_range(), Some(Range { min: 48, max: 144 }));
+
+    let hdr = edid.hdr_static_metadata().unwrap();
+    assert!(hdr.sdr && hdr.pq && !hdr.hdr && !hdr.hlg);
+    assert_eq!(hdr.max_luminance_nits(), Some(400.0));
+    let fall = hdr.max_frame_average_luminance_nits().unwrap();
+    assert!((fall - 282.84).abs() < 0.01, "{}", fall);
+    let min = hdr.min_luminance_nits().unwrap();
+    assert!((min - 0.063).abs() < 0.001, "{}", min);
+}
+
+/// A CTA extension laid out like an HDMI 2.1 panel's, written out byte by byte rather than
+/// built: the HF-VSDB carries TMDS, FRL, ALLM and DSC fields around a 40-240 Hz VRR range.
+const HDMI_2_1_CTA: [u8; BLOCK_SIZE] = [
+    0x02, 0x03, 0x1d, 0x70, 0x43, 0x90, 0x61, 0x76, 0x6d, 0xd8, 0x5d, 0xc4, 0x01, 0x78, 0x80, 0x63,
+    0x02, 0x28, 0xf0, 0x00, 0x00, 0x00, 0xe6, 0x06, 0x05, 0x01, 0x60, 0x50, 0x20, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x26,
+];
+
+#[test]
+fn parse_hdmi_2_1() {
+    let data = [&MOCK_EDID[..BLOCK_SIZE], &HDMI_2_1_CTA[..]].concat();
+    let edid = Edid::parse(&data).unwrap();
+    let cta = edid.cta_extensions().next().unwrap();
+    let vics: Vec<_> = cta.video.iter().map(|svd| (svd.vic, svd.native)).collect();
+    assert_eq!(vics, [(16, true), (97, false), (118, false)]);
+    assert_eq!(cta.vendor_blocks.len(), 1);
+    assert_eq!(cta.vendor_blocks[0].data, [0x01, 0x78, 0x80, 0x63, 0x02, 0x28, 0xf0, 0x00, 0x00, 0x00]);
+    assert_eq!(edid.vrr_range(), Some(Range { min: 40, max: 240 }));
+    assert!(edid.hdr_static_metadata().unwrap().pq);
+}
+
+#[test]
+fn parse_errors() {
+    assert_eq!(Edid::parse(&MOCK_EDID[..100]), Err(EdidError::Truncated { len: 100, expected: 128 }));
+    assert_eq!(Edid::parse(&MOCK_EDID[..128]), Err(EdidError::Truncated { len: 128, expected: 256 }));
+
+    let mut edid = MOCK_EDID;
+    edid[0] = 1;
+    assert_eq!(Edid::parse(&edid), Err(EdidError::Header));
+
+    let mut edid = MOCK_EDID;
+    edid[200] ^= 0x10;
+    assert_eq!(Edid::parse(&edid), Err(EdidError::Checksum(1)));
+    assert_eq!(EdidError::Checksum(1).to_string(), "EDID block 1 has an invalid checksum");
+
+    // trailing bytes past the announced extensions are ignored
+    let mut edid = MOCK_EDID.to_vec();
+    edid.extend_from_slice(&[0xff; 128]);
+    assert!(Edid::parse(&edid).is_ok());
+}
+
+#[test]
+fn descriptor_text() {
+    let edid = Edid::parse(&MOCK_EDID).unwrap();
+    assert!(edid.descriptors.contains(&Descriptor::ProductName("Mock Display".into())));
+}
+
+#[test]
+fn set() {
+    let mock = nvapi_mock::install(System::default());
+    nvapi::initialize().unwrap();
+    let gpu = &PhysicalGpu::enumerate().unwrap()[0];
+    assert_eq!(gpu.display_ids_connected(ConnectedIdsFlags::empty()).unwrap().len(), 1);
+
+    // give the unconnected output a monitor
+    let edid = EdidBuilder::new("Headless", Mode { width: 2560, height: 1440, refresh: 60, interlaced: false })
+        .refresh_range(Range { min: 48, max: 144 })
+        .build();
+    gpu.set_edid(0x80061087, &edid).unwrap();
+    assert_eq!(gpu.display_ids_connected(ConnectedIdsFlags::empty()).unwrap().len(), 2);
+    assert_eq!(gpu.edid(0x80061087).unwrap(), edid);
+    assert_eq!(Edid::parse(&gpu.edid(0x80061087).unwrap()).unwrap().name(), Some("Headless"));
+
+    gpu.set_edid(0x80061087, &[]).unwrap();
+    assert_eq!(gpu.edid(0x80061087).unwrap_err(), Status::DataNotFound);
+    assert_eq!(gpu.set_edid(0x1234, &edid).unwrap_err(), Status::InvalidArgument);
+    assert_eq!(mock.with(|system| system.gpus[0].displays[1].edid_id), 2);
+}
+
+#[test]
+fn set_pages() {
+    let mock = nvapi_mock::install(System::default());
+    nvapi::initialize().unwrap();
+    nvapi::reset_negotiated_versions();
+    let gpu = &PhysicalGpu::enumerate().unwrap()[0];
+
+    let long = long_edid(4);
+    gpu.set_edid(DISPLAY_ID, &long).unwrap();
+    assert_eq!(mock.with(|system| system.gpus[0].displays[0].edid.clone()), long);
+
+    // paging needs the newest version, so older drivers can't take this
+    mock.reject_version(sys::Api::NvAPI_GPU_SetEDID, sys::gpu::display::NV_EDID_VER3);
+    assert_eq!(gpu.set_edid(DISPLAY_ID, &long).unwrap_err(), Status::IncompatibleStructVersion);
+    gpu.set_edid(DISPLAY_ID, &MOCK_EDID).unwrap();
+    assert_eq!(mock.with(|system| system.gpus[0].displays[0].edid.clone()), &MOCK_EDID[..]);
+    nvapi::reset_negotiated_versions();
+}
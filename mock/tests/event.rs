@@ -0,0 +1,113 @@
+extern crate nvapi;
+extern crate nvapi_mock;
+
+use std::thread;
+use std::sync::{Arc, Mutex};
+use nvapi::{EventSubscription, SyncEvent, Status};
+use nvapi::sys::Api;
+use nvapi_mock::System;
+
+#[test]
+fn sync_events() {
+    let mock = nvapi_mock::install(System::default());
+    nvapi::initialize().unwrap();
+
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let subscription = {
+        let received = received.clone();
+        EventSubscription::sync_events(move |event| received.lock().unwrap().push((event, thread::current().id()))).unwrap()
+    };
+    assert_eq!(mock.event_callbacks(), 1);
+
+    mock.fire_sync_event(SyncEvent::HouseSyncLoss);
+    mock.fire_sync_event(SyncEvent::SyncLoss);
+    let events = received.lock().unwrap().clone();
+    assert_eq!(events.iter().map(|&(event, _)| event).collect::<Vec<_>>(), vec![SyncEvent::HouseSyncLoss, SyncEvent::SyncLoss]);
+    // delivered on the driver's thread rather than ours
+    assert!(events.iter().all(|&(_, thread)| thread != thread::current().id()));
+
+    drop(subscription);
+    assert_eq!(mock.event_callbacks(), 0);
+    mock.fire_sync_event(SyncEvent::SyncGain);
+    assert_eq!(received.lock().unwrap().len(), 2);
+}
+
+#[test]
+fn shared_registration() {
+    let mock = nvapi_mock::install(System::default());
+    nvapi::initialize().unwrap();
+
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let subscribe = |name: &'static str| {
+        let received = received.clone();
+        EventSubscription::sync_events(move |event| received.lock().unwrap().push((name, event))).unwrap()
+    };
+    let first = subscribe("first");
+    let second = subscribe("second");
+    // the driver hands out one handle per process, so both share a registration
+    assert_eq!(first.handle().as_ptr(), second.handle().as_ptr());
+    assert_eq!(mock.event_callbacks(), 1);
+
+    drop(first);
+    assert_eq!(mock.event_callbacks(), 1);
+    mock.fire_sync_event(SyncEvent::SyncGain);
+    assert_eq!(*received.lock().unwrap(), [("second", SyncEvent::SyncGain)]);
+
+    drop(second);
+    assert_eq!(mock.event_callbacks(), 0);
+}
+
+#[test]
+fn one_shot() {
+    let mock = nvapi_mock::install(System::default());
+    nvapi::initialize().unwrap();
+
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let slot = Arc::new(Mutex::new(None));
+    let subscription = {
+        let (received, slot) = (received.clone(), slot.clone());
+        EventSubscription::sync_events(move |event| {
+            received.lock().unwrap().push(event);
+            drop(slot.lock().unwrap().take());
+        }).unwrap()
+    };
+    *slot.lock().unwrap() = Some(subscription);
+
+    mock.fire_sync_event(SyncEvent::SyncLoss);
+    mock.fire_sync_event(SyncEvent::SyncGain);
+    assert_eq!(*received.lock().unwrap(), [SyncEvent::SyncLoss]);
+    assert!(slot.lock().unwrap().is_none());
+
+    // the driver's thread can't unregister, so the next subscription picks it up
+    assert_eq!(mock.event_callbacks(), 1);
+    drop(EventSubscription::sync_events(|_| ()).unwrap());
+    assert_eq!(mock.event_callbacks(), 0);
+}
+
+#[test]
+fn panicking_callback() {
+    let mock = nvapi_mock::install(System::default());
+    nvapi::initialize().unwrap();
+
+    let calls = Arc::new(Mutex::new(0));
+    let _subscription = {
+        let calls = calls.clone();
+        EventSubscription::sync_events(move |_| {
+            *calls.lock().unwrap() += 1;
+            panic!("callback failed")
+        }).unwrap()
+    };
+    mock.fire_sync_event(SyncEvent::Rj45Loss);
+    mock.fire_sync_event(SyncEvent::Rj45Gain);
+    assert_eq!(*calls.lock().unwrap(), 2);
+}
+
+#[test]
+fn register_failure() {
+    let mock = nvapi_mock::install(System::default());
+    nvapi::initialize().unwrap();
+
+    mock.fail(Api::NvAPI_Event_RegisterCallback, Status::Error);
+    assert_eq!(EventSubscription::sync_events(|_| ()).unwrap_err(), Status::Error);
+    assert_eq!(mock.event_callbacks(), 0);
+}
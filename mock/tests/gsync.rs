@@ -0,0 +1,127 @@
+extern crate nvapi;
+extern crate nvapi_mock;
+
+use nvapi::{SyncDevice, Status};
+use nvapi::gsync::{self, SyncDisplay, DisplaySyncState, SyncSource, Polarity, ControlParameters};
+use nvapi_mock::{System, GSyncDevice};
+
+const PRIMARY: u32 = 0x80061086;
+const SECONDARY: u32 = 0x80061087;
+
+fn system() -> System {
+    let mut system = System::default();
+    // plugged in, but not part of the desktop
+    system.gpus[0].displays[1].connected = true;
+    system.gsync_devices.push(GSyncDevice::default());
+    system
+}
+
+fn display(display_id: u32, state: DisplaySyncState) -> SyncDisplay {
+    SyncDisplay {
+        display_id,
+        masterable: false,
+        state,
+    }
+}
+
+fn device() -> SyncDevice {
+    SyncDevice::enumerate().unwrap().remove(0)
+}
+
+#[test]
+fn enumerate() {
+    let _mock = nvapi_mock::install(System::default());
+    nvapi::initialize().unwrap();
+    assert!(SyncDevice::enumerate().unwrap().is_empty());
+    drop(_mock);
+
+    let _mock = nvapi_mock::install(system());
+    nvapi::initialize().unwrap();
+    let caps = device().capabilities().unwrap();
+    assert_eq!((caps.board_id, caps.revision, caps.extended_revision), (0x358, 1, 6));
+    assert!(caps.max_multiply_divide.is_some());
+
+    let topology = device().topology().unwrap();
+    assert_eq!(topology.gpus.len(), 1);
+    assert_eq!(topology.gpus[0].gpu.full_name().unwrap(), nvapi::PhysicalGpu::enumerate().unwrap()[0].full_name().unwrap());
+    assert!(topology.gpus[0].proxy.is_none());
+    assert!(!topology.gpus[0].synced);
+    assert_eq!(topology.displays, vec![
+        SyncDisplay { display_id: PRIMARY, masterable: true, state: DisplaySyncState::Unsynced },
+        SyncDisplay { display_id: SECONDARY, masterable: false, state: DisplaySyncState::Unsynced },
+    ]);
+}
+
+#[test]
+fn frame_lock() {
+    let _mock = nvapi_mock::install(system());
+    nvapi::initialize().unwrap();
+
+    let device = device();
+    let gpu = &nvapi::PhysicalGpu::enumerate().unwrap()[0];
+    assert!(!device.is_frame_locked().unwrap());
+    assert!(!device.sync_status(gpu).unwrap().signal_available);
+
+    gsync::set_sync_state(&[display(PRIMARY, DisplaySyncState::Master)]).unwrap();
+    assert!(device.is_frame_locked().unwrap());
+    assert!(device.sync_status(gpu).unwrap().is_locked());
+    assert!(device.topology().unwrap().gpus[0].synced);
+    assert_eq!(device.status_parameters().unwrap().refresh_rate, 600000);
+
+    // displays that aren't listed are unsynced again
+    gsync::set_sync_state(&[]).unwrap();
+    assert!(!device.is_frame_locked().unwrap());
+    assert!(device.topology().unwrap().displays.iter().all(|display| display.state == DisplaySyncState::Unsynced));
+
+    assert_eq!(gsync::set_sync_state(&[display(SECONDARY, DisplaySyncState::Master)]).unwrap_err(), Status::InvalidSyncTopology);
+    assert_eq!(gsync::set_sync_state(&[display(0x80061088, DisplaySyncState::Slave)]).unwrap_err(), Status::InvalidSyncTopology);
+    assert_eq!(gsync::set_sync_state(&[display(PRIMARY, DisplaySyncState::Master), display(PRIMARY, DisplaySyncState::Master)]).unwrap_err(), Status::InvalidArgument);
+}
+
+#[test]
+fn house_sync() {
+    let mock = nvapi_mock::install(system());
+    nvapi::initialize().unwrap();
+
+    let device = device();
+    let mut params = device.control_parameters().unwrap();
+    assert_eq!(params.source, SyncSource::Vsync);
+    params.source = SyncSource::HouseSync;
+    params.polarity = Polarity::FallingEdge;
+    params.sync_skew.lines = 10;
+    assert_eq!(device.set_control_parameters(&params).unwrap_err(), Status::SyncMasterNotFound);
+
+    gsync::set_sync_state(&[display(PRIMARY, DisplaySyncState::Master)]).unwrap();
+    device.set_control_parameters(&params).unwrap();
+    assert_eq!(device.control_parameters().unwrap(), params);
+    assert!(!device.is_frame_locked().unwrap());
+    assert!(!device.status_parameters().unwrap().house_sync);
+
+    // a 50 Hz signal can't lock a 60 Hz display
+    mock.with(|system| system.gsync_devices[0].house_sync = 500000);
+    let status = device.status_parameters().unwrap();
+    assert!(status.house_sync);
+    assert_eq!((status.refresh_rate, status.house_sync_rate), (500000, 500000));
+    assert!(!device.is_frame_locked().unwrap());
+
+    mock.with(|system| system.gsync_devices[0].house_sync = 599940);
+    assert!(device.is_frame_locked().unwrap());
+
+    let mut invalid = params;
+    invalid.sync_skew.lines = params.sync_skew.max_lines + 1;
+    assert_eq!(device.set_control_parameters(&invalid).unwrap_err(), Status::InvalidArgument);
+    let mut invalid = params;
+    invalid.house_sync_output = true;
+    assert_eq!(device.set_control_parameters(&invalid).unwrap_err(), Status::InvalidArgument);
+}
+
+#[test]
+fn json_round_trip() {
+    let _mock = nvapi_mock::install(system());
+    nvapi::initialize().unwrap();
+
+    let params = device().control_parameters().unwrap();
+    let json = serde_json::to_string_pretty(&params).unwrap();
+    let parsed: ControlParameters = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, params);
+}
@@ -0,0 +1,123 @@
+extern crate nvapi_hi;
+extern crate nvapi_mock;
+extern crate serde_json;
+
+use std::iter;
+use nvapi_hi::{Gpu, GpuInfo, Percentage, Celsius, Microvolts, KilohertzDelta, RamType, ThermalTarget, EccConfiguration};
+use nvapi_hi::nvapi::Status;
+use nvapi_hi::nvapi::sys::Api;
+use nvapi_mock::{System, Ecc};
+
+fn gpu() -> Gpu {
+    Gpu::enumerate().unwrap().into_iter().next().unwrap()
+}
+
+#[test]
+fn session() {
+    let mock = nvapi_mock::install(System::default());
+    let gpu = gpu();
+    assert!(gpu.inner().session().is_some());
+    assert_eq!(mock.refcount(), 1);
+
+    drop(gpu);
+    assert_eq!(mock.refcount(), 0);
+}
+
+#[test]
+fn info() {
+    let mock = nvapi_mock::install(System::default());
+    let gpu = gpu();
+    let expected = mock.with(|system| system.gpus[0].clone());
+
+    let info = gpu.info().unwrap();
+    assert_eq!(info.name, expected.full_name);
+    assert_eq!(info.pcie.unwrap().max.unwrap().lanes, expected.pcie.current.1);
+    assert_eq!(info.codename, expected.short_name);
+    assert_eq!(info.sensors.len(), expected.sensors.len());
+    assert_eq!(info.coolers.len(), expected.coolers.len());
+    assert_eq!(info.power_limits.len(), expected.power_limits.len());
+    assert_eq!(info.sensor_limits.len(), expected.thermal_limits.len());
+    assert_eq!(info.vfp_locks.len(), expected.vfp_locks.len());
+}
+
+#[test]
+fn unrecognized_values() {
+    let mock = nvapi_mock::install(System::default());
+    mock.with(|system| {
+        system.gpus[0].ram_type = RamType::Unrecognized(99);
+        system.gpus[0].sensors[0].target = ThermalTarget::Unrecognized(0x40);
+    });
+    let gpu = gpu();
+
+    let info = gpu.info().unwrap();
+    assert_eq!(info.ram_type, RamType::Unrecognized(99));
+    assert_eq!(info.ram_type.raw(), 99);
+    assert_eq!(info.ram_type.to_string(), "Unknown (99)");
+    assert!(RamType::GDDR5X < info.ram_type && RamType::DDR3 < RamType::GDDR5);
+    assert_eq!(info.sensors[0].target, ThermalTarget::Unrecognized(0x40));
+
+    let json = serde_json::to_string(&info).unwrap();
+    let parsed: GpuInfo = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, info);
+}
+
+#[test]
+fn status() {
+    let mock = nvapi_mock::install(System::default());
+    let gpu = gpu();
+    let expected = mock.with(|system| system.gpus[0].clone());
+
+    let status = gpu.status().unwrap();
+    assert_eq!(status.voltage, Some(Microvolts(expected.core_voltage)));
+    assert_eq!(status.tachometer, Some(expected.tachometer));
+    assert_eq!(status.pcie.unwrap().lanes, expected.pcie.current.1);
+    assert_eq!(status.vfp.unwrap().graphics.len(), expected.vfp.len());
+}
+
+#[test]
+fn ecc() {
+    let mock = nvapi_mock::install(System::default());
+    let gpu = gpu();
+    assert_eq!(gpu.info().unwrap().ecc, None);
+    assert_eq!(gpu.status().unwrap().ecc_errors, None);
+
+    mock.with(|system| system.gpus[0].ecc = Some(Ecc {
+        pending: false,
+        aggregate: (0, 1),
+        ..Default::default()
+    }));
+    let ecc = gpu.info().unwrap().ecc.unwrap();
+    assert_eq!(ecc.configuration, EccConfiguration::Deferred);
+    assert!(ecc.enabled);
+    assert_eq!(ecc.pending, Some(false));
+    assert_eq!(gpu.status().unwrap().ecc_errors.unwrap().aggregate.double_bit, 1);
+
+    // The configuration is optional even when ECC is supported
+    mock.fail(Api::NvAPI_GPU_GetECCConfigurationInfo, Status::NotSupported);
+    let ecc = gpu.info().unwrap().ecc.unwrap();
+    assert!(ecc.enabled);
+    assert_eq!((ecc.pending, ecc.enabled_by_default), (None, None));
+}
+
+#[test]
+fn settings() {
+    let mock = nvapi_mock::install(System::default());
+    let gpu = gpu();
+
+    gpu.set_voltage_boost(Percentage(50)).unwrap();
+    gpu.set_power_limits(iter::once(Percentage(110))).unwrap();
+    gpu.set_sensor_limits(iter::once(Celsius(75))).unwrap();
+    gpu.set_vfp(iter::once((4, KilohertzDelta(50000))), iter::empty()).unwrap();
+    gpu.set_vfp_lock(Microvolts(1000000)).unwrap();
+
+    let settings = gpu.settings().unwrap();
+    assert_eq!(settings.voltage_boost, Some(Percentage(50)));
+    assert_eq!(settings.power_limits, [Percentage(110)]);
+    assert_eq!(settings.sensor_limits, [Celsius(75)]);
+    assert_eq!(settings.vfp.unwrap().graphics[&4], KilohertzDelta(50000));
+    assert_eq!(mock.with(|system| system.gpus[0].vfp_locks.last().unwrap().voltage), 1000000);
+
+    gpu.reset_vfp().unwrap();
+    gpu.reset_vfp_lock().unwrap();
+    assert!(mock.with(|system| system.gpus[0].vfp.iter().all(|p| p.delta == 0)));
+}
@@ -0,0 +1,64 @@
+extern crate nvapi;
+extern crate nvapi_mock;
+
+use nvapi::{LogicalGpu, PhysicalGpu, Display, Status};
+use nvapi_mock::{System, VirtualGpu};
+
+fn system() -> System {
+    System {
+        gpus: (1..4).map(|bus_id| VirtualGpu {
+            bus_id,
+            .. Default::default()
+        }).collect(),
+        sli_groups: vec![vec![0, 2]],
+        .. Default::default()
+    }
+}
+
+fn bus_ids(gpus: &[PhysicalGpu]) -> Vec<u32> {
+    gpus.iter().map(|gpu| gpu.bus_id().unwrap()).collect()
+}
+
+#[test]
+fn enumerate() {
+    let _mock = nvapi_mock::install(system());
+    nvapi::initialize().unwrap();
+
+    let logical = LogicalGpu::enumerate().unwrap();
+    assert_eq!(logical.len(), 2);
+    assert_eq!(bus_ids(&logical[0].physical_gpus().unwrap()), [1, 3]);
+    assert_eq!(bus_ids(&logical[1].physical_gpus().unwrap()), [2]);
+}
+
+#[test]
+fn physical_mapping() {
+    let _mock = nvapi_mock::install(system());
+    nvapi::initialize().unwrap();
+
+    let gpus = PhysicalGpu::enumerate().unwrap();
+    let logical = gpus[2].logical_gpu().unwrap();
+    assert_eq!(logical.handle().as_ptr(), LogicalGpu::enumerate().unwrap()[0].handle().as_ptr());
+    assert_eq!(bus_ids(&logical.physical_gpus().unwrap()), [1, 3]);
+    assert_eq!(bus_ids(&gpus[1].logical_gpu().unwrap().physical_gpus().unwrap()), [2]);
+}
+
+#[test]
+fn from_display() {
+    let _mock = nvapi_mock::install(system());
+    nvapi::initialize().unwrap();
+
+    // each GPU drives one active display
+    let display = &Display::enumerate().unwrap()[1];
+    let logical = display.logical_gpu().unwrap();
+    assert_eq!(bus_ids(&logical.physical_gpus().unwrap()), [2]);
+}
+
+#[test]
+fn invalidated() {
+    let mock = nvapi_mock::install(system());
+    nvapi::initialize().unwrap();
+
+    let logical = LogicalGpu::enumerate().unwrap();
+    mock.invalidate_handles();
+    assert_eq!(logical[0].physical_gpus().unwrap_err(), Status::HandleInvalidated);
+}
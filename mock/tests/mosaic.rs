@@ -0,0 +1,166 @@
+extern crate nvapi;
+extern crate nvapi_mock;
+
+use nvapi::{DisplayConfig, Display, Rotation, Range, Status};
+use nvapi::mosaic::{self, DisplayGrid, DisplayMode, Viewport, SetDisplayTopoFlags, DisplayCapsProblem, DisplayTopoWarning};
+use nvapi_mock::System;
+
+const PRIMARY: u32 = 0x80061086;
+const SECONDARY: u32 = 0x80061087;
+
+const MODE: DisplayMode = DisplayMode {
+    width: 1920,
+    height: 1080,
+    color_depth: 32,
+    refresh_rate: 60,
+};
+
+fn system() -> System {
+    let mut system = System::default();
+    // plug in the second output without adding it to the desktop
+    system.gpus[0].displays[1].connected = true;
+    system
+}
+
+/// Two displays side by side, leaving a gap for their bezels.
+fn wall() -> DisplayGrid {
+    let mut grid = DisplayGrid::new(1, 2, &[PRIMARY, SECONDARY], MODE);
+    grid.bezel_correction = true;
+    grid.displays[1].overlap = (-40, 0);
+    grid
+}
+
+#[test]
+fn enumerate() {
+    let _mock = nvapi_mock::install(system());
+    nvapi::initialize().unwrap();
+
+    let grids = mosaic::enumerate().unwrap();
+    assert_eq!(grids, vec![DisplayGrid::new(1, 1, &[PRIMARY], MODE)]);
+    assert!(!grids[0].is_spanned());
+    assert_eq!(mosaic::viewports(PRIMARY, None).unwrap(), (vec![Viewport { left: 0, top: 0, right: 1919, bottom: 1079 }], false));
+    assert_eq!(mosaic::viewports(SECONDARY, None).unwrap_err(), Status::MosaicNotActive);
+}
+
+#[test]
+fn apply() {
+    let mock = nvapi_mock::install(system());
+    nvapi::initialize().unwrap();
+
+    let statuses = mosaic::validate(&[wall()], SetDisplayTopoFlags::empty()).unwrap();
+    assert!(statuses[0].is_valid());
+    assert_eq!(statuses[0].displays.iter().map(|status| status.display_id).collect::<Vec<_>>(), vec![PRIMARY, SECONDARY]);
+    assert!(mock.with(|system| system.mosaic_grids.is_empty()));
+
+    mosaic::apply(&[wall()], SetDisplayTopoFlags::empty()).unwrap();
+    assert_eq!(mosaic::enumerate().unwrap(), vec![wall()]);
+    assert_eq!(Display::enumerate().unwrap().len(), 2);
+
+    // the gap is part of the desktop, but not shown on either display
+    let config = DisplayConfig::get().unwrap();
+    assert_eq!(config.paths.len(), 1);
+    assert_eq!((config.paths[0].source.resolution.width, config.paths[0].source.resolution.height), (3880, 1080));
+    assert_eq!(config.display_ids().collect::<Vec<_>>(), vec![PRIMARY, SECONDARY]);
+    assert_eq!(mosaic::viewports(SECONDARY, None).unwrap(), (vec![
+        Viewport { left: 0, top: 0, right: 1919, bottom: 1079 },
+        Viewport { left: 1960, top: 0, right: 3879, bottom: 1079 },
+    ], true));
+    let (viewports, _) = mosaic::viewports(PRIMARY, Some((2600, 720))).unwrap();
+    assert_eq!(viewports[1], Viewport { left: 1320, top: 0, right: 2599, bottom: 719 });
+    assert_eq!(mosaic::viewports(PRIMARY, Some((1920, 1080))).unwrap_err(), Status::InvalidArgument);
+
+    // break the wall up into separate desktops again
+    let single = |id| DisplayGrid::new(1, 1, &[id], MODE);
+    mosaic::apply(&[single(PRIMARY), single(SECONDARY)], SetDisplayTopoFlags::empty()).unwrap();
+    assert_eq!(mosaic::enumerate().unwrap(), vec![single(PRIMARY), single(SECONDARY)]);
+    let config = DisplayConfig::get().unwrap();
+    assert_eq!(config.paths.len(), 2);
+    assert!(config.paths[0].source.gdi_primary);
+    assert_eq!(config.paths[1].source.position, (1920, 0));
+}
+
+#[test]
+fn rotated() {
+    let _mock = nvapi_mock::install(system());
+    nvapi::initialize().unwrap();
+
+    let portrait = DisplayMode { width: 1080, height: 1920, .. MODE };
+    let mut grid = DisplayGrid::new(1, 2, &[PRIMARY, SECONDARY], portrait);
+    for display in &mut grid.displays {
+        display.rotation = Rotation::Degrees90;
+    }
+    let modes = grid.display_modes().unwrap();
+    assert!(modes.contains(&portrait));
+    assert!(!modes.contains(&MODE));
+
+    mosaic::apply(&[grid.clone()], SetDisplayTopoFlags::empty()).unwrap();
+    assert_eq!(mosaic::enumerate().unwrap(), vec![grid]);
+    let config = DisplayConfig::get().unwrap();
+    assert_eq!(config.paths[0].source.resolution.width, 2160);
+    assert!(config.paths[0].targets.iter().all(|target| target.rotation == Rotation::Degrees90));
+}
+
+#[test]
+fn invalid() {
+    let _mock = nvapi_mock::install(system());
+    nvapi::initialize().unwrap();
+
+    let status = |grid: DisplayGrid| mosaic::validate(&[grid], SetDisplayTopoFlags::empty()).unwrap().remove(0);
+
+    let mut grid = wall();
+    grid.displays[1].overlap = (-1000, 0);
+    let overlapping = status(grid.clone());
+    assert!(!overlapping.is_valid());
+    assert_eq!(overlapping.displays[1].errors, DisplayCapsProblem::NOT_SUPPORTED);
+    assert!(overlapping.displays[0].errors.is_empty());
+    assert_eq!(mosaic::apply(&[grid], SetDisplayTopoFlags::empty()).unwrap_err(), Status::TopoNotPossible);
+
+    let grid = DisplayGrid::new(1, 2, &[PRIMARY, SECONDARY], DisplayMode { width: 1024, height: 768, .. MODE });
+    assert_eq!(status(grid).errors, DisplayCapsProblem::NO_COMMON_TIMINGS);
+
+    let grid = DisplayGrid::new(1, 2, &[PRIMARY, 0x80061088], MODE);
+    assert_eq!(status(grid).displays[1].errors, DisplayCapsProblem::NO_DISPLAY_CONNECTED);
+
+    // gaps are only allowed with bezel correction
+    let mut grid = wall();
+    grid.bezel_correction = false;
+    let gaps = status(grid);
+    assert!(gaps.is_valid());
+    assert_eq!(gaps.displays[1].warnings, DisplayTopoWarning::DISPLAY_POSITION);
+
+    let reload = mosaic::validate(&[wall()], SetDisplayTopoFlags::NO_DRIVER_RELOAD).unwrap();
+    assert_eq!(reload[0].warnings, DisplayTopoWarning::DRIVER_RELOAD_REQUIRED);
+    assert_eq!(mosaic::apply(&[wall()], SetDisplayTopoFlags::NO_DRIVER_RELOAD).unwrap_err(), Status::ModeChangeFailed);
+
+    let duplicate = DisplayGrid::new(1, 2, &[PRIMARY, PRIMARY], MODE);
+    assert_eq!(mosaic::validate(&[duplicate], SetDisplayTopoFlags::empty()).unwrap_err(), Status::InvalidArgument);
+    let misshapen = DisplayGrid::new(2, 2, &[PRIMARY, SECONDARY], MODE);
+    assert_eq!(mosaic::validate(&[misshapen], SetDisplayTopoFlags::empty()).unwrap_err(), Status::InvalidArgument);
+    assert_eq!(mosaic::apply(&[], SetDisplayTopoFlags::empty()).unwrap_err(), Status::InvalidArgument);
+}
+
+#[test]
+fn overlap_limits() {
+    let _mock = nvapi_mock::install(system());
+    nvapi::initialize().unwrap();
+
+    let limits = wall().overlap_limits().unwrap();
+    assert_eq!(limits.x, Range { min: -480, max: 480 });
+    assert_eq!(limits.y, Range { min: 0, max: 0 });
+
+    let grid = DisplayGrid::new(3, 3, &[PRIMARY; 9], MODE);
+    assert_eq!(grid.topology(), None);
+    assert_eq!(grid.overlap_limits().unwrap_err(), Status::NotSupported);
+}
+
+#[test]
+fn json_round_trip() {
+    let _mock = nvapi_mock::install(system());
+    nvapi::initialize().unwrap();
+
+    let json = serde_json::to_string_pretty(&[wall()]).unwrap();
+    let grids: Vec<DisplayGrid> = serde_json::from_str(&json).unwrap();
+    assert_eq!(grids, vec![wall()]);
+    mosaic::apply(&grids, SetDisplayTopoFlags::empty()).unwrap();
+    assert_eq!(mosaic::enumerate().unwrap(), grids);
+}
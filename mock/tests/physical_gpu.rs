@@ -0,0 +1,344 @@
+extern crate nvapi;
+extern crate nvapi_mock;
+
+use nvapi::{PhysicalGpu, Status, ClockFrequencyType, ClockDomain, ConnectedIdsFlags, Kilohertz, KilohertzDelta, EccConfiguration, EccErrorCounts, BusType, PcieLink};
+use nvapi::sys::i2c::I2cSpeed;
+use nvapi::sys::Api;
+use nvapi_mock::{System, VirtualGpu, I2cDevice, Ecc};
+
+fn gpus() -> Vec<PhysicalGpu> {
+    nvapi::initialize().unwrap();
+    PhysicalGpu::enumerate().unwrap()
+}
+
+#[test]
+fn not_initialized() {
+    let _mock = nvapi_mock::install(System::default());
+    assert_eq!(PhysicalGpu::enumerate().unwrap_err(), Status::ApiNotInitialized);
+}
+
+#[test]
+fn refcount() {
+    let mock = nvapi_mock::install(System::default());
+    nvapi::initialize().unwrap();
+    nvapi::initialize().unwrap();
+    assert_eq!(mock.refcount(), 2);
+    nvapi::unload().unwrap();
+    nvapi::unload().unwrap();
+    assert_eq!(mock.refcount(), 0);
+    assert_eq!(nvapi::unload().unwrap_err(), Status::ApiNotInitialized);
+}
+
+#[test]
+fn enumerate() {
+    let _mock = nvapi_mock::install(System::new(vec![VirtualGpu::default(); 3]));
+    assert_eq!(gpus().len(), 3);
+}
+
+#[test]
+fn no_devices() {
+    let _mock = nvapi_mock::install(System::new(Vec::new()));
+    nvapi::initialize().unwrap();
+    assert!(PhysicalGpu::enumerate().unwrap().is_empty());
+}
+
+#[test]
+fn identity() {
+    let mock = nvapi_mock::install(System::default());
+    let gpu = &gpus()[0];
+    let expected = mock.with(|system| system.gpus[0].clone());
+
+    assert_eq!(gpu.full_name().unwrap(), expected.full_name);
+    assert_eq!(gpu.short_name().unwrap(), expected.short_name);
+    assert_eq!(gpu.vbios_version_string().unwrap(), expected.vbios_version);
+    assert_eq!(gpu.pci_identifiers().unwrap().device_id, expected.pci.device_id);
+    assert_eq!(gpu.board_number().unwrap(), expected.board_number);
+    assert_eq!(gpu.core_count().unwrap(), expected.core_count);
+}
+
+#[test]
+fn memory() {
+    let mock = nvapi_mock::install(System::default());
+    let gpu = &gpus()[0];
+    let expected = mock.with(|system| system.gpus[0].memory);
+
+    let info = gpu.memory_info().unwrap();
+    assert_eq!(info.dedicated.0, expected.dedicated);
+    // The extended variant reports 32-bit byte counts
+    mock.with(|system| system.gpus[0].memory.dedicated = 1024 * 1024);
+    let info = gpu.memory_info_ex().unwrap();
+    assert_eq!(info.dedicated.0, 1024 * 1024);
+}
+
+#[test]
+fn clocks() {
+    let mock = nvapi_mock::install(System::default());
+    let gpu = &gpus()[0];
+    let expected = mock.with(|system| system.gpus[0].clocks.boost[&ClockDomain::Graphics]);
+
+    let boost = gpu.clock_frequencies(ClockFrequencyType::Boost).unwrap();
+    assert_eq!(boost[&ClockDomain::Graphics], Kilohertz(expected));
+}
+
+#[test]
+fn set_pstates() {
+    let mock = nvapi_mock::install(System::default());
+    let gpu = &gpus()[0];
+    let pstate = mock.with(|system| system.gpus[0].pstates[0].id);
+
+    gpu.set_pstates(Some((pstate, ClockDomain::Graphics, KilohertzDelta(100000))).into_iter()).unwrap();
+    let delta = mock.with(|system| system.gpus[0].pstates[0].clocks.iter()
+        .find(|c| c.domain == ClockDomain::Graphics).unwrap().delta.value
+    );
+    assert_eq!(delta, 100000);
+
+    let err = gpu.set_pstates(Some((pstate, ClockDomain::Graphics, KilohertzDelta(i32::MAX))).into_iter()).unwrap_err();
+    assert_eq!(err, Status::InvalidArgument);
+}
+
+#[test]
+fn display_ids() {
+    let mock = nvapi_mock::install(System::default());
+    let gpu = &gpus()[0];
+
+    let all = gpu.display_ids_all().unwrap();
+    assert_eq!(all.len(), 2);
+    let connected = gpu.display_ids_connected(ConnectedIdsFlags::empty()).unwrap();
+    assert_eq!(connected.len(), 1);
+
+    mock.with(|system| system.gpus[0].displays.clear());
+    assert!(gpu.display_ids_all().unwrap().is_empty());
+    assert!(gpu.display_ids_connected(ConnectedIdsFlags::empty()).unwrap().is_empty());
+}
+
+#[test]
+fn i2c() {
+    let mock = nvapi_mock::install(System::default());
+    mock.with(|system| system.gpus[0].i2c.push(I2cDevice {
+        port: None,
+        address: 0x50,
+        registers: (0..0x80).collect(),
+    }));
+    let gpu = &gpus()[0];
+
+    let mut data = [0u8; 4];
+    gpu.i2c_read(1, None, true, 0x50, &[0x10], &mut data, I2cSpeed::Default).unwrap();
+    assert_eq!(data, [0x10, 0x11, 0x12, 0x13]);
+
+    gpu.i2c_write(1, None, true, 0x50, &[0x20], &[0xaa, 0xbb], I2cSpeed::Default).unwrap();
+    assert_eq!(mock.with(|system| system.gpus[0].i2c[0].registers[0x20..0x22].to_vec()), [0xaa, 0xbb]);
+
+    assert!(gpu.i2c_read(1, None, true, 0x37, &[], &mut data, I2cSpeed::Default).is_err());
+}
+
+#[test]
+fn pcie() {
+    let mock = nvapi_mock::install(System::default());
+    let gpu = &gpus()[0];
+
+    let info = gpu.pcie_info().unwrap();
+    assert_eq!((info.bus_type, info.bus_id, info.irq), (BusType::PciExpress, 1, 16));
+    assert_eq!(info.current(), Some(PcieLink { generation: 3, lanes: 16 }));
+    assert_eq!(info.max, info.current());
+    assert_eq!(info.is_degraded(), Some(false));
+    assert_eq!(gpu.pcie_links().unwrap().len(), 3);
+
+    // a bad riser
+    mock.with(|system| system.gpus[0].pcie.current = (2500, 4));
+    let info = gpu.pcie_info().unwrap();
+    assert_eq!(info.current().unwrap().to_string(), "Gen1 x4");
+    assert_eq!(info.max.unwrap().to_string(), "Gen3 x16");
+    assert_eq!(info.is_degraded(), Some(true));
+
+    // the documented fields don't depend on the private interface
+    mock.fail(Api::NvAPI_GPU_GetPCIEInfo, Status::NoImplementation);
+    let info = gpu.pcie_info().unwrap();
+    assert_eq!((info.bus_type, info.bus_id, info.irq, info.lanes), (BusType::PciExpress, 1, 16, 4));
+    assert_eq!((info.generation, info.max, info.is_degraded()), (None, None, None));
+    mock.clear_failures();
+
+    mock.with(|system| system.gpus[0].bus_type = BusType::Pci);
+    assert_eq!(gpu.bus_type().unwrap(), BusType::Pci);
+    assert_eq!(gpu.pcie_info().unwrap_err(), Status::NotSupported);
+}
+
+#[test]
+fn ecc() {
+    let mock = nvapi_mock::install(System::default());
+    let gpu = &gpus()[0];
+
+    assert!(!gpu.ecc_status().unwrap().supported);
+    assert_eq!(gpu.ecc_errors().unwrap_err(), Status::NotSupported);
+
+    mock.with(|system| system.gpus[0].ecc = Some(Ecc {
+        current: (3, 0),
+        aggregate: (41, 2),
+        ..Default::default()
+    }));
+    let status = gpu.ecc_status().unwrap();
+    assert!(status.supported && status.enabled);
+    assert_eq!(status.configuration, EccConfiguration::Deferred);
+    let errors = gpu.ecc_errors().unwrap();
+    assert_eq!(errors.current, EccErrorCounts { single_bit: 3, double_bit: 0 });
+    assert_eq!(errors.aggregate, EccErrorCounts { single_bit: 41, double_bit: 2 });
+
+    gpu.reset_ecc_errors(true, false).unwrap();
+    let errors = gpu.ecc_errors().unwrap();
+    assert_eq!((errors.current, errors.aggregate.double_bit), (Default::default(), 2));
+
+    // deferred until the next reboot
+    gpu.set_ecc_configuration(false, true).unwrap();
+    assert!(!gpu.ecc_configuration().unwrap().enabled);
+    assert!(gpu.ecc_configuration().unwrap().enabled_by_default);
+    assert!(gpu.ecc_status().unwrap().enabled);
+
+    mock.with(|system| system.gpus[0].ecc.as_mut().unwrap().configuration = EccConfiguration::Immediate);
+    gpu.set_ecc_configuration(false, true).unwrap();
+    assert!(!gpu.ecc_status().unwrap().enabled);
+}
+
+#[test]
+fn struct_version_fallback() {
+    use nvapi::sys::driverapi::{NV_DISPLAY_DRIVER_MEMORY_INFO_VER_1, NV_DISPLAY_DRIVER_MEMORY_INFO_VER_2, NV_DISPLAY_DRIVER_MEMORY_INFO_VER_3};
+
+    let mock = nvapi_mock::install(System::default());
+    let gpu = &gpus()[0];
+    nvapi::reset_negotiated_versions();
+    let expected = mock.with(|system| system.gpus[0].memory);
+
+    gpu.memory_info().unwrap();
+    assert_eq!(nvapi::negotiated_version(Api::NvAPI_GPU_GetMemoryInfo), Some(NV_DISPLAY_DRIVER_MEMORY_INFO_VER_3));
+
+    // An older driver that only knows the first two versions
+    mock.reject_version(Api::NvAPI_GPU_GetMemoryInfo, NV_DISPLAY_DRIVER_MEMORY_INFO_VER_3);
+    let info = gpu.memory_info().unwrap();
+    assert_eq!(info.dedicated_available_current.0, expected.dedicated_available_current);
+    assert_eq!(info.dedicated_evictions, 0);
+    assert_eq!(nvapi::negotiated_version(Api::NvAPI_GPU_GetMemoryInfo), Some(NV_DISPLAY_DRIVER_MEMORY_INFO_VER_2));
+
+    // The cached version is tried first
+    mock.clear_calls();
+    gpu.memory_info().unwrap();
+    assert_eq!(mock.calls(), [Api::NvAPI_GPU_GetMemoryInfo]);
+
+    mock.reject_version(Api::NvAPI_GPU_GetMemoryInfo, NV_DISPLAY_DRIVER_MEMORY_INFO_VER_2);
+    let info = gpu.memory_info().unwrap();
+    assert_eq!(info.dedicated.0, expected.dedicated);
+    assert_eq!(nvapi::negotiated_version(Api::NvAPI_GPU_GetMemoryInfo), Some(NV_DISPLAY_DRIVER_MEMORY_INFO_VER_1));
+
+    mock.reject_version(Api::NvAPI_GPU_GetMemoryInfo, NV_DISPLAY_DRIVER_MEMORY_INFO_VER_1);
+    let err = gpu.memory_info().unwrap_err();
+    assert_eq!(err, Status::IncompatibleStructVersion);
+    assert_eq!(err.api(), Some(Api::NvAPI_GPU_GetMemoryInfo));
+    assert!(err.version().is_some());
+    assert_eq!(nvapi::negotiated_version(Api::NvAPI_GPU_GetMemoryInfo), None);
+
+    // Other failures are not retried
+    mock.clear_failures();
+    mock.reject_version(Api::NvAPI_I2CRead, nvapi::sys::i2c::NV_I2C_INFO_VER3);
+    mock.with(|system| system.gpus[0].i2c.push(I2cDevice {
+        port: None,
+        address: 0x50,
+        registers: (0..0x80).collect(),
+    }));
+    let mut data = [0u8; 2];
+    gpu.i2c_read(1, None, true, 0x50, &[0x10], &mut data, I2cSpeed::Default).unwrap();
+    assert_eq!(data, [0x10, 0x11]);
+    assert_eq!(nvapi::negotiated_version(Api::NvAPI_I2CRead), Some(nvapi::sys::i2c::NV_I2C_INFO_VER2));
+    mock.clear_calls();
+    assert!(gpu.i2c_read(1, None, true, 0x37, &[], &mut data, I2cSpeed::Default).is_err());
+    assert_eq!(mock.calls(), [Api::NvAPI_I2CRead]);
+
+    // Versions that can't carry the port aren't tried
+    mock.clear_calls();
+    let err = gpu.i2c_read(1, Some(1), true, 0x50, &[0x10], &mut data, I2cSpeed::Default).unwrap_err();
+    assert_eq!(err, Status::IncompatibleStructVersion);
+    assert_eq!(mock.calls(), [Api::NvAPI_I2CRead]);
+    nvapi::reset_negotiated_versions();
+}
+
+#[test]
+fn handle_invalidation() {
+    let second = VirtualGpu {
+        full_name: "GeForce GTX 1070".into(),
+        bus_id: 2,
+        .. Default::default()
+    };
+    let mock = nvapi_mock::install(System::new(vec![VirtualGpu::default(), second]));
+    let gpus = gpus();
+    let handle = *gpus[1].handle();
+    let identity = gpus[1].identity().unwrap();
+    assert_eq!(identity.bus_id, Some(2));
+    assert_ne!(identity, gpus[0].identity().unwrap());
+
+    // A topology change reorders the cards and hands out new handles
+    mock.invalidate_handles();
+    mock.with(|system| system.gpus.reverse());
+    assert_eq!(gpus[1].full_name().unwrap(), "GeForce GTX 1070");
+    assert_ne!(gpus[1].current_handle().as_ptr(), handle.as_ptr());
+    assert_eq!(gpus[1].handle().as_ptr(), handle.as_ptr());
+    assert_eq!(gpus[1].identity().unwrap(), identity);
+    assert_eq!(gpus[0].full_name().unwrap(), "GeForce GTX 1080");
+
+    // The card is gone
+    mock.invalidate_handles();
+    mock.with(|system| system.gpus.remove(0));
+    assert_eq!(gpus[1].full_name().unwrap_err(), Status::HandleInvalidated);
+    assert_eq!(gpus[1].rebind().unwrap_err(), Status::NvidiaDeviceNotFound);
+    assert!(gpus[0].full_name().is_ok());
+}
+
+#[test]
+fn deferred_identity() {
+    let mock = nvapi_mock::install(System::default());
+    mock.fail(Api::NvAPI_GPU_GetPCIIdentifiers, Status::Error);
+    let gpu = &gpus()[0];
+    assert_eq!(gpu.identity().unwrap_err(), Status::Error);
+
+    // Identified on first use, which allows it to be rebound later
+    mock.clear_failures();
+    let identity = gpu.identity().unwrap();
+    mock.invalidate_handles();
+    assert!(gpu.full_name().is_ok());
+    assert_eq!(gpu.identity().unwrap(), identity);
+}
+
+#[test]
+fn failure_injection() {
+    let mock = nvapi_mock::install(System::default());
+    let gpu = &gpus()[0];
+
+    mock.fail(Api::NvAPI_GPU_GetFullName, Status::HandleInvalidated);
+    assert_eq!(gpu.full_name().unwrap_err(), Status::HandleInvalidated);
+    mock.clear_failures();
+    assert!(gpu.full_name().is_ok());
+    assert!(mock.calls().contains(&Api::NvAPI_GPU_GetFullName));
+}
+
+#[test]
+fn error_context() {
+    let mock = nvapi_mock::install(System::default());
+    let gpu = &gpus()[0];
+
+    mock.fail(Api::NvAPI_GPU_GetPstates20, Status::NotSupported);
+    let err = gpu.pstates().unwrap_err();
+    assert_eq!(err, Status::NotSupported);
+    assert_eq!(err.api(), Some(Api::NvAPI_GPU_GetPstates20));
+    assert_eq!(err.version(), Some(nvapi::sys::gpu::pstate::NV_GPU_PERF_PSTATES20_INFO_VER));
+
+    mock.fail_raw(Api::NvAPI_GPU_GetFullName, -1000);
+    let err = gpu.full_name().unwrap_err();
+    assert_eq!(err.raw(), -1000);
+    assert_eq!(err.status(), Status::Error);
+    assert_eq!(err.api(), Some(Api::NvAPI_GPU_GetFullName));
+    assert_eq!(err.version(), None);
+    assert_eq!(Status::from(err), Status::Error);
+
+    // A failure that was never turned into an error isn't attributed once another call succeeds
+    mock.fail(Api::NvAPI_GPU_GetFullName, Status::NotSupported);
+    let mut name = [0; 64];
+    assert_eq!(unsafe { nvapi::sys::gpu::NvAPI_GPU_GetFullName(*gpu.handle(), &mut name) }, Status::NotSupported.raw());
+    gpu.short_name().unwrap();
+    let err = nvapi::sys::status_result(Status::NotSupported.raw()).unwrap_err();
+    assert_eq!(err.api(), None);
+}
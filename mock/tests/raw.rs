@@ -0,0 +1,52 @@
+extern crate nvapi;
+extern crate nvapi_mock;
+
+use nvapi::{PhysicalGpu, Status};
+use nvapi::sys::{raw, Api};
+use nvapi::sys::driverapi::{NV_DISPLAY_DRIVER_MEMORY_INFO_V1, NV_DISPLAY_DRIVER_MEMORY_INFO_VER_1};
+use nvapi_mock::System;
+
+#[test]
+fn raw_calls() {
+    let mock = nvapi_mock::install(System::default());
+    nvapi::initialize().unwrap();
+    let gpu = &PhysicalGpu::enumerate().unwrap()[0];
+    let expected = mock.with(|system| system.gpus[0].memory);
+
+    let mut data = raw::versioned_buffer(1, std::mem::size_of::<NV_DISPLAY_DRIVER_MEMORY_INFO_V1>());
+    assert_eq!(raw::buffer_version(&data), Some(NV_DISPLAY_DRIVER_MEMORY_INFO_VER_1));
+    unsafe { gpu.call_raw(Api::NvAPI_GPU_GetMemoryInfo, &mut data, true) }.unwrap();
+    assert_eq!(u32::from_ne_bytes([data[4], data[5], data[6], data[7]]), expected.dedicated);
+
+    let mut data = [0u8; 64];
+    unsafe { nvapi::call_raw(Api::NvAPI_GetInterfaceVersionString, &mut data, false) }.unwrap();
+    let len = data.iter().position(|&c| c == 0).unwrap();
+    assert_eq!(std::str::from_utf8(&data[..len]).unwrap(), nvapi::interface_version().unwrap());
+
+    // Unsupported versions fail like any other call, with the version attributed
+    let mut data = raw::versioned_buffer(9, 16);
+    let err = unsafe { gpu.call_raw(Api::NvAPI_GPU_GetMemoryInfo, &mut data, true) }.unwrap_err();
+    assert_eq!(err, Status::IncompatibleStructVersion);
+    assert_eq!(err.api(), Some(Api::NvAPI_GPU_GetMemoryInfo));
+    assert_eq!(err.version(), raw::buffer_version(&data));
+
+    // Buffers smaller than their version claims never reach the driver
+    let mut data = raw::versioned_buffer(1, 64);
+    let mut short = data[..32].to_vec();
+    mock.clear_calls();
+    assert_eq!(unsafe { gpu.call_raw(Api::NvAPI_GPU_GetMemoryInfo, &mut short, true) }.unwrap_err(), Status::InvalidArgument);
+    assert!(mock.calls().is_empty());
+    assert!(unsafe { gpu.call_raw(Api::NvAPI_GPU_GetMemoryInfo, &mut data, true) }.is_err());
+
+    // Versioned buffers must start with a version
+    let mut data = [0u8; 64];
+    assert_eq!(unsafe { gpu.call_raw(Api::NvAPI_GPU_GetMemoryInfo, &mut data, true) }.unwrap_err(), Status::InvalidArgument);
+
+    // Large structures carry their full size, as `MAKE_NVAPI_VERSION` does
+    let data = raw::versioned_buffer(1, 0x12000);
+    assert_eq!(raw::buffer_version(&data), Some(0x12000 | 1 << 16));
+
+    // Functions the driver doesn't export
+    let mut data = raw::versioned_buffer(1, 16);
+    assert_eq!(unsafe { gpu.call_raw(Api::NvAPI_GPU_ClientFanCoolersGetInfo, &mut data, true) }.unwrap_err(), Status::NoImplementation);
+}
@@ -0,0 +1,47 @@
+extern crate nvapi;
+extern crate nvapi_hi;
+extern crate nvapi_mock;
+
+use nvapi::{Nvapi, PhysicalGpu, Status};
+use nvapi_mock::System;
+
+#[test]
+fn session() {
+    let mock = nvapi_mock::install(System::default());
+    assert!(Nvapi::current().is_none());
+
+    let a = Nvapi::new().unwrap();
+    let b = Nvapi::new().unwrap();
+    assert_eq!(mock.refcount(), 1);
+    assert_eq!(a.references(), 2);
+
+    let gpu = nvapi_hi::Gpu::new(a.gpus().unwrap().remove(0));
+    let plain = PhysicalGpu::enumerate().unwrap().remove(0);
+    assert!(plain.session().is_some());
+    drop(a);
+    drop(b);
+    drop(plain);
+
+    // The remaining GPU keeps the library loaded
+    assert_eq!(mock.refcount(), 1);
+    assert!(gpu.info().is_ok());
+    drop(gpu);
+    assert_eq!(mock.refcount(), 0);
+    assert!(Nvapi::current().is_none());
+
+    // Independent of the free functions
+    nvapi::initialize().unwrap();
+    let session = Nvapi::new().unwrap();
+    nvapi::unload().unwrap();
+    assert!(session.gpus().unwrap()[0].full_name().is_ok());
+    drop(session);
+    assert_eq!(PhysicalGpu::enumerate().unwrap_err(), Status::ApiNotInitialized);
+}
+
+#[test]
+fn session_failure() {
+    let mock = nvapi_mock::install(System::default());
+    mock.fail(nvapi::sys::Api::NvAPI_Initialize, Status::LibraryNotFound);
+    assert_eq!(Nvapi::new().unwrap_err(), Status::LibraryNotFound);
+    assert!(Nvapi::current().is_none());
+}
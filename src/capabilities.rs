@@ -0,0 +1,157 @@
+use std::collections::BTreeMap;
+use log::trace;
+use serde::{Serialize, Deserialize};
+use crate::sys::{self, Api, Status};
+use crate::gpu::{PhysicalGpu, ClockFrequencyType, ConnectedIdsFlags};
+use crate::logical::LogicalGpu;
+
+/// How well the installed driver supports a function.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Support {
+    /// `nvapi_QueryInterface` doesn't know the function.
+    Missing,
+    /// The function is exported, but has no read-only query to confirm it works.
+    Exported,
+    /// The query failed with `NVAPI_NO_IMPLEMENTATION`.
+    NoImplementation,
+    /// The query failed with `NVAPI_NOT_SUPPORTED`.
+    NotSupported,
+    /// The query succeeded.
+    Supported,
+    /// The query failed for some other reason.
+    Failed(Status),
+}
+
+impl Support {
+    fn from_result<T>(result: sys::Result<T>) -> Self {
+        match result {
+            Ok(..) => Support::Supported,
+            Err(e) => match e.status() {
+                Status::NoImplementation => Support::NoImplementation,
+                Status::NotSupported => Support::NotSupported,
+                status => Support::Failed(status),
+            },
+        }
+    }
+
+    /// Whether the function can be expected to work.
+    ///
+    /// Functions that were only resolved are given the benefit of the doubt.
+    pub fn is_available(&self) -> bool {
+        matches!(*self, Support::Exported | Support::Supported)
+    }
+}
+
+/// Which NVAPI functions the driver implements.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Capabilities {
+    /// Every known `Api`, including those that are `Missing`.
+    pub apis: BTreeMap<Api, Support>,
+}
+
+impl Capabilities {
+    /// Resolves every known function without calling any of them.
+    fn resolve() -> Self {
+        Capabilities {
+            apis: Api::all().map(|api| (api, match sys::nvapi::nvapi_QueryInterface(api.id()) {
+                Ok(..) => Support::Exported,
+                Err(..) => Support::Missing,
+            })).collect(),
+        }
+    }
+
+    fn probe<T, F: FnOnce() -> sys::Result<T>>(&mut self, api: Api, f: F) {
+        if let Some(support) = self.apis.get_mut(&api) {
+            if *support != Support::Missing {
+                *support = Support::from_result(f());
+            }
+        }
+    }
+
+    pub fn support(&self, api: Api) -> Support {
+        self.apis.get(&api).cloned().unwrap_or(Support::Missing)
+    }
+
+    pub fn is_available(&self, api: Api) -> bool {
+        self.support(api).is_available()
+    }
+
+    /// The functions with the given level of support.
+    pub fn with_support(&self, support: Support) -> impl Iterator<Item=Api> + '_ {
+        self.apis.iter().filter(move |&(_, s)| *s == support).map(|(&api, _)| api)
+    }
+}
+
+/// Reports which functions the driver exports, probing those that don't need a GPU.
+pub fn capabilities() -> Capabilities {
+    trace!("capabilities()");
+    let mut caps = Capabilities::resolve();
+    caps.probe(Api::NvAPI_GetInterfaceVersionString, crate::interface_version);
+    caps.probe(Api::NvAPI_SYS_GetDriverAndBranchVersion, crate::driver_version);
+    caps.probe(Api::NvAPI_EnumPhysicalGPUs, PhysicalGpu::enumerate);
+    caps.probe(Api::NvAPI_EnumLogicalGPUs, LogicalGpu::enumerate);
+    caps
+}
+
+/// Adds the results of read-only queries against `gpu` to the global capabilities.
+pub(crate) fn gpu_capabilities(gpu: &PhysicalGpu) -> Capabilities {
+    let mut caps = capabilities();
+    caps.probe(Api::NvAPI_GPU_GetFullName, || gpu.full_name());
+    caps.probe(Api::NvAPI_GPU_GetShortName, || gpu.short_name());
+    caps.probe(Api::NvAPI_GPU_GetVbiosVersionString, || gpu.vbios_version_string());
+    caps.probe(Api::NvAPI_GetDriverModel, || gpu.driver_model());
+    caps.probe(Api::NvAPI_GetGPUIDFromPhysicalGPU, || gpu.gpu_id());
+    caps.probe(Api::NvAPI_GPU_GetPCIIdentifiers, || gpu.pci_identifiers());
+    caps.probe(Api::NvAPI_GPU_GetBoardInfo, || gpu.board_number());
+    caps.probe(Api::NvAPI_GPU_GetBusId, || gpu.bus_id());
+    caps.probe(Api::NvAPI_GPU_GetBusSlotId, || gpu.bus_slot_id());
+    caps.probe(Api::NvAPI_GPU_GetBusType, || gpu.bus_type());
+    caps.probe(Api::NvAPI_GPU_GetIRQ, || gpu.irq());
+    caps.probe(Api::NvAPI_GPU_GetCurrentPCIEDownstreamWidth, || gpu.pcie_lanes());
+    caps.probe(Api::NvAPI_GPU_GetPCIEInfo, || gpu.pcie_links());
+    caps.probe(Api::NvAPI_GetLogicalGPUFromPhysicalGPU, || gpu.logical_gpu());
+    caps.probe(Api::NvAPI_GPU_GetSystemType, || gpu.system_type());
+    caps.probe(Api::NvAPI_GPU_GetGpuCoreCount, || gpu.core_count());
+    caps.probe(Api::NvAPI_GPU_GetShaderPipeCount, || gpu.shader_pipe_count());
+    caps.probe(Api::NvAPI_GPU_GetShaderSubPipeCount, || gpu.shader_sub_pipe_count());
+    caps.probe(Api::NvAPI_GPU_GetRamType, || gpu.ram_type());
+    caps.probe(Api::NvAPI_GPU_GetRamMaker, || gpu.ram_maker());
+    caps.probe(Api::NvAPI_GPU_GetRamBusWidth, || gpu.ram_bus_width());
+    caps.probe(Api::NvAPI_GPU_GetRamBankCount, || gpu.ram_bank_count());
+    caps.probe(Api::NvAPI_GPU_GetPartitionCount, || gpu.ram_partition_count());
+    caps.probe(Api::NvAPI_GPU_GetFoundry, || gpu.foundry());
+    caps.probe(Api::NvAPI_GPU_GetTachReading, || gpu.tachometer());
+    caps.probe(Api::NvAPI_GPU_GetMemoryInfo, || gpu.memory_info());
+    caps.probe(Api::NvAPI_GPU_GetMemoryInfoEx, || gpu.memory_info_ex());
+    caps.probe(Api::NvAPI_GPU_GetAllClockFrequencies, || gpu.clock_frequencies(ClockFrequencyType::Current));
+    caps.probe(Api::NvAPI_GPU_GetCurrentPstate, || gpu.current_pstate());
+    caps.probe(Api::NvAPI_GPU_GetPstates20, || gpu.pstates());
+    caps.probe(Api::NvAPI_GPU_GetDynamicPstatesInfoEx, || gpu.dynamic_pstates_info());
+    caps.probe(Api::NvAPI_GPU_GetUsages, || gpu.usages());
+    caps.probe(Api::NvAPI_GPU_ClockClientClkVfPointsGetInfo, || gpu.vfp_mask());
+    caps.probe(Api::NvAPI_GPU_ClockClientClkDomainsGetInfo, || gpu.vfp_ranges());
+    caps.probe(Api::NvAPI_GPU_PerfClientLimitsGetStatus, || gpu.vfp_locks());
+    caps.probe(Api::NvAPI_GPU_ClientVoltRailsGetStatus, || gpu.core_voltage());
+    caps.probe(Api::NvAPI_GPU_ClientVoltRailsGetControl, || gpu.core_voltage_boost());
+    caps.probe(Api::NvAPI_GPU_ClientPowerTopologyGetStatus, || gpu.power_usage());
+    caps.probe(Api::NvAPI_GPU_ClientPowerPoliciesGetInfo, || gpu.power_limit_info());
+    caps.probe(Api::NvAPI_GPU_ClientPowerPoliciesGetStatus, || gpu.power_limit());
+    caps.probe(Api::NvAPI_GPU_GetThermalSettings, || gpu.thermal_settings(None));
+    caps.probe(Api::NvAPI_GPU_ClientThermalPoliciesGetInfo, || gpu.thermal_limit_info());
+    caps.probe(Api::NvAPI_GPU_ClientThermalPoliciesGetStatus, || gpu.thermal_limit());
+    caps.probe(Api::NvAPI_GPU_GetCoolerSettings, || gpu.cooler_settings(None));
+    caps.probe(Api::NvAPI_GPU_PerfPoliciesGetInfo, || gpu.perf_info());
+    caps.probe(Api::NvAPI_GPU_PerfPoliciesGetStatus, || gpu.perf_status());
+    caps.probe(Api::NvAPI_GPU_GetVoltageDomainsStatus, || gpu.voltage_domains_status());
+    caps.probe(Api::NvAPI_GPU_GetVoltageStep, || gpu.voltage_step());
+    caps.probe(Api::NvAPI_GPU_GetVoltages, || gpu.voltage_table());
+    caps.probe(Api::NvAPI_GPU_GetPerfDecreaseInfo, || gpu.performance_decrease());
+    caps.probe(Api::NvAPI_GPU_GetECCStatusInfo, || gpu.ecc_status());
+    caps.probe(Api::NvAPI_GPU_GetECCErrorInfo, || gpu.ecc_errors());
+    caps.probe(Api::NvAPI_GPU_GetECCConfigurationInfo, || gpu.ecc_configuration());
+    caps.probe(Api::NvAPI_GPU_GetAllDisplayIds, || gpu.display_ids_all());
+    caps.probe(Api::NvAPI_GPU_GetConnectedDisplayIds, || gpu.display_ids_connected(ConnectedIdsFlags::empty()));
+    caps
+}
@@ -5,7 +5,7 @@ use crate::sys::gpu::{clock, power};
 use crate::sys;
 use serde::{Serialize, Deserialize};
 use log::trace;
-use crate::types::{Kilohertz, Kilohertz2, KilohertzDelta, Kilohertz2Delta, Percentage, Percentage1000, Microvolts, CelsiusShifted, Range, RawConversion};
+use crate::types::{Kilohertz, Kilohertz2, KilohertzDelta, Kilohertz2Delta, Percentage, Percentage1000, Microvolts, CelsiusShifted, Range, RawConversion, checked_entries};
 
 pub use sys::gpu::clock::PublicClockId as ClockDomain;
 pub use sys::gpu::clock::private::ClockLockMode;
@@ -34,8 +34,8 @@ impl RawConversion for clock::private::NV_USAGES_INFO {
         trace!("convert_raw({:#?})", self);
         self.usages.iter().enumerate()
             .filter(|&(_, ref usage)| usage.bIsPresent.get())
-            .map(|(i, usage)| crate::pstate::UtilizationDomain::from_raw(i as _)
-                .and_then(|i| Percentage::from_raw(usage.percentage).map(|p| (i, p)))
+            .map(|(i, usage)| Percentage::from_raw(usage.percentage)
+                .map(|p| (crate::pstate::UtilizationDomain::from_raw_lossless(i as _), p))
             ).collect()
     }
 }
@@ -146,7 +146,7 @@ impl RawConversion for clock::private::NV_GPU_CLOCK_CLIENT_CLK_VF_POINTS_INFO_CL
             clock::private::NV_GPU_CLOCK_CLIENT_CLK_VF_POINTS_INFO_CLOCK {
                 a: 0, b: 0, c: 0, d: 0, memDelta: 0, gpuDelta: 0,
             } => Ok(VfpMaskType::Unknown),
-            _ => Err(sys::ArgumentRangeError),
+            _ => Err(sys::ArgumentRangeError::new()),
         }
     }
 }
@@ -187,7 +187,7 @@ impl RawConversion for clock::private::NV_GPU_CLOCK_CLIENT_CLK_VF_POINTS_CONTROL
             clock::private::NV_GPU_CLOCK_CLIENT_CLK_VF_POINTS_CONTROL_GPU_DELTA {
                 a: 0, b: 0, c: 0, d: 0, e: 0, freqDeltaKHz, g: 0, h: 0, i: 0,
             } => Ok(freqDeltaKHz),
-            _ => Err(sys::ArgumentRangeError),
+            _ => Err(sys::ArgumentRangeError::new()),
         }
     }
 }
@@ -209,7 +209,7 @@ impl RawConversion for clock::private::NV_GPU_CLOCK_CLIENT_CLK_VF_POINTS_CONTROL
             mem_delta: self.memFilled.iter().enumerate().filter_map(|(i, &filled)| match filled {
                 1 => Some(Ok(i)),
                 0 => None,
-                _ => Some(Err(sys::ArgumentRangeError)),
+                _ => Some(Err(sys::ArgumentRangeError::new())),
             }).map(|i| i.map(|i| (self.gpuDeltas.len() + i, self.memDeltas[i].into())))
             .collect::<Result<_, _>>()?,
         })
@@ -237,14 +237,14 @@ impl RawConversion for clock::private::NV_GPU_CLOCK_CLIENT_CLK_DOMAINS_INFO_ENTR
                 a: 0, clockType, c: 0, d: 0, e: 0, f: 0, g: 0, h: 0, i: 0,
                 j: 0, rangeMax, rangeMin, tempMax, n: 0, o: 0, p: 0, q: 0, r: 0,
             } => Ok(ClockRange {
-                domain: ClockDomain::from_raw(clockType)?,
+                domain: ClockDomain::from_raw_lossless(clockType),
                 range: Range {
                     max: Kilohertz2Delta(rangeMax),
                     min: Kilohertz2Delta(rangeMin),
                 },
                 temp_max: CelsiusShifted(tempMax),
             }),
-            _ => Err(sys::ArgumentRangeError),
+            _ => Err(sys::ArgumentRangeError::new()),
         }
     }
 }
@@ -255,7 +255,7 @@ impl RawConversion for clock::private::NV_GPU_CLOCK_CLIENT_CLK_DOMAINS_INFO {
 
     fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
         trace!("convert_raw({:#?})", self);
-        self.entries[..(self.numClocks - 1) as usize].iter()
+        checked_entries(&self.entries, "numClocks", self.numClocks as i64 - 1)?.iter()
             .map(RawConversion::convert_raw)
             .collect::<Result<_, _>>()
     }
@@ -295,7 +295,7 @@ impl RawConversion for power::private::NV_GPU_CLOCK_CLIENT_CLK_VF_POINTS_STATUS_
                 frequency: freq_kHz,
                 voltage: Microvolts(voltage_uV),
             }),
-            _ => Err(sys::ArgumentRangeError),
+            _ => Err(sys::ArgumentRangeError::new()),
         }
     }
 }
@@ -342,7 +342,7 @@ impl RawConversion for power::private::NV_GPU_CLIENT_VOLT_RAILS_STATUS_V1 {
                 version: _, flags: 0, ref zero,
                 value_uV, ref unknown,
             } if all_zero(zero) && all_zero(unknown) => Ok(Microvolts(value_uV)),
-            _ => Err(sys::ArgumentRangeError),
+            _ => Err(sys::ArgumentRangeError::new()),
         }
     }
 }
@@ -358,7 +358,7 @@ impl RawConversion for power::private::NV_GPU_CLIENT_VOLT_RAILS_CONTROL_V1 {
             power::private::NV_GPU_CLIENT_VOLT_RAILS_CONTROL {
                 version: _, percent, ref unknown,
             } if all_zero(unknown) => Percentage::from_raw(percent),
-            _ => Err(sys::ArgumentRangeError),
+            _ => Err(sys::ArgumentRangeError::new()),
         }
     }
 }
@@ -390,14 +390,14 @@ impl RawConversion for power::private::NV_GPU_POWER_INFO_ENTRY {
                 pstate, b: 0, c: 0, min_power, e: 0, f: 0,
                 def_power, h: 0, i: 0, max_power, k: 0,
             } => Ok(PowerInfoEntry {
-                pstate: crate::pstate::PState::from_raw(pstate as _)?,
+                pstate: crate::pstate::PState::from_raw_lossless(pstate as _),
                 range: Range {
                     min: Percentage1000(min_power),
                     max: Percentage1000(max_power),
                 },
                 default_limit: Percentage1000(def_power),
             }),
-            _ => Err(sys::ArgumentRangeError),
+            _ => Err(sys::ArgumentRangeError::new()),
         }
     }
 }
@@ -411,7 +411,7 @@ impl RawConversion for power::private::NV_GPU_POWER_INFO {
         trace!("convert_raw({:#?})", self);
         Ok(PowerInfo {
             valid: self.valid != 0,
-            entries: self.entries[..self.count as usize].iter().map(RawConversion::convert_raw).collect::<Result<_, _>>()?,
+            entries: checked_entries(&self.entries, "count", self.count as _)?.iter().map(RawConversion::convert_raw).collect::<Result<_, _>>()?,
         })
     }
 }
@@ -427,7 +427,7 @@ impl RawConversion for power::private::NV_GPU_POWER_TOPO_ENTRY {
             power::private::NV_GPU_POWER_TOPO_ENTRY {
                 a: unknown, b: 0, power, d: 0
             } => Ok(Percentage1000(power)),
-            _ => Err(sys::ArgumentRangeError),
+            _ => Err(sys::ArgumentRangeError::new()),
         }
     }
 }
@@ -439,7 +439,7 @@ impl RawConversion for power::private::NV_GPU_POWER_TOPO {
     #[allow(non_snake_case)]
     fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
         trace!("convert_raw({:#?})", self);
-        self.entries[..self.count as usize].iter().map(RawConversion::convert_raw).collect()
+        checked_entries(&self.entries, "count", self.count as _)?.iter().map(RawConversion::convert_raw).collect()
     }
 }
 
@@ -454,7 +454,7 @@ impl RawConversion for power::private::NV_GPU_POWER_STATUS_ENTRY {
             power::private::NV_GPU_POWER_STATUS_ENTRY {
                 a: 0, b: 0, power, d: 0,
             } => Ok(Percentage1000(power)),
-            _ => Err(sys::ArgumentRangeError),
+            _ => Err(sys::ArgumentRangeError::new()),
         }
     }
 }
@@ -466,7 +466,7 @@ impl RawConversion for power::private::NV_GPU_POWER_STATUS {
     #[allow(non_snake_case)]
     fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
         trace!("convert_raw({:#?})", self);
-        self.entries[..self.count as usize].iter().map(RawConversion::convert_raw).collect()
+        checked_entries(&self.entries, "count", self.count as _)?.iter().map(RawConversion::convert_raw).collect()
     }
 }
 
@@ -488,10 +488,10 @@ impl RawConversion for clock::private::NV_GPU_PERF_CLIENT_LIMITS_ENTRY {
             clock::private::NV_GPU_PERF_CLIENT_LIMITS_ENTRY {
                 id: _id, b: 0, mode, d: 0, voltage_uV, f: 0,
             } => Ok(ClockLockEntry {
-                mode: ClockLockMode::from_raw(mode)?,
+                mode: ClockLockMode::from_raw_lossless(mode),
                 voltage: Microvolts(voltage_uV),
             }),
-            _ => Err(sys::ArgumentRangeError),
+            _ => Err(sys::ArgumentRangeError::new()),
         }
     }
 }
@@ -503,9 +503,9 @@ impl RawConversion for clock::private::NV_GPU_PERF_CLIENT_LIMITS {
     fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
         trace!("convert_raw({:#?})", self);
         if self.flags != 0 {
-            Err(sys::ArgumentRangeError)
+            Err(sys::ArgumentRangeError::new())
         } else {
-            self.entries[..self.count as usize].iter().map(|v| v.convert_raw().map(|e| (v.id as usize, e))).collect()
+            checked_entries(&self.entries, "count", self.count as _)?.iter().map(|v| v.convert_raw().map(|e| (v.id as usize, e))).collect()
         }
     }
 }
@@ -526,7 +526,7 @@ impl RawConversion for power::private::NV_GPU_PERF_INFO {
         // TODO: check padding
         Ok(PerfInfo {
             max_unknown: self.maxUnknown,
-            limits: PerfFlags::from_bits(self.limitSupport).ok_or(sys::ArgumentRangeError)?,
+            limits: PerfFlags::from_bits(self.limitSupport).ok_or(sys::ArgumentRangeError::new())?,
         })
     }
 }
@@ -550,9 +550,9 @@ impl RawConversion for power::private::NV_GPU_PERF_STATUS {
                 flags: 0, limits, zero0: 0, unknown, zero1: 0, ..
             } => Ok(PerfStatus {
                 unknown: unknown,
-                limits: PerfFlags::from_bits(limits).ok_or(sys::ArgumentRangeError)?,
+                limits: PerfFlags::from_bits(limits).ok_or(sys::ArgumentRangeError::new())?,
             }),
-            _ => Err(sys::ArgumentRangeError),
+            _ => Err(sys::ArgumentRangeError::new()),
         }
     }
 }
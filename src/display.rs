@@ -0,0 +1,210 @@
+use std::ffi::CString;
+use log::trace;
+use crate::sys::{self, dispcontrol, Status};
+use crate::sys::handles::{NvDisplayHandle, NvUnAttachedDisplayHandle};
+use crate::types::{RawConversion, checked_entries};
+use crate::gpu::{PhysicalGpu, DisplayId};
+use crate::logical::LogicalGpu;
+use crate::session::Nvapi;
+
+/// Display names may not contain a nul byte.
+fn display_name(name: &str) -> sys::Result<CString> {
+    CString::new(name).map_err(|_| Status::InvalidArgument.into())
+}
+
+/// Calls `f` with increasing indices until the driver reports `NVAPI_END_ENUMERATION`.
+fn enumerate_handles<H: Default, F: FnMut(u32, &mut H) -> sys::NvAPI_Status>(mut f: F) -> sys::Result<Vec<H>> {
+    let mut handles = Vec::new();
+    for index in 0.. {
+        let mut handle = H::default();
+        match f(index, &mut handle) {
+            sys::status::NVAPI_END_ENUMERATION | sys::status::NVAPI_NVIDIA_DEVICE_NOT_FOUND => break,
+            status => sys::status_result(status)?,
+        }
+        handles.push(handle);
+    }
+    Ok(handles)
+}
+
+fn display_id_by_name(name: &str) -> sys::Result<u32> {
+    let name = display_name(name)?;
+    let mut id = 0;
+    sys::status_result(unsafe { dispcontrol::NvAPI_DISP_GetDisplayIdByDisplayName(name.as_ptr(), &mut id) })
+        .map(|_| id)
+}
+
+/// A display attached to the desktop, driven by one or more GPUs.
+///
+/// Display handles are invalidated by a mode set, after which the displays must be
+/// enumerated again.
+#[derive(Debug)]
+pub struct Display {
+    handle: NvDisplayHandle,
+    session: Option<Nvapi>,
+}
+
+unsafe impl Send for Display { }
+
+impl Display {
+    pub fn handle(&self) -> NvDisplayHandle {
+        self.handle
+    }
+
+    /// The session this display holds on to, if it was obtained while one was alive.
+    pub fn session(&self) -> Option<&Nvapi> {
+        self.session.as_ref()
+    }
+
+    /// Enumerates the attached displays, joining the current `Nvapi` session if there is one.
+    pub fn enumerate() -> sys::Result<Vec<Self>> {
+        trace!("display.enumerate()");
+        let session = Nvapi::current();
+        enumerate_handles(|index, handle| unsafe { dispcontrol::NvAPI_EnumNvidiaDisplayHandle(index, handle) })
+            .map(|handles| handles.into_iter().map(|handle| Display {
+                handle,
+                session: session.clone(),
+            }).collect())
+    }
+
+    /// Looks up a display by its GDI name, such as `\\.\DISPLAY1`.
+    pub fn from_name(name: &str) -> sys::Result<Self> {
+        trace!("display.from_name({:?})", name);
+        let name = display_name(name)?;
+        let mut handle = Default::default();
+        sys::status_result(unsafe { dispcontrol::NvAPI_GetAssociatedNvidiaDisplayHandle(name.as_ptr(), &mut handle) })
+            .map(|_| Display {
+                handle,
+                session: Nvapi::current(),
+            })
+    }
+
+    /// Finds the attached display with the given id.
+    pub fn from_display_id(id: u32) -> sys::Result<Self> {
+        trace!("display.from_display_id({:#x})", id);
+        for display in Self::enumerate()? {
+            if display.display_id()? == id {
+                return Ok(display)
+            }
+        }
+        Err(Status::NvidiaDeviceNotFound.into())
+    }
+
+    /// The GDI name, such as `\\.\DISPLAY1`.
+    pub fn name(&self) -> sys::Result<String> {
+        trace!("display.name()");
+        let mut str = sys::types::short_string();
+        sys::status_result(unsafe { dispcontrol::NvAPI_GetAssociatedNvidiaDisplayName(self.handle, &mut str) })
+            .and_then(|_| str.convert_raw().map_err(Into::into))
+    }
+
+    /// The id this display is reported with by `PhysicalGpu::display_ids_*`.
+    pub fn display_id(&self) -> sys::Result<u32> {
+        trace!("display.display_id()");
+        display_id_by_name(&self.name()?)
+    }
+
+    /// The GPUs driving this display, starting with the one the output is attached to.
+    pub fn physical_gpus(&self) -> sys::Result<Vec<PhysicalGpu>> {
+        trace!("display.physical_gpus()");
+        let mut handles = [Default::default(); sys::types::NVAPI_MAX_PHYSICAL_GPUS];
+        let mut len = 0;
+        sys::status_result(unsafe { dispcontrol::NvAPI_GetPhysicalGPUsFromDisplay(self.handle, &mut handles, &mut len) })
+            .and_then(|_| checked_entries(&handles, "pGpuCount", len as _).map_err(From::from))
+            .map(|handles| handles.iter()
+                .map(|&handle| PhysicalGpu::from_handle(handle, self.session.clone()))
+                .collect()
+            )
+    }
+
+    /// The logical GPU driving this display.
+    pub fn logical_gpu(&self) -> sys::Result<LogicalGpu> {
+        LogicalGpu::from_display(self)
+    }
+}
+
+/// A connected display that isn't part of the desktop.
+#[derive(Debug)]
+pub struct UnattachedDisplay {
+    handle: NvUnAttachedDisplayHandle,
+    session: Option<Nvapi>,
+}
+
+unsafe impl Send for UnattachedDisplay { }
+
+impl UnattachedDisplay {
+    pub fn handle(&self) -> NvUnAttachedDisplayHandle {
+        self.handle
+    }
+
+    /// The session this display holds on to, if it was obtained while one was alive.
+    pub fn session(&self) -> Option<&Nvapi> {
+        self.session.as_ref()
+    }
+
+    /// Enumerates the unattached displays, joining the current `Nvapi` session if there is one.
+    pub fn enumerate() -> sys::Result<Vec<Self>> {
+        trace!("unattached_display.enumerate()");
+        let session = Nvapi::current();
+        enumerate_handles(|index, handle| unsafe { dispcontrol::NvAPI_EnumNvidiaUnAttachedDisplayHandle(index, handle) })
+            .map(|handles| handles.into_iter().map(|handle| UnattachedDisplay {
+                handle,
+                session: session.clone(),
+            }).collect())
+    }
+
+    /// Looks up a display by its GDI name, such as `\\.\DISPLAY2`.
+    pub fn from_name(name: &str) -> sys::Result<Self> {
+        trace!("unattached_display.from_name({:?})", name);
+        let name = display_name(name)?;
+        let mut handle = Default::default();
+        sys::status_result(unsafe { dispcontrol::NvAPI_DISP_GetAssociatedUnAttachedNvidiaDisplayHandle(name.as_ptr(), &mut handle) })
+            .map(|_| UnattachedDisplay {
+                handle,
+                session: Nvapi::current(),
+            })
+    }
+
+    /// Finds the unattached display with the given id.
+    pub fn from_display_id(id: u32) -> sys::Result<Self> {
+        trace!("unattached_display.from_display_id({:#x})", id);
+        for display in Self::enumerate()? {
+            if display.display_id()? == id {
+                return Ok(display)
+            }
+        }
+        Err(Status::NvidiaDeviceNotFound.into())
+    }
+
+    pub fn name(&self) -> sys::Result<String> {
+        trace!("unattached_display.name()");
+        let mut str = sys::types::short_string();
+        sys::status_result(unsafe { dispcontrol::NvAPI_GetUnAttachedAssociatedDisplayName(self.handle, &mut str) })
+            .and_then(|_| str.convert_raw().map_err(Into::into))
+    }
+
+    /// The id this display is reported with by `PhysicalGpu::display_ids_*`.
+    pub fn display_id(&self) -> sys::Result<u32> {
+        trace!("unattached_display.display_id()");
+        display_id_by_name(&self.name()?)
+    }
+
+    /// The GPU that would render to this display.
+    pub fn physical_gpu(&self) -> sys::Result<PhysicalGpu> {
+        trace!("unattached_display.physical_gpu()");
+        let mut handle = Default::default();
+        sys::status_result(unsafe { dispcontrol::NvAPI_GetPhysicalGPUFromUnAttachedDisplay(self.handle, &mut handle) })
+            .map(|_| PhysicalGpu::from_handle(handle, self.session.clone()))
+    }
+}
+
+impl DisplayId {
+    /// The attached display with this id.
+    pub fn display(&self) -> sys::Result<Display> {
+        Display::from_display_id(self.display_id)
+    }
+
+    /// The unattached display with this id.
+    pub fn unattached_display(&self) -> sys::Result<UnattachedDisplay> {
+        UnattachedDisplay::from_display_id(self.display_id)
+    }
+}
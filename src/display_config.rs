@@ -0,0 +1,399 @@
+use std::ptr;
+use std::collections::BTreeSet;
+use log::trace;
+use serde::{Serialize, Deserialize};
+use crate::sys::{self, dispcontrol};
+use crate::sys::dispcontrol::{SourceModeFlags, TargetInfoFlags};
+use crate::types::{Kilohertz, RawConversion};
+
+pub use sys::dispcontrol::{Rotation, Scaling, ColorFormat, SpanningOrientation, TimingOverride, DisplayConfigFlags};
+
+/// The global desktop layout: which sources exist, where they sit and which displays
+/// show them.
+///
+/// Serializes to a plain description that can be saved, compared against the current
+/// layout with `diff`, and restored with `apply` after a driver update or redocking.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct DisplayConfig {
+    pub paths: Vec<PathInfo>,
+}
+
+/// A desktop source, shown on each of its targets (more than one for clone mode).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathInfo {
+    /// The Windows CCD source id; all zero lets the driver assign them.
+    pub source_id: u32,
+    pub source: SourceMode,
+    pub targets: Vec<TargetInfo>,
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Resolution {
+    pub width: u32,
+    pub height: u32,
+    /// Bits per pixel
+    pub color_depth: u32,
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct SourceMode {
+    pub resolution: Resolution,
+    pub color_format: ColorFormat,
+    /// The top left corner on the desktop.
+    pub position: (i32, i32),
+    pub spanning: SpanningOrientation,
+    pub gdi_primary: bool,
+    pub sli_focus: bool,
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct TargetInfo {
+    pub display_id: u32,
+    /// The Windows CCD target id, only used for non-NVIDIA adapters.
+    pub target_id: u32,
+    pub rotation: Rotation,
+    pub scaling: Scaling,
+    /// In millihertz, 0 keeps the current refresh rate.
+    pub refresh_rate: u32,
+    pub interlaced: bool,
+    /// The primary display of a clone group, which is not the same as the GDI primary.
+    pub primary: bool,
+    pub timing_override: TimingOverride,
+    /// Only applied with `TimingOverride::Custom`.
+    pub timing: Option<Timing>,
+}
+
+/// A VESA style display timing.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Timing {
+    pub h_visible: u16,
+    pub h_border: u16,
+    pub h_front_porch: u16,
+    pub h_sync_width: u16,
+    pub h_total: u16,
+    pub h_sync_negative: bool,
+    pub v_visible: u16,
+    pub v_border: u16,
+    pub v_front_porch: u16,
+    pub v_sync_width: u16,
+    pub v_total: u16,
+    pub v_sync_negative: bool,
+    pub interlaced: bool,
+    /// Rounded to 10 kHz by the driver.
+    pub pixel_clock: Kilohertz,
+    /// In millihertz
+    pub refresh_rate: u32,
+}
+
+/// A difference between two configs, keyed by the display it affects.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DisplayConfigChange {
+    /// The display is only part of the new config.
+    Added(u32),
+    /// The display is only part of the old config.
+    Removed(u32),
+    /// The source shown on the display changed mode or position.
+    Source {
+        display_id: u32,
+        old: SourceMode,
+        new: SourceMode,
+    },
+    /// The displays sharing a source with this one changed.
+    Clones {
+        display_id: u32,
+        old: Vec<u32>,
+        new: Vec<u32>,
+    },
+    Target {
+        display_id: u32,
+        old: TargetInfo,
+        new: TargetInfo,
+    },
+}
+
+impl RawConversion for dispcontrol::NV_TIMING {
+    type Target = Option<Timing>;
+    type Error = sys::ArgumentRangeError;
+
+    fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
+        trace!("convert_raw({:#?})", self);
+        if self.pclk == 0 {
+            return Ok(None)
+        }
+
+        Ok(Some(Timing {
+            h_visible: self.HVisible,
+            h_border: self.HBorder,
+            h_front_porch: self.HFrontPorch,
+            h_sync_width: self.HSyncWidth,
+            h_total: self.HTotal,
+            h_sync_negative: self.HSyncPol != 0,
+            v_visible: self.VVisible,
+            v_border: self.VBorder,
+            v_front_porch: self.VFrontPorch,
+            v_sync_width: self.VSyncWidth,
+            v_total: self.VTotal,
+            v_sync_negative: self.VSyncPol != 0,
+            interlaced: self.interlaced != 0,
+            pixel_clock: Kilohertz(self.pclk.saturating_mul(10)),
+            refresh_rate: self.etc.rrx1k,
+        }))
+    }
+}
+
+impl Timing {
+    fn to_raw(self) -> dispcontrol::NV_TIMING {
+        let mut raw = dispcontrol::NV_TIMING::zeroed();
+        raw.HVisible = self.h_visible;
+        raw.HBorder = self.h_border;
+        raw.HFrontPorch = self.h_front_porch;
+        raw.HSyncWidth = self.h_sync_width;
+        raw.HTotal = self.h_total;
+        raw.HSyncPol = self.h_sync_negative as _;
+        raw.VVisible = self.v_visible;
+        raw.VBorder = self.v_border;
+        raw.VFrontPorch = self.v_front_porch;
+        raw.VSyncWidth = self.v_sync_width;
+        raw.VTotal = self.v_total;
+        raw.VSyncPol = self.v_sync_negative as _;
+        raw.interlaced = self.interlaced as _;
+        raw.pclk = self.pixel_clock.0 / 10;
+        raw.etc.rr = (self.refresh_rate.saturating_add(500) / 1000) as _;
+        raw.etc.rrx1k = self.refresh_rate;
+        raw
+    }
+}
+
+impl RawConversion for dispcontrol::NV_DISPLAYCONFIG_SOURCE_MODE_INFO {
+    type Target = SourceMode;
+    type Error = sys::ArgumentRangeError;
+
+    fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
+        trace!("convert_raw({:#?})", self);
+        let flags = SourceModeFlags::from_bits_truncate(self.flags);
+        Ok(SourceMode {
+            resolution: Resolution {
+                width: self.resolution.width,
+                height: self.resolution.height,
+                color_depth: self.resolution.colorDepth,
+            },
+            color_format: ColorFormat::from_raw_lossless(self.colorFormat),
+            position: (self.position.x, self.position.y),
+            spanning: SpanningOrientation::from_raw_lossless(self.spanningOrientation),
+            gdi_primary: flags.contains(SourceModeFlags::GDI_PRIMARY),
+            sli_focus: flags.contains(SourceModeFlags::SLI_FOCUS),
+        })
+    }
+}
+
+impl RawConversion for (dispcontrol::NV_DISPLAYCONFIG_PATH_TARGET_INFO, dispcontrol::NV_DISPLAYCONFIG_PATH_ADVANCED_TARGET_INFO) {
+    type Target = TargetInfo;
+    type Error = sys::ArgumentRangeError;
+
+    fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
+        trace!("convert_raw({:#?})", self);
+        let (ref target, ref details) = *self;
+        let flags = TargetInfoFlags::from_bits_truncate(details.flags);
+        Ok(TargetInfo {
+            display_id: target.displayId,
+            target_id: target.targetId,
+            rotation: Rotation::from_raw_lossless(details.rotation),
+            scaling: Scaling::from_raw_lossless(details.scaling),
+            refresh_rate: details.refreshRate1K,
+            interlaced: flags.contains(TargetInfoFlags::INTERLACED),
+            primary: flags.contains(TargetInfoFlags::PRIMARY),
+            timing_override: TimingOverride::from_raw_lossless(details.timingOverride),
+            timing: details.timing.convert_raw()?,
+        })
+    }
+}
+
+/// An array of `NV_DISPLAYCONFIG_PATH_INFO` along with the buffers its entries point into.
+struct RawPaths {
+    paths: Vec<dispcontrol::NV_DISPLAYCONFIG_PATH_INFO>,
+    sources: Vec<dispcontrol::NV_DISPLAYCONFIG_SOURCE_MODE_INFO>,
+    targets: Vec<Vec<dispcontrol::NV_DISPLAYCONFIG_PATH_TARGET_INFO>>,
+    details: Vec<Vec<dispcontrol::NV_DISPLAYCONFIG_PATH_ADVANCED_TARGET_INFO>>,
+}
+
+impl RawPaths {
+    /// Paths without any target buffers only have their target count filled in.
+    fn new<I: IntoIterator<Item=usize>>(target_counts: I) -> Self {
+        let mut path = dispcontrol::NV_DISPLAYCONFIG_PATH_INFO::zeroed();
+        path.version = dispcontrol::NV_DISPLAYCONFIG_PATH_INFO_VER;
+        let mut details = dispcontrol::NV_DISPLAYCONFIG_PATH_ADVANCED_TARGET_INFO::zeroed();
+        details.version = dispcontrol::NV_DISPLAYCONFIG_PATH_ADVANCED_TARGET_INFO_VER;
+
+        let details: Vec<_> = target_counts.into_iter().map(|count| vec![details; count]).collect();
+        let mut raw = RawPaths {
+            paths: vec![path; details.len()],
+            sources: vec![dispcontrol::NV_DISPLAYCONFIG_SOURCE_MODE_INFO::zeroed(); details.len()],
+            targets: details.iter().map(|details| vec![dispcontrol::NV_DISPLAYCONFIG_PATH_TARGET_INFO::zeroed(); details.len()]).collect(),
+            details,
+        };
+        for (((path, source), targets), details) in raw.paths.iter_mut().zip(&mut raw.sources).zip(&mut raw.targets).zip(&mut raw.details) {
+            path.sourceModeInfo = source;
+            path.targetInfoCount = targets.len() as _;
+            if !targets.is_empty() {
+                path.targetInfo = targets.as_mut_ptr();
+            }
+            for (target, details) in targets.iter_mut().zip(details) {
+                target.details = details;
+            }
+        }
+        raw
+    }
+
+    fn get(&mut self) -> sys::Result<()> {
+        let mut count = self.paths.len() as u32;
+        sys::status_result(unsafe { dispcontrol::NvAPI_DISP_GetDisplayConfig(&mut count, self.paths.as_mut_ptr()) })?;
+        // the layout may have changed since it was counted
+        if count as usize != self.paths.len() {
+            return Err(sys::Status::InsufficientBuffer.into())
+        }
+        Ok(())
+    }
+}
+
+impl DisplayConfig {
+    /// Reads the current desktop layout.
+    pub fn get() -> sys::Result<Self> {
+        trace!("display_config()");
+        let mut count = 0;
+        sys::status_result(unsafe { dispcontrol::NvAPI_DISP_GetDisplayConfig(&mut count, ptr::null_mut()) })?;
+        if count == 0 {
+            return Ok(Default::default())
+        }
+
+        let mut counts = RawPaths::new((0..count).map(|_| 0));
+        counts.get()?;
+        let mut raw = RawPaths::new(counts.paths.iter().map(|path| path.targetInfoCount as usize));
+        raw.get()?;
+
+        raw.paths.iter().zip(&raw.sources).zip(raw.targets.iter().zip(&raw.details)).map(|((path, source), (targets, details))| Ok(PathInfo {
+            source_id: path.sourceId,
+            source: source.convert_raw()?,
+            targets: targets.iter().cloned().zip(details.iter().cloned()).map(|target| target.convert_raw()).collect::<Result<_, sys::ArgumentRangeError>>()?,
+        })).collect::<Result<_, sys::ArgumentRangeError>>()
+            .map(|paths| DisplayConfig { paths })
+            .map_err(From::from)
+    }
+
+    fn to_raw(&self) -> RawPaths {
+        let mut raw = RawPaths::new(self.paths.iter().map(|path| path.targets.len()));
+        for (i, path) in self.paths.iter().enumerate() {
+            raw.paths[i].sourceId = path.source_id;
+
+            let source = &mut raw.sources[i];
+            source.resolution.width = path.source.resolution.width;
+            source.resolution.height = path.source.resolution.height;
+            source.resolution.colorDepth = path.source.resolution.color_depth;
+            source.colorFormat = path.source.color_format.raw();
+            source.position.x = path.source.position.0;
+            source.position.y = path.source.position.1;
+            source.spanningOrientation = path.source.spanning.raw();
+            let mut flags = SourceModeFlags::empty();
+            flags.set(SourceModeFlags::GDI_PRIMARY, path.source.gdi_primary);
+            flags.set(SourceModeFlags::SLI_FOCUS, path.source.sli_focus);
+            source.flags = flags.bits();
+
+            for (j, target) in path.targets.iter().enumerate() {
+                raw.targets[i][j].displayId = target.display_id;
+                raw.targets[i][j].targetId = target.target_id;
+
+                let details = &mut raw.details[i][j];
+                details.rotation = target.rotation.raw();
+                details.scaling = target.scaling.raw();
+                details.refreshRate1K = target.refresh_rate;
+                let mut flags = TargetInfoFlags::empty();
+                flags.set(TargetInfoFlags::INTERLACED, target.interlaced);
+                flags.set(TargetInfoFlags::PRIMARY, target.primary);
+                details.flags = flags.bits();
+                details.timingOverride = target.timing_override.raw();
+                if let Some(ref timing) = target.timing {
+                    details.timing = timing.to_raw();
+                }
+            }
+        }
+        raw
+    }
+
+    /// Checks whether the driver would accept this layout, without applying it.
+    pub fn validate(&self) -> sys::Result<()> {
+        trace!("display_config.validate()");
+        self.apply(DisplayConfigFlags::VALIDATE_ONLY)
+    }
+
+    /// Applies this layout; displays left out of it are removed from the desktop.
+    ///
+    /// A mode set invalidates existing display handles.
+    pub fn apply(&self, flags: DisplayConfigFlags) -> sys::Result<()> {
+        trace!("display_config.apply({:?})", flags);
+        let mut raw = self.to_raw();
+        let paths = if raw.paths.is_empty() { ptr::null_mut() } else { raw.paths.as_mut_ptr() };
+        sys::status_result(unsafe { dispcontrol::NvAPI_DISP_SetDisplayConfig(raw.paths.len() as _, paths, flags.bits()) })
+    }
+
+    /// Every display across all paths.
+    pub fn display_ids(&self) -> impl Iterator<Item=u32> + '_ {
+        self.paths.iter().flat_map(|path| &path.targets).map(|target| target.display_id)
+    }
+
+    /// The path and target showing `display_id`.
+    pub fn find(&self, display_id: u32) -> Option<(&PathInfo, &TargetInfo)> {
+        self.paths.iter().flat_map(|path| path.targets.iter().map(move |target| (path, target)))
+            .find(|&(_, target)| target.display_id == display_id)
+    }
+
+    /// What changes going from this config to `other`, ordered by display id.
+    pub fn diff(&self, other: &Self) -> Vec<DisplayConfigChange> {
+        let ids: BTreeSet<_> = self.display_ids().chain(other.display_ids()).collect();
+        let clones = |path: &PathInfo, id| path.targets.iter().map(|target| target.display_id).filter(|&other| other != id).collect::<Vec<_>>();
+
+        let mut changes = Vec::new();
+        for id in ids {
+            let (old_path, old, new_path, new) = match (self.find(id), other.find(id)) {
+                (Some((old_path, old)), Some((new_path, new))) => (old_path, old, new_path, new),
+                (None, _) => {
+                    changes.push(DisplayConfigChange::Added(id));
+                    continue
+                },
+                (_, None) => {
+                    changes.push(DisplayConfigChange::Removed(id));
+                    continue
+                },
+            };
+
+            if old_path.source != new_path.source {
+                changes.push(DisplayConfigChange::Source {
+                    display_id: id,
+                    old: old_path.source,
+                    new: new_path.source,
+                });
+            }
+            let (old_clones, new_clones) = (clones(old_path, id), clones(new_path, id));
+            if old_clones != new_clones {
+                changes.push(DisplayConfigChange::Clones {
+                    display_id: id,
+                    old: old_clones,
+                    new: new_clones,
+                });
+            }
+            if old != new {
+                changes.push(DisplayConfigChange::Target {
+                    display_id: id,
+                    old: *old,
+                    new: *new,
+                });
+            }
+        }
+        changes
+    }
+}
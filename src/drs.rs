@@ -0,0 +1,518 @@
+use log::trace;
+use serde::{Serialize, Deserialize};
+use crate::sys::{self, drs, Api, Status};
+use crate::sys::handles::{NvDRSSessionHandle, NvDRSProfileHandle};
+use crate::sys::drs::{ApplicationFlags, SettingType};
+use crate::types::{RawConversion, checked_entries, unicode_string};
+use crate::versioned::{negotiate, negotiate_from};
+use crate::session::Nvapi;
+use crate::nip::{NipProfile, NipSetting};
+use crate::drs_settings::KnownSetting;
+
+pub use sys::drs::{GpuSupport, SettingLocation};
+
+/// A driver settings (DRS) session.
+///
+/// The session works on a snapshot of the driver's settings database: it is loaded
+/// when the session is created, and changes only take effect once `save`d.
+/// The session is destroyed on drop.
+#[derive(Debug)]
+pub struct DrsSession {
+    handle: NvDRSSessionHandle,
+    session: Option<Nvapi>,
+}
+
+unsafe impl Send for DrsSession { }
+
+impl Drop for DrsSession {
+    fn drop(&mut self) {
+        trace!("drs.destroy()");
+        let _ = unsafe { drs::NvAPI_DRS_DestroySession(self.handle) };
+    }
+}
+
+impl DrsSession {
+    /// Creates a session and loads the current settings, joining the current `Nvapi`
+    /// session if there is one.
+    pub fn new() -> sys::Result<Self> {
+        trace!("drs.new()");
+        let mut handle = Default::default();
+        sys::status_result(unsafe { drs::NvAPI_DRS_CreateSession(&mut handle) })?;
+        let session = DrsSession {
+            handle,
+            session: Nvapi::current(),
+        };
+        session.load()?;
+        Ok(session)
+    }
+
+    pub fn handle(&self) -> NvDRSSessionHandle {
+        self.handle
+    }
+
+    /// The session this one holds on to, if it was created while one was alive.
+    pub fn session(&self) -> Option<&Nvapi> {
+        self.session.as_ref()
+    }
+
+    /// Reloads the settings, discarding any unsaved changes.
+    pub fn load(&self) -> sys::Result<()> {
+        trace!("drs.load()");
+        sys::status_result(unsafe { drs::NvAPI_DRS_LoadSettings(self.handle) })
+    }
+
+    /// Commits the changes made in this session to the driver.
+    pub fn save(&self) -> sys::Result<()> {
+        trace!("drs.save()");
+        sys::status_result(unsafe { drs::NvAPI_DRS_SaveSettings(self.handle) })
+    }
+
+    fn profile(&self, handle: NvDRSProfileHandle) -> Profile<'_> {
+        Profile {
+            session: self,
+            handle,
+        }
+    }
+
+    pub fn profiles(&self) -> sys::Result<Vec<Profile<'_>>> {
+        trace!("drs.profiles()");
+        let mut profiles = Vec::new();
+        for index in 0.. {
+            let mut handle = Default::default();
+            match unsafe { drs::NvAPI_DRS_EnumProfiles(self.handle, index, &mut handle) } {
+                sys::status::NVAPI_END_ENUMERATION => break,
+                status => sys::status_result(status)?,
+            }
+            profiles.push(self.profile(handle));
+        }
+        Ok(profiles)
+    }
+
+    /// The profile every other profile inherits settings from.
+    pub fn base_profile(&self) -> sys::Result<Profile<'_>> {
+        trace!("drs.base_profile()");
+        let mut handle = Default::default();
+        sys::status_result(unsafe { drs::NvAPI_DRS_GetBaseProfile(self.handle, &mut handle) })
+            .map(|_| self.profile(handle))
+    }
+
+    /// The profile applications without a profile of their own use.
+    pub fn current_global_profile(&self) -> sys::Result<Profile<'_>> {
+        trace!("drs.current_global_profile()");
+        let mut handle = Default::default();
+        sys::status_result(unsafe { drs::NvAPI_DRS_GetCurrentGlobalProfile(self.handle, &mut handle) })
+            .map(|_| self.profile(handle))
+    }
+
+    pub fn set_current_global_profile(&self, name: &str) -> sys::Result<()> {
+        trace!("drs.set_current_global_profile({:?})", name);
+        let name = unicode_string(name)?;
+        sys::status_result(unsafe { drs::NvAPI_DRS_SetCurrentGlobalProfile(self.handle, &name) })
+    }
+
+    pub fn find_profile(&self, name: &str) -> sys::Result<Profile<'_>> {
+        trace!("drs.find_profile({:?})", name);
+        let name = unicode_string(name)?;
+        let mut handle = Default::default();
+        sys::status_result(unsafe { drs::NvAPI_DRS_FindProfileByName(self.handle, &name, &mut handle) })
+            .map(|_| self.profile(handle))
+    }
+
+    /// Finds the profile an executable belongs to. `name` may be a full path.
+    pub fn find_application(&self, name: &str) -> sys::Result<(Profile<'_>, Application)> {
+        trace!("drs.find_application({:?})", name);
+        let name = unicode_string(name)?;
+        let mut handle = Default::default();
+        let mut app = drs::NVDRS_APPLICATION::zeroed();
+        negotiate(Api::NvAPI_DRS_FindApplicationByName, &mut app, |app| unsafe { drs::NvAPI_DRS_FindApplicationByName(self.handle, &name, &mut handle, app) })?;
+        app.convert_raw().map_err(From::from)
+            .map(|app| (self.profile(handle), app))
+    }
+
+    pub fn create_profile(&self, name: &str, gpu_support: GpuSupport) -> sys::Result<Profile<'_>> {
+        trace!("drs.create_profile({:?}, {:?})", name, gpu_support);
+        let mut info = drs::NVDRS_PROFILE::zeroed();
+        info.version = drs::NVDRS_PROFILE_VER;
+        info.profileName = unicode_string(name)?;
+        info.gpuSupport = gpu_support.bits();
+        let mut handle = Default::default();
+        sys::status_result(unsafe { drs::NvAPI_DRS_CreateProfile(self.handle, &mut info, &mut handle) })
+            .map(|_| self.profile(handle))
+    }
+
+    /// Applies exported profiles, creating any that don't exist yet.
+    ///
+    /// Executables already attached to a profile are kept, and settings are overwritten.
+    /// The changes take effect once `save`d.
+    pub fn import(&self, profiles: &[NipProfile]) -> sys::Result<()> {
+        trace!("drs.import({:?})", profiles);
+        for nip in profiles {
+            let profile = match self.find_profile(&nip.name) {
+                Err(e) if e == Status::ProfileNotFound => self.create_profile(&nip.name, GpuSupport::GEFORCE | GpuSupport::QUADRO)?,
+                res => res?,
+            };
+            let apps = profile.applications()?;
+            for executable in &nip.executables {
+                if !apps.iter().any(|app| app.name.eq_ignore_ascii_case(executable)) {
+                    profile.add_application(&Application::new(executable))?;
+                }
+            }
+            for setting in &nip.settings {
+                profile.set_setting(setting.id, &setting.value)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Removes all user profiles and restores the predefined ones.
+    pub fn restore_all_defaults(&self) -> sys::Result<()> {
+        trace!("drs.restore_all_defaults()");
+        sys::status_result(unsafe { drs::NvAPI_DRS_RestoreAllDefaults(self.handle) })
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProfileInfo {
+    pub name: String,
+    pub gpu_support: GpuSupport,
+    /// Whether the profile ships with the driver.
+    pub predefined: bool,
+    pub application_count: u32,
+    pub setting_count: u32,
+}
+
+impl RawConversion for drs::NVDRS_PROFILE {
+    type Target = ProfileInfo;
+    type Error = sys::ArgumentRangeError;
+
+    fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
+        Ok(ProfileInfo {
+            name: self.profileName.convert_raw()?,
+            gpu_support: GpuSupport::from_bits_truncate(self.gpuSupport),
+            predefined: self.isPredefined != 0,
+            application_count: self.numOfApps,
+            setting_count: self.numOfSettings,
+        })
+    }
+}
+
+/// An executable attached to a profile.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Application {
+    /// The executable's file name, such as `game.exe`.
+    pub name: String,
+    pub friendly_name: String,
+    pub launcher: String,
+    /// Files that must be present next to the executable for it to match.
+    pub file_in_folder: String,
+    /// Only matches when launched with this command line.
+    pub command_line: Option<String>,
+    pub metro: bool,
+    /// Whether the application ships with the driver. Ignored when adding applications.
+    pub predefined: bool,
+}
+
+impl RawConversion for drs::NVDRS_APPLICATION {
+    type Target = Application;
+    type Error = sys::ArgumentRangeError;
+
+    /// Fields the negotiated version doesn't have are left zeroed by the driver.
+    fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
+        let flags = ApplicationFlags::from_bits_truncate(self.v3.flags);
+        Ok(Application {
+            name: self.v1().appName.convert_raw()?,
+            friendly_name: self.v1().userFriendlyName.convert_raw()?,
+            launcher: self.v1().launcher.convert_raw()?,
+            file_in_folder: self.v3.v2.fileInFolder.convert_raw()?,
+            command_line: match flags.contains(ApplicationFlags::COMMAND_LINE) {
+                true => Some(self.commandLine.convert_raw()?),
+                false => None,
+            },
+            metro: flags.contains(ApplicationFlags::METRO),
+            predefined: self.v1().isPredefined != 0,
+        })
+    }
+}
+
+impl Application {
+    pub fn new(name: &str) -> Self {
+        Application {
+            name: name.into(),
+            .. Default::default()
+        }
+    }
+
+    fn to_raw(&self) -> sys::Result<drs::NVDRS_APPLICATION> {
+        let mut raw = drs::NVDRS_APPLICATION::zeroed();
+        raw.v1_mut().appName = unicode_string(&self.name)?;
+        raw.v1_mut().userFriendlyName = unicode_string(&self.friendly_name)?;
+        raw.v1_mut().launcher = unicode_string(&self.launcher)?;
+        raw.v3.v2.fileInFolder = unicode_string(&self.file_in_folder)?;
+        let mut flags = ApplicationFlags::empty();
+        flags.set(ApplicationFlags::METRO, self.metro);
+        flags.set(ApplicationFlags::COMMAND_LINE, self.command_line.is_some());
+        raw.v3.flags = flags.bits();
+        raw.commandLine = unicode_string(self.command_line.as_deref().unwrap_or_default())?;
+        Ok(raw)
+    }
+
+    /// The oldest structure version that can describe this application.
+    fn oldest_version(&self) -> u32 {
+        if self.command_line.is_some() {
+            drs::NVDRS_APPLICATION_VER_V4
+        } else if self.metro {
+            drs::NVDRS_APPLICATION_VER_V3
+        } else if !self.file_in_folder.is_empty() {
+            drs::NVDRS_APPLICATION_VER_V2
+        } else {
+            drs::NVDRS_APPLICATION_VER_V1
+        }
+    }
+}
+
+/// The value of a setting. Strings are always written as unicode strings.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SettingValue {
+    Dword(u32),
+    Binary(Vec<u8>),
+    String(String),
+}
+
+impl SettingValue {
+    fn from_raw(ty: drs::NVDRS_SETTING_TYPE, raw: &drs::NVDRS_SETTING_UNION) -> Result<Self, sys::ArgumentRangeError> {
+        Ok(match SettingType::from_raw(ty)? {
+            SettingType::Dword => SettingValue::Dword(unsafe { raw.u32Value }),
+            SettingType::Binary => {
+                let binary = unsafe { &raw.binaryValue };
+                SettingValue::Binary(checked_entries(&binary.valueData, "valueLength", binary.valueLength as _)?.to_vec())
+            },
+            _ => SettingValue::String(unsafe { raw.wszValue }.convert_raw()?),
+        })
+    }
+
+    fn setting_type(&self) -> SettingType {
+        match *self {
+            SettingValue::Dword(..) => SettingType::Dword,
+            SettingValue::Binary(..) => SettingType::Binary,
+            SettingValue::String(..) => SettingType::WString,
+        }
+    }
+
+    fn to_raw(&self) -> sys::Result<drs::NVDRS_SETTING_UNION> {
+        let mut raw = drs::NVDRS_SETTING_UNION::zeroed();
+        match *self {
+            SettingValue::Dword(value) => raw.u32Value = value,
+            SettingValue::Binary(ref data) => {
+                let binary = unsafe { &mut raw.binaryValue };
+                binary.valueData.get_mut(..data.len())
+                    .ok_or(Status::InvalidArgument)?
+                    .copy_from_slice(data);
+                binary.valueLength = data.len() as _;
+            },
+            SettingValue::String(ref str) => raw.wszValue = unicode_string(str)?,
+        }
+        Ok(raw)
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Setting {
+    pub id: u32,
+    /// May be empty for settings the driver has no name for.
+    pub name: String,
+    pub value: SettingValue,
+    /// The value the driver ships with, if the setting is predefined.
+    pub predefined: Option<SettingValue>,
+    /// The profile `value` was inherited from.
+    pub location: SettingLocation,
+}
+
+impl RawConversion for drs::NVDRS_SETTING {
+    type Target = Setting;
+    type Error = sys::ArgumentRangeError;
+
+    fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
+        trace!("convert_raw({:#?})", self);
+        Ok(Setting {
+            id: self.settingId,
+            name: self.settingName.convert_raw()?,
+            value: SettingValue::from_raw(self.settingType, &self.current)?,
+            predefined: match self.isPredefinedValid != 0 {
+                true => Some(SettingValue::from_raw(self.settingType, &self.predefined)?),
+                false => None,
+            },
+            location: SettingLocation::from_raw(self.settingLocation)?,
+        })
+    }
+}
+
+/// A profile within a `DrsSession`, which applies settings to the applications attached
+/// to it.
+#[derive(Debug, Copy, Clone)]
+pub struct Profile<'s> {
+    session: &'s DrsSession,
+    handle: NvDRSProfileHandle,
+}
+
+impl<'s> Profile<'s> {
+    pub fn handle(&self) -> NvDRSProfileHandle {
+        self.handle
+    }
+
+    pub fn info(&self) -> sys::Result<ProfileInfo> {
+        trace!("profile.info()");
+        let mut info = drs::NVDRS_PROFILE::zeroed();
+        info.version = drs::NVDRS_PROFILE_VER;
+        sys::status_result(unsafe { drs::NvAPI_DRS_GetProfileInfo(self.session.handle, self.handle, &mut info) })?;
+        info.convert_raw().map_err(From::from)
+    }
+
+    pub fn set_gpu_support(&self, gpu_support: GpuSupport) -> sys::Result<()> {
+        trace!("profile.set_gpu_support({:?})", gpu_support);
+        let mut info = drs::NVDRS_PROFILE::zeroed();
+        info.version = drs::NVDRS_PROFILE_VER;
+        sys::status_result(unsafe { drs::NvAPI_DRS_GetProfileInfo(self.session.handle, self.handle, &mut info) })?;
+        info.gpuSupport = gpu_support.bits();
+        sys::status_result(unsafe { drs::NvAPI_DRS_SetProfileInfo(self.session.handle, self.handle, &mut info) })
+    }
+
+    /// Exports the profile as NVIDIA Profile Inspector does, leaving out settings that
+    /// are still at their predefined value.
+    pub fn export(&self) -> sys::Result<NipProfile> {
+        trace!("profile.export()");
+        Ok(NipProfile {
+            name: self.info()?.name,
+            executables: self.applications()?.into_iter().map(|app| app.name).collect(),
+            settings: self.settings()?.into_iter()
+                .filter(|setting| setting.predefined.as_ref() != Some(&setting.value))
+                .map(|setting| NipSetting {
+                    id: setting.id,
+                    name: setting.name,
+                    value: setting.value,
+                }).collect(),
+        })
+    }
+
+    /// Deletes a user profile. Predefined profiles are restored to their defaults instead.
+    pub fn delete(self) -> sys::Result<()> {
+        trace!("profile.delete()");
+        sys::status_result(unsafe { drs::NvAPI_DRS_DeleteProfile(self.session.handle, self.handle) })
+    }
+
+    pub fn applications(&self) -> sys::Result<Vec<Application>> {
+        trace!("profile.applications()");
+        let mut apps = Vec::new();
+        for index in 0.. {
+            let mut app = drs::NVDRS_APPLICATION::zeroed();
+            let mut count = 1;
+            match negotiate(Api::NvAPI_DRS_EnumApplications, &mut app, |app| unsafe { drs::NvAPI_DRS_EnumApplications(self.session.handle, self.handle, index, &mut count, app) }) {
+                Err(e) if e == Status::EndEnumeration => break,
+                res => res?,
+            };
+            apps.push(app.convert_raw()?);
+        }
+        Ok(apps)
+    }
+
+    pub fn application(&self, name: &str) -> sys::Result<Application> {
+        trace!("profile.application({:?})", name);
+        let name = unicode_string(name)?;
+        let mut app = drs::NVDRS_APPLICATION::zeroed();
+        negotiate(Api::NvAPI_DRS_GetApplicationInfo, &mut app, |app| unsafe { drs::NvAPI_DRS_GetApplicationInfo(self.session.handle, self.handle, &name, app) })?;
+        app.convert_raw().map_err(From::from)
+    }
+
+    /// Attaches an executable to this profile. An executable can only belong to one profile.
+    pub fn add_application(&self, app: &Application) -> sys::Result<()> {
+        trace!("profile.add_application({:?})", app);
+        let mut raw = app.to_raw()?;
+        negotiate_from(Api::NvAPI_DRS_CreateApplication, &mut raw, app.oldest_version(), |raw| unsafe { drs::NvAPI_DRS_CreateApplication(self.session.handle, self.handle, raw) })
+            .map(drop)
+    }
+
+    pub fn remove_application(&self, name: &str) -> sys::Result<()> {
+        trace!("profile.remove_application({:?})", name);
+        let name = unicode_string(name)?;
+        sys::status_result(unsafe { drs::NvAPI_DRS_DeleteApplication(self.session.handle, self.handle, &name) })
+    }
+
+    /// The settings stored in this profile, without any inherited from other profiles.
+    pub fn settings(&self) -> sys::Result<Vec<Setting>> {
+        trace!("profile.settings()");
+        let mut setting = drs::NVDRS_SETTING::zeroed();
+        setting.version = drs::NVDRS_SETTING_VER;
+        let mut settings = vec![setting; self.info()?.setting_count as usize];
+        if settings.is_empty() {
+            return Ok(Vec::new())
+        }
+
+        let mut count = settings.len() as u32;
+        match unsafe { drs::NvAPI_DRS_EnumSettings(self.session.handle, self.handle, 0, &mut count, settings.as_mut_ptr()) } {
+            sys::status::NVAPI_END_ENUMERATION => count = 0,
+            status => sys::status_result(status)?,
+        }
+        checked_entries(&settings, "settingsCount", count as _)?.iter()
+            .map(|setting| setting.convert_raw().map_err(From::from))
+            .collect()
+    }
+
+    /// Looks up a setting, falling back to the global and base profiles when this
+    /// profile doesn't have it.
+    pub fn setting(&self, id: u32) -> sys::Result<Setting> {
+        trace!("profile.setting({:#010x})", id);
+        let mut setting = drs::NVDRS_SETTING::zeroed();
+        setting.version = drs::NVDRS_SETTING_VER;
+        sys::status_result(unsafe { drs::NvAPI_DRS_GetSetting(self.session.handle, self.handle, id, &mut setting) })?;
+        setting.convert_raw().map_err(From::from)
+    }
+
+    pub fn set_setting(&self, id: u32, value: &SettingValue) -> sys::Result<()> {
+        trace!("profile.set_setting({:#010x}, {:?})", id, value);
+        let mut setting = drs::NVDRS_SETTING::zeroed();
+        setting.version = drs::NVDRS_SETTING_VER;
+        setting.settingId = id;
+        setting.settingType = value.setting_type().raw();
+        setting.current = value.to_raw()?;
+        sys::status_result(unsafe { drs::NvAPI_DRS_SetSetting(self.session.handle, self.handle, &mut setting) })
+    }
+
+    /// Sets a well-known setting by its configuration key and value name, such as
+    /// `power_management_mode` and `prefer_max_performance`.
+    pub fn set_known_setting(&self, key: &str, value: &str) -> sys::Result<()> {
+        trace!("profile.set_known_setting({:?}, {:?})", key, value);
+        let setting = KnownSetting::find(key).ok_or(Status::SettingNotFound)?;
+        let value = setting.parse(value).ok_or(Status::InvalidArgument)?;
+        self.set_setting(setting.id, &SettingValue::Dword(value))
+    }
+
+    /// The name of a well-known setting's value, as accepted by `set_known_setting`.
+    pub fn known_setting(&self, key: &str) -> sys::Result<String> {
+        trace!("profile.known_setting({:?})", key);
+        let known = KnownSetting::find(key).ok_or(Status::SettingNotFound)?;
+        match self.setting(known.id)?.value {
+            SettingValue::Dword(value) => Ok(known.format(value)),
+            _ => Err(Status::DatatypeMismatch.into()),
+        }
+    }
+
+    /// Removes a setting so it is inherited again. Predefined settings are restored instead.
+    pub fn delete_setting(&self, id: u32) -> sys::Result<()> {
+        trace!("profile.delete_setting({:#010x})", id);
+        sys::status_result(unsafe { drs::NvAPI_DRS_DeleteProfileSetting(self.session.handle, self.handle, id) })
+    }
+
+    /// Restores a predefined profile. User profiles are deleted, and report `ProfileRemoved`.
+    pub fn restore_defaults(self) -> sys::Result<()> {
+        trace!("profile.restore_defaults()");
+        sys::status_result(unsafe { drs::NvAPI_DRS_RestoreProfileDefault(self.session.handle, self.handle) })
+    }
+
+    pub fn restore_default_setting(&self, id: u32) -> sys::Result<()> {
+        trace!("profile.restore_default_setting({:#010x})", id);
+        sys::status_result(unsafe { drs::NvAPI_DRS_RestoreProfileDefaultSetting(self.session.handle, self.handle, id) })
+    }
+}
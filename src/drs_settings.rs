@@ -0,0 +1,298 @@
+//! Well-known driver settings, with names that can be written in configuration files.
+//!
+//! Every setting has a snake_case `key` such as `power_management_mode`, and its values
+//! are named after the variants of the matching enum in `sys::driversettings`, so
+//! `PowerManagementMode::PreferMaxPerformance` is written `prefer_max_performance`.
+
+use std::fmt;
+use std::os::raw::c_int;
+use log::trace;
+use crate::sys::{self, drs};
+use crate::sys::driversettings::*;
+use crate::types::{Range, RawConversion, unicode_string};
+
+/// A driver setting known to this crate.
+#[derive(Debug)]
+pub struct KnownSetting {
+    pub id: u32,
+    /// The snake_case name used in configuration files.
+    pub key: &'static str,
+    /// The name the driver reports for the setting.
+    pub name: &'static str,
+    pub default: u32,
+    /// Plain numbers the setting accepts besides its named values.
+    pub range: Option<Range<u32>>,
+    values: fn() -> Vec<(String, u32)>,
+}
+
+fn snake_case(name: &str) -> String {
+    let mut snake = String::with_capacity(name.len() + 4);
+    for (i, c) in name.chars().enumerate() {
+        if c.is_ascii_uppercase() && i > 0 {
+            snake.push('_');
+        }
+        snake.push(c.to_ascii_lowercase());
+    }
+    snake
+}
+
+fn named<T: fmt::Debug + Into<c_int>, I: Iterator<Item=T>>(values: I) -> Vec<(String, u32)> {
+    values.map(|value| (snake_case(&format!("{:?}", value)), value.into() as u32)).collect()
+}
+
+/// The settings known to this crate.
+pub static KNOWN_SETTINGS: &[KnownSetting] = &[
+    KnownSetting {
+        id: VSYNCMODE_ID,
+        key: "vsync",
+        name: VSYNCMODE_STRING,
+        default: VSYNCMODE_DEFAULT as _,
+        range: None,
+        values: || named(VsyncMode::values()),
+    },
+    KnownSetting {
+        id: VSYNCTEARCONTROL_ID,
+        key: "vsync_tear_control",
+        name: VSYNCTEARCONTROL_STRING,
+        default: VSYNCTEARCONTROL_DEFAULT as _,
+        range: None,
+        values: || named(VsyncTearControl::values()),
+    },
+    KnownSetting {
+        id: FRL_FPS_ID,
+        key: "frame_rate_limiter",
+        name: FRL_FPS_STRING,
+        default: FRL_FPS_DEFAULT,
+        range: Some(Range { min: FRL_FPS_MIN, max: FRL_FPS_MAX }),
+        values: || vec![("off".into(), FRL_FPS_DISABLED)],
+    },
+    KnownSetting {
+        id: PRERENDERLIMIT_ID,
+        key: "max_prerendered_frames",
+        name: PRERENDERLIMIT_STRING,
+        default: PRERENDERLIMIT_DEFAULT,
+        range: Some(Range { min: PRERENDERLIMIT_MIN, max: PRERENDERLIMIT_MAX }),
+        values: || vec![("application_controlled".into(), PRERENDERLIMIT_APP_CONTROLLED)],
+    },
+    KnownSetting {
+        id: LOW_LATENCY_MODE_ID,
+        key: "low_latency_mode",
+        name: LOW_LATENCY_MODE_STRING,
+        default: LOW_LATENCY_MODE_DEFAULT as _,
+        range: None,
+        values: || named(LowLatencyMode::values()),
+    },
+    KnownSetting {
+        id: PREFERRED_PSTATE_ID,
+        key: "power_management_mode",
+        name: PREFERRED_PSTATE_STRING,
+        default: PREFERRED_PSTATE_DEFAULT as _,
+        range: None,
+        values: || named(PowerManagementMode::values()),
+    },
+    KnownSetting {
+        id: QUALITY_ENHANCEMENTS_ID,
+        key: "texture_filtering_quality",
+        name: QUALITY_ENHANCEMENTS_STRING,
+        default: QUALITY_ENHANCEMENTS_DEFAULT as _,
+        range: None,
+        values: || named(TextureFilteringQuality::values()),
+    },
+    KnownSetting {
+        id: PS_TEXFILTER_NO_NEG_LODBIAS_ID,
+        key: "texture_filtering_negative_lod_bias",
+        name: PS_TEXFILTER_NO_NEG_LODBIAS_STRING,
+        default: PS_TEXFILTER_NO_NEG_LODBIAS_DEFAULT as _,
+        range: None,
+        values: || named(NegativeLodBias::values()),
+    },
+    KnownSetting {
+        id: PS_TEXFILTER_BILINEAR_IN_ANISO_ID,
+        key: "texture_filtering_trilinear_optimization",
+        name: PS_TEXFILTER_BILINEAR_IN_ANISO_STRING,
+        default: PS_TEXFILTER_BILINEAR_IN_ANISO_DEFAULT as _,
+        range: None,
+        values: || named(TrilinearOptimization::values()),
+    },
+    KnownSetting {
+        id: PS_TEXFILTER_ANISO_OPTS2_ID,
+        key: "texture_filtering_anisotropic_sample_optimization",
+        name: PS_TEXFILTER_ANISO_OPTS2_STRING,
+        default: PS_TEXFILTER_ANISO_OPTS2_DEFAULT as _,
+        range: None,
+        values: || named(AnisotropicSampleOptimization::values()),
+    },
+    KnownSetting {
+        id: ANISO_MODE_SELECTOR_ID,
+        key: "anisotropic_filtering_mode",
+        name: ANISO_MODE_SELECTOR_STRING,
+        default: ANISO_MODE_SELECTOR_DEFAULT as _,
+        range: None,
+        values: || named(AnisotropicFilteringMode::values()),
+    },
+    KnownSetting {
+        id: ANISO_MODE_LEVEL_ID,
+        key: "anisotropic_filtering",
+        name: ANISO_MODE_LEVEL_STRING,
+        default: ANISO_MODE_LEVEL_DEFAULT as _,
+        range: None,
+        values: || named(AnisotropicFilteringLevel::values()),
+    },
+    KnownSetting {
+        id: OGL_THREAD_CONTROL_ID,
+        key: "threaded_optimization",
+        name: OGL_THREAD_CONTROL_STRING,
+        default: OGL_THREAD_CONTROL_DEFAULT as _,
+        range: None,
+        values: || named(ThreadedOptimization::values()),
+    },
+    KnownSetting {
+        id: OGL_TRIPLE_BUFFER_ID,
+        key: "triple_buffering",
+        name: OGL_TRIPLE_BUFFER_STRING,
+        default: OGL_TRIPLE_BUFFER_DEFAULT as _,
+        range: None,
+        values: || named(TripleBuffering::values()),
+    },
+    KnownSetting {
+        id: VRR_APP_OVERRIDE_ID,
+        key: "gsync_application_mode",
+        name: VRR_APP_OVERRIDE_STRING,
+        default: VRR_APP_OVERRIDE_DEFAULT as _,
+        range: None,
+        values: || named(GsyncApplicationMode::values()),
+    },
+    KnownSetting {
+        id: VRR_MODE_ID,
+        key: "gsync_global_mode",
+        name: VRR_MODE_STRING,
+        default: VRR_MODE_DEFAULT as _,
+        range: None,
+        values: || named(GsyncGlobalMode::values()),
+    },
+    KnownSetting {
+        id: REFRESH_RATE_OVERRIDE_ID,
+        key: "preferred_refresh_rate",
+        name: REFRESH_RATE_OVERRIDE_STRING,
+        default: REFRESH_RATE_OVERRIDE_DEFAULT as _,
+        range: None,
+        values: || named(PreferredRefreshRate::values()),
+    },
+    KnownSetting {
+        id: FXAA_ENABLE_ID,
+        key: "fxaa",
+        name: FXAA_ENABLE_STRING,
+        default: FXAA_ENABLE_DEFAULT as _,
+        range: None,
+        values: || named(Fxaa::values()),
+    },
+    KnownSetting {
+        id: MAXWELL_B_SAMPLE_INTERLEAVE_ID,
+        key: "mfaa",
+        name: MAXWELL_B_SAMPLE_INTERLEAVE_STRING,
+        default: MAXWELL_B_SAMPLE_INTERLEAVE_DEFAULT as _,
+        range: None,
+        values: || named(Mfaa::values()),
+    },
+    KnownSetting {
+        id: PS_SHADERDISKCACHE_ID,
+        key: "shader_cache",
+        name: PS_SHADERDISKCACHE_STRING,
+        default: PS_SHADERDISKCACHE_DEFAULT as _,
+        range: None,
+        values: || named(ShaderCache::values()),
+    },
+];
+
+impl KnownSetting {
+    /// Looks up a setting by its key or the name the driver knows it by, ignoring case.
+    pub fn find(name: &str) -> Option<&'static Self> {
+        KNOWN_SETTINGS.iter()
+            .find(|setting| setting.key.eq_ignore_ascii_case(name) || setting.name.eq_ignore_ascii_case(name))
+    }
+
+    pub fn from_id(id: u32) -> Option<&'static Self> {
+        KNOWN_SETTINGS.iter().find(|setting| setting.id == id)
+    }
+
+    /// The named values of the setting.
+    pub fn values(&self) -> Vec<(String, u32)> {
+        (self.values)()
+    }
+
+    pub fn value_name(&self, value: u32) -> Option<String> {
+        self.values().into_iter().find(|&(_, v)| v == value).map(|(name, _)| name)
+    }
+
+    /// Parses a named value, or a decimal or `0x` prefixed hexadecimal number within `range`.
+    pub fn parse(&self, value: &str) -> Option<u32> {
+        let named = self.values().into_iter().find(|(name, _)| name.eq_ignore_ascii_case(value));
+        if let Some((_, value)) = named {
+            return Some(value)
+        }
+
+        let value = match value.strip_prefix("0x") {
+            Some(hex) => u32::from_str_radix(hex, 16),
+            None => value.parse(),
+        }.ok()?;
+        self.range.filter(|range| range.min <= value && value <= range.max)
+            .map(|_| value)
+    }
+
+    /// The name of `value` if it has one, or the number.
+    pub fn format(&self, value: u32) -> String {
+        match (self.value_name(value), self.range) {
+            (Some(name), _) => name,
+            (None, Some(..)) => value.to_string(),
+            (None, None) => format!("{:#010x}", value),
+        }
+    }
+}
+
+impl fmt::Display for KnownSetting {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.key)
+    }
+}
+
+/// Asks the driver for the id of a setting by the name it knows it by.
+pub fn setting_id_from_name(name: &str) -> sys::Result<u32> {
+    trace!("drs.setting_id_from_name({:?})", name);
+    let name = unicode_string(name)?;
+    let mut id = 0;
+    sys::status_result(unsafe { drs::NvAPI_DRS_GetSettingIdFromName(&name, &mut id) })
+        .map(|_| id)
+}
+
+/// Asks the driver for the name of a setting.
+pub fn setting_name_from_id(id: u32) -> sys::Result<String> {
+    trace!("drs.setting_name_from_id({:#010x})", id);
+    let mut name = sys::types::unicode_string();
+    sys::status_result(unsafe { drs::NvAPI_DRS_GetSettingNameFromId(id, &mut name) })?;
+    name.convert_raw().map_err(From::from)
+}
+
+/// The ids of all settings the driver recognizes.
+pub fn available_setting_ids() -> sys::Result<Vec<u32>> {
+    trace!("drs.available_setting_ids()");
+    // there's no way to ask for the count, so grow until the driver leaves room to spare
+    let mut ids = vec![0; 1024];
+    loop {
+        let mut count = ids.len() as u32;
+        sys::status_result(unsafe { drs::NvAPI_DRS_EnumAvailableSettingIds(ids.as_mut_ptr(), &mut count) })?;
+        if (count as usize) < ids.len() {
+            ids.truncate(count as usize);
+            return Ok(ids)
+        }
+        ids.resize(ids.len() * 2, 0);
+    }
+}
+
+/// Resolves a configuration key or driver setting name to its id, asking the driver about
+/// settings not known to this crate.
+pub fn setting_id(name: &str) -> sys::Result<u32> {
+    match KnownSetting::find(name) {
+        Some(setting) => Ok(setting.id),
+        None => setting_id_from_name(name),
+    }
+}
@@ -0,0 +1,1020 @@
+//! Decoding of EDID 1.x structures and their CTA-861 extension blocks.
+//!
+//! Nothing here talks to the driver, so EDIDs read from elsewhere can be decoded just
+//! the same. Parts that aren't decoded are kept as raw bytes.
+
+use std::{fmt, error};
+use serde::{Serialize, Deserialize};
+use crate::types::{Kilohertz, Range};
+
+/// The size of the base block, and of each extension block.
+pub const BLOCK_SIZE: usize = 128;
+
+/// The most blocks an EDID can consist of, including the base block.
+pub const MAX_BLOCKS: usize = 256;
+
+/// The fastest pixel clock a detailed timing can express.
+pub const MAX_PIXEL_CLOCK: Kilohertz = Kilohertz(655350);
+
+/// The tag of a CTA-861 extension block.
+pub const CTA_EXTENSION_TAG: u8 = 0x02;
+
+const HEADER: [u8; 8] = [0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00];
+
+/// The OUI of the HDMI Forum vendor-specific data block.
+const HDMI_FORUM_OUI: u32 = 0xc45dd8;
+
+/// Why an EDID couldn't be decoded.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum EdidError {
+    /// There are fewer bytes than the base block announces.
+    Truncated {
+        len: usize,
+        expected: usize,
+    },
+    /// The base block doesn't start with the fixed EDID header.
+    Header,
+    /// The bytes of the block at this index don't sum to zero.
+    Checksum(usize),
+}
+
+impl fmt::Display for EdidError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            EdidError::Truncated { len, expected } => write!(f, "EDID truncated to {} of {} bytes", len, expected),
+            EdidError::Header => write!(f, "missing EDID header"),
+            EdidError::Checksum(block) => write!(f, "EDID block {} has an invalid checksum", block),
+        }
+    }
+}
+
+impl error::Error for EdidError { }
+
+/// Whether the bytes of a block sum to zero, as they must.
+pub fn checksum_valid(block: &[u8]) -> bool {
+    block.iter().fold(0u8, |sum, &b| sum.wrapping_add(b)) == 0
+}
+
+/// A decoded EDID.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Edid {
+    /// The three-letter PNP manufacturer id.
+    pub manufacturer: String,
+    pub product_code: u16,
+    /// The numeric serial number, if one is set.
+    pub serial_number: Option<u32>,
+    /// The week of manufacture, if given.
+    pub week: Option<u8>,
+    /// The year of manufacture, or the model year if `model_year` is set.
+    pub year: u16,
+    pub model_year: bool,
+    /// The EDID version and revision, such as `(1, 4)`.
+    pub version: (u8, u8),
+    /// Whether the video input is digital rather than analog.
+    pub digital: bool,
+    /// The width and height of the screen in centimetres, if known.
+    pub screen_size: Option<(u8, u8)>,
+    /// The display gamma multiplied by 100, if given.
+    pub gamma: Option<u16>,
+    pub established_timings: Vec<Mode>,
+    /// Standard timings, including those listed in descriptors.
+    pub standard_timings: Vec<Mode>,
+    /// Detailed timings from the base block, the first of which is the preferred mode.
+    pub detailed_timings: Vec<DetailedTiming>,
+    pub descriptors: Vec<Descriptor>,
+    pub extensions: Vec<Extension>,
+}
+
+/// A video mode identified only by its resolution and refresh rate.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Mode {
+    pub width: u16,
+    pub height: u16,
+    /// In Hz.
+    pub refresh: u16,
+    pub interlaced: bool,
+}
+
+impl Mode {
+    const fn new(width: u16, height: u16, refresh: u16) -> Self {
+        Mode { width, height, refresh, interlaced: false }
+    }
+}
+
+impl fmt::Display for Mode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}x{}{}@{}", self.width, self.height, if self.interlaced { "i" } else { "" }, self.refresh)
+    }
+}
+
+/// The timings of bytes 0x23 through 0x25, from the most significant bit.
+const ESTABLISHED_TIMINGS: [Mode; 17] = [
+    Mode::new(720, 400, 70), Mode::new(720, 400, 88), Mode::new(640, 480, 60), Mode::new(640, 480, 67),
+    Mode::new(640, 480, 72), Mode::new(640, 480, 75), Mode::new(800, 600, 56), Mode::new(800, 600, 60),
+    Mode::new(800, 600, 72), Mode::new(800, 600, 75), Mode::new(832, 624, 75),
+    Mode { width: 1024, height: 768, refresh: 87, interlaced: true },
+    Mode::new(1024, 768, 60), Mode::new(1024, 768, 70), Mode::new(1024, 768, 75), Mode::new(1280, 1024, 75),
+    Mode::new(1152, 870, 75),
+];
+
+/// An 18-byte detailed timing descriptor.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct DetailedTiming {
+    pub pixel_clock: Kilohertz,
+    pub h_active: u16,
+    pub h_blanking: u16,
+    pub h_sync_offset: u16,
+    pub h_sync_width: u16,
+    pub v_active: u16,
+    pub v_blanking: u16,
+    pub v_sync_offset: u16,
+    pub v_sync_width: u16,
+    /// The width and height of the image in millimetres.
+    pub image_size: (u16, u16),
+    pub h_border: u8,
+    pub v_border: u8,
+    pub interlaced: bool,
+    /// The sync signal definition, bits 4:1 of the flags byte.
+    pub sync: u8,
+}
+
+impl DetailedTiming {
+    /// Decodes a descriptor, which is a display descriptor rather than a timing if its pixel clock is zero.
+    fn parse(d: &[u8]) -> Option<Self> {
+        let pixel_clock = u16::from_le_bytes([d[0], d[1]]);
+        if pixel_clock == 0 {
+            return None
+        }
+
+        let high = |b: u8| b as u16 >> 4;
+        let low = |b: u8| b as u16 & 0xf;
+        Some(DetailedTiming {
+            pixel_clock: Kilohertz(pixel_clock as u32 * 10),
+            h_active: d[2] as u16 | high(d[4]) << 8,
+            h_blanking: d[3] as u16 | low(d[4]) << 8,
+            v_active: d[5] as u16 | high(d[7]) << 8,
+            v_blanking: d[6] as u16 | low(d[7]) << 8,
+            h_sync_offset: d[8] as u16 | (d[11] as u16 >> 6 & 0x3) << 8,
+            h_sync_width: d[9] as u16 | (d[11] as u16 >> 4 & 0x3) << 8,
+            v_sync_offset: high(d[10]) | (d[11] as u16 >> 2 & 0x3) << 4,
+            v_sync_width: low(d[10]) | (d[11] as u16 & 0x3) << 4,
+            image_size: (d[12] as u16 | high(d[14]) << 8, d[13] as u16 | low(d[14]) << 8),
+            h_border: d[15],
+            v_border: d[16],
+            interlaced: d[17] & 0x80 != 0,
+            sync: d[17] >> 1 & 0xf,
+        })
+    }
+
+    /// The refresh rate in mHz, or 0 if the timing has no area.
+    pub fn refresh_millihertz(&self) -> u32 {
+        let total = (self.h_active as u64 + self.h_blanking as u64) * (self.v_active as u64 + self.v_blanking as u64);
+        match total {
+            0 => 0,
+            total => (self.pixel_clock.0 as u64 * 1_000_000 / total) as u32,
+        }
+    }
+
+    pub fn mode(&self) -> Mode {
+        Mode {
+            width: self.h_active,
+            height: self.v_active,
+            refresh: ((self.refresh_millihertz() + 500) / 1000) as u16,
+            interlaced: self.interlaced,
+        }
+    }
+
+    /// A progressive timing for `mode` with CVT reduced blanking, version 2.
+    ///
+    /// The pixel clock is rounded to the 10 kHz an EDID can express.
+    pub fn reduced_blanking(mode: Mode) -> Self {
+        const H_BLANK: u16 = 80;
+        const V_SYNC: u16 = 8;
+        const V_BACK_PORCH: u16 = 6;
+        const MIN_V_BLANK_US: f64 = 460.0;
+
+        let refresh = mode.refresh.max(1) as f64;
+        let h_period = (1_000_000.0 / refresh - MIN_V_BLANK_US) / mode.height.max(1) as f64;
+        let v_blanking = ((MIN_V_BLANK_US / h_period) as u16 + 1).max(1 + V_SYNC + V_BACK_PORCH);
+        let total = (mode.width as f64 + H_BLANK as f64) * (mode.height as f64 + v_blanking as f64);
+        DetailedTiming {
+            pixel_clock: Kilohertz(((refresh * total / 10_000.0).round() * 10.0) as u32),
+            h_active: mode.width,
+            h_blanking: H_BLANK,
+            h_sync_offset: 8,
+            h_sync_width: 32,
+            v_active: mode.height,
+            v_blanking,
+            v_sync_offset: v_blanking - V_SYNC - V_BACK_PORCH,
+            v_sync_width: V_SYNC,
+            image_size: (0, 0),
+            h_border: 0,
+            v_border: 0,
+            interlaced: false,
+            // digital separate sync, +hsync -vsync
+            sync: 0xd,
+        }
+    }
+
+    /// Encodes the timing as an 18-byte descriptor.
+    ///
+    /// A pixel clock above `MAX_PIXEL_CLOCK` is clamped to it.
+    pub fn to_bytes(&self) -> [u8; 18] {
+        let split = |high: u16, low: u16| ((high >> 8) as u8 & 0xf) << 4 | ((low >> 8) as u8 & 0xf);
+        let clock = (self.pixel_clock.0 / 10).min(u16::MAX as u32) as u16;
+        let mut d = [0u8; 18];
+        d[..2].copy_from_slice(&clock.to_le_bytes());
+        d[2] = self.h_active as u8;
+        d[3] = self.h_blanking as u8;
+        d[4] = split(self.h_active, self.h_blanking);
+        d[5] = self.v_active as u8;
+        d[6] = self.v_blanking as u8;
+        d[7] = split(self.v_active, self.v_blanking);
+        d[8] = self.h_sync_offset as u8;
+        d[9] = self.h_sync_width as u8;
+        d[10] = (self.v_sync_offset as u8 & 0xf) << 4 | (self.v_sync_width as u8 & 0xf);
+        d[11] = ((self.h_sync_offset >> 8) as u8 & 0x3) << 6 | ((self.h_sync_width >> 8) as u8 & 0x3) << 4
+            | ((self.v_sync_offset >> 4) as u8 & 0x3) << 2 | ((self.v_sync_width >> 4) as u8 & 0x3);
+        d[12] = self.image_size.0 as u8;
+        d[13] = self.image_size.1 as u8;
+        d[14] = split(self.image_size.0, self.image_size.1);
+        d[15] = self.h_border;
+        d[16] = self.v_border;
+        d[17] = if self.interlaced { 0x80 } else { 0 } | (self.sync & 0xf) << 1;
+        d
+    }
+}
+
+/// An 18-byte descriptor that isn't a detailed timing.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Descriptor {
+    /// Tag 0xFC.
+    ProductName(String),
+    /// Tag 0xFF.
+    SerialNumber(String),
+    /// Tag 0xFE.
+    Text(String),
+    /// Tag 0xFD.
+    RangeLimits(RangeLimits),
+    /// Any other tag, along with the 13 bytes of payload.
+    Other {
+        tag: u8,
+        data: Vec<u8>,
+    },
+}
+
+impl Descriptor {
+    fn parse(d: &[u8]) -> Self {
+        let (tag, data) = (d[3], &d[5..18]);
+        match tag {
+            0xfc => Descriptor::ProductName(descriptor_text(data)),
+            0xff => Descriptor::SerialNumber(descriptor_text(data)),
+            0xfe => Descriptor::Text(descriptor_text(data)),
+            0xfd => Descriptor::RangeLimits(RangeLimits::parse(d[4], data)),
+            tag => Descriptor::Other {
+                tag,
+                data: data.to_vec(),
+            },
+        }
+    }
+}
+
+/// Text ends at a line feed and is padded with spaces.
+fn descriptor_text(data: &[u8]) -> String {
+    let text = data.split(|&b| b == b'\n').next().unwrap_or_default();
+    String::from_utf8_lossy(text).trim_end().to_owned()
+}
+
+/// The display range limits descriptor.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct RangeLimits {
+    /// In Hz.
+    pub vertical: Range<u16>,
+    /// In kHz.
+    pub horizontal: Range<u16>,
+    /// In MHz, if given.
+    pub max_pixel_clock: Option<u16>,
+}
+
+impl RangeLimits {
+    fn parse(offsets: u8, data: &[u8]) -> Self {
+        // EDID 1.4 adds 255 to a rate when its offset bit is set
+        let rate = |value: u8, bit: u8| value as u16 + if offsets & (1 << bit) != 0 { 255 } else { 0 };
+        RangeLimits {
+            vertical: Range { min: rate(data[0], 0), max: rate(data[1], 1) },
+            horizontal: Range { min: rate(data[2], 2), max: rate(data[3], 3) },
+            max_pixel_clock: match data[4] {
+                0 => None,
+                clock => Some(clock as u16 * 10),
+            },
+        }
+    }
+}
+
+/// An extension block.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Extension {
+    Cta(CtaExtension),
+    /// An extension that isn't decoded, as the whole raw block.
+    Other(Vec<u8>),
+}
+
+/// A CTA-861 extension block.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CtaExtension {
+    pub revision: u8,
+    pub underscan: bool,
+    pub basic_audio: bool,
+    pub ycbcr444: bool,
+    pub ycbcr422: bool,
+    /// How many of the detailed timings are native formats.
+    pub native_timings: u8,
+    pub video: Vec<ShortVideoDescriptor>,
+    pub audio: Vec<ShortAudioDescriptor>,
+    /// The speaker allocation payload, with the first byte in the lowest bits.
+    pub speaker_allocation: Option<u32>,
+    pub hdr_static_metadata: Option<HdrStaticMetadata>,
+    /// The refresh rates in Hz supported by variable refresh, from the HDMI Forum vendor block.
+    pub vrr: Option<Range<u16>>,
+    pub vendor_blocks: Vec<VendorBlock>,
+    /// Data blocks that aren't otherwise decoded.
+    pub other_blocks: Vec<DataBlock>,
+    pub detailed_timings: Vec<DetailedTiming>,
+}
+
+impl CtaExtension {
+    fn parse(block: &[u8]) -> Self {
+        let timings_offset = (block[2] as usize).min(BLOCK_SIZE - 1);
+        let mut cta = CtaExtension {
+            revision: block[1],
+            underscan: block[3] & 0x80 != 0,
+            basic_audio: block[3] & 0x40 != 0,
+            ycbcr444: block[3] & 0x20 != 0,
+            ycbcr422: block[3] & 0x10 != 0,
+            native_timings: block[3] & 0xf,
+            .. Default::default()
+        };
+
+        // an offset of zero means there are neither data blocks nor timings
+        if timings_offset < 4 {
+            return cta
+        }
+
+        let mut blocks = &block[4..timings_offset];
+        while let Some((&header, rest)) = blocks.split_first() {
+            let len = (header & 0x1f) as usize;
+            let Some(payload) = rest.get(..len) else { break };
+            cta.parse_data_block(header >> 5, payload);
+            blocks = &rest[len..];
+        }
+
+        cta.detailed_timings = block[timings_offset..BLOCK_SIZE - 1].chunks_exact(18)
+            .map_while(DetailedTiming::parse)
+            .collect();
+        cta
+    }
+
+    fn parse_data_block(&mut self, tag: u8, payload: &[u8]) {
+        match (tag, payload.first()) {
+            (1, _) => self.audio.extend(payload.chunks_exact(3).map(ShortAudioDescriptor::parse)),
+            (2, _) => self.video.extend(payload.iter().map(|&b| ShortVideoDescriptor::parse(b))),
+            (3, _) if payload.len() >= 3 => {
+                let oui = u32::from_le_bytes([payload[0], payload[1], payload[2], 0]);
+                // the HF-VSDB's VRRmin and VRRmax follow the version and four bytes of rates and flags
+                if oui == HDMI_FORUM_OUI && payload.len() >= 10 && payload[8] & 0x3f != 0 {
+                    self.vrr = Some(Range {
+                        min: (payload[8] & 0x3f) as u16,
+                        max: (payload[8] as u16 & 0xc0) << 2 | payload[9] as u16,
+                    });
+                }
+                self.vendor_blocks.push(VendorBlock {
+                    oui,
+                    data: payload[3..].to_vec(),
+                });
+            },
+            (4, _) if payload.len() >= 3 => self.speaker_allocation = Some(u32::from_le_bytes([payload[0], payload[1], payload[2], 0])),
+            (7, Some(&6)) if payload.len() >= 3 => self.hdr_static_metadata = Some(HdrStaticMetadata::parse(&payload[1..])),
+            (tag, extended_tag) => self.other_blocks.push(DataBlock {
+                tag,
+                extended_tag: match tag {
+                    7 => extended_tag.cloned(),
+                    _ => None,
+                },
+                data: match tag {
+                    7 => payload.get(1..).unwrap_or_default().to_vec(),
+                    _ => payload.to_vec(),
+                },
+            }),
+        }
+    }
+}
+
+/// A CTA data block that isn't otherwise decoded.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataBlock {
+    pub tag: u8,
+    /// The extended tag of tag 7 blocks, which isn't included in `data`.
+    pub extended_tag: Option<u8>,
+    pub data: Vec<u8>,
+}
+
+/// A vendor-specific data block.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VendorBlock {
+    pub oui: u32,
+    /// The payload following the OUI.
+    pub data: Vec<u8>,
+}
+
+/// An entry of the video data block.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ShortVideoDescriptor {
+    /// The CTA-861 video identification code.
+    pub vic: u8,
+    pub native: bool,
+}
+
+impl ShortVideoDescriptor {
+    fn parse(b: u8) -> Self {
+        // only the first 64 codes can be flagged as native
+        match b {
+            129..=192 => ShortVideoDescriptor { vic: b & 0x7f, native: true },
+            vic => ShortVideoDescriptor { vic, native: false },
+        }
+    }
+}
+
+/// The audio format code of a short audio descriptor.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum AudioFormat {
+    Lpcm,
+    Ac3,
+    Mpeg1,
+    Mp3,
+    Mpeg2,
+    AacLc,
+    Dts,
+    Atrac,
+    OneBitAudio,
+    EnhancedAc3,
+    DtsHd,
+    Mat,
+    Dst,
+    WmaPro,
+    /// A reserved code, or one that refers to an extended format.
+    Other(u8),
+}
+
+impl AudioFormat {
+    fn from_code(code: u8) -> Self {
+        match code {
+            1 => AudioFormat::Lpcm,
+            2 => AudioFormat::Ac3,
+            3 => AudioFormat::Mpeg1,
+            4 => AudioFormat::Mp3,
+            5 => AudioFormat::Mpeg2,
+            6 => AudioFormat::AacLc,
+            7 => AudioFormat::Dts,
+            8 => AudioFormat::Atrac,
+            9 => AudioFormat::OneBitAudio,
+            10 => AudioFormat::EnhancedAc3,
+            11 => AudioFormat::DtsHd,
+            12 => AudioFormat::Mat,
+            13 => AudioFormat::Dst,
+            14 => AudioFormat::WmaPro,
+            code => AudioFormat::Other(code),
+        }
+    }
+}
+
+/// An entry of the audio data block.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ShortAudioDescriptor {
+    pub format: AudioFormat,
+    pub channels: u8,
+    /// The raw sample rate bits; see `sample_rates`.
+    pub sample_rates: u8,
+    /// The third byte, whose meaning depends on `format`; see `bit_depths`.
+    pub detail: u8,
+}
+
+impl ShortAudioDescriptor {
+    fn parse(sad: &[u8]) -> Self {
+        ShortAudioDescriptor {
+            format: AudioFormat::from_code(sad[0] >> 3 & 0xf),
+            channels: (sad[0] & 0x7) + 1,
+            sample_rates: sad[1] & 0x7f,
+            detail: sad[2],
+        }
+    }
+
+    /// The supported sample rates in Hz.
+    pub fn sample_rates(&self) -> Vec<u32> {
+        [32000, 44100, 48000, 88200, 96000, 176400, 192000].iter().enumerate()
+            .filter(|&(bit, _)| self.sample_rates & (1 << bit) != 0)
+            .map(|(_, &rate)| rate)
+            .collect()
+    }
+
+    /// The supported sample sizes in bits, which are only given for LPCM.
+    pub fn bit_depths(&self) -> Vec<u8> {
+        match self.format {
+            AudioFormat::Lpcm => [16, 20, 24].iter().enumerate()
+                .filter(|&(bit, _)| self.detail & (1 << bit) != 0)
+                .map(|(_, &depth)| depth)
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// The HDR static metadata data block.
+///
+/// Luminance values are kept as the coded values; the `*_nits` methods convert them.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct HdrStaticMetadata {
+    /// Traditional gamma, SDR luminance range.
+    pub sdr: bool,
+    /// Traditional gamma, HDR luminance range.
+    pub hdr: bool,
+    /// SMPTE ST 2084.
+    pub pq: bool,
+    /// Hybrid log-gamma.
+    pub hlg: bool,
+    /// The supported static metadata descriptor types, as bits.
+    pub metadata_types: u8,
+    pub max_luminance: Option<u8>,
+    pub max_frame_average_luminance: Option<u8>,
+    pub min_luminance: Option<u8>,
+}
+
+impl HdrStaticMetadata {
+    fn parse(data: &[u8]) -> Self {
+        HdrStaticMetadata {
+            sdr: data[0] & 0x1 != 0,
+            hdr: data[0] & 0x2 != 0,
+            pq: data[0] & 0x4 != 0,
+            hlg: data[0] & 0x8 != 0,
+            metadata_types: data[1],
+            max_luminance: data.get(2).cloned(),
+            max_frame_average_luminance: data.get(3).cloned(),
+            min_luminance: data.get(4).cloned(),
+        }
+    }
+
+    fn luminance(cv: u8) -> f64 {
+        50.0 * 2f64.powf(cv as f64 / 32.0)
+    }
+
+    /// The desired content max luminance in cd/m².
+    pub fn max_luminance_nits(&self) -> Option<f64> {
+        self.max_luminance.map(Self::luminance)
+    }
+
+    /// The desired content max frame-average luminance in cd/m².
+    pub fn max_frame_average_luminance_nits(&self) -> Option<f64> {
+        self.max_frame_average_luminance.map(Self::luminance)
+    }
+
+    /// The desired content min luminance in cd/m², which is relative to the max.
+    pub fn min_luminance_nits(&self) -> Option<f64> {
+        let max = self.max_luminance_nits()?;
+        self.min_luminance.map(|cv| max * (cv as f64 / 255.0).powi(2) / 100.0)
+    }
+}
+
+impl Edid {
+    /// Decodes the base block and the extensions it announces, validating their checksums.
+    ///
+    /// Any bytes past the announced extensions are ignored.
+    pub fn parse(data: &[u8]) -> Result<Self, EdidError> {
+        if data.len() < BLOCK_SIZE {
+            return Err(EdidError::Truncated { len: data.len(), expected: BLOCK_SIZE })
+        }
+        if data[..HEADER.len()] != HEADER {
+            return Err(EdidError::Header)
+        }
+        let expected = (1 + data[126] as usize) * BLOCK_SIZE;
+        if data.len() < expected {
+            return Err(EdidError::Truncated { len: data.len(), expected })
+        }
+        let blocks: Vec<_> = data[..expected].chunks_exact(BLOCK_SIZE).collect();
+        if let Some(block) = blocks.iter().position(|block| !checksum_valid(block)) {
+            return Err(EdidError::Checksum(block))
+        }
+
+        let base = blocks[0];
+        let version = (base[18], base[19]);
+        let manufacturer = u16::from_be_bytes([base[8], base[9]]);
+        let mut edid = Edid {
+            manufacturer: [10, 5, 0].iter().map(|&shift| match (manufacturer >> shift & 0x1f) as u8 {
+                letter @ 1..=26 => (b'A' + letter - 1) as char,
+                _ => '?',
+            }).collect(),
+            product_code: u16::from_le_bytes([base[10], base[11]]),
+            serial_number: match u32::from_le_bytes([base[12], base[13], base[14], base[15]]) {
+                0 => None,
+                serial => Some(serial),
+            },
+            week: match base[16] {
+                0 | 0xff => None,
+                week => Some(week),
+            },
+            year: 1990 + base[17] as u16,
+            model_year: base[16] == 0xff,
+            version,
+            digital: base[20] & 0x80 != 0,
+            screen_size: match (base[21], base[22]) {
+                (0, _) | (_, 0) => None,
+                size => Some(size),
+            },
+            gamma: match base[23] {
+                0xff => None,
+                gamma => Some(gamma as u16 + 100),
+            },
+            established_timings: ESTABLISHED_TIMINGS.iter().enumerate()
+                .filter(|&(bit, _)| base[0x23 + bit / 8] & (0x80 >> (bit % 8)) != 0)
+                .map(|(_, &mode)| mode)
+                .collect(),
+            standard_timings: base[0x26..0x36].chunks_exact(2)
+                .filter_map(|t| standard_timing(t, version))
+                .collect(),
+            detailed_timings: Vec::new(),
+            descriptors: Vec::new(),
+            extensions: Vec::new(),
+        };
+
+        for d in base[0x36..0x7e].chunks_exact(18) {
+            match DetailedTiming::parse(d) {
+                Some(timing) => edid.detailed_timings.push(timing),
+                // additional standard timings
+                None if d[3] == 0xfa => edid.standard_timings.extend(d[5..17].chunks_exact(2).filter_map(|t| standard_timing(t, version))),
+                // dummy descriptors
+                None if d[3] == 0x10 => (),
+                None => edid.descriptors.push(Descriptor::parse(d)),
+            }
+        }
+
+        edid.extensions = blocks[1..].iter().map(|&block| match block[0] {
+            CTA_EXTENSION_TAG => Extension::Cta(CtaExtension::parse(block)),
+            _ => Extension::Other(block.to_vec()),
+        }).collect();
+
+        Ok(edid)
+    }
+
+    /// The product name from the display descriptors.
+    pub fn name(&self) -> Option<&str> {
+        self.descriptors.iter().find_map(|d| match *d {
+            Descriptor::ProductName(ref name) => Some(&name[..]),
+            _ => None,
+        })
+    }
+
+    /// The serial number string from the display descriptors.
+    pub fn serial_string(&self) -> Option<&str> {
+        self.descriptors.iter().find_map(|d| match *d {
+            Descriptor::SerialNumber(ref serial) => Some(&serial[..]),
+            _ => None,
+        })
+    }
+
+    pub fn range_limits(&self) -> Option<&RangeLimits> {
+        self.descriptors.iter().find_map(|d| match *d {
+            Descriptor::RangeLimits(ref limits) => Some(limits),
+            _ => None,
+        })
+    }
+
+    pub fn cta_extensions(&self) -> impl Iterator<Item=&CtaExtension> {
+        self.extensions.iter().filter_map(|e| match *e {
+            Extension::Cta(ref cta) => Some(cta),
+            Extension::Other(..) => None,
+        })
+    }
+
+    /// The HDR static metadata of the first CTA extension that has any.
+    pub fn hdr_static_metadata(&self) -> Option<&HdrStaticMetadata> {
+        self.cta_extensions().find_map(|cta| cta.hdr_static_metadata.as_ref())
+    }
+
+    /// The variable refresh range advertised by a CTA extension.
+    pub fn vrr_range(&self) -> Option<Range<u16>> {
+        self.cta_extensions().find_map(|cta| cta.vrr)
+    }
+
+    /// The short audio descriptors of all CTA extensions.
+    pub fn audio(&self) -> impl Iterator<Item=&ShortAudioDescriptor> {
+        self.cta_extensions().flat_map(|cta| cta.audio.iter())
+    }
+}
+
+fn standard_timing(t: &[u8], version: (u8, u8)) -> Option<Mode> {
+    if matches!((t[0], t[1]), (0x01, 0x01) | (0x00, 0x00) | (0x20, 0x20)) {
+        return None
+    }
+
+    let width = (t[0] as u16 + 31) * 8;
+    Some(Mode::new(width, standard_height(width, t[1] >> 6, version), (t[1] & 0x3f) as u16 + 60))
+}
+
+/// The height a standard timing's aspect ratio code gives `width`.
+fn standard_height(width: u16, aspect: u8, version: (u8, u8)) -> u16 {
+    match aspect {
+        0 if version < (1, 3) => width,
+        0 => width * 10 / 16,
+        1 => width * 3 / 4,
+        2 => width * 4 / 5,
+        _ => width * 9 / 16,
+    }
+}
+
+/// Generates an EDID from a description of a display, for example to give a headless GPU
+/// a specific monitor with `PhysicalGpu::set_edid`.
+///
+/// The result is an EDID 1.4 base block, followed by CTA-861 extensions when the modes,
+/// refresh range or HDR support don't fit into it. Every block has a valid checksum.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EdidBuilder {
+    name: String,
+    manufacturer: String,
+    product_code: u16,
+    serial_number: Option<u32>,
+    serial_string: Option<String>,
+    year: u16,
+    screen_size: Option<(u8, u8)>,
+    preferred: DetailedTiming,
+    modes: Vec<Mode>,
+    refresh_range: Option<Range<u16>>,
+    hdr: Option<HdrStaticMetadata>,
+}
+
+impl EdidBuilder {
+    /// A display called `name`, whose preferred mode uses CVT reduced blanking.
+    ///
+    /// Names longer than 13 bytes are cut short. The preferred mode should not need a
+    /// pixel clock above `MAX_PIXEL_CLOCK`.
+    pub fn new(name: &str, preferred: Mode) -> Self {
+        EdidBuilder {
+            name: name.into(),
+            manufacturer: "NVD".into(),
+            product_code: 0,
+            serial_number: None,
+            serial_string: None,
+            year: 2020,
+            screen_size: None,
+            preferred: DetailedTiming::reduced_blanking(preferred),
+            modes: Vec::new(),
+            refresh_range: None,
+            hdr: None,
+        }
+    }
+
+    /// The three-letter PNP id, `NVD` by default. Anything but `A` to `Z` encodes as `?`.
+    pub fn manufacturer(mut self, manufacturer: &str) -> Self {
+        self.manufacturer = manufacturer.into();
+        self
+    }
+
+    pub fn product_code(mut self, product_code: u16) -> Self {
+        self.product_code = product_code;
+        self
+    }
+
+    pub fn serial_number(mut self, serial_number: u32) -> Self {
+        self.serial_number = Some(serial_number);
+        self
+    }
+
+    /// Adds a serial number descriptor, cut short to 13 bytes.
+    pub fn serial_string(mut self, serial: &str) -> Self {
+        self.serial_string = Some(serial.into());
+        self
+    }
+
+    /// The year of manufacture, 2020 by default.
+    pub fn year(mut self, year: u16) -> Self {
+        self.year = year;
+        self
+    }
+
+    /// The width and height of the screen in centimetres.
+    pub fn screen_size(mut self, width: u8, height: u8) -> Self {
+        self.screen_size = Some((width, height));
+        self
+    }
+
+    /// Replaces the generated preferred timing with an exact one.
+    pub fn preferred_timing(mut self, timing: DetailedTiming) -> Self {
+        self.preferred = timing;
+        self
+    }
+
+    /// Adds a supported mode, listed as an established or standard timing where
+    /// possible and as a reduced blanking detailed timing otherwise.
+    ///
+    /// Modes whose timing needs a pixel clock above `MAX_PIXEL_CLOCK` can't be described
+    /// by an EDID and are left out.
+    pub fn mode(mut self, mode: Mode) -> Self {
+        self.modes.push(mode);
+        self
+    }
+
+    /// The supported refresh rates in Hz, advertised as range limits and, when the
+    /// minimum is at most 63 Hz, as the HDMI Forum VRR range.
+    pub fn refresh_range(mut self, range: Range<u16>) -> Self {
+        self.refresh_range = Some(range);
+        self
+    }
+
+    /// Advertises HDR support, which also raises the colour depth to 10 bits.
+    pub fn hdr(mut self, hdr: HdrStaticMetadata) -> Self {
+        self.hdr = Some(hdr);
+        self
+    }
+
+    pub fn build(&self) -> Vec<u8> {
+        let version = (1, 4);
+        let mut established = 0u32;
+        let mut standard = Vec::new();
+        let mut detailed = Vec::new();
+        for &mode in self.modes.iter().filter(|&&mode| mode != self.preferred.mode()) {
+            if let Some(bit) = ESTABLISHED_TIMINGS.iter().position(|&m| m == mode) {
+                established |= 0x800000 >> bit;
+            } else if let (Some(timing), true) = (standard_timing_bytes(mode, version), standard.len() < 8) {
+                standard.push(timing);
+            } else {
+                detailed.push(DetailedTiming::reduced_blanking(mode));
+            }
+        }
+        detailed.retain(|timing| timing.pixel_clock <= MAX_PIXEL_CLOCK);
+
+        let mut base = [0u8; BLOCK_SIZE];
+        base[..8].copy_from_slice(&HEADER);
+        let manufacturer = self.manufacturer.bytes().chain(std::iter::repeat(0)).take(3)
+            .map(|c| match c {
+                b'A'..=b'Z' => (c - b'A' + 1) as u16,
+                _ => 0,
+            }).fold(0u16, |id, letter| id << 5 | letter);
+        base[8..10].copy_from_slice(&manufacturer.to_be_bytes());
+        base[10..12].copy_from_slice(&self.product_code.to_le_bytes());
+        base[12..16].copy_from_slice(&self.serial_number.unwrap_or(0).to_le_bytes());
+        base[17] = self.year.saturating_sub(1990).min(255) as u8;
+        base[18] = version.0;
+        base[19] = version.1;
+        // digital DisplayPort input, with 8 or 10 bits per colour
+        base[20] = if self.hdr.is_some() { 0xb5 } else { 0xa5 };
+        let (width, height) = self.screen_size.unwrap_or_default();
+        base[21] = width;
+        base[22] = height;
+        // gamma 2.2
+        base[23] = 120;
+        // sRGB default colour space, preferred timing is native, continuous frequency with range limits
+        base[24] = 0x06 | if self.refresh_range.is_some() { 0x01 } else { 0 };
+        // sRGB chromaticity
+        base[25..35].copy_from_slice(&[0xee, 0x91, 0xa3, 0x54, 0x4c, 0x99, 0x26, 0x0f, 0x50, 0x54]);
+        base[35..38].copy_from_slice(&established.to_be_bytes()[1..]);
+        for (slot, timing) in base[0x26..0x36].chunks_exact_mut(2).zip(standard.iter().map(Some).chain(std::iter::repeat(None))) {
+            slot.copy_from_slice(timing.unwrap_or(&[0x01, 0x01]));
+        }
+
+        let mut preferred = self.preferred;
+        if preferred.image_size == (0, 0) {
+            preferred.image_size = (width as u16 * 10, height as u16 * 10);
+        }
+        let mut descriptors = vec![preferred.to_bytes()];
+        if let Some(range) = self.refresh_range {
+            let timings: Vec<_> = self.modes.iter().map(|&mode| DetailedTiming::reduced_blanking(mode))
+                .chain(Some(self.preferred))
+                .collect();
+            descriptors.push(range_limits_descriptor(range, &timings));
+        }
+        descriptors.push(text_descriptor(0xfc, &self.name));
+        if let Some(ref serial) = self.serial_string {
+            descriptors.push(text_descriptor(0xff, serial));
+        }
+        // the rest are dummy descriptors
+        descriptors.resize(4, [0, 0, 0, 0x10, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        for (slot, descriptor) in base[0x36..0x7e].chunks_exact_mut(18).zip(&descriptors) {
+            slot.copy_from_slice(descriptor);
+        }
+
+        let extensions = self.cta_blocks(detailed);
+        base[126] = extensions.len() as u8;
+        let mut edid = base.to_vec();
+        for block in extensions {
+            edid.extend_from_slice(&block);
+        }
+        for block in edid.chunks_exact_mut(BLOCK_SIZE) {
+            let sum = block[..BLOCK_SIZE - 1].iter().fold(0u8, |sum, &b| sum.wrapping_add(b));
+            block[BLOCK_SIZE - 1] = 0u8.wrapping_sub(sum);
+        }
+        edid
+    }
+
+    /// CTA extensions holding the data blocks and the timings that didn't fit into the
+    /// base block, without checksums.
+    fn cta_blocks(&self, timings: Vec<DetailedTiming>) -> Vec<[u8; BLOCK_SIZE]> {
+        let mut data = Vec::new();
+        if let Some(range) = self.refresh_range.filter(|range| (1..=0x3f).contains(&range.min) && range.max <= 0x3ff) {
+            data.push(3 << 5 | 10);
+            data.extend_from_slice(&HDMI_FORUM_OUI.to_le_bytes()[..3]);
+            // version 1, and no TMDS, FRL or other features
+            data.extend_from_slice(&[1, 0, 0, 0, 0]);
+            data.push(range.min as u8 | ((range.max >> 8) as u8) << 6);
+            data.push(range.max as u8);
+        }
+        if let Some(hdr) = self.hdr {
+            let luminance = [hdr.max_luminance, hdr.max_frame_average_luminance, hdr.min_luminance];
+            let luminance = &luminance[..luminance.iter().rposition(Option::is_some).map_or(0, |last| last + 1)];
+            data.push(7 << 5 | (3 + luminance.len()) as u8);
+            data.push(6);
+            data.push(hdr.sdr as u8 | (hdr.hdr as u8) << 1 | (hdr.pq as u8) << 2 | (hdr.hlg as u8) << 3);
+            data.push(hdr.metadata_types);
+            data.extend(luminance.iter().map(|cv| cv.unwrap_or(0)));
+        }
+        if data.is_empty() && timings.is_empty() {
+            return Vec::new()
+        }
+
+        let mut blocks = Vec::new();
+        let mut timings = timings.iter().peekable();
+        while blocks.is_empty() || timings.peek().is_some() {
+            let mut block = [0u8; BLOCK_SIZE];
+            block[0] = CTA_EXTENSION_TAG;
+            block[1] = 3;
+            let offset = 4 + if blocks.is_empty() { data.len() } else { 0 };
+            block[2] = offset as u8;
+            block[4..offset].copy_from_slice(&data[..offset - 4]);
+            for slot in block[offset..BLOCK_SIZE - 1].chunks_exact_mut(18) {
+                match timings.next() {
+                    Some(timing) => slot.copy_from_slice(&timing.to_bytes()),
+                    None => break,
+                }
+            }
+            blocks.push(block);
+            if blocks.len() == MAX_BLOCKS - 1 {
+                break
+            }
+        }
+        blocks
+    }
+}
+
+fn standard_timing_bytes(mode: Mode, version: (u8, u8)) -> Option<[u8; 2]> {
+    if mode.interlaced || !mode.width.is_multiple_of(8) || !(256..=2288).contains(&mode.width) || !(60..=123).contains(&mode.refresh) {
+        return None
+    }
+    (0..4).find(|&aspect| standard_height(mode.width, aspect, version) == mode.height)
+        .map(|aspect| [(mode.width / 8 - 31) as u8, aspect << 6 | (mode.refresh - 60) as u8])
+}
+
+fn text_descriptor(tag: u8, text: &str) -> [u8; 18] {
+    let mut d = [0u8; 18];
+    d[3] = tag;
+    let text = &text.as_bytes()[..text.len().min(13)];
+    d[5..5 + text.len()].copy_from_slice(text);
+    if text.len() < 13 {
+        d[5 + text.len()] = b'\n';
+        for b in &mut d[6 + text.len()..] {
+            *b = b' ';
+        }
+    }
+    d
+}
+
+/// Range limits covering `vertical` and the line rates and pixel clocks of `timings`.
+fn range_limits_descriptor(vertical: Range<u16>, timings: &[DetailedTiming]) -> [u8; 18] {
+    let line_rate = |t: &DetailedTiming| t.pixel_clock.0 as f64 / (t.h_active + t.h_blanking).max(1) as f64;
+    let h_min = timings.iter().map(line_rate).fold(f64::MAX, f64::min).floor().clamp(1.0, 255.0) as u8;
+    let h_max = timings.iter().map(line_rate).fold(0.0, f64::max).ceil().clamp(1.0, 255.0) as u8;
+    let max_clock = timings.iter().map(|t| t.pixel_clock.0).max().unwrap_or(0);
+    let rate = |hz: u16| if hz > 255 { ((hz - 255).min(255) as u8, true) } else { (hz as u8, false) };
+    let ((v_min, v_min_offset), (v_max, v_max_offset)) = (rate(vertical.min), rate(vertical.max));
+
+    let mut d = [0u8; 18];
+    d[3] = 0xfd;
+    d[4] = v_min_offset as u8 | (v_max_offset as u8) << 1;
+    d[5..9].copy_from_slice(&[v_min, v_max, h_min, h_max]);
+    d[9] = max_clock.div_ceil(10_000).clamp(1, 255) as u8;
+    // range limits only, with no secondary timing formula
+    d[10] = 0x01;
+    d[11] = b'\n';
+    for b in &mut d[12..] {
+        *b = b' ';
+    }
+    d
+}
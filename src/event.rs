@@ -0,0 +1,133 @@
+use std::cell::Cell;
+use std::os::raw::c_void;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::sync::atomic::{AtomicU64, Ordering};
+use log::trace;
+use crate::sys::{self, event};
+use crate::sys::handles::NvEventHandle;
+use crate::sys::event::EventType;
+use crate::session::Nvapi;
+
+pub use sys::event::QSyncEvent as SyncEvent;
+
+/// A subscriber's closure, which is taken out on drop so that it's never called again.
+type Callback = Mutex<Option<Box<dyn FnMut(SyncEvent) + Send>>>;
+
+/// The driver hands out one handle per process, and unregistering it removes every
+/// callback, so a single registration is shared by all subscriptions.
+///
+/// Held while registering or unregistering, but never from the driver's thread.
+static REGISTRATION: Mutex<Option<usize>> = Mutex::new(None);
+static CALLBACKS: Mutex<Vec<(u64, Arc<Callback>)>> = Mutex::new(Vec::new());
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+thread_local! {
+    /// The subscription whose closure this thread is running, if it's the driver's thread.
+    static DISPATCHING: Cell<Option<u64>> = const { Cell::new(None) };
+}
+
+fn lock<T>(m: &Mutex<T>) -> MutexGuard<'_, T> {
+    m.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// A closure registered for driver events, which is unregistered on drop.
+///
+/// The driver calls it from a thread of its own, one event at a time, and not at all
+/// once the subscription has been dropped, which the closure may do itself to handle a
+/// single event. The driver only reports sync events, so display hotplugs still have to
+/// be noticed by polling.
+#[derive(Debug)]
+pub struct EventSubscription {
+    id: u64,
+    handle: NvEventHandle,
+    session: Option<Nvapi>,
+}
+
+unsafe impl Send for EventSubscription { }
+
+impl Drop for EventSubscription {
+    fn drop(&mut self) {
+        trace!("event.unregister()");
+        let dispatching = DISPATCHING.with(Cell::get);
+        let mut registration = lock(&REGISTRATION);
+        let (callback, last) = {
+            let mut callbacks = lock(&CALLBACKS);
+            let index = callbacks.iter().position(|&(id, _)| id == self.id);
+            (index.map(|index| callbacks.remove(index).1), callbacks.is_empty())
+        };
+
+        // unregistering waits for calls in progress, which may be waiting on `CALLBACKS`,
+        // and can't return at all while this thread is in the middle of one; the
+        // registration is then left for the next subscription to reuse
+        if last && dispatching.is_none() {
+            if let Some(handle) = registration.take() {
+                // the closures live on in `CALLBACKS`, so there's nothing to leak if this fails
+                unsafe { event::NvAPI_Event_UnregisterCallback(NvEventHandle::from_raw(handle as *const _)) };
+            }
+        }
+        drop(registration);
+
+        // waits for a call in progress on the driver's thread, unless it's this one
+        match callback {
+            Some(callback) if dispatching != Some(self.id) => { lock(&callback).take(); },
+            _ => (),
+        }
+    }
+}
+
+extern "C" fn sync_event(data: event::NV_QSYNC_EVENT_DATA, _param: *mut c_void) {
+    let event = SyncEvent::from_raw_lossless(data.eventType);
+    let callbacks: Vec<_> = lock(&CALLBACKS).clone();
+    for &(id, ref callback) in &callbacks {
+        // a panic must not unwind into the driver's thread
+        let _ = panic::catch_unwind(AssertUnwindSafe(|| {
+            if let Some(ref mut callback) = *lock(callback) {
+                DISPATCHING.with(|dispatching| dispatching.set(Some(id)));
+                callback(event)
+            }
+        }));
+        DISPATCHING.with(|dispatching| dispatching.set(None));
+    }
+}
+
+impl EventSubscription {
+    /// Calls `f` whenever a sync device gains or loses sync, house sync or an RJ45
+    /// connection. Joins the current `Nvapi` session if there is one.
+    pub fn sync_events<F: FnMut(SyncEvent) + Send + 'static>(f: F) -> sys::Result<Self> {
+        trace!("event.sync_events()");
+        let mut registration = lock(&REGISTRATION);
+        let handle = match *registration {
+            Some(handle) => NvEventHandle::from_raw(handle as *const _),
+            None => {
+                let mut raw = event::NV_EVENT_REGISTER_CALLBACK::zeroed();
+                raw.version = event::NV_EVENT_REGISTER_CALLBACK_VERSION;
+                raw.eventId = EventType::QSync.raw();
+                raw.nvCallBackFunc = Some(sync_event);
+                let mut handle = Default::default();
+                sys::status_result(unsafe { event::NvAPI_Event_RegisterCallback(&mut raw, &mut handle) })?;
+                *registration = Some(handle.as_ptr() as usize);
+                handle
+            },
+        };
+
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        let callback: Box<dyn FnMut(SyncEvent) + Send> = Box::new(f);
+        lock(&CALLBACKS).push((id, Arc::new(Mutex::new(Some(callback)))));
+        Ok(EventSubscription {
+            id,
+            handle,
+            session: Nvapi::current(),
+        })
+    }
+
+    /// The handle of the registration, which is shared by every subscription in the process.
+    pub fn handle(&self) -> NvEventHandle {
+        self.handle
+    }
+
+    /// The session this subscription holds on to, if it was made while one was alive.
+    pub fn session(&self) -> Option<&Nvapi> {
+        self.session.as_ref()
+    }
+}
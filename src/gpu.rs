@@ -1,46 +1,149 @@
 use std::{ptr, fmt};
+use std::cell::{Cell, OnceCell};
 use std::convert::Infallible;
-use log::trace;
+use log::{trace, debug};
 use serde::{Serialize, Deserialize};
-use crate::sys::gpu::{self, pstate, clock, power, cooler, thermal, display};
-use crate::sys::{self, driverapi, i2c};
-use crate::types::{Kibibytes, KilohertzDelta, Kilohertz2Delta, Microvolts, Percentage, Percentage1000, RawConversion};
+use crate::sys::gpu::{self, pstate, clock, power, cooler, thermal, display, ecc};
+use crate::sys::{self, driverapi, i2c, Api};
+use crate::types::{Kibibytes, KilohertzDelta, Kilohertz2Delta, Microvolts, Percentage, Percentage1000, RawConversion, checked_entries};
 use crate::thermal::CoolerLevel;
 use crate::clock::{ClockDomain, VfpMask};
 use crate::pstate::PState;
-
+use crate::versioned::{negotiate, negotiate_from};
+use crate::session::Nvapi;
+use crate::logical::LogicalGpu;
+use crate::edid;
+
+/// A physical GPU, which keeps the session it was enumerated from alive.
+///
+/// When the driver reports `HandleInvalidated` after a mode set or topology change,
+/// calls are retried once after rebinding to the card with the same `GpuIdentity`.
 #[derive(Debug)]
-pub struct PhysicalGpu(sys::handles::NvPhysicalGpuHandle);
+pub struct PhysicalGpu {
+    handle: sys::handles::NvPhysicalGpuHandle,
+    current: Cell<sys::handles::NvPhysicalGpuHandle>,
+    identity: OnceCell<GpuIdentity>,
+    session: Option<Nvapi>,
+}
 
 unsafe impl Send for PhysicalGpu { }
 
-pub use sys::gpu::{SystemType, PerformanceDecreaseReason};
+pub use sys::gpu::{SystemType, PerformanceDecreaseReason, BusType};
 pub use sys::gpu::private::{RamType, RamMaker, Foundry, VendorId as Vendor};
 pub use sys::gpu::clock::ClockFrequencyType;
 pub use sys::gpu::display::{ConnectedIdsFlags, DisplayIdsFlags, MonitorConnectorType};
+pub use sys::gpu::ecc::EccConfiguration;
 pub type ClockFrequencies = <sys::gpu::clock::NV_GPU_CLOCK_FREQUENCIES as RawConversion>::Target;
 pub type Utilizations = <pstate::NV_GPU_DYNAMIC_PSTATES_INFO_EX as RawConversion>::Target;
 
 impl PhysicalGpu {
+    /// The handle this GPU was enumerated with.
     pub fn handle(&self) -> &sys::handles::NvPhysicalGpuHandle {
-        &self.0
+        &self.handle
+    }
+
+    /// The handle calls are made with, which differs from `handle` once the GPU is rebound.
+    pub fn current_handle(&self) -> sys::handles::NvPhysicalGpuHandle {
+        self.current.get()
+    }
+
+    /// The session this GPU holds on to, if it was enumerated while one was alive.
+    pub fn session(&self) -> Option<&Nvapi> {
+        self.session.as_ref()
+    }
+
+    /// Identifies the card independently of its handle.
+    ///
+    /// This is captured during enumeration when possible, and otherwise on first use,
+    /// so it remains available after the handle is invalidated.
+    pub fn identity(&self) -> sys::Result<GpuIdentity> {
+        match self.identity.get() {
+            Some(identity) => Ok(identity.clone()),
+            None => GpuIdentity::query(self.current_handle())
+                .map(|identity| self.identity.get_or_init(|| identity).clone()),
+        }
+    }
+
+    /// Re-enumerates the GPUs and switches to the handle of the same card.
+    ///
+    /// This happens automatically when a call fails with `HandleInvalidated`.
+    pub fn rebind(&self) -> sys::Result<()> {
+        trace!("gpu.rebind()");
+        let identity = self.identity.get().ok_or(sys::Status::HandleInvalidated)?;
+        let handle = enumerate_handles()?.into_iter()
+            .find(|&handle| GpuIdentity::query(handle).ok().as_ref() == Some(identity))
+            .ok_or(sys::Status::NvidiaDeviceNotFound)?;
+        self.current.set(handle);
+        Ok(())
+    }
+
+    /// Calls `f` with the current handle, rebinding and trying again if it has been invalidated.
+    pub(crate) fn retry<F: FnMut(sys::handles::NvPhysicalGpuHandle) -> sys::NvAPI_Status>(&self, mut f: F) -> sys::NvAPI_Status {
+        match f(self.current_handle()) {
+            sys::status::NVAPI_HANDLE_INVALIDATED if self.rebind().is_ok() => f(self.current_handle()),
+            status => status,
+        }
     }
 
+    /// Enumerates the physical GPUs, joining the current `Nvapi` session if there is one.
     pub fn enumerate() -> sys::Result<Vec<Self>> {
+        Self::enumerate_in(Nvapi::current())
+    }
+
+    pub(crate) fn enumerate_in(session: Option<Nvapi>) -> sys::Result<Vec<Self>> {
         trace!("gpu.enumerate()");
-        let mut handles = [Default::default(); sys::types::NVAPI_MAX_PHYSICAL_GPUS];
-        let mut len = 0;
-        match unsafe { gpu::NvAPI_EnumPhysicalGPUs(&mut handles, &mut len) } {
-            sys::status::NVAPI_NVIDIA_DEVICE_NOT_FOUND => Ok(Vec::new()),
-            status => sys::status_result(status).map(move |_| handles[..len as usize].iter().cloned().map(PhysicalGpu).collect()),
+        enumerate_handles().map(|handles| handles.into_iter()
+            .map(|handle| Self::from_handle(handle, session.clone()))
+            .collect()
+        )
+    }
+
+    pub(crate) fn from_handle(handle: sys::handles::NvPhysicalGpuHandle, session: Option<Nvapi>) -> Self {
+        // the identity can't be queried once the handle is invalidated, so try while it's fresh
+        let identity = OnceCell::new();
+        match GpuIdentity::query(handle) {
+            Ok(value) => drop(identity.set(value)),
+            Err(e) => debug!("unable to identify GPU {:?}, it can't be rebound until identified: {}", handle, e),
+        }
+
+        PhysicalGpu {
+            handle,
+            current: Cell::new(handle),
+            identity,
+            session,
         }
     }
 
+    /// The logical GPU this one is a part of.
+    pub fn logical_gpu(&self) -> sys::Result<LogicalGpu> {
+        LogicalGpu::from_physical(self)
+    }
+
+    /// Probes which functions the driver implements for this GPU.
+    ///
+    /// Only read-only queries are issued; anything else is reported as merely `Exported`.
+    pub fn capabilities(&self) -> crate::Capabilities {
+        trace!("gpu.capabilities()");
+        crate::capabilities::gpu_capabilities(self)
+    }
+
+    /// Calls a function without a typed binding as `(NvPhysicalGpuHandle, *mut T)`, passing `data` as the structure.
+    ///
+    /// When `versioned`, `data` starts with a `version` field; see `sys::raw` for building it.
+    ///
+    /// # Safety
+    ///
+    /// `api` must take a GPU handle and a pointer to a structure no larger than `data`.
+    pub unsafe fn call_raw(&self, api: Api, data: &mut [u8], versioned: bool) -> sys::Result<()> {
+        trace!("gpu.call_raw({}, {} bytes, {:?})", api, data.len(), versioned);
+        sys::status_result(self.retry(|h| sys::raw::call(api, Some(h), data, versioned)))
+    }
+
     pub fn tachometer(&self) -> sys::Result<u32> {
         trace!("gpu.tachometer()");
         let mut out = 0;
         unsafe {
-            sys::status_result(cooler::NvAPI_GPU_GetTachReading(self.0, &mut out))
+            sys::status_result(self.retry(|h| cooler::NvAPI_GPU_GetTachReading(h, &mut out)))
                 .map(move |_| out)
         }
     }
@@ -49,7 +152,7 @@ impl PhysicalGpu {
         trace!("gpu.short_name()");
         let mut str = sys::types::short_string();
         unsafe {
-            sys::status_result(gpu::private::NvAPI_GPU_GetShortName(self.0, &mut str))
+            sys::status_result(self.retry(|h| gpu::private::NvAPI_GPU_GetShortName(h, &mut str)))
                 .and_then(|_| str.convert_raw().map_err(Into::into))
         }
     }
@@ -58,7 +161,7 @@ impl PhysicalGpu {
         trace!("gpu.full_name()");
         let mut str = sys::types::short_string();
         unsafe {
-            sys::status_result(gpu::NvAPI_GPU_GetFullName(self.0, &mut str))
+            sys::status_result(self.retry(|h| gpu::NvAPI_GPU_GetFullName(h, &mut str)))
                 .and_then(|_| str.convert_raw().map_err(Into::into))
         }
     }
@@ -67,7 +170,7 @@ impl PhysicalGpu {
         trace!("gpu.vbios_version_string()");
         let mut str = sys::types::short_string();
         unsafe {
-            sys::status_result(gpu::NvAPI_GPU_GetVbiosVersionString(self.0, &mut str))
+            sys::status_result(self.retry(|h| gpu::NvAPI_GPU_GetVbiosVersionString(h, &mut str)))
                 .and_then(|_| str.convert_raw().map_err(Into::into))
         }
     }
@@ -76,7 +179,7 @@ impl PhysicalGpu {
         trace!("gpu.driver_model()");
         let mut value = 0;
         unsafe {
-            sys::status_result(gpu::private::NvAPI_GetDriverModel(self.0, &mut value))
+            sys::status_result(self.retry(|h| gpu::private::NvAPI_GetDriverModel(h, &mut value)))
                 .map(|_| DriverModel::new(value))
         }
     }
@@ -85,7 +188,7 @@ impl PhysicalGpu {
         trace!("gpu.gpu_id()");
         let mut value = 0;
         unsafe {
-            sys::status_result(gpu::private::NvAPI_GetGPUIDFromPhysicalGPU(self.0, &mut value))
+            sys::status_result(self.retry(|h| gpu::private::NvAPI_GetGPUIDFromPhysicalGPU(h, &mut value)))
                 .map(|_| value)
         }
     }
@@ -94,17 +197,92 @@ impl PhysicalGpu {
         trace!("gpu.pci_identifiers()");
         let mut pci = PciIdentifiers::default();
         unsafe {
-            sys::status_result(gpu::NvAPI_GPU_GetPCIIdentifiers(self.0, &mut pci.device_id, &mut pci.subsystem_id, &mut pci.revision_id, &mut pci.ext_device_id))
+            sys::status_result(self.retry(|h| gpu::NvAPI_GPU_GetPCIIdentifiers(h, &mut pci.device_id, &mut pci.subsystem_id, &mut pci.revision_id, &mut pci.ext_device_id)))
                 .map(|_| pci)
         }
     }
 
+    pub fn bus_id(&self) -> sys::Result<u32> {
+        trace!("gpu.bus_id()");
+        let mut value = 0;
+        sys::status_result(self.retry(|h| unsafe { gpu::NvAPI_GPU_GetBusId(h, &mut value) }))
+            .map(|_| value)
+    }
+
+    pub fn bus_slot_id(&self) -> sys::Result<u32> {
+        trace!("gpu.bus_slot_id()");
+        let mut value = 0;
+        sys::status_result(self.retry(|h| unsafe { gpu::NvAPI_GPU_GetBusSlotId(h, &mut value) }))
+            .map(|_| value)
+    }
+
+    pub fn bus_type(&self) -> sys::Result<BusType> {
+        trace!("gpu.bus_type()");
+        let mut value = gpu::NVAPI_GPU_BUS_TYPE_UNDEFINED;
+        sys::status_result(self.retry(|h| unsafe { gpu::NvAPI_GPU_GetBusType(h, &mut value) }))
+            .map(|_| BusType::from_raw_lossless(value))
+    }
+
+    pub fn irq(&self) -> sys::Result<u32> {
+        trace!("gpu.irq()");
+        let mut value = 0;
+        sys::status_result(self.retry(|h| unsafe { gpu::NvAPI_GPU_GetIRQ(h, &mut value) }))
+            .map(|_| value)
+    }
+
+    /// The number of PCIe lanes currently in use.
+    pub fn pcie_lanes(&self) -> sys::Result<u32> {
+        trace!("gpu.pcie_lanes()");
+        let mut value = 0;
+        sys::status_result(self.retry(|h| unsafe { gpu::NvAPI_GPU_GetCurrentPCIEDownstreamWidth(h, &mut value) }))
+            .map(|_| value)
+    }
+
+    /// The PCIe link as currently trained, followed by the fastest link each performance level allows.
+    pub fn pcie_links(&self) -> sys::Result<Vec<PcieLink>> {
+        trace!("gpu.pcie_links()");
+        let mut data = gpu::private::NV_GPU_PCIE_INFO::zeroed();
+        data.version = gpu::private::NV_GPU_PCIE_INFO_VER;
+
+        sys::status_result(self.retry(|h| unsafe { gpu::private::NvAPI_GPU_GetPCIEInfo(h, &mut data) }))
+            .and_then(|_| data.convert_raw().map_err(From::from))
+    }
+
+    /// Where the GPU sits on the bus, and how its PCIe link compares to the fastest it supports.
+    ///
+    /// The link generation and limits come from a private interface, and are left empty
+    /// when the driver doesn't provide it.
+    pub fn pcie_info(&self) -> sys::Result<PcieInfo> {
+        trace!("gpu.pcie_info()");
+        let lanes = self.pcie_lanes()?;
+        let links = match self.pcie_links() {
+            Ok(links) => Some(links),
+            Err(e) => {
+                debug!("PCIe link details unavailable for GPU {:?}: {}", self.handle, e);
+                None
+            },
+        };
+
+        Ok(PcieInfo {
+            bus_type: self.bus_type()?,
+            bus_id: self.bus_id()?,
+            bus_slot_id: self.bus_slot_id()?,
+            irq: self.irq()?,
+            lanes,
+            generation: links.as_ref().and_then(|links| links.first()).map(|link| link.generation),
+            max: links.map(|links| PcieLink {
+                generation: links.iter().map(|link| link.generation).max().unwrap_or_default(),
+                lanes: links.iter().map(|link| link.lanes).max().unwrap_or_default(),
+            }),
+        })
+    }
+
     pub fn board_number(&self) -> sys::Result<[u8; 0x10]> {
         trace!("gpu.board_number()");
         let mut data = gpu::NV_BOARD_INFO::zeroed();
         data.version = gpu::NV_BOARD_INFO_VER;
         unsafe {
-            sys::status_result(gpu::NvAPI_GPU_GetBoardInfo(self.0, &mut data))
+            sys::status_result(self.retry(|h| gpu::NvAPI_GPU_GetBoardInfo(h, &mut data)))
                 .map(|_| data.BoardNum)
         }
     }
@@ -113,8 +291,8 @@ impl PhysicalGpu {
         trace!("gpu.system_type()");
         let mut ty = gpu::NV_SYSTEM_TYPE_UNKNOWN;
         unsafe {
-            sys::status_result(gpu::NvAPI_GPU_GetSystemType(self.0, &mut ty))
-                .and_then(|_| gpu::SystemType::from_raw(ty).map_err(From::from))
+            sys::status_result(self.retry(|h| gpu::NvAPI_GPU_GetSystemType(h, &mut ty)))
+                .map(|_| gpu::SystemType::from_raw_lossless(ty))
         }
     }
 
@@ -122,7 +300,7 @@ impl PhysicalGpu {
         trace!("gpu.core_count()");
         let mut value = 0;
         unsafe {
-            sys::status_result(gpu::NvAPI_GPU_GetGpuCoreCount(self.0, &mut value))
+            sys::status_result(self.retry(|h| gpu::NvAPI_GPU_GetGpuCoreCount(h, &mut value)))
                 .map(|_| value)
         }
     }
@@ -131,7 +309,7 @@ impl PhysicalGpu {
         trace!("gpu.shader_pipe_count()");
         let mut value = 0;
         unsafe {
-            sys::status_result(gpu::private::NvAPI_GPU_GetShaderPipeCount(self.0, &mut value))
+            sys::status_result(self.retry(|h| gpu::private::NvAPI_GPU_GetShaderPipeCount(h, &mut value)))
                 .map(|_| value)
         }
     }
@@ -140,7 +318,7 @@ impl PhysicalGpu {
         trace!("gpu.shader_sub_pipe_count()");
         let mut value = 0;
         unsafe {
-            sys::status_result(gpu::NvAPI_GPU_GetShaderSubPipeCount(self.0, &mut value))
+            sys::status_result(self.retry(|h| gpu::NvAPI_GPU_GetShaderSubPipeCount(h, &mut value)))
                 .map(|_| value)
         }
     }
@@ -149,8 +327,8 @@ impl PhysicalGpu {
         trace!("gpu.ram_type()");
         let mut value = gpu::private::NV_GPU_RAM_UNKNOWN;
         unsafe {
-            sys::status_result(gpu::private::NvAPI_GPU_GetRamType(self.0, &mut value))
-                .and_then(|_| gpu::private::RamType::from_raw(value).map_err(From::from))
+            sys::status_result(self.retry(|h| gpu::private::NvAPI_GPU_GetRamType(h, &mut value)))
+                .map(|_| gpu::private::RamType::from_raw_lossless(value))
         }
     }
 
@@ -158,8 +336,8 @@ impl PhysicalGpu {
         trace!("gpu.ram_maker()");
         let mut value = gpu::private::NV_GPU_RAM_MAKER_UNKNOWN;
         unsafe {
-            sys::status_result(gpu::private::NvAPI_GPU_GetRamMaker(self.0, &mut value))
-                .and_then(|_| gpu::private::RamMaker::from_raw(value).map_err(From::from))
+            sys::status_result(self.retry(|h| gpu::private::NvAPI_GPU_GetRamMaker(h, &mut value)))
+                .map(|_| gpu::private::RamMaker::from_raw_lossless(value))
         }
     }
 
@@ -167,7 +345,7 @@ impl PhysicalGpu {
         trace!("gpu.ram_bus_width()");
         let mut value = 0;
         unsafe {
-            sys::status_result(gpu::private::NvAPI_GPU_GetRamBusWidth(self.0, &mut value))
+            sys::status_result(self.retry(|h| gpu::private::NvAPI_GPU_GetRamBusWidth(h, &mut value)))
                 .map(|_| value)
         }
     }
@@ -176,7 +354,7 @@ impl PhysicalGpu {
         trace!("gpu.ram_bank_count()");
         let mut value = 0;
         unsafe {
-            sys::status_result(gpu::private::NvAPI_GPU_GetRamBankCount(self.0, &mut value))
+            sys::status_result(self.retry(|h| gpu::private::NvAPI_GPU_GetRamBankCount(h, &mut value)))
                 .map(|_| value)
         }
     }
@@ -185,7 +363,7 @@ impl PhysicalGpu {
         trace!("gpu.ram_partition_count()");
         let mut value = 0;
         unsafe {
-            sys::status_result(gpu::private::NvAPI_GPU_GetPartitionCount(self.0, &mut value))
+            sys::status_result(self.retry(|h| gpu::private::NvAPI_GPU_GetPartitionCount(h, &mut value)))
                 .map(|_| value)
         }
     }
@@ -194,17 +372,16 @@ impl PhysicalGpu {
         trace!("gpu.foundry()");
         let mut value = gpu::private::NV_GPU_FOUNDRY_UNKNOWN;
         unsafe {
-            sys::status_result(gpu::private::NvAPI_GPU_GetFoundry(self.0, &mut value))
-                .and_then(|_| gpu::private::Foundry::from_raw(value).map_err(From::from))
+            sys::status_result(self.retry(|h| gpu::private::NvAPI_GPU_GetFoundry(h, &mut value)))
+                .map(|_| gpu::private::Foundry::from_raw_lossless(value))
         }
     }
 
     pub fn memory_info(&self) -> sys::Result<MemoryInfo> {
         trace!("gpu.memory_info()");
         let mut data = driverapi::NV_DISPLAY_DRIVER_MEMORY_INFO::zeroed();
-        data.version = driverapi::NV_DISPLAY_DRIVER_MEMORY_INFO_VER;
 
-        sys::status_result(unsafe { driverapi::NvAPI_GPU_GetMemoryInfo(self.0, &mut data) })
+        negotiate(Api::NvAPI_GPU_GetMemoryInfo, &mut data, |data| unsafe { self.retry(|h| driverapi::NvAPI_GPU_GetMemoryInfo(h, data)) })
             .and_then(|_| data.convert_raw().map_err(Into::into))
     }
 
@@ -213,17 +390,16 @@ impl PhysicalGpu {
         let mut data = driverapi::NV_DISPLAY_DRIVER_MEMORY_INFO_EX_V1::zeroed();
         data.version = driverapi::NV_DISPLAY_DRIVER_MEMORY_INFO_EX_VER;
 
-        sys::status_result(unsafe { driverapi::NvAPI_GPU_GetMemoryInfoEx(self.0, &mut data) })
+        sys::status_result(unsafe { self.retry(|h| driverapi::NvAPI_GPU_GetMemoryInfoEx(h, &mut data)) })
             .and_then(|_| data.convert_raw().map_err(Into::into))
     }
 
     pub fn clock_frequencies(&self, clock_type: ClockFrequencyType) -> sys::Result<ClockFrequencies> {
         trace!("gpu.clock_frequencies({:?})", clock_type);
         let mut clocks = clock::NV_GPU_CLOCK_FREQUENCIES::zeroed();
-        clocks.version = clock::NV_GPU_CLOCK_FREQUENCIES_VER;
         clocks.set_ClockType(clock_type.raw());
 
-        sys::status_result(unsafe { clock::NvAPI_GPU_GetAllClockFrequencies(self.0, &mut clocks) })
+        negotiate(Api::NvAPI_GPU_GetAllClockFrequencies, &mut clocks, |clocks| unsafe { self.retry(|h| clock::NvAPI_GPU_GetAllClockFrequencies(h, clocks)) })
             .and_then(|_| clocks.convert_raw().map_err(Into::into))
     }
 
@@ -231,17 +407,16 @@ impl PhysicalGpu {
         trace!("gpu.current_pstate()");
         let mut pstate = 0;
 
-        sys::status_result(unsafe { pstate::NvAPI_GPU_GetCurrentPstate(self.0, &mut pstate) })?;
+        sys::status_result(unsafe { self.retry(|h| pstate::NvAPI_GPU_GetCurrentPstate(h, &mut pstate)) })?;
 
-        PState::from_raw(pstate).map_err(From::from)
+        Ok(PState::from_raw_lossless(pstate))
     }
 
     pub fn pstates(&self) -> sys::Result<<pstate::NV_GPU_PERF_PSTATES20_INFO as RawConversion>::Target> {
         trace!("gpu.pstates()");
         let mut info = pstate::NV_GPU_PERF_PSTATES20_INFO::zeroed();
-        info.version = pstate::NV_GPU_PERF_PSTATES20_INFO_VER;
 
-        sys::status_result(unsafe { pstate::NvAPI_GPU_GetPstates20(self.0, &mut info) })
+        negotiate(Api::NvAPI_GPU_GetPstates20, &mut info, |info| unsafe { self.retry(|h| pstate::NvAPI_GPU_GetPstates20(h, info)) })
             .and_then(|_| info.convert_raw().map_err(From::from))
     }
 
@@ -250,7 +425,6 @@ impl PhysicalGpu {
         use std::collections::BTreeMap;
 
         let mut info = pstate::NV_GPU_PERF_PSTATES20_INFO::zeroed();
-        info.version = pstate::NV_GPU_PERF_PSTATES20_INFO_VER;
 
         let mut map: BTreeMap<PState, (usize, usize)> = Default::default();
         for (pstate, clock, delta) in deltas {
@@ -267,7 +441,8 @@ impl PhysicalGpu {
         info.numPstates = map.len() as _;
         info.numClocks = map.iter().map(|v| (v.1).1).max().unwrap_or(0) as _;
 
-        sys::status_result(unsafe { pstate::private::NvAPI_GPU_SetPstates20(self.0, &info) })
+        negotiate(Api::NvAPI_GPU_SetPstates20, &mut info, |info| unsafe { self.retry(|h| pstate::private::NvAPI_GPU_SetPstates20(h, info)) })
+            .map(drop)
     }
 
     pub fn dynamic_pstates_info(&self) -> sys::Result<Utilizations> {
@@ -275,7 +450,7 @@ impl PhysicalGpu {
         let mut info = pstate::NV_GPU_DYNAMIC_PSTATES_INFO_EX::zeroed();
         info.version = pstate::NV_GPU_DYNAMIC_PSTATES_INFO_EX_VER;
 
-        sys::status_result(unsafe { pstate::NvAPI_GPU_GetDynamicPstatesInfoEx(self.0, &mut info) })
+        sys::status_result(unsafe { self.retry(|h| pstate::NvAPI_GPU_GetDynamicPstatesInfoEx(h, &mut info)) })
             .and_then(|_| info.convert_raw().map_err(From::from))
     }
 
@@ -285,7 +460,7 @@ impl PhysicalGpu {
         let mut usages = clock::private::NV_USAGES_INFO::zeroed();
         usages.version = clock::private::NV_USAGES_INFO_VER;
 
-        sys::status_result(unsafe { clock::private::NvAPI_GPU_GetUsages(self.0, &mut usages) })
+        sys::status_result(unsafe { self.retry(|h| clock::private::NvAPI_GPU_GetUsages(h, &mut usages)) })
             .and_then(|_| usages.convert_raw().map_err(From::from))
     }
 
@@ -294,7 +469,7 @@ impl PhysicalGpu {
         let mut data = clock::private::NV_GPU_CLOCK_CLIENT_CLK_VF_POINTS_INFO::zeroed();
         data.version = clock::private::NV_GPU_CLOCK_CLIENT_CLK_VF_POINTS_INFO_VER;
 
-        sys::status_result(unsafe { clock::private::NvAPI_GPU_ClockClientClkVfPointsGetInfo(self.0, &mut data) })
+        sys::status_result(unsafe { self.retry(|h| clock::private::NvAPI_GPU_ClockClientClkVfPointsGetInfo(h, &mut data)) })
             .and_then(|_| data.convert_raw().map_err(From::from))
     }
 
@@ -304,7 +479,7 @@ impl PhysicalGpu {
         data.version = clock::private::NV_GPU_CLOCK_CLIENT_CLK_VF_POINTS_CONTROL_VER;
         data.mask = mask;
 
-        sys::status_result(unsafe { clock::private::NvAPI_GPU_ClockClientClkVfPointsGetControl(self.0, &mut data) })
+        sys::status_result(unsafe { self.retry(|h| clock::private::NvAPI_GPU_ClockClientClkVfPointsGetControl(h, &mut data)) })
             .and_then(|_| data.convert_raw().map_err(From::from))
     }
 
@@ -323,7 +498,7 @@ impl PhysicalGpu {
             data.memDeltas[i] = delta.0;
         }
 
-        sys::status_result(unsafe { clock::private::NvAPI_GPU_ClockClientClkVfPointsSetControl(self.0, &data) })
+        sys::status_result(unsafe { self.retry(|h| clock::private::NvAPI_GPU_ClockClientClkVfPointsSetControl(h, &data)) })
     }
 
     pub fn vfp_ranges(&self) -> sys::Result<<clock::private::NV_GPU_CLOCK_CLIENT_CLK_DOMAINS_INFO as RawConversion>::Target> {
@@ -331,7 +506,7 @@ impl PhysicalGpu {
         let mut data = clock::private::NV_GPU_CLOCK_CLIENT_CLK_DOMAINS_INFO::zeroed();
         data.version = clock::private::NV_GPU_CLOCK_CLIENT_CLK_DOMAINS_INFO_VER;
 
-        sys::status_result(unsafe { clock::private::NvAPI_GPU_ClockClientClkDomainsGetInfo(self.0, &mut data) })
+        sys::status_result(unsafe { self.retry(|h| clock::private::NvAPI_GPU_ClockClientClkDomainsGetInfo(h, &mut data)) })
             .and_then(|_| data.convert_raw().map_err(From::from))
     }
 
@@ -340,7 +515,7 @@ impl PhysicalGpu {
         let mut data = clock::private::NV_GPU_PERF_CLIENT_LIMITS::zeroed();
         data.version = clock::private::NV_GPU_PERF_CLIENT_LIMITS_VER;
 
-        sys::status_result(unsafe { clock::private::NvAPI_GPU_PerfClientLimitsGetStatus(self.0, &mut data) })
+        sys::status_result(unsafe { self.retry(|h| clock::private::NvAPI_GPU_PerfClientLimitsGetStatus(h, &mut data)) })
             .and_then(|_| data.convert_raw().map_err(From::from))
     }
 
@@ -365,7 +540,7 @@ impl PhysicalGpu {
             }
         }
 
-        sys::status_result(unsafe { clock::private::NvAPI_GPU_PerfClientLimitsSetStatus(self.0, &data) })
+        sys::status_result(unsafe { self.retry(|h| clock::private::NvAPI_GPU_PerfClientLimitsSetStatus(h, &data)) })
     }
 
     pub fn vfp_curve(&self, mask: [u32; 4]) -> sys::Result<<power::private::NV_GPU_CLOCK_CLIENT_CLK_VF_POINTS_STATUS as RawConversion>::Target> {
@@ -374,7 +549,7 @@ impl PhysicalGpu {
         data.version = power::private::NV_GPU_CLOCK_CLIENT_CLK_VF_POINTS_STATUS_VER;
         data.mask = mask;
 
-        sys::status_result(unsafe { power::private::NvAPI_GPU_ClockClientClkVfPointsGetStatus(self.0, &mut data) })
+        sys::status_result(unsafe { self.retry(|h| power::private::NvAPI_GPU_ClockClientClkVfPointsGetStatus(h, &mut data)) })
             .and_then(|_| data.convert_raw().map_err(From::from))
     }
 
@@ -383,7 +558,7 @@ impl PhysicalGpu {
         let mut data = power::private::NV_GPU_CLIENT_VOLT_RAILS_STATUS::zeroed();
         data.version = power::private::NV_GPU_CLIENT_VOLT_RAILS_STATUS_VER;
 
-        sys::status_result(unsafe { power::private::NvAPI_GPU_ClientVoltRailsGetStatus(self.0, &mut data) })
+        sys::status_result(unsafe { self.retry(|h| power::private::NvAPI_GPU_ClientVoltRailsGetStatus(h, &mut data)) })
             .and_then(|_| data.convert_raw().map_err(From::from))
     }
 
@@ -392,7 +567,7 @@ impl PhysicalGpu {
         let mut data = power::private::NV_GPU_CLIENT_VOLT_RAILS_CONTROL::zeroed();
         data.version = power::private::NV_GPU_CLIENT_VOLT_RAILS_CONTROL_VER;
 
-        sys::status_result(unsafe { power::private::NvAPI_GPU_ClientVoltRailsGetControl(self.0, &mut data) })
+        sys::status_result(unsafe { self.retry(|h| power::private::NvAPI_GPU_ClientVoltRailsGetControl(h, &mut data)) })
             .and_then(|_| data.convert_raw().map_err(From::from))
     }
 
@@ -402,7 +577,7 @@ impl PhysicalGpu {
         data.version = power::private::NV_GPU_CLIENT_VOLT_RAILS_CONTROL_VER;
         data.percent = value.0;
 
-        sys::status_result(unsafe { power::private::NvAPI_GPU_ClientVoltRailsSetControl(self.0, &data) })
+        sys::status_result(unsafe { self.retry(|h| power::private::NvAPI_GPU_ClientVoltRailsSetControl(h, &data)) })
     }
 
     pub fn power_usage(&self) -> sys::Result<<power::private::NV_GPU_POWER_TOPO as RawConversion>::Target> {
@@ -410,7 +585,7 @@ impl PhysicalGpu {
         let mut data = power::private::NV_GPU_POWER_TOPO::zeroed();
         data.version = power::private::NV_GPU_POWER_TOPO_VER;
 
-        sys::status_result(unsafe { power::private::NvAPI_GPU_ClientPowerTopologyGetStatus(self.0, &mut data) })
+        sys::status_result(unsafe { self.retry(|h| power::private::NvAPI_GPU_ClientPowerTopologyGetStatus(h, &mut data)) })
             .and_then(|_| data.convert_raw().map_err(From::from))
     }
 
@@ -419,7 +594,7 @@ impl PhysicalGpu {
         let mut data = power::private::NV_GPU_POWER_INFO::zeroed();
         data.version = power::private::NV_GPU_POWER_INFO_VER;
 
-        sys::status_result(unsafe { power::private::NvAPI_GPU_ClientPowerPoliciesGetInfo(self.0, &mut data) })
+        sys::status_result(unsafe { self.retry(|h| power::private::NvAPI_GPU_ClientPowerPoliciesGetInfo(h, &mut data)) })
             .and_then(|_| data.convert_raw().map_err(From::from))
     }
 
@@ -428,7 +603,7 @@ impl PhysicalGpu {
         let mut data = power::private::NV_GPU_POWER_STATUS::zeroed();
         data.version = power::private::NV_GPU_POWER_STATUS_VER;
 
-        sys::status_result(unsafe { power::private::NvAPI_GPU_ClientPowerPoliciesGetStatus(self.0, &mut data) })
+        sys::status_result(unsafe { self.retry(|h| power::private::NvAPI_GPU_ClientPowerPoliciesGetStatus(h, &mut data)) })
             .and_then(|_| data.convert_raw().map_err(From::from))
     }
 
@@ -443,7 +618,7 @@ impl PhysicalGpu {
             data.count += 1;
         }
 
-        sys::status_result(unsafe { power::private::NvAPI_GPU_ClientPowerPoliciesSetStatus(self.0, &data) })
+        sys::status_result(unsafe { self.retry(|h| power::private::NvAPI_GPU_ClientPowerPoliciesSetStatus(h, &data)) })
     }
 
     pub fn thermal_settings(&self, index: Option<u32>) -> sys::Result<<thermal::NV_GPU_THERMAL_SETTINGS as RawConversion>::Target> {
@@ -451,7 +626,7 @@ impl PhysicalGpu {
         let mut data = thermal::NV_GPU_THERMAL_SETTINGS::zeroed();
         data.version = thermal::NV_GPU_THERMAL_SETTINGS_VER;
 
-        sys::status_result(unsafe { thermal::NvAPI_GPU_GetThermalSettings(self.0, index.unwrap_or(thermal::NVAPI_THERMAL_TARGET_ALL as _), &mut data) })
+        sys::status_result(unsafe { self.retry(|h| thermal::NvAPI_GPU_GetThermalSettings(h, index.unwrap_or(thermal::NVAPI_THERMAL_TARGET_ALL as _), &mut data)) })
             .and_then(|_| data.convert_raw().map_err(From::from))
     }
 
@@ -460,7 +635,7 @@ impl PhysicalGpu {
         let mut data = thermal::private::NV_GPU_THERMAL_INFO::zeroed();
         data.version = thermal::private::NV_GPU_THERMAL_INFO_VER;
 
-        sys::status_result(unsafe { thermal::private::NvAPI_GPU_ClientThermalPoliciesGetInfo(self.0, &mut data) })
+        sys::status_result(unsafe { self.retry(|h| thermal::private::NvAPI_GPU_ClientThermalPoliciesGetInfo(h, &mut data)) })
             .and_then(|_| data.convert_raw().map_err(From::from))
     }
 
@@ -469,7 +644,7 @@ impl PhysicalGpu {
         let mut data = thermal::private::NV_GPU_CLIENT_THERMAL_POLICIES_STATUS::zeroed();
         data.version = thermal::private::NV_GPU_CLIENT_THERMAL_POLICIES_STATUS_VER;
 
-        sys::status_result(unsafe { thermal::private::NvAPI_GPU_ClientThermalPoliciesGetStatus(self.0, &mut data) })
+        sys::status_result(unsafe { self.retry(|h| thermal::private::NvAPI_GPU_ClientThermalPoliciesGetStatus(h, &mut data)) })
             .and_then(|_| data.convert_raw().map_err(From::from))
     }
 
@@ -485,7 +660,7 @@ impl PhysicalGpu {
             data.flags += 1;
         }
 
-        sys::status_result(unsafe { thermal::private::NvAPI_GPU_ClientThermalPoliciesSetStatus(self.0, &data) })
+        sys::status_result(unsafe { self.retry(|h| thermal::private::NvAPI_GPU_ClientThermalPoliciesSetStatus(h, &data)) })
     }
 
     pub fn cooler_settings(&self, index: Option<u32>) -> sys::Result<<cooler::private::NV_GPU_COOLER_SETTINGS as RawConversion>::Target> {
@@ -493,7 +668,7 @@ impl PhysicalGpu {
         let mut data = cooler::private::NV_GPU_COOLER_SETTINGS::zeroed();
         data.version = cooler::private::NV_GPU_COOLER_SETTINGS_VER;
 
-        sys::status_result(unsafe { cooler::private::NvAPI_GPU_GetCoolerSettings(self.0, index.unwrap_or(cooler::private::NVAPI_COOLER_TARGET_ALL as _), &mut data) })
+        sys::status_result(unsafe { self.retry(|h| cooler::private::NvAPI_GPU_GetCoolerSettings(h, index.unwrap_or(cooler::private::NVAPI_COOLER_TARGET_ALL as _), &mut data)) })
             .and_then(|_| data.convert_raw().map_err(From::from))
     }
 
@@ -507,13 +682,13 @@ impl PhysicalGpu {
             entry.currentPolicy = level.policy.raw();
         }
 
-        sys::status_result(unsafe { cooler::private::NvAPI_GPU_SetCoolerLevels(self.0, index.unwrap_or(cooler::private::NVAPI_COOLER_TARGET_ALL as _), &mut data) })
+        sys::status_result(unsafe { self.retry(|h| cooler::private::NvAPI_GPU_SetCoolerLevels(h, index.unwrap_or(cooler::private::NVAPI_COOLER_TARGET_ALL as _), &mut data)) })
     }
 
     pub fn restore_cooler_settings(&self, index: &[u32]) -> sys::Result<()> {
         trace!("gpu.restore_cooler_settings({:?})", index);
         let ptr = if index.is_empty() { ptr::null() } else { index.as_ptr() };
-        sys::status_result(unsafe { cooler::private::NvAPI_GPU_RestoreCoolerSettings(self.0, ptr, index.len() as u32) })
+        sys::status_result(unsafe { self.retry(|h| cooler::private::NvAPI_GPU_RestoreCoolerSettings(h, ptr, index.len() as u32)) })
     }
 
     pub fn cooler_policy_table(&self, index: u32, policy: crate::thermal::CoolerPolicy) -> sys::Result<<cooler::private::NV_GPU_COOLER_POLICY_TABLE as RawConversion>::Target> {
@@ -523,7 +698,7 @@ impl PhysicalGpu {
         data.policy = policy.raw();
         let mut count = 0;
 
-        sys::status_result(unsafe { cooler::private::NvAPI_GPU_GetCoolerPolicyTable(self.0, index, &mut data, &mut count) })
+        sys::status_result(unsafe { self.retry(|h| cooler::private::NvAPI_GPU_GetCoolerPolicyTable(h, index, &mut data, &mut count)) })
             .and_then(|_| data.convert_raw().map_err(From::from)).map(|mut c| {
                 c.levels.truncate(count as usize);
                 // TODO: ensure remaining levels are null?
@@ -536,13 +711,13 @@ impl PhysicalGpu {
         let mut data = cooler::private::NV_GPU_COOLER_POLICY_TABLE::zeroed();
         data.version = cooler::private::NV_GPU_COOLER_POLICY_TABLE_VER;
 
-        sys::status_result(unsafe { cooler::private::NvAPI_GPU_SetCoolerPolicyTable(self.0, index, &data, value.levels.len() as u32) })
+        sys::status_result(unsafe { self.retry(|h| cooler::private::NvAPI_GPU_SetCoolerPolicyTable(h, index, &data, value.levels.len() as u32)) })
     }
 
     pub fn restore_cooler_policy_table(&self, index: &[u32], policy: crate::thermal::CoolerPolicy) -> sys::Result<()> {
         trace!("gpu.restore_cooler_policy_table({:?}, {:?})", index, policy);
         let ptr = if index.is_empty() { ptr::null() } else { index.as_ptr() };
-        sys::status_result(unsafe { cooler::private::NvAPI_GPU_RestoreCoolerPolicyTable(self.0, ptr, index.len() as u32, policy.raw()) })
+        sys::status_result(unsafe { self.retry(|h| cooler::private::NvAPI_GPU_RestoreCoolerPolicyTable(h, ptr, index.len() as u32, policy.raw())) })
     }
 
     pub fn perf_info(&self) -> sys::Result<<power::private::NV_GPU_PERF_INFO as RawConversion>::Target> {
@@ -550,7 +725,7 @@ impl PhysicalGpu {
         let mut data = power::private::NV_GPU_PERF_INFO::zeroed();
         data.version = power::private::NV_GPU_PERF_INFO_VER;
 
-        sys::status_result(unsafe { power::private::NvAPI_GPU_PerfPoliciesGetInfo(self.0, &mut data) })
+        sys::status_result(unsafe { self.retry(|h| power::private::NvAPI_GPU_PerfPoliciesGetInfo(h, &mut data)) })
             .and_then(|_| data.convert_raw().map_err(From::from))
     }
 
@@ -559,7 +734,7 @@ impl PhysicalGpu {
         let mut data = power::private::NV_GPU_PERF_STATUS::zeroed();
         data.version = power::private::NV_GPU_PERF_STATUS_VER;
 
-        sys::status_result(unsafe { power::private::NvAPI_GPU_PerfPoliciesGetStatus(self.0, &mut data) })
+        sys::status_result(unsafe { self.retry(|h| power::private::NvAPI_GPU_PerfPoliciesGetStatus(h, &mut data)) })
             .and_then(|_| data.convert_raw().map_err(From::from))
     }
 
@@ -568,7 +743,7 @@ impl PhysicalGpu {
         let mut data = power::private::NV_VOLT_STATUS::zeroed();
         data.version = power::private::NV_VOLT_STATUS_VER;
 
-        sys::status_result(unsafe { power::private::NvAPI_GPU_GetVoltageDomainsStatus(self.0, &mut data) })
+        sys::status_result(unsafe { self.retry(|h| power::private::NvAPI_GPU_GetVoltageDomainsStatus(h, &mut data)) })
             .and_then(|_| data.convert_raw().map_err(From::from))
     }
 
@@ -577,7 +752,7 @@ impl PhysicalGpu {
         let mut data = power::private::NV_VOLT_STATUS::zeroed();
         data.version = power::private::NV_VOLT_STATUS_VER;
 
-        sys::status_result(unsafe { power::private::NvAPI_GPU_GetVoltageStep(self.0, &mut data) })
+        sys::status_result(unsafe { self.retry(|h| power::private::NvAPI_GPU_GetVoltageStep(h, &mut data)) })
             .and_then(|_| data.convert_raw().map_err(From::from))
     }
 
@@ -586,7 +761,7 @@ impl PhysicalGpu {
         let mut data = power::private::NV_VOLT_TABLE::zeroed();
         data.version = power::private::NV_VOLT_TABLE_VER;
 
-        sys::status_result(unsafe { power::private::NvAPI_GPU_GetVoltages(self.0, &mut data) })
+        sys::status_result(unsafe { self.retry(|h| power::private::NvAPI_GPU_GetVoltages(h, &mut data)) })
             .and_then(|_| data.convert_raw().map_err(From::from))
     }
 
@@ -595,14 +770,60 @@ impl PhysicalGpu {
 
         let mut data = gpu::NV_GPU_PERF_DECREASE_NONE;
 
-        sys::status_result(unsafe { gpu::NvAPI_GPU_GetPerfDecreaseInfo(self.0, &mut data) })
+        sys::status_result(unsafe { self.retry(|h| gpu::NvAPI_GPU_GetPerfDecreaseInfo(h, &mut data)) })
             .map(|_| PerformanceDecreaseReason::from_bits_truncate(data))
     }
 
+    /// Whether the board has ECC memory, and whether it is currently active.
+    pub fn ecc_status(&self) -> sys::Result<EccStatus> {
+        trace!("gpu.ecc_status()");
+        let mut data = ecc::NV_GPU_ECC_STATUS_INFO::zeroed();
+        data.version = ecc::NV_GPU_ECC_STATUS_INFO_VER;
+
+        sys::status_result(unsafe { self.retry(|h| ecc::NvAPI_GPU_GetECCStatusInfo(h, &mut data)) })
+            .and_then(|_| data.convert_raw().map_err(From::from))
+    }
+
+    pub fn ecc_errors(&self) -> sys::Result<EccErrors> {
+        trace!("gpu.ecc_errors()");
+        let mut data = ecc::NV_GPU_ECC_ERROR_INFO::zeroed();
+        data.version = ecc::NV_GPU_ECC_ERROR_INFO_VER;
+
+        sys::status_result(unsafe { self.retry(|h| ecc::NvAPI_GPU_GetECCErrorInfo(h, &mut data)) })
+            .and_then(|_| data.convert_raw().map_err(From::from))
+    }
+
+    /// Clears the error counters since boot, the aggregate ones, or both.
+    ///
+    /// Requires administrator privileges.
+    pub fn reset_ecc_errors(&self, current: bool, aggregate: bool) -> sys::Result<()> {
+        trace!("gpu.reset_ecc_errors({:?}, {:?})", current, aggregate);
+        sys::status_result(unsafe { self.retry(|h| ecc::NvAPI_GPU_ResetECCErrorInfo(h, current as _, aggregate as _)) })
+    }
+
+    /// The stored ECC setting, which may not yet be the one in effect.
+    pub fn ecc_configuration(&self) -> sys::Result<EccConfigurationInfo> {
+        trace!("gpu.ecc_configuration()");
+        let mut data = ecc::NV_GPU_ECC_CONFIGURATION_INFO::zeroed();
+        data.version = ecc::NV_GPU_ECC_CONFIGURATION_INFO_VER;
+
+        sys::status_result(unsafe { self.retry(|h| ecc::NvAPI_GPU_GetECCConfigurationInfo(h, &mut data)) })
+            .and_then(|_| data.convert_raw().map_err(From::from))
+    }
+
+    /// Enables or disables ECC memory after the next reboot, or right away if `immediately`
+    /// is set and `EccStatus::configuration` allows it.
+    ///
+    /// Requires administrator privileges.
+    pub fn set_ecc_configuration(&self, enable: bool, immediately: bool) -> sys::Result<()> {
+        trace!("gpu.set_ecc_configuration({:?}, {:?})", enable, immediately);
+        sys::status_result(unsafe { self.retry(|h| ecc::NvAPI_GPU_SetECCConfiguration(h, enable as _, immediately as _)) })
+    }
+
     pub fn display_ids_all(&self) -> sys::Result<Vec<<display::NV_GPU_DISPLAYIDS as RawConversion>::Target>> {
         trace!("gpu.display_ids_all()");
         let mut count = 0;
-        sys::status_result(unsafe { display::NvAPI_GPU_GetAllDisplayIds(self.0, ptr::null_mut(), &mut count) })?;
+        sys::status_result(unsafe { self.retry(|h| display::NvAPI_GPU_GetAllDisplayIds(h, ptr::null_mut(), &mut count)) })?;
         if count == 0 {
             return Ok(Vec::new());
         }
@@ -610,14 +831,15 @@ impl PhysicalGpu {
         data.version = display::NV_GPU_DISPLAYIDS_VER;
         let mut data = vec![data; count as usize];
 
-        sys::status_result(unsafe { display::NvAPI_GPU_GetAllDisplayIds(self.0, data.as_mut_ptr(), &mut count) })
-            .and_then(|_| data.into_iter().map(|v| v.convert_raw().map_err(From::from)).collect())
+        sys::status_result(unsafe { self.retry(|h| display::NvAPI_GPU_GetAllDisplayIds(h, data.as_mut_ptr(), &mut count)) })
+            .and_then(|_| checked_entries(&data, "pDisplayIdCount", count as _).map_err(From::from))
+            .and_then(|data| data.iter().map(|v| v.convert_raw().map_err(From::from)).collect())
     }
 
     pub fn display_ids_connected(&self, flags: ConnectedIdsFlags) -> sys::Result<Vec<<display::NV_GPU_DISPLAYIDS as RawConversion>::Target>> {
         trace!("gpu.display_ids_connected({:?})", flags);
         let mut count = 0;
-        sys::status_result(unsafe { display::NvAPI_GPU_GetConnectedDisplayIds(self.0, ptr::null_mut(), &mut count, flags.bits()) })?;
+        sys::status_result(unsafe { self.retry(|h| display::NvAPI_GPU_GetConnectedDisplayIds(h, ptr::null_mut(), &mut count, flags.bits())) })?;
         if count == 0 {
             return Ok(Vec::new());
         }
@@ -625,14 +847,88 @@ impl PhysicalGpu {
         data.version = display::NV_GPU_DISPLAYIDS_VER;
         let mut data = vec![data; count as usize];
 
-        sys::status_result(unsafe { display::NvAPI_GPU_GetConnectedDisplayIds(self.0, data.as_mut_ptr(), &mut count, flags.bits()) })
-            .and_then(|_| data.into_iter().map(|v| v.convert_raw().map_err(From::from)).collect())
+        sys::status_result(unsafe { self.retry(|h| display::NvAPI_GPU_GetConnectedDisplayIds(h, data.as_mut_ptr(), &mut count, flags.bits())) })
+            .and_then(|_| checked_entries(&data, "pDisplayIdCount", count as _).map_err(From::from))
+            .and_then(|data| data.iter().map(|v| v.convert_raw().map_err(From::from)).collect())
+    }
+
+    /// Reads the raw EDID of a display connected to this GPU: the base block followed by
+    /// any extension blocks. See `Edid::parse` to decode it.
+    ///
+    /// `display_id` comes from `display_ids_connected`; older drivers expect an output mask.
+    pub fn edid(&self, display_id: u32) -> sys::Result<Vec<u8>> {
+        trace!("gpu.edid({:#x})", display_id);
+        // the EDID can change between pages, in which case the read starts over
+        for _ in 0..3 {
+            let mut data = display::NV_EDID::zeroed();
+            let version = negotiate(Api::NvAPI_GPU_GetEDID, &mut data, |data| unsafe { self.retry(|h| display::NvAPI_GPU_GetEDID(h, display_id, data)) })?;
+            let size = match version {
+                display::NV_EDID_VER1 => (1 + data.EDID_Data[126] as usize) * edid::BLOCK_SIZE,
+                _ => data.sizeofEDID as usize,
+            };
+            if size > edid::BLOCK_SIZE * edid::MAX_BLOCKS {
+                return Err(sys::ArgumentRangeError::field("sizeofEDID", size as i64).into())
+            }
+            if version != display::NV_EDID_VER3 {
+                return Ok(data.EDID_Data[..size.min(display::NV_EDID_DATA_SIZE)].to_vec())
+            }
+
+            let id = data.edidId;
+            let mut edid = Vec::with_capacity(size);
+            while edid.len() < size {
+                if !edid.is_empty() {
+                    data.offset = edid.len() as u32;
+                    sys::status_result(unsafe { self.retry(|h| display::NvAPI_GPU_GetEDID(h, display_id, &mut data)) })?;
+                    if data.edidId != id {
+                        break
+                    }
+                }
+                let page = (size - edid.len()).min(display::NV_EDID_DATA_SIZE);
+                edid.extend_from_slice(&data.EDID_Data[..page]);
+            }
+            if edid.len() == size {
+                return Ok(edid)
+            }
+        }
+
+        Err(sys::Error::new(sys::Status::Error).with_context("EDID kept changing while being read"))
+    }
+
+    /// Overrides the EDID of a display output, such as one built with `EdidBuilder`.
+    ///
+    /// The output appears connected with this EDID until the override is removed by
+    /// passing an empty `edid`, or until reboot. Not supported on GeForce boards.
+    pub fn set_edid(&self, display_id: u32, edid: &[u8]) -> sys::Result<()> {
+        trace!("gpu.set_edid({:#x}, {} bytes)", display_id, edid.len());
+        if edid.len() > edid::BLOCK_SIZE * edid::MAX_BLOCKS {
+            return Err(sys::ArgumentRangeError::field("sizeofEDID", edid.len() as i64).into())
+        }
+
+        let mut pages = edid.chunks(display::NV_EDID_DATA_SIZE);
+        let mut data = display::NV_EDID::zeroed();
+        let first = pages.next().unwrap_or_default();
+        data.EDID_Data[..first.len()].copy_from_slice(first);
+        data.sizeofEDID = edid.len() as u32;
+        // the first version has no size, so can't remove an override, and only the newest can page
+        let oldest = match edid.len() {
+            0 => display::NV_EDID_VER2,
+            len if len > display::NV_EDID_DATA_SIZE => display::NV_EDID_VER3,
+            _ => display::NV_EDID_VER1,
+        };
+        negotiate_from(Api::NvAPI_GPU_SetEDID, &mut data, oldest, |data| unsafe { self.retry(|h| display::NvAPI_GPU_SetEDID(h, display_id, data)) })?;
+
+        for page in pages {
+            data.offset += display::NV_EDID_DATA_SIZE as u32;
+            data.EDID_Data = [0; display::NV_EDID_DATA_SIZE];
+            data.EDID_Data[..page.len()].copy_from_slice(page);
+            sys::status_result(unsafe { self.retry(|h| display::NvAPI_GPU_SetEDID(h, display_id, &mut data)) })?;
+        }
+        Ok(())
     }
 
     pub fn i2c_read(&self, display_mask: u32, port: Option<u8>, port_is_ddc: bool, address: u8, register: &[u8], bytes: &mut [u8], speed: i2c::I2cSpeed) -> sys::Result<usize> {
         trace!("i2c_read({}, {:?}, {:?}, 0x{:02x}, {:?}, {:?})", display_mask, port, port_is_ddc, address, register, speed);
         let mut data = i2c::NV_I2C_INFO::zeroed();
-        data.version = i2c::NV_I2C_INFO_VER;
         data.displayMask = display_mask;
         data.bIsDDCPort = if port_is_ddc { sys::NV_TRUE } else { sys::NV_FALSE } as _;
         data.i2cDevAddress = address << 1;
@@ -647,14 +943,13 @@ impl PhysicalGpu {
             data.bIsPortIdSet = sys::NV_TRUE as _;
         }
 
-        sys::status_result(unsafe { i2c::NvAPI_I2CRead(self.0, &mut data) })
+        negotiate_from(Api::NvAPI_I2CRead, &mut data, i2c_oldest_version(port, speed), |data| unsafe { self.retry(|h| i2c::NvAPI_I2CRead(h, data)) })
             .map(|_| data.cbSize as usize) // TODO: not actually sure if this ever changes?
     }
 
     pub fn i2c_write(&self, display_mask: u32, port: Option<u8>, port_is_ddc: bool, address: u8, register: &[u8], bytes: &[u8], speed: i2c::I2cSpeed) -> sys::Result<()> {
         trace!("i2c_write({}, {:?}, {:?}, 0x{:02x}, {:?}, {:?})", display_mask, port, port_is_ddc, address, register, speed);
         let mut data = i2c::NV_I2C_INFO::zeroed();
-        data.version = i2c::NV_I2C_INFO_VER;
         data.displayMask = display_mask;
         data.bIsDDCPort = if port_is_ddc { sys::NV_TRUE } else { sys::NV_FALSE } as _;
         data.i2cDevAddress = address << 1;
@@ -669,11 +964,62 @@ impl PhysicalGpu {
             data.bIsPortIdSet = sys::NV_TRUE as _;
         }
 
-        sys::status_result(unsafe { i2c::NvAPI_I2CWrite(self.0, &mut data) })
+        negotiate_from(Api::NvAPI_I2CWrite, &mut data, i2c_oldest_version(port, speed), |data| unsafe { self.retry(|h| i2c::NvAPI_I2CWrite(h, data)) })
             .map(drop)
     }
 }
 
+/// Older `NV_I2C_INFO` versions lack the port and speed fields, so a request that sets
+/// them mustn't fall back to those.
+fn i2c_oldest_version(port: Option<u8>, speed: i2c::I2cSpeed) -> u32 {
+    match (port, speed) {
+        (Some(..), _) => i2c::NV_I2C_INFO_VER3,
+        (None, i2c::I2cSpeed::Default) => i2c::NV_I2C_INFO_VER1,
+        (None, _) => i2c::NV_I2C_INFO_VER2,
+    }
+}
+
+fn enumerate_handles() -> sys::Result<Vec<sys::handles::NvPhysicalGpuHandle>> {
+    let mut handles = [Default::default(); sys::types::NVAPI_MAX_PHYSICAL_GPUS];
+    let mut len = 0;
+    match unsafe { gpu::NvAPI_EnumPhysicalGPUs(&mut handles, &mut len) } {
+        sys::status::NVAPI_NVIDIA_DEVICE_NOT_FOUND => Ok(Vec::new()),
+        status => sys::status_result(status)
+            .and_then(|_| checked_entries(&handles, "pGpuCount", len as _).map_err(From::from))
+            .map(|handles| handles.to_vec()),
+    }
+}
+
+/// Identifies a physical card across handle invalidation and re-enumeration.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Default, PartialOrd, Ord, PartialEq, Eq, Hash)]
+pub struct GpuIdentity {
+    pub pci: PciIdentifiers,
+    /// `None` if the driver doesn't report it.
+    pub bus_id: Option<u32>,
+    pub bus_slot_id: Option<u32>,
+    pub board_number: Option<[u8; 0x10]>,
+}
+
+impl GpuIdentity {
+    fn query(handle: sys::handles::NvPhysicalGpuHandle) -> sys::Result<Self> {
+        let mut pci = PciIdentifiers::default();
+        let mut bus_id = 0;
+        let mut bus_slot_id = 0;
+        let mut board = gpu::NV_BOARD_INFO::zeroed();
+        board.version = gpu::NV_BOARD_INFO_VER;
+        unsafe {
+            sys::status_result(gpu::NvAPI_GPU_GetPCIIdentifiers(handle, &mut pci.device_id, &mut pci.subsystem_id, &mut pci.revision_id, &mut pci.ext_device_id))?;
+            Ok(GpuIdentity {
+                pci,
+                bus_id: sys::status_result(gpu::NvAPI_GPU_GetBusId(handle, &mut bus_id)).ok().map(|_| bus_id),
+                bus_slot_id: sys::status_result(gpu::NvAPI_GPU_GetBusSlotId(handle, &mut bus_slot_id)).ok().map(|_| bus_slot_id),
+                board_number: sys::status_result(gpu::NvAPI_GPU_GetBoardInfo(handle, &mut board)).ok().map(|_| board.BoardNum),
+            })
+        }
+    }
+}
+
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Copy, Clone, Default, PartialOrd, Ord, PartialEq, Eq, Hash)]
 pub struct PciIdentifiers {
@@ -720,6 +1066,11 @@ impl PciIdentifiers {
     pub fn vendor(&self) -> Result<Vendor, sys::ArgumentRangeError> {
         Vendor::from_raw(self.vendor_id() as _)
     }
+
+    /// Like `vendor`, but preserves unknown vendors as `Vendor::Unrecognized`.
+    pub fn vendor_lossless(&self) -> Vendor {
+        Vendor::from_raw_lossless(self.vendor_id() as _)
+    }
 }
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -832,9 +1183,179 @@ impl RawConversion for display::NV_GPU_DISPLAYIDS {
 
     fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
         Ok(DisplayId {
-            connector: MonitorConnectorType::from_raw(self.connectorType)?,
+            connector: MonitorConnectorType::from_raw_lossless(self.connectorType),
             display_id: self.displayId,
             flags: DisplayIdsFlags::from_bits_truncate(self.flags),
         })
     }
 }
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash)]
+pub struct EccStatus {
+    pub supported: bool,
+    /// How a change to the configuration takes effect
+    pub configuration: EccConfiguration,
+    /// Whether ECC is active right now
+    pub enabled: bool,
+}
+
+impl RawConversion for ecc::NV_GPU_ECC_STATUS_INFO {
+    type Target = EccStatus;
+    type Error = Infallible;
+
+    fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
+        Ok(EccStatus {
+            supported: self.flags & ecc::NV_GPU_ECC_STATUS_IS_SUPPORTED != 0,
+            configuration: EccConfiguration::from_raw_lossless(self.configurationOptions),
+            enabled: self.enabledFlags & ecc::NV_GPU_ECC_STATUS_IS_ENABLED != 0,
+        })
+    }
+}
+
+/// Single-bit errors are corrected, double-bit errors are not.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, Default, PartialOrd, Ord, PartialEq, Eq, Hash)]
+pub struct EccErrorCounts {
+    pub single_bit: u64,
+    pub double_bit: u64,
+}
+
+impl RawConversion for ecc::NV_GPU_ECC_ERROR_COUNTS {
+    type Target = EccErrorCounts;
+    type Error = Infallible;
+
+    fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
+        Ok(EccErrorCounts {
+            single_bit: self.singleBitErrors,
+            double_bit: self.doubleBitErrors,
+        })
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, Default, PartialOrd, Ord, PartialEq, Eq, Hash)]
+pub struct EccErrors {
+    /// Errors since boot
+    pub current: EccErrorCounts,
+    /// Errors since the counters were last reset
+    pub aggregate: EccErrorCounts,
+}
+
+impl RawConversion for ecc::NV_GPU_ECC_ERROR_INFO {
+    type Target = EccErrors;
+    type Error = Infallible;
+
+    fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
+        Ok(EccErrors {
+            current: self.current.convert_raw()?,
+            aggregate: self.aggregate.convert_raw()?,
+        })
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash)]
+pub struct EccConfigurationInfo {
+    /// The stored setting, pending until the next reboot if it differs from `EccStatus::enabled`
+    pub enabled: bool,
+    pub enabled_by_default: bool,
+}
+
+impl RawConversion for ecc::NV_GPU_ECC_CONFIGURATION_INFO {
+    type Target = EccConfigurationInfo;
+    type Error = Infallible;
+
+    fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
+        let flags = ecc::EccConfigurationFlags::from_bits_truncate(self.flags);
+        Ok(EccConfigurationInfo {
+            enabled: flags.contains(ecc::EccConfigurationFlags::IS_ENABLED),
+            enabled_by_default: flags.contains(ecc::EccConfigurationFlags::IS_ENABLED_BY_DEFAULT),
+        })
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, Default, PartialOrd, Ord, PartialEq, Eq, Hash)]
+pub struct PcieLink {
+    /// PCIe generation, or 0 if the transfer rate isn't recognized
+    pub generation: u32,
+    pub lanes: u32,
+}
+
+impl PcieLink {
+    /// The PCIe generation that signals at `rate` MT/s per lane.
+    pub fn generation_from_transfer_rate(rate: u32) -> u32 {
+        match rate {
+            2500 => 1,
+            5000 => 2,
+            8000 => 3,
+            16000 => 4,
+            32000 => 5,
+            64000 => 6,
+            _ => 0,
+        }
+    }
+}
+
+impl fmt::Display for PcieLink {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Gen{} x{}", self.generation, self.lanes)
+    }
+}
+
+impl RawConversion for gpu::private::NV_GPU_PCIE_INFO_ENTRY {
+    type Target = PcieLink;
+    type Error = Infallible;
+
+    fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
+        Ok(PcieLink {
+            generation: PcieLink::generation_from_transfer_rate(self.transferRate),
+            lanes: self.lanes,
+        })
+    }
+}
+
+impl RawConversion for gpu::private::NV_GPU_PCIE_INFO {
+    type Target = Vec<PcieLink>;
+    type Error = Infallible;
+
+    fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
+        self.entries.iter()
+            .take_while(|entry| entry.transferRate != 0)
+            .map(RawConversion::convert_raw)
+            .collect()
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash)]
+pub struct PcieInfo {
+    pub bus_type: BusType,
+    pub bus_id: u32,
+    pub bus_slot_id: u32,
+    pub irq: u32,
+    /// The current link width
+    pub lanes: u32,
+    /// The current link generation, if the driver reports it
+    pub generation: Option<u32>,
+    /// The fastest link any performance level allows, if the driver reports it
+    pub max: Option<PcieLink>,
+}
+
+impl PcieInfo {
+    /// The link as currently trained, if the driver reports its generation.
+    pub fn current(&self) -> Option<PcieLink> {
+        self.generation.map(|generation| PcieLink { generation, lanes: self.lanes })
+    }
+
+    /// Whether the link is narrower or slower than the GPU supports, or `None` when the
+    /// driver doesn't report the limits.
+    ///
+    /// The generation also drops while the GPU idles to save power, so only a narrow
+    /// link is conclusive unless the GPU is under load.
+    pub fn is_degraded(&self) -> Option<bool> {
+        let (current, max) = (self.current()?, self.max?);
+        Some(current.lanes < max.lanes || current.generation < max.generation)
+    }
+}
@@ -0,0 +1,400 @@
+//! G-SYNC and Quadro Sync boards frame lock the displays of the GPUs plugged into them,
+//! and through house sync, the displays of several systems.
+//!
+//! A render cluster usually makes a single display the master, slaves every other
+//! display to it, and then checks `SyncDevice::is_frame_locked` on each node before
+//! starting playback.
+
+use std::ptr;
+use std::convert::Infallible;
+use log::trace;
+use serde::{Serialize, Deserialize};
+use crate::sys::{self, gsync};
+use crate::sys::handles::NvGSyncDeviceHandle;
+use crate::sys::gsync::{GSyncDisplayFlags, GSyncGpuFlags, GSyncCapabilitiesFlags, GSyncControlFlags, GSyncStatusParamsFlags};
+use crate::types::{RawConversion, checked_entries};
+use crate::gpu::PhysicalGpu;
+use crate::session::Nvapi;
+
+pub use sys::gsync::{DisplaySyncState, GSyncConnector as Connector, GSyncPolarity as Polarity, GSyncVideoMode as VideoMode, GSyncSource as SyncSource, GSyncRj45Io as Rj45Io};
+
+/// A sync board, which keeps the session it was enumerated from alive.
+///
+/// Handles are invalidated by topology changes, after which the devices must be
+/// enumerated again.
+#[derive(Debug)]
+pub struct SyncDevice {
+    handle: NvGSyncDeviceHandle,
+    session: Option<Nvapi>,
+}
+
+unsafe impl Send for SyncDevice { }
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct SyncCapabilities {
+    pub board_id: u32,
+    /// FPGA major revision
+    pub revision: u32,
+    /// FPGA minor revision
+    pub extended_revision: u32,
+    /// The most the sync signal can be multiplied or divided by, if the board can at all.
+    pub max_multiply_divide: Option<u32>,
+}
+
+/// The GPUs plugged into a sync device, and the displays attached to them.
+#[derive(Debug)]
+pub struct SyncTopology {
+    pub gpus: Vec<SyncGpu>,
+    pub displays: Vec<SyncDisplay>,
+}
+
+#[derive(Debug)]
+pub struct SyncGpu {
+    pub gpu: PhysicalGpu,
+    pub connector: Connector,
+    /// The GPU this one is connected to the device through, if it isn't plugged in directly.
+    pub proxy: Option<PhysicalGpu>,
+    /// Whether the GPU is frame locked.
+    pub synced: bool,
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct SyncDisplay {
+    pub display_id: u32,
+    /// Whether the display can be the master. Ignored by `set_sync_state`.
+    pub masterable: bool,
+    pub state: DisplaySyncState,
+}
+
+/// Whether a GPU is locked to the device's signal.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct SyncStatus {
+    pub synced: bool,
+    /// Whether the stereo phase also matches the master.
+    pub stereo_synced: bool,
+    pub signal_available: bool,
+}
+
+impl SyncStatus {
+    /// Whether the GPU is in sync with a signal that is actually there.
+    pub fn is_locked(&self) -> bool {
+        self.synced && self.signal_available
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Rj45Port {
+    pub io: Rj45Io,
+    /// Erroneously connected to an ethernet hub.
+    pub ethernet: bool,
+}
+
+/// The signals a sync device sees.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct StatusParameters {
+    /// The rate of the sync signal in 0.0001 Hz, or 0 without one.
+    pub refresh_rate: u32,
+    pub ports: [Rj45Port; gsync::NVAPI_MAX_RJ45_PER_GSYNC],
+    /// Whether a house sync signal is connected.
+    pub house_sync: bool,
+    /// The rate of the incoming house sync signal in 0.0001 Hz.
+    pub house_sync_rate: u32,
+    /// Whether the board is slaved to another board's master.
+    pub internal_slave: bool,
+}
+
+/// A delay applied to the sync signal.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct SyncDelay {
+    pub lines: u32,
+    pub pixels: u32,
+    /// The most lines the current mode allows. Read only.
+    pub max_lines: u32,
+    /// The fewest pixels the current mode allows. Read only.
+    pub min_pixels: u32,
+}
+
+/// How a sync device generates its frame lock signal.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ControlParameters {
+    /// The edges of the house sync signal that are used.
+    pub polarity: Polarity,
+    /// The kind of house sync signal.
+    pub video_mode: VideoMode,
+    /// The number of pulses between frame lock signals.
+    pub interval: u32,
+    pub source: SyncSource,
+    pub interlace: bool,
+    /// Drives house sync out of the RJ45 ports; only with the `Vsync` source.
+    pub house_sync_output: bool,
+    /// The delay between the sync signal and the GPUs' signal.
+    pub sync_skew: SyncDelay,
+    /// The delay before the master starts.
+    pub startup_delay: SyncDelay,
+}
+
+impl RawConversion for gsync::NV_GSYNC_CAPABILITIES {
+    type Target = SyncCapabilities;
+    type Error = Infallible;
+
+    fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
+        trace!("convert_raw({:#?})", self);
+        let flags = GSyncCapabilitiesFlags::from_bits_truncate(self.flags);
+        Ok(SyncCapabilities {
+            board_id: self.boardId,
+            revision: self.revision,
+            extended_revision: self.extendedRevision,
+            max_multiply_divide: match flags.contains(GSyncCapabilitiesFlags::IS_MUL_DIV_SUPPORTED) {
+                true => Some(self.maxMulDivValue),
+                false => None,
+            },
+        })
+    }
+}
+
+impl RawConversion for gsync::NV_GSYNC_DISPLAY {
+    type Target = SyncDisplay;
+    type Error = Infallible;
+
+    fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
+        Ok(SyncDisplay {
+            display_id: self.displayId,
+            masterable: GSyncDisplayFlags::from_bits_truncate(self.flags).contains(GSyncDisplayFlags::IS_MASTERABLE),
+            state: DisplaySyncState::from_raw_lossless(self.syncState),
+        })
+    }
+}
+
+impl RawConversion for gsync::NV_GSYNC_STATUS {
+    type Target = SyncStatus;
+    type Error = Infallible;
+
+    fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
+        Ok(SyncStatus {
+            synced: self.bIsSynced != 0,
+            stereo_synced: self.bIsStereoSynced != 0,
+            signal_available: self.bIsSyncSignalAvailable != 0,
+        })
+    }
+}
+
+impl RawConversion for gsync::NV_GSYNC_STATUS_PARAMS {
+    type Target = StatusParameters;
+    type Error = Infallible;
+
+    fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
+        trace!("convert_raw({:#?})", self);
+        let port = |i: usize| Rj45Port {
+            io: Rj45Io::from_raw_lossless(self.RJ45_IO[i]),
+            ethernet: self.RJ45_Ethernet[i] != 0,
+        };
+        Ok(StatusParameters {
+            refresh_rate: self.refreshRate,
+            ports: [port(0), port(1)],
+            house_sync: self.bHouseSync != 0,
+            house_sync_rate: self.houseSyncIncoming,
+            internal_slave: GSyncStatusParamsFlags::from_bits_truncate(self.flags).contains(GSyncStatusParamsFlags::INTERNAL_SLAVE),
+        })
+    }
+}
+
+impl RawConversion for gsync::NV_GSYNC_DELAY {
+    type Target = SyncDelay;
+    type Error = Infallible;
+
+    fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
+        Ok(SyncDelay {
+            lines: self.numLines,
+            pixels: self.numPixels,
+            max_lines: self.maxLines,
+            min_pixels: self.minPixels,
+        })
+    }
+}
+
+impl RawConversion for gsync::NV_GSYNC_CONTROL_PARAMS {
+    type Target = ControlParameters;
+    type Error = Infallible;
+
+    fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
+        trace!("convert_raw({:#?})", self);
+        let flags = GSyncControlFlags::from_bits_truncate(self.flags);
+        Ok(ControlParameters {
+            polarity: Polarity::from_raw_lossless(self.polarity),
+            video_mode: VideoMode::from_raw_lossless(self.vmode),
+            interval: self.interval,
+            source: SyncSource::from_raw_lossless(self.source),
+            interlace: flags.contains(GSyncControlFlags::INTERLACE_MODE),
+            house_sync_output: flags.contains(GSyncControlFlags::SYNC_SOURCE_IS_OUTPUT),
+            sync_skew: self.syncSkew.convert_raw()?,
+            startup_delay: self.startupDelay.convert_raw()?,
+        })
+    }
+}
+
+fn empty_control_params() -> gsync::NV_GSYNC_CONTROL_PARAMS {
+    let mut raw = gsync::NV_GSYNC_CONTROL_PARAMS::zeroed();
+    raw.version = gsync::NV_GSYNC_CONTROL_PARAMS_VER;
+    raw.syncSkew.version = gsync::NV_GSYNC_DELAY_VER;
+    raw.startupDelay.version = gsync::NV_GSYNC_DELAY_VER;
+    raw
+}
+
+impl SyncDevice {
+    pub fn handle(&self) -> NvGSyncDeviceHandle {
+        self.handle
+    }
+
+    /// The session this device holds on to, if it was enumerated while one was alive.
+    pub fn session(&self) -> Option<&Nvapi> {
+        self.session.as_ref()
+    }
+
+    /// Enumerates the sync devices, joining the current `Nvapi` session if there is one.
+    /// A system without any is reported as an empty list.
+    pub fn enumerate() -> sys::Result<Vec<Self>> {
+        Self::enumerate_in(Nvapi::current())
+    }
+
+    pub(crate) fn enumerate_in(session: Option<Nvapi>) -> sys::Result<Vec<Self>> {
+        trace!("gsync.enumerate()");
+        let mut handles = [Default::default(); gsync::NVAPI_MAX_GSYNC_DEVICES];
+        let mut len = 0;
+        match unsafe { gsync::NvAPI_GSync_EnumSyncDevices(&mut handles, &mut len) } {
+            sys::status::NVAPI_NVIDIA_DEVICE_NOT_FOUND => Ok(Vec::new()),
+            status => sys::status_result(status)
+                .and_then(|_| checked_entries(&handles, "gsyncCount", len as _).map_err(From::from))
+                .map(|handles| handles.iter().map(|&handle| SyncDevice {
+                    handle,
+                    session: session.clone(),
+                }).collect()),
+        }
+    }
+
+    pub fn capabilities(&self) -> sys::Result<SyncCapabilities> {
+        trace!("gsync.capabilities()");
+        let mut caps = gsync::NV_GSYNC_CAPABILITIES::zeroed();
+        caps.version = gsync::NV_GSYNC_CAPABILITIES_VER;
+        sys::status_result(unsafe { gsync::NvAPI_GSync_QueryCapabilities(self.handle, &mut caps) })
+            .and_then(|_| caps.convert_raw().map_err(From::from))
+    }
+
+    /// The GPUs plugged into the device and the displays attached to them, along with
+    /// their sync state.
+    pub fn topology(&self) -> sys::Result<SyncTopology> {
+        trace!("gsync.topology()");
+        let (mut gpu_count, mut display_count) = (0, 0);
+        sys::status_result(unsafe { gsync::NvAPI_GSync_GetTopology(self.handle, &mut gpu_count, ptr::null_mut(), &mut display_count, ptr::null_mut()) })?;
+
+        let mut gpu = gsync::NV_GSYNC_GPU::zeroed();
+        gpu.version = gsync::NV_GSYNC_GPU_VER;
+        let mut gpus = vec![gpu; gpu_count as usize];
+        let mut display = gsync::NV_GSYNC_DISPLAY::zeroed();
+        display.version = gsync::NV_GSYNC_DISPLAY_VER;
+        let mut displays = vec![display; display_count as usize];
+        sys::status_result(unsafe { gsync::NvAPI_GSync_GetTopology(self.handle, &mut gpu_count, gpus.as_mut_ptr(), &mut display_count, displays.as_mut_ptr()) })?;
+
+        let gpus = checked_entries(&gpus, "gsyncGpuCount", gpu_count as _)?;
+        let displays = checked_entries(&displays, "gsyncDisplayCount", display_count as _)?;
+        Ok(SyncTopology {
+            gpus: gpus.iter().map(|gpu| SyncGpu {
+                gpu: PhysicalGpu::from_handle(gpu.hPhysicalGpu, self.session.clone()),
+                connector: Connector::from_raw_lossless(gpu.connector),
+                proxy: match gpu.hProxyPhysicalGpu.as_ptr().is_null() {
+                    true => None,
+                    false => Some(PhysicalGpu::from_handle(gpu.hProxyPhysicalGpu, self.session.clone())),
+                },
+                synced: GSyncGpuFlags::from_bits_truncate(gpu.flags).contains(GSyncGpuFlags::IS_SYNCED),
+            }).collect(),
+            displays: displays.iter().map(|display| display.convert_raw()).collect::<Result<_, _>>()
+                .map_err(sys::Error::from)?,
+        })
+    }
+
+    /// Whether `gpu`, which must be plugged into this device, is frame locked.
+    pub fn sync_status(&self, gpu: &PhysicalGpu) -> sys::Result<SyncStatus> {
+        trace!("gsync.sync_status({:?})", gpu);
+        let mut status = gsync::NV_GSYNC_STATUS::zeroed();
+        status.version = gsync::NV_GSYNC_STATUS_VER;
+        sys::status_result(gpu.retry(|h| unsafe { gsync::NvAPI_GSync_GetSyncStatus(self.handle, h, &mut status) }))
+            .and_then(|_| status.convert_raw().map_err(From::from))
+    }
+
+    /// Whether every GPU plugged into the device is locked to an available signal.
+    pub fn is_frame_locked(&self) -> sys::Result<bool> {
+        trace!("gsync.is_frame_locked()");
+        let topology = self.topology()?;
+        if topology.gpus.is_empty() {
+            return Ok(false)
+        }
+        for gpu in &topology.gpus {
+            if !self.sync_status(&gpu.gpu)?.is_locked() {
+                return Ok(false)
+            }
+        }
+        Ok(true)
+    }
+
+    pub fn status_parameters(&self) -> sys::Result<StatusParameters> {
+        trace!("gsync.status_parameters()");
+        let mut params = gsync::NV_GSYNC_STATUS_PARAMS::zeroed();
+        params.version = gsync::NV_GSYNC_STATUS_PARAMS_VER;
+        sys::status_result(unsafe { gsync::NvAPI_GSync_GetStatusParameters(self.handle, &mut params) })
+            .and_then(|_| params.convert_raw().map_err(From::from))
+    }
+
+    pub fn control_parameters(&self) -> sys::Result<ControlParameters> {
+        trace!("gsync.control_parameters()");
+        let mut params = empty_control_params();
+        sys::status_result(unsafe { gsync::NvAPI_GSync_GetControlParameters(self.handle, &mut params) })
+            .and_then(|_| params.convert_raw().map_err(From::from))
+    }
+
+    /// Changes how the device generates its signal, which requires a master display.
+    ///
+    /// Settings that aren't part of `ControlParameters` are left as they are.
+    pub fn set_control_parameters(&self, params: &ControlParameters) -> sys::Result<()> {
+        trace!("gsync.set_control_parameters({:?})", params);
+        let mut raw = empty_control_params();
+        sys::status_result(unsafe { gsync::NvAPI_GSync_GetControlParameters(self.handle, &mut raw) })?;
+
+        raw.polarity = params.polarity.raw();
+        raw.vmode = params.video_mode.raw();
+        raw.interval = params.interval;
+        raw.source = params.source.raw();
+        let mut flags = GSyncControlFlags::from_bits_truncate(raw.flags);
+        flags.set(GSyncControlFlags::INTERLACE_MODE, params.interlace);
+        flags.set(GSyncControlFlags::SYNC_SOURCE_IS_OUTPUT, params.house_sync_output);
+        raw.flags = flags.bits();
+        raw.syncSkew.numLines = params.sync_skew.lines;
+        raw.syncSkew.numPixels = params.sync_skew.pixels;
+        raw.startupDelay.numLines = params.startup_delay.lines;
+        raw.startupDelay.numPixels = params.startup_delay.pixels;
+        sys::status_result(unsafe { gsync::NvAPI_GSync_SetControlParameters(self.handle, &mut raw) })
+    }
+}
+
+/// Sets the sync state of every display attached to a sync device in the system.
+/// Displays that aren't listed become unsynced, and at most one may be the master.
+pub fn set_sync_state(displays: &[SyncDisplay]) -> sys::Result<()> {
+    trace!("gsync.set_sync_state({:?})", displays);
+    let mut raw: Vec<_> = displays.iter().map(|display| {
+        let mut raw = gsync::NV_GSYNC_DISPLAY::zeroed();
+        raw.version = gsync::NV_GSYNC_DISPLAY_VER;
+        raw.displayId = display.display_id;
+        raw.syncState = display.state.raw();
+        raw
+    }).collect();
+    let ptr = match raw.is_empty() {
+        true => ptr::null_mut(),
+        false => raw.as_mut_ptr(),
+    };
+    sys::status_result(unsafe { gsync::NvAPI_GSync_SetSyncStateSettings(raw.len() as _, ptr, 0) })
+}
@@ -66,13 +66,13 @@ impl<G: Borrow<PhysicalGpu>> I2c<G> {
 }
 
 impl<G> i2c::Master for I2c<G> {
-    type Error = sys::Status;
+    type Error = sys::Error;
 }
 
 impl<G> i2c::Address for I2c<G> {
     fn set_slave_address(&mut self, addr: u16, tenbit: bool) -> sys::Result<()> {
         if tenbit {
-            Err(sys::Status::InvalidArgument)
+            Err(sys::Status::InvalidArgument.into())
         } else {
             self.address = addr as u8;
             Ok(())
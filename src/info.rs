@@ -43,3 +43,15 @@ pub fn unload() -> sys::Result<()> {
         sys::status_result(sys::nvapi::NvAPI_Unload())
     }
 }
+
+/// Calls a function without a typed binding as `(*mut T)`, passing `data` as the structure.
+///
+/// When `versioned`, `data` starts with a `version` field; see `sys::raw` for building it.
+///
+/// # Safety
+///
+/// `api` must take a single pointer to a structure no larger than `data`.
+pub unsafe fn call_raw(api: sys::Api, data: &mut [u8], versioned: bool) -> sys::Result<()> {
+    trace!("call_raw({}, {} bytes, {:?})", api, data.len(), versioned);
+    sys::status_result(sys::raw::call(api, None, data, versioned))
+}
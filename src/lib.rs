@@ -8,7 +8,20 @@ mod pstate;
 mod clock;
 mod thermal;
 mod gpu;
+mod logical;
+mod display;
+mod display_config;
+pub mod edid;
+pub mod mosaic;
+pub mod gsync;
+mod drs;
+mod event;
+pub mod nip;
+pub mod drs_settings;
 mod info;
+mod versioned;
+mod capabilities;
+mod session;
 #[cfg(feature = "i2c")]
 mod i2c_impl;
 
@@ -17,8 +30,21 @@ pub use pstate::*;
 pub use clock::*;
 pub use thermal::*;
 pub use gpu::*;
+pub use logical::LogicalGpu;
+pub use display::{Display, UnattachedDisplay};
+pub use display_config::{DisplayConfig, DisplayConfigChange, PathInfo, SourceMode, TargetInfo, Resolution, Timing, Rotation, Scaling, ColorFormat, SpanningOrientation, TimingOverride, DisplayConfigFlags};
+pub use edid::{Edid, EdidBuilder};
+pub use mosaic::DisplayGrid;
+pub use gsync::SyncDevice;
+pub use event::{EventSubscription, SyncEvent};
+pub use drs::{DrsSession, Profile, ProfileInfo, Application, Setting, SettingValue, SettingLocation, GpuSupport};
+pub use nip::{NipProfile, NipSetting};
+pub use drs_settings::KnownSetting;
 pub use info::*;
+pub use versioned::{negotiated_version, reset_negotiated_versions};
+pub use capabilities::*;
+pub use session::Nvapi;
 #[cfg(feature = "i2c")]
 pub use i2c_impl::*;
 
-pub use sys::{Status, Result};
+pub use sys::{Status, Error, Result};
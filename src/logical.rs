@@ -0,0 +1,85 @@
+use log::trace;
+use crate::sys::{self, gpu};
+use crate::sys::handles::NvLogicalGpuHandle;
+use crate::types::checked_entries;
+use crate::gpu::PhysicalGpu;
+use crate::session::Nvapi;
+use crate::display::Display;
+
+/// One or more physical GPUs acting in concert as a single device, as with SLI.
+///
+/// Logical handles are invalidated by topology changes, after which the logical GPUs
+/// must be enumerated again.
+#[derive(Debug)]
+pub struct LogicalGpu {
+    handle: NvLogicalGpuHandle,
+    session: Option<Nvapi>,
+}
+
+unsafe impl Send for LogicalGpu { }
+
+impl LogicalGpu {
+    pub fn handle(&self) -> NvLogicalGpuHandle {
+        self.handle
+    }
+
+    /// The session this GPU holds on to, if it was obtained while one was alive.
+    pub fn session(&self) -> Option<&Nvapi> {
+        self.session.as_ref()
+    }
+
+    /// Enumerates the logical GPUs, joining the current `Nvapi` session if there is one.
+    pub fn enumerate() -> sys::Result<Vec<Self>> {
+        Self::enumerate_in(Nvapi::current())
+    }
+
+    pub(crate) fn enumerate_in(session: Option<Nvapi>) -> sys::Result<Vec<Self>> {
+        trace!("logical.enumerate()");
+        let mut handles = [Default::default(); sys::types::NVAPI_MAX_LOGICAL_GPUS];
+        let mut len = 0;
+        match unsafe { gpu::NvAPI_EnumLogicalGPUs(&mut handles, &mut len) } {
+            sys::status::NVAPI_NVIDIA_DEVICE_NOT_FOUND => Ok(Vec::new()),
+            status => sys::status_result(status)
+                .and_then(|_| checked_entries(&handles, "pGpuCount", len as _).map_err(From::from))
+                .map(|handles| handles.iter().map(|&handle| LogicalGpu {
+                    handle,
+                    session: session.clone(),
+                }).collect()),
+        }
+    }
+
+    /// The logical GPU that `gpu` is a part of.
+    pub fn from_physical(gpu: &PhysicalGpu) -> sys::Result<Self> {
+        trace!("logical.from_physical({:?})", gpu);
+        let mut handle = Default::default();
+        sys::status_result(unsafe { gpu.retry(|h| gpu::NvAPI_GetLogicalGPUFromPhysicalGPU(h, &mut handle)) })
+            .map(|_| LogicalGpu {
+                handle,
+                session: gpu.session().cloned(),
+            })
+    }
+
+    /// The logical GPU driving an attached display.
+    pub fn from_display(display: &Display) -> sys::Result<Self> {
+        trace!("logical.from_display({:?})", display);
+        let mut handle = Default::default();
+        sys::status_result(unsafe { gpu::NvAPI_GetLogicalGPUFromDisplay(display.handle(), &mut handle) })
+            .map(|_| LogicalGpu {
+                handle,
+                session: display.session().cloned(),
+            })
+    }
+
+    /// The physical boards that make up this device.
+    pub fn physical_gpus(&self) -> sys::Result<Vec<PhysicalGpu>> {
+        trace!("logical.physical_gpus()");
+        let mut handles = [Default::default(); sys::types::NVAPI_MAX_PHYSICAL_GPUS];
+        let mut len = 0;
+        sys::status_result(unsafe { gpu::NvAPI_GetPhysicalGPUsFromLogicalGPU(self.handle, &mut handles, &mut len) })
+            .and_then(|_| checked_entries(&handles, "pGpuCount", len as _).map_err(From::from))
+            .map(|handles| handles.iter()
+                .map(|&handle| PhysicalGpu::from_handle(handle, self.session.clone()))
+                .collect()
+            )
+    }
+}
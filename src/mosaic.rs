@@ -0,0 +1,366 @@
+//! Mosaic spans a single desktop across a grid of displays, such as a video wall.
+//!
+//! The current layout is reported as a list of `DisplayGrid`s, with every display that
+//! isn't part of a Mosaic making up a 1x1 grid of its own. Grids serialize to a plain
+//! description, so a layout can be kept under version control and applied again later.
+
+use std::ptr;
+use std::convert::Infallible;
+use log::trace;
+use serde::{Serialize, Deserialize};
+use crate::sys::{self, mosaic};
+use crate::sys::mosaic::{GridTopologyFlags, DisplayTopoStatusFlags};
+use crate::types::{Range, RawConversion, checked_entries};
+use crate::Rotation;
+
+pub use sys::mosaic::{Topology, PixelShiftType, SetDisplayTopoFlags, DisplayCapsProblem, DisplayTopoWarning};
+
+/// Displays arranged in rows and columns, showing one desktop between them.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DisplayGrid {
+    pub rows: u32,
+    pub columns: u32,
+    /// Row by row, `rows * columns` of them.
+    pub displays: Vec<GridDisplay>,
+    /// The mode every display in the grid runs at.
+    pub mode: DisplayMode,
+    /// Hides the part of the desktop behind the bezels, using negative overlaps as the
+    /// gaps between displays.
+    pub bezel_correction: bool,
+    /// Immersive Gaming instead of Mosaic SLI, on Quadro boards.
+    pub immersive_gaming: bool,
+    /// Base Mosaic (Panoramic) instead of Mosaic SLI, on NVS and Quadro boards.
+    pub base_mosaic: bool,
+    pub pixel_shift: bool,
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct GridDisplay {
+    pub display_id: u32,
+    /// Pixels shared with the displays to the left and above; negative for a gap.
+    pub overlap: (i32, i32),
+    pub rotation: Rotation,
+    pub pixel_shift: PixelShiftType,
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct DisplayMode {
+    /// The size of each display, as oriented on the desktop.
+    pub width: u32,
+    pub height: u32,
+    /// Bits per pixel
+    pub color_depth: u32,
+    /// In hertz
+    pub refresh_rate: u32,
+}
+
+/// How far displays in a grid may overlap; negative values are gaps.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct OverlapLimits {
+    pub x: Range<i32>,
+    pub y: Range<i32>,
+}
+
+/// What the driver found wrong with a grid. Errors keep it from being applied, while
+/// warnings only mean it's sub-optimal.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GridStatus {
+    pub errors: DisplayCapsProblem,
+    pub warnings: DisplayTopoWarning,
+    pub displays: Vec<DisplayStatus>,
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct DisplayStatus {
+    pub display_id: u32,
+    pub errors: DisplayCapsProblem,
+    pub warnings: DisplayTopoWarning,
+    pub supports_rotation: bool,
+}
+
+impl GridStatus {
+    /// Whether neither the grid nor any of its displays has errors.
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty() && self.displays.iter().all(|display| display.errors.is_empty())
+    }
+}
+
+/// The part of the desktop each display in a grid shows, with inclusive edges.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Viewport {
+    pub left: u32,
+    pub top: u32,
+    pub right: u32,
+    pub bottom: u32,
+}
+
+impl RawConversion for mosaic::NV_MOSAIC_GRID_TOPO {
+    type Target = DisplayGrid;
+    type Error = sys::ArgumentRangeError;
+
+    fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
+        trace!("convert_raw({:#?})", self);
+        let flags = GridTopologyFlags::from_bits_truncate(self.flags);
+        Ok(DisplayGrid {
+            rows: self.rows,
+            columns: self.columns,
+            displays: checked_entries(&self.displays, "displayCount", self.displayCount as _)?.iter().map(|display| GridDisplay {
+                display_id: display.displayId,
+                overlap: (display.overlapX, display.overlapY),
+                rotation: Rotation::from_raw_lossless(display.rotation),
+                pixel_shift: PixelShiftType::from_raw_lossless(display.pixelShiftType),
+            }).collect(),
+            mode: DisplayMode {
+                width: self.displaySettings.width,
+                height: self.displaySettings.height,
+                color_depth: self.displaySettings.bpp,
+                refresh_rate: self.displaySettings.freq,
+            },
+            bezel_correction: flags.contains(GridTopologyFlags::APPLY_WITH_BEZEL_CORRECT),
+            immersive_gaming: flags.contains(GridTopologyFlags::IMMERSIVE_GAMING),
+            base_mosaic: flags.contains(GridTopologyFlags::BASE_MOSAIC),
+            pixel_shift: flags.contains(GridTopologyFlags::PIXEL_SHIFT),
+        })
+    }
+}
+
+impl RawConversion for mosaic::NV_MOSAIC_DISPLAY_TOPO_STATUS {
+    type Target = GridStatus;
+    type Error = sys::ArgumentRangeError;
+
+    fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
+        trace!("convert_raw({:#?})", self);
+        Ok(GridStatus {
+            errors: DisplayCapsProblem::from_bits_truncate(self.errorFlags),
+            warnings: DisplayTopoWarning::from_bits_truncate(self.warningFlags),
+            displays: checked_entries(&self.displays, "displayCount", self.displayCount as _)?.iter().map(|display| DisplayStatus {
+                display_id: display.displayId,
+                errors: DisplayCapsProblem::from_bits_truncate(display.errorFlags),
+                warnings: DisplayTopoWarning::from_bits_truncate(display.warningFlags),
+                supports_rotation: DisplayTopoStatusFlags::from_bits_truncate(display.flags).contains(DisplayTopoStatusFlags::SUPPORTS_ROTATION),
+            }).collect(),
+        })
+    }
+}
+
+impl RawConversion for mosaic::NV_MOSAIC_DISPLAY_SETTING {
+    type Target = DisplayMode;
+    type Error = Infallible;
+
+    fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
+        Ok(DisplayMode {
+            width: self.width,
+            height: self.height,
+            color_depth: self.bpp,
+            refresh_rate: self.freq,
+        })
+    }
+}
+
+impl DisplayMode {
+    fn to_raw(self) -> mosaic::NV_MOSAIC_DISPLAY_SETTING_V1 {
+        let mut raw = mosaic::NV_MOSAIC_DISPLAY_SETTING_V1::zeroed();
+        raw.version = mosaic::NVAPI_MOSAIC_DISPLAY_SETTING_VER1;
+        raw.width = self.width;
+        raw.height = self.height;
+        raw.bpp = self.color_depth;
+        raw.freq = self.refresh_rate;
+        raw
+    }
+}
+
+fn empty_grid() -> mosaic::NV_MOSAIC_GRID_TOPO {
+    let mut raw = mosaic::NV_MOSAIC_GRID_TOPO::zeroed();
+    raw.version = mosaic::NV_MOSAIC_GRID_TOPO_VER;
+    raw
+}
+
+impl DisplayGrid {
+    /// A grid of `rows` by `columns` displays, listed row by row, without any overlap.
+    pub fn new(rows: u32, columns: u32, display_ids: &[u32], mode: DisplayMode) -> Self {
+        DisplayGrid {
+            rows,
+            columns,
+            displays: display_ids.iter().map(|&display_id| GridDisplay {
+                display_id,
+                overlap: (0, 0),
+                rotation: Rotation::Degrees0,
+                pixel_shift: PixelShiftType::None,
+            }).collect(),
+            mode,
+            bezel_correction: false,
+            immersive_gaming: false,
+            base_mosaic: false,
+            pixel_shift: false,
+        }
+    }
+
+    /// The display in the given row and column.
+    pub fn display(&self, row: u32, column: u32) -> Option<&GridDisplay> {
+        match row < self.rows && column < self.columns {
+            true => self.displays.get((row * self.columns + column) as usize),
+            false => None,
+        }
+    }
+
+    /// Whether the grid spans more than a single display.
+    pub fn is_spanned(&self) -> bool {
+        self.displays.len() > 1
+    }
+
+    /// The basic Mosaic topology with the same shape, if there is one.
+    pub fn topology(&self) -> Option<Topology> {
+        Topology::basic(self.rows, self.columns)
+    }
+
+    /// The grid as passed to the driver. Grids that would overflow the display array
+    /// are rejected rather than truncated.
+    fn to_raw(&self, reload_allowed: bool) -> sys::Result<mosaic::NV_MOSAIC_GRID_TOPO> {
+        if self.displays.len() > mosaic::NV_MOSAIC_MAX_DISPLAYS {
+            return Err(sys::Status::InvalidArgument.into())
+        }
+
+        let mut raw = empty_grid();
+        raw.rows = self.rows;
+        raw.columns = self.columns;
+        raw.displayCount = self.displays.len() as _;
+        let mut flags = GridTopologyFlags::empty();
+        flags.set(GridTopologyFlags::APPLY_WITH_BEZEL_CORRECT, self.bezel_correction);
+        flags.set(GridTopologyFlags::IMMERSIVE_GAMING, self.immersive_gaming);
+        flags.set(GridTopologyFlags::BASE_MOSAIC, self.base_mosaic);
+        flags.set(GridTopologyFlags::DRIVER_RELOAD_ALLOWED, reload_allowed);
+        flags.set(GridTopologyFlags::PIXEL_SHIFT, self.pixel_shift);
+        raw.flags = flags.bits();
+        for (raw, display) in raw.displays.iter_mut().zip(&self.displays) {
+            raw.version = mosaic::NV_MOSAIC_GRID_TOPO_DISPLAY_VER;
+            raw.displayId = display.display_id;
+            raw.overlapX = display.overlap.0;
+            raw.overlapY = display.overlap.1;
+            raw.rotation = display.rotation.raw();
+            raw.pixelShiftType = display.pixel_shift.raw();
+        }
+        raw.displaySettings = self.mode.to_raw();
+        Ok(raw)
+    }
+
+    /// The modes every display in the grid supports.
+    pub fn display_modes(&self) -> sys::Result<Vec<DisplayMode>> {
+        trace!("mosaic.display_modes({:?})", self);
+        let mut grid = self.to_raw(false)?;
+        let mut count = 0;
+        sys::status_result(unsafe { mosaic::NvAPI_Mosaic_EnumDisplayModes(&mut grid, ptr::null_mut(), &mut count) })?;
+        if count == 0 {
+            return Ok(Vec::new())
+        }
+
+        let mut setting = mosaic::NV_MOSAIC_DISPLAY_SETTING::zeroed();
+        setting.version = mosaic::NVAPI_MOSAIC_DISPLAY_SETTING_VER;
+        let mut settings = vec![setting; count as usize];
+        sys::status_result(unsafe { mosaic::NvAPI_Mosaic_EnumDisplayModes(&mut grid, settings.as_mut_ptr(), &mut count) })?;
+        settings.truncate(count as usize);
+        settings.iter().map(|setting| setting.convert_raw()).collect::<Result<_, _>>()
+            .map_err(From::from)
+    }
+
+    /// How far displays may overlap in a grid of this shape and mode. Only shapes with a
+    /// basic topology are supported.
+    pub fn overlap_limits(&self) -> sys::Result<OverlapLimits> {
+        trace!("mosaic.overlap_limits({}x{})", self.rows, self.columns);
+        let topology = self.topology().ok_or(sys::Status::NotSupported)?;
+        let mut brief = mosaic::NV_MOSAIC_TOPO_BRIEF::zeroed();
+        brief.version = mosaic::NVAPI_MOSAIC_TOPO_BRIEF_VER;
+        brief.topo = topology.raw();
+        let mut setting = mosaic::NV_MOSAIC_DISPLAY_SETTING::zeroed();
+        setting.version = mosaic::NVAPI_MOSAIC_DISPLAY_SETTING_VER;
+        setting.width = self.mode.width;
+        setting.height = self.mode.height;
+        setting.bpp = self.mode.color_depth;
+        setting.freq = self.mode.refresh_rate;
+        setting.rrx1k = self.mode.refresh_rate.saturating_mul(1000);
+
+        let mut limits = OverlapLimits {
+            x: Range { min: 0, max: 0 },
+            y: Range { min: 0, max: 0 },
+        };
+        sys::status_result(unsafe { mosaic::NvAPI_Mosaic_GetOverlapLimits(&mut brief, &mut setting,
+            &mut limits.x.min, &mut limits.x.max, &mut limits.y.min, &mut limits.y.max) })?;
+        Ok(limits)
+    }
+}
+
+/// The grids currently making up the desktop.
+pub fn enumerate() -> sys::Result<Vec<DisplayGrid>> {
+    trace!("mosaic.enumerate()");
+    let mut count = 0;
+    sys::status_result(unsafe { mosaic::NvAPI_Mosaic_EnumDisplayGrids(ptr::null_mut(), &mut count) })?;
+    if count == 0 {
+        return Ok(Vec::new())
+    }
+
+    let mut grids = vec![empty_grid(); count as usize];
+    sys::status_result(unsafe { mosaic::NvAPI_Mosaic_EnumDisplayGrids(grids.as_mut_ptr(), &mut count) })?;
+    grids.truncate(count as usize);
+    grids.iter().map(|grid| grid.convert_raw()).collect::<Result<_, _>>()
+        .map_err(From::from)
+}
+
+fn raw_grids(grids: &[DisplayGrid], flags: SetDisplayTopoFlags) -> sys::Result<Vec<mosaic::NV_MOSAIC_GRID_TOPO>> {
+    if grids.is_empty() {
+        return Err(sys::Status::InvalidArgument.into())
+    }
+
+    let reload_allowed = !flags.contains(SetDisplayTopoFlags::NO_DRIVER_RELOAD);
+    grids.iter().map(|grid| grid.to_raw(reload_allowed)).collect()
+}
+
+/// Checks what the driver thinks of `grids`, without applying them.
+pub fn validate(grids: &[DisplayGrid], flags: SetDisplayTopoFlags) -> sys::Result<Vec<GridStatus>> {
+    trace!("mosaic.validate({:?}, {:?})", grids, flags);
+    let mut raw = raw_grids(grids, flags)?;
+    let mut status = mosaic::NV_MOSAIC_DISPLAY_TOPO_STATUS::zeroed();
+    status.version = mosaic::NV_MOSAIC_DISPLAY_TOPO_STATUS_VER;
+    let mut statuses = vec![status; raw.len()];
+    sys::status_result(unsafe { mosaic::NvAPI_Mosaic_ValidateDisplayGrids(flags.bits(), raw.as_mut_ptr(), statuses.as_mut_ptr(), raw.len() as _) })?;
+    statuses.iter().map(|status| status.convert_raw()).collect::<Result<_, _>>()
+        .map_err(From::from)
+}
+
+/// Applies `grids`, replacing whatever the displays they use were showing. A single
+/// display is returned to the regular desktop by applying it as a 1x1 grid.
+///
+/// A driver reload is allowed unless `flags` contains `NO_DRIVER_RELOAD`. Changing the
+/// layout invalidates existing display handles.
+pub fn apply(grids: &[DisplayGrid], flags: SetDisplayTopoFlags) -> sys::Result<()> {
+    trace!("mosaic.apply({:?}, {:?})", grids, flags);
+    let mut raw = raw_grids(grids, flags)?;
+    sys::status_result(unsafe { mosaic::NvAPI_Mosaic_SetDisplayGrids(raw.as_mut_ptr(), raw.len() as _, flags.bits()) })
+}
+
+/// The viewports of the displays in the grid showing `display_id`, in grid order, along
+/// with whether they account for bezels.
+///
+/// `resolution` picks another desktop size for the grid, or the current one if `None`.
+pub fn viewports(display_id: u32, resolution: Option<(u32, u32)>) -> sys::Result<(Vec<Viewport>, bool)> {
+    trace!("mosaic.viewports({:#x}, {:?})", display_id, resolution);
+    let (width, height) = resolution.unwrap_or((0, 0));
+    let mut rects = [sys::types::NV_RECT::zeroed(); mosaic::NV_MOSAIC_MAX_DISPLAYS];
+    let mut bezel_corrected = 0;
+    sys::status_result(unsafe { mosaic::NvAPI_Mosaic_GetDisplayViewportsByResolution(display_id, width, height, &mut rects, &mut bezel_corrected) })?;
+
+    // the driver doesn't say how many displays the grid has, and leaves the rest zeroed
+    let count = rects.iter().rposition(|rect| rect.right != 0 || rect.bottom != 0).map_or(0, |i| i + 1);
+    let viewports = rects[..count].iter().map(|rect| Viewport {
+        left: rect.left,
+        top: rect.top,
+        right: rect.right,
+        bottom: rect.bottom,
+    }).collect();
+    Ok((viewports, bezel_corrected != 0))
+}
@@ -0,0 +1,411 @@
+//! Reading and writing driver profiles in the XML `.nip` format of NVIDIA Profile Inspector.
+//!
+//! Nothing here talks to the driver; use `Profile::export` and `DrsSession::import` to
+//! move profiles between a settings session and these types.
+
+use std::{fmt, error};
+use std::fmt::Write;
+use serde::{Serialize, Deserialize};
+use crate::drs::SettingValue;
+
+/// A profile with its executables and the settings it changes.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct NipProfile {
+    pub name: String,
+    /// File names of the executables the profile applies to.
+    pub executables: Vec<String>,
+    pub settings: Vec<NipSetting>,
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NipSetting {
+    pub id: u32,
+    /// Informational only, and may be empty.
+    pub name: String,
+    pub value: SettingValue,
+}
+
+/// Why a `.nip` file couldn't be read.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum NipError {
+    /// The data is neither UTF-8 nor UTF-16 with a byte order mark.
+    Encoding,
+    /// The XML is malformed at this line.
+    Syntax {
+        line: usize,
+        reason: &'static str,
+    },
+    /// A profile or setting lacks a required element.
+    Missing(&'static str),
+    /// An element holds a value that can't be interpreted.
+    Invalid {
+        element: &'static str,
+        value: String,
+    },
+}
+
+impl fmt::Display for NipError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            NipError::Encoding => write!(f, "profile data is not valid UTF-8 or UTF-16"),
+            NipError::Syntax { line, reason } => write!(f, "malformed profile XML at line {}: {}", line, reason),
+            NipError::Missing(element) => write!(f, "missing <{}> element", element),
+            NipError::Invalid { element, ref value } => write!(f, "invalid <{}> value {:?}", element, value),
+        }
+    }
+}
+
+impl error::Error for NipError { }
+
+const BOM: char = '\u{feff}';
+
+/// Far deeper than profiles nest, but shallow enough that a hostile file can't exhaust the
+/// stack.
+const MAX_DEPTH: usize = 64;
+
+/// Encodes profiles the way NVIDIA Profile Inspector writes them: UTF-16LE with a byte
+/// order mark.
+pub fn encode(profiles: &[NipProfile]) -> Vec<u8> {
+    let xml = to_xml(profiles);
+    Some(BOM).into_iter().chain(xml.chars()).collect::<String>()
+        .encode_utf16().flat_map(|c| c.to_le_bytes()).collect()
+}
+
+/// Decodes a `.nip` file, which may be UTF-8 or UTF-16 with a byte order mark.
+pub fn decode(data: &[u8]) -> Result<Vec<NipProfile>, NipError> {
+    let utf16 = |data: &[u8], from: fn([u8; 2]) -> u16| -> Result<String, NipError> {
+        if !data.len().is_multiple_of(2) {
+            return Err(NipError::Encoding)
+        }
+        let units: Vec<u16> = data.chunks_exact(2).map(|c| from([c[0], c[1]])).collect();
+        String::from_utf16(&units).map_err(|_| NipError::Encoding)
+    };
+    let xml = match data {
+        [0xff, 0xfe, data @ ..] => utf16(data, u16::from_le_bytes)?,
+        [0xfe, 0xff, data @ ..] => utf16(data, u16::from_be_bytes)?,
+        [0xef, 0xbb, 0xbf, data @ ..] | data => String::from_utf8(data.to_vec()).map_err(|_| NipError::Encoding)?,
+    };
+    from_xml(&xml)
+}
+
+const VALUE_DWORD: &str = "Dword";
+const VALUE_STRING: &str = "String";
+const VALUE_BINARY: &str = "Binary";
+
+fn escape(str: &str) -> String {
+    let mut escaped = String::with_capacity(str.len());
+    for c in str.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '\r' => escaped.push_str("&#xD;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn write_element(xml: &mut String, indent: usize, name: &str, text: &str) {
+    let indent = "  ".repeat(indent);
+    let _ = match text.is_empty() {
+        true => write!(xml, "{}<{} />\r\n", indent, name),
+        false => write!(xml, "{}<{}>{}</{}>\r\n", indent, name, escape(text), name),
+    };
+}
+
+/// Formats profiles as `.nip` XML.
+pub fn to_xml(profiles: &[NipProfile]) -> String {
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"utf-16\"?>\r\n");
+    xml.push_str("<ArrayOfProfile xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\" xmlns:xsd=\"http://www.w3.org/2001/XMLSchema\">\r\n");
+    for profile in profiles {
+        xml.push_str("  <Profile>\r\n");
+        write_element(&mut xml, 2, "ProfileName", &profile.name);
+        match profile.executables.is_empty() {
+            true => xml.push_str("    <Executeables />\r\n"),
+            false => {
+                xml.push_str("    <Executeables>\r\n");
+                for executable in &profile.executables {
+                    write_element(&mut xml, 3, "string", executable);
+                }
+                xml.push_str("    </Executeables>\r\n");
+            },
+        }
+        match profile.settings.is_empty() {
+            true => xml.push_str("    <Settings />\r\n"),
+            false => {
+                xml.push_str("    <Settings>\r\n");
+                for setting in &profile.settings {
+                    let (ty, value) = match setting.value {
+                        SettingValue::Dword(value) => (VALUE_DWORD, value.to_string()),
+                        SettingValue::String(ref value) => (VALUE_STRING, value.clone()),
+                        SettingValue::Binary(ref data) => (VALUE_BINARY, data.iter().fold("0x".to_owned(), |mut hex, b| {
+                            let _ = write!(hex, "{:02X}", b);
+                            hex
+                        })),
+                    };
+                    xml.push_str("      <ProfileSetting>\r\n");
+                    write_element(&mut xml, 4, "SettingNameInfo", &setting.name);
+                    write_element(&mut xml, 4, "SettingID", &setting.id.to_string());
+                    write_element(&mut xml, 4, "SettingValue", &value);
+                    write_element(&mut xml, 4, "ValueType", ty);
+                    xml.push_str("      </ProfileSetting>\r\n");
+                }
+                xml.push_str("    </Settings>\r\n");
+            },
+        }
+        xml.push_str("  </Profile>\r\n");
+    }
+    xml.push_str("</ArrayOfProfile>\r\n");
+    xml
+}
+
+/// Parses `.nip` XML. Unknown elements are ignored.
+pub fn from_xml(xml: &str) -> Result<Vec<NipProfile>, NipError> {
+    let root = Parser { xml, pos: 0, depth: 0 }.document()?;
+    if root.name != "ArrayOfProfile" {
+        return Err(NipError::Missing("ArrayOfProfile"))
+    }
+    root.children("Profile").map(profile).collect()
+}
+
+fn profile(element: &Element) -> Result<NipProfile, NipError> {
+    Ok(NipProfile {
+        name: element.child("ProfileName").ok_or(NipError::Missing("ProfileName"))?.text.clone(),
+        executables: element.child("Executeables").into_iter()
+            .flat_map(|executables| executables.children("string"))
+            .map(|executable| executable.text.clone())
+            .collect(),
+        settings: element.child("Settings").into_iter()
+            .flat_map(|settings| settings.children("ProfileSetting"))
+            .map(setting)
+            .collect::<Result<_, _>>()?,
+    })
+}
+
+fn setting(element: &Element) -> Result<NipSetting, NipError> {
+    let text = |name: &'static str| element.child(name).map(|e| &e.text[..]).ok_or(NipError::Missing(name));
+    let invalid = |element: &'static str, value: &str| NipError::Invalid {
+        element,
+        value: value.into(),
+    };
+    let dword = |element: &'static str, value: &str| {
+        let value = value.trim();
+        match value.strip_prefix("0x").or_else(|| value.strip_prefix("0X")) {
+            Some(hex) => u32::from_str_radix(hex, 16),
+            None => value.parse(),
+        }.map_err(|_| invalid(element, value))
+    };
+
+    let value = text("SettingValue")?;
+    let value = match text("ValueType")?.trim() {
+        VALUE_DWORD => SettingValue::Dword(dword("SettingValue", value)?),
+        VALUE_STRING => SettingValue::String(value.into()),
+        VALUE_BINARY => {
+            let hex = value.trim();
+            let hex = hex.strip_prefix("0x").or_else(|| hex.strip_prefix("0X")).unwrap_or(hex);
+            if hex.len() % 2 != 0 || !hex.is_ascii() {
+                return Err(invalid("SettingValue", value))
+            }
+            SettingValue::Binary((0..hex.len()).step_by(2)
+                .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| invalid("SettingValue", value)))
+                .collect::<Result<_, _>>()?)
+        },
+        ty => return Err(invalid("ValueType", ty)),
+    };
+
+    Ok(NipSetting {
+        id: dword("SettingID", text("SettingID")?)?,
+        name: element.child("SettingNameInfo").map(|e| e.text.clone()).unwrap_or_default(),
+        value,
+    })
+}
+
+/// The subset of XML that `.nip` files use: elements and text, without mixed content.
+#[derive(Debug, Default)]
+struct Element {
+    name: String,
+    text: String,
+    children: Vec<Element>,
+}
+
+impl Element {
+    fn children<'a>(&'a self, name: &'a str) -> impl Iterator<Item=&'a Element> + 'a {
+        self.children.iter().filter(move |child| child.name == name)
+    }
+
+    fn child(&self, name: &str) -> Option<&Element> {
+        self.children.iter().find(|child| child.name == name)
+    }
+}
+
+struct Parser<'a> {
+    xml: &'a str,
+    pos: usize,
+    /// How many elements enclose the current one
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, reason: &'static str) -> NipError {
+        NipError::Syntax {
+            line: 1 + self.xml[..self.pos].matches('\n').count(),
+            reason,
+        }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.xml[self.pos..]
+    }
+
+    fn skip_until(&mut self, end: &str, reason: &'static str) -> Result<&'a str, NipError> {
+        let rest = self.rest();
+        let len = rest.find(end).ok_or_else(|| self.error(reason))?;
+        self.pos += len + end.len();
+        Ok(&rest[..len])
+    }
+
+    /// Skips whitespace, comments and processing instructions.
+    fn skip_misc(&mut self) -> Result<(), NipError> {
+        loop {
+            let rest = self.rest();
+            let trimmed = rest.trim_start();
+            self.pos += rest.len() - trimmed.len();
+            if trimmed.starts_with("<?") {
+                self.skip_until("?>", "unterminated processing instruction")?;
+            } else if trimmed.starts_with("<!--") {
+                self.skip_until("-->", "unterminated comment")?;
+            } else {
+                return Ok(())
+            }
+        }
+    }
+
+    fn document(mut self) -> Result<Element, NipError> {
+        if self.rest().starts_with(BOM) {
+            self.pos += BOM.len_utf8();
+        }
+        self.skip_misc()?;
+        if self.rest().starts_with("<!DOCTYPE") {
+            return Err(self.error("document type declarations are not supported"))
+        }
+        let root = self.element()?;
+        self.skip_misc()?;
+        match self.rest().is_empty() {
+            true => Ok(root),
+            false => Err(self.error("content after the root element")),
+        }
+    }
+
+    fn name(&mut self) -> Result<&'a str, NipError> {
+        let rest = self.rest();
+        let len = rest.find(|c: char| c.is_whitespace() || c == '>' || c == '/').unwrap_or(rest.len());
+        if len == 0 {
+            return Err(self.error("expected a name"))
+        }
+        self.pos += len;
+        Ok(&rest[..len])
+    }
+
+    fn element(&mut self) -> Result<Element, NipError> {
+        if !self.rest().starts_with('<') {
+            return Err(self.error("expected an element"))
+        }
+        self.pos += 1;
+        let mut element = Element {
+            name: self.name()?.into(),
+            .. Default::default()
+        };
+
+        // attributes, which hold nothing of interest
+        let mut quote = None;
+        let len = self.rest().char_indices().find(|&(_, c)| match quote {
+            Some(q) => {
+                if c == q {
+                    quote = None;
+                }
+                false
+            },
+            None if c == '"' || c == '\'' => {
+                quote = Some(c);
+                false
+            },
+            None => c == '>',
+        }).map(|(i, _)| i).ok_or_else(|| self.error("unterminated start tag"))?;
+        let empty = self.rest()[..len].ends_with('/');
+        self.pos += len + 1;
+        if empty {
+            return Ok(element)
+        }
+
+        loop {
+            let rest = self.rest();
+            let len = rest.find('<').ok_or_else(|| self.error("unterminated element"))?;
+            element.text.push_str(&self.unescape(&rest[..len])?);
+            self.pos += len;
+
+            let rest = self.rest();
+            if rest.starts_with("</") {
+                self.pos += 2;
+                if self.name()? != element.name {
+                    return Err(self.error("mismatched end tag"))
+                }
+                self.skip_until(">", "unterminated end tag")?;
+                break
+            } else if rest.starts_with("<!--") {
+                self.skip_until("-->", "unterminated comment")?;
+            } else if rest.starts_with("<![CDATA[") {
+                self.pos += "<![CDATA[".len();
+                let data = self.skip_until("]]>", "unterminated CDATA section")?;
+                element.text.push_str(&normalize(data));
+            } else if rest.starts_with("<?") {
+                self.skip_until("?>", "unterminated processing instruction")?;
+            } else if self.depth == MAX_DEPTH {
+                return Err(self.error("elements nested too deeply"))
+            } else {
+                self.depth += 1;
+                element.children.push(self.element()?);
+                self.depth -= 1;
+            }
+        }
+
+        // whitespace between child elements isn't content
+        if !element.children.is_empty() {
+            element.text.clear();
+        }
+        Ok(element)
+    }
+
+    fn unescape(&self, text: &str) -> Result<String, NipError> {
+        let text = normalize(text);
+        let mut unescaped = String::with_capacity(text.len());
+        let mut parts = text.split('&');
+        unescaped.push_str(parts.next().unwrap_or_default());
+        for part in parts {
+            let (entity, rest) = part.split_once(';').ok_or_else(|| self.error("unterminated entity reference"))?;
+            let c = match entity {
+                "lt" => '<',
+                "gt" => '>',
+                "amp" => '&',
+                "quot" => '"',
+                "apos" => '\'',
+                _ => entity.strip_prefix("#x").map(|hex| u32::from_str_radix(hex, 16))
+                    .or_else(|| entity.strip_prefix('#').map(|dec| dec.parse()))
+                    .and_then(|c| c.ok())
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| self.error("unknown entity reference"))?,
+            };
+            unescaped.push(c);
+            unescaped.push_str(rest);
+        }
+        Ok(unescaped)
+    }
+}
+
+/// XML normalizes literal line endings, but not escaped ones.
+fn normalize(text: &str) -> String {
+    text.replace("\r\n", "\n").replace('\r', "\n")
+}
@@ -3,7 +3,7 @@ use std::convert::Infallible;
 use log::trace;
 use crate::sys::gpu::pstate;
 use crate::sys;
-use crate::types::{Microvolts, MicrovoltsDelta, Kilohertz, KilohertzDelta, Percentage, Range, Delta, RawConversion};
+use crate::types::{Microvolts, MicrovoltsDelta, Kilohertz, KilohertzDelta, Percentage, Range, Delta, RawConversion, checked_entries};
 use crate::clock::ClockDomain;
 
 pub use sys::gpu::pstate::{PstateId as PState, VoltageInfoDomain as VoltageDomain, UtilizationDomain};
@@ -76,10 +76,10 @@ impl PStateSettings {
     pub fn from_raw(settings: &pstate::NV_GPU_PERF_PSTATES20_PSTATE, num_clocks: usize, num_base_voltages: usize) -> Result<Self, sys::ArgumentRangeError> {
         trace!("convert_raw({:#?}, {:?}, {:?})", settings, num_clocks, num_base_voltages);
         Ok(PStateSettings {
-            id: PState::from_raw(settings.pstateId)?,
+            id: PState::from_raw_lossless(settings.pstateId),
             editable: settings.bIsEditable.get(),
-            clocks: settings.clocks[..num_clocks].iter().map(RawConversion::convert_raw).collect::<Result<_, _>>()?,
-            base_voltages: settings.baseVoltages[..num_base_voltages].iter().map(RawConversion::convert_raw).collect::<Result<_, _>>()?,
+            clocks: checked_entries(&settings.clocks, "numClocks", num_clocks as _)?.iter().map(RawConversion::convert_raw).collect::<Result<_, _>>()?,
+            base_voltages: checked_entries(&settings.baseVoltages, "numBaseVoltages", num_base_voltages as _)?.iter().map(RawConversion::convert_raw).collect::<Result<_, _>>()?,
         })
     }
 }
@@ -92,8 +92,8 @@ impl RawConversion for pstate::NV_GPU_PERF_PSTATES20_INFO_V2 {
         trace!("convert_raw({:#?})", self);
         Ok(PStates {
             editable: self.bIsEditable.get(),
-            pstates: self.pstates[..self.numPstates as usize].iter().map(|ps| PStateSettings::from_raw(ps, self.numClocks as _, self.numBaseVoltages as _)).collect::<Result<_, _>>()?,
-            overvolt: self.voltages[..self.numVoltages as usize].iter().map(RawConversion::convert_raw).collect::<Result<_, _>>()?,
+            pstates: checked_entries(&self.pstates, "numPstates", self.numPstates as _)?.iter().map(|ps| PStateSettings::from_raw(ps, self.numClocks as _, self.numBaseVoltages as _)).collect::<Result<_, _>>()?,
+            overvolt: checked_entries(&self.voltages, "numVoltages", self.numVoltages as _)?.iter().map(RawConversion::convert_raw).collect::<Result<_, _>>()?,
         })
     }
 }
@@ -105,7 +105,7 @@ impl RawConversion for pstate::NV_GPU_PERF_PSTATE20_BASE_VOLTAGE_ENTRY_V1 {
     fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
         trace!("convert_raw({:#?})", self);
         Ok(BaseVoltage {
-            voltage_domain: VoltageDomain::from_raw(self.domainId)?,
+            voltage_domain: VoltageDomain::from_raw_lossless(self.domainId),
             editable: self.bIsEditable.get(),
             voltage: Microvolts(self.volt_uV),
             voltage_delta: match self.voltDelta_uV.convert_raw()? {
@@ -129,20 +129,20 @@ impl RawConversion for pstate::NV_GPU_PSTATE20_CLOCK_ENTRY_V1 {
         trace!("convert_raw({:#?})", self);
         Ok(match self.data.get(pstate::PstateClockType::from_raw(self.typeId)?) {
             pstate::NV_GPU_PSTATE20_CLOCK_ENTRY_DATA_VALUE::Single(single) => ClockEntry::Single {
-                domain: ClockDomain::from_raw(self.domainId)?,
+                domain: ClockDomain::from_raw_lossless(self.domainId),
                 editable: self.bIsEditable.get(),
                 frequency_delta: self.freqDelta_kHz.convert_raw()?,
                 frequency: Kilohertz(single.freq_kHz),
             },
             pstate::NV_GPU_PSTATE20_CLOCK_ENTRY_DATA_VALUE::Range(range) => ClockEntry::Range {
-                domain: ClockDomain::from_raw(self.domainId)?,
+                domain: ClockDomain::from_raw_lossless(self.domainId),
                 editable: self.bIsEditable.get(),
                 frequency_delta: self.freqDelta_kHz.convert_raw()?,
                 frequency_range: Range {
                     min: Kilohertz(range.minFreq_kHz),
                     max: Kilohertz(range.maxFreq_kHz),
                 },
-                voltage_domain: VoltageDomain::from_raw(range.domainId)?,
+                voltage_domain: VoltageDomain::from_raw_lossless(range.domainId),
                 voltage_range: Range {
                     min: Microvolts(range.minVoltage_uV),
                     max: Microvolts(range.maxVoltage_uV),
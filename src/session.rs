@@ -0,0 +1,64 @@
+use std::sync::{Arc, Mutex, Weak};
+use log::trace;
+use crate::sys;
+use crate::gpu::PhysicalGpu;
+use crate::logical::LogicalGpu;
+
+/// Pairs one `NvAPI_Initialize` with the `NvAPI_Unload` that follows the last drop.
+#[derive(Debug)]
+struct Session;
+
+impl Drop for Session {
+    fn drop(&mut self) {
+        trace!("session.drop()");
+        let _ = crate::unload();
+    }
+}
+
+static SESSION: Mutex<Weak<Session>> = Mutex::new(Weak::new());
+
+/// A reference to the initialized library.
+///
+/// NVAPI is initialized when the first `Nvapi` is created and unloaded when the last
+/// one (including clones held by `PhysicalGpu`s) is dropped, so independent components
+/// can each hold their own without tearing the library down under each other.
+/// Calls to the free `initialize` and `unload` functions are counted separately by the
+/// driver and don't affect sessions.
+#[derive(Debug, Clone)]
+pub struct Nvapi(Arc<Session>);
+
+impl Nvapi {
+    /// Joins the current session, initializing the library if there is none.
+    pub fn new() -> sys::Result<Self> {
+        trace!("session.new()");
+        let mut session = SESSION.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(current) = session.upgrade() {
+            return Ok(Nvapi(current))
+        }
+
+        crate::initialize()?;
+        let current = Arc::new(Session);
+        *session = Arc::downgrade(&current);
+        Ok(Nvapi(current))
+    }
+
+    /// The current session, if one is alive.
+    pub fn current() -> Option<Self> {
+        SESSION.lock().unwrap_or_else(|e| e.into_inner()).upgrade().map(Nvapi)
+    }
+
+    /// Enumerates the physical GPUs in the system, each holding on to this session.
+    pub fn gpus(&self) -> sys::Result<Vec<PhysicalGpu>> {
+        PhysicalGpu::enumerate_in(Some(self.clone()))
+    }
+
+    /// Enumerates the logical GPUs in the system, each holding on to this session.
+    pub fn logical_gpus(&self) -> sys::Result<Vec<LogicalGpu>> {
+        LogicalGpu::enumerate_in(Some(self.clone()))
+    }
+
+    /// The number of live references to this session.
+    pub fn references(&self) -> usize {
+        Arc::strong_count(&self.0)
+    }
+}
@@ -1,7 +1,7 @@
 use log::trace;
 use crate::sys::gpu::{thermal, cooler};
 use crate::sys;
-use crate::types::{Percentage, Celsius, CelsiusShifted, Range, RawConversion};
+use crate::types::{Percentage, Celsius, CelsiusShifted, Range, RawConversion, checked_entries};
 
 pub use sys::gpu::thermal::{ThermalController, ThermalTarget};
 
@@ -20,13 +20,13 @@ impl RawConversion for thermal::NV_GPU_THERMAL_SETTINGS_SENSOR {
     fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
         trace!("convert_raw({:#?})", self);
         Ok(Sensor {
-            controller: ThermalController::from_raw(self.controller)?,
+            controller: ThermalController::from_raw_lossless(self.controller),
             default_temperature_range: Range {
                 min: Celsius(self.defaultMinTemp),
                 max: Celsius(self.defaultMaxTemp),
             },
             current_temperature: Celsius(self.currentTemp),
-            target: ThermalTarget::from_raw(self.target)?,
+            target: ThermalTarget::from_raw_lossless(self.target),
         })
     }
 }
@@ -37,7 +37,7 @@ impl RawConversion for thermal::NV_GPU_THERMAL_SETTINGS {
 
     fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
         trace!("convert_raw({:#?})", self);
-        self.sensor[..self.count as usize].iter().map(RawConversion::convert_raw).collect()
+        checked_entries(&self.sensor, "count", self.count as _)?.iter().map(RawConversion::convert_raw).collect()
     }
 }
 
@@ -57,7 +57,7 @@ impl RawConversion for thermal::private::NV_GPU_THERMAL_INFO_ENTRY {
     fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
         trace!("convert_raw({:#?})", self);
         Ok(ThermalInfo {
-            controller: ThermalController::from_raw(self.controller)?,
+            controller: ThermalController::from_raw_lossless(self.controller),
             unknown: self.unknown,
             temperature_range: Range {
                 min: CelsiusShifted(self.minTemp),
@@ -75,7 +75,7 @@ impl RawConversion for thermal::private::NV_GPU_THERMAL_INFO {
 
     fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
         trace!("convert_raw({:#?})", self);
-        self.entries[..self.count as usize].iter()
+        checked_entries(&self.entries, "count", self.count as _)?.iter()
             .map(RawConversion::convert_raw)
             .collect::<Result<_, _>>()
             .map(|t| (self.flags as _, t))
@@ -96,7 +96,7 @@ impl RawConversion for thermal::private::NV_GPU_CLIENT_THERMAL_POLICIES_STATUS_E
     fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
         trace!("convert_raw({:#?})", self);
         Ok(ThermalLimit {
-            controller: ThermalController::from_raw(self.controller)?,
+            controller: ThermalController::from_raw_lossless(self.controller),
             value: CelsiusShifted(self.value as _),
             flags: self.flags,
         })
@@ -109,7 +109,7 @@ impl RawConversion for thermal::private::NV_GPU_CLIENT_THERMAL_POLICIES_STATUS {
 
     fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
         trace!("convert_raw({:#?})", self);
-        self.entries[..self.flags as usize].iter()
+        checked_entries(&self.entries, "flags", self.flags as _)?.iter()
             .map(RawConversion::convert_raw)
             .collect::<Result<_, _>>()
     }
@@ -138,8 +138,8 @@ impl RawConversion for cooler::private::NV_GPU_COOLER_SETTINGS_COOLER {
     fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
         trace!("convert_raw({:#?})", self);
         Ok(Cooler {
-            kind: CoolerType::from_raw(self.type_)?,
-            controller: CoolerController::from_raw(self.controller)?,
+            kind: CoolerType::from_raw_lossless(self.type_),
+            controller: CoolerController::from_raw_lossless(self.controller),
             default_level_range: Range {
                 min: Percentage::from_raw(self.defaultMinLevel)?,
                 max: Percentage::from_raw(self.defaultMaxLevel)?,
@@ -149,11 +149,11 @@ impl RawConversion for cooler::private::NV_GPU_COOLER_SETTINGS_COOLER {
                 max: Percentage::from_raw(self.currentMaxLevel)?,
             },
             current_level: Percentage::from_raw(self.currentLevel)?,
-            default_policy: CoolerPolicy::from_raw(self.defaultPolicy)?,
-            current_policy: CoolerPolicy::from_raw(self.currentPolicy)?,
-            target: CoolerTarget::from_raw(self.target)?,
-            control: CoolerControl::from_raw(self.controlType)?,
-            active: cooler::private::CoolerActivityLevel::from_raw(self.active)?.get(),
+            default_policy: CoolerPolicy::from_raw_lossless(self.defaultPolicy),
+            current_policy: CoolerPolicy::from_raw_lossless(self.currentPolicy),
+            target: CoolerTarget::from_raw_lossless(self.target),
+            control: CoolerControl::from_raw_lossless(self.controlType),
+            active: cooler::private::CoolerActivityLevel::from_raw_lossless(self.active).get(),
         })
     }
 }
@@ -164,7 +164,7 @@ impl RawConversion for cooler::private::NV_GPU_COOLER_SETTINGS {
 
     fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
         trace!("convert_raw({:#?})", self);
-        self.cooler[..self.count as usize].iter().map(RawConversion::convert_raw).collect()
+        checked_entries(&self.cooler, "count", self.count as _)?.iter().map(RawConversion::convert_raw).collect()
     }
 }
 
@@ -182,7 +182,7 @@ impl RawConversion for cooler::private::NV_GPU_SETCOOLER_LEVEL_COOLER {
         trace!("convert_raw({:#?})", self);
         Ok(CoolerLevel {
             level: Percentage::from_raw(self.currentLevel)?,
-            policy: CoolerPolicy::from_raw(self.currentPolicy)?,
+            policy: CoolerPolicy::from_raw_lossless(self.currentPolicy),
         })
     }
 }
@@ -231,7 +231,7 @@ impl RawConversion for cooler::private::NV_GPU_COOLER_POLICY_TABLE {
     fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
         trace!("convert_raw({:#?})", self);
         Ok(CoolerPolicyTable {
-            policy: CoolerPolicy::from_raw(self.policy)?,
+            policy: CoolerPolicy::from_raw_lossless(self.policy),
             levels: self.policyCoolerLevel.iter().map(RawConversion::convert_raw).collect::<Result<_, _>>()?,
         })
     }
@@ -1,4 +1,3 @@
-use std::ffi::CStr;
 use std::{fmt, ops};
 use std::convert::Infallible;
 use serde::{Serialize, Deserialize};
@@ -11,15 +10,46 @@ pub trait RawConversion {
     fn convert_raw(&self) -> Result<Self::Target, Self::Error>;
 }
 
+/// The first `count` entries of a fixed-size array filled in by the driver.
+///
+/// Fails with an error naming `field` when the driver reports more entries than the
+/// array holds, or a negative count.
+pub(crate) fn checked_entries<'a, T>(entries: &'a [T], field: &'static str, count: i64) -> Result<&'a [T], sys::ArgumentRangeError> {
+    usize::try_from(count).ok()
+        .and_then(|len| entries.get(..len))
+        .ok_or_else(|| sys::ArgumentRangeError::field(field, count))
+}
+
 impl RawConversion for sys::types::NvAPI_ShortString {
     type Target = String;
     type Error = Infallible;
 
     fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
-        unsafe {
-            Ok(CStr::from_ptr(self.as_ptr()).to_string_lossy().into_owned())
-        }
+        // the driver isn't trusted to nul-terminate the buffer
+        let bytes: Vec<u8> = self.iter().map(|&c| c as u8).take_while(|&c| c != 0).collect();
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+}
+
+impl RawConversion for sys::types::NvAPI_UnicodeString {
+    type Target = String;
+    type Error = Infallible;
+
+    fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
+        let len = self.iter().position(|&c| c == 0).unwrap_or(self.len());
+        Ok(String::from_utf16_lossy(&self[..len]))
+    }
+}
+
+/// Encodes `str` for the driver, which needs room for a nul terminator.
+pub(crate) fn unicode_string(str: &str) -> sys::Result<sys::types::NvAPI_UnicodeString> {
+    let encoded: Vec<u16> = str.encode_utf16().collect();
+    let mut unicode = sys::types::unicode_string();
+    if encoded.len() >= unicode.len() || encoded.contains(&0) {
+        return Err(sys::Status::InvalidArgument.into())
     }
+    unicode[..encoded.len()].copy_from_slice(&encoded);
+    Ok(unicode)
 }
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -393,7 +423,7 @@ impl Percentage {
     pub fn from_raw(v: u32) -> Result<Self, sys::ArgumentRangeError> {
         match v {
             v @ 0..=100 => Ok(Percentage(v)),
-            _ => Err(sys::ArgumentRangeError),
+            _ => Err(sys::ArgumentRangeError::new()),
         }
     }
 }
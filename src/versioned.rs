@@ -0,0 +1,64 @@
+use std::collections::BTreeMap;
+use std::sync::{Mutex, MutexGuard};
+use log::trace;
+use crate::sys::{self, status, Api, NvAPI_Status};
+use crate::sys::versioned::VersionedStruct;
+
+static NEGOTIATED: Mutex<BTreeMap<Api, u32>> = Mutex::new(BTreeMap::new());
+
+fn negotiated() -> MutexGuard<'static, BTreeMap<Api, u32>> {
+    NEGOTIATED.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// The structure version the driver last accepted for `api`, if it has been called.
+pub fn negotiated_version(api: Api) -> Option<u32> {
+    negotiated().get(&api).cloned()
+}
+
+/// Forgets all negotiated versions, so the next call to each API starts over from the newest.
+pub fn reset_negotiated_versions() {
+    negotiated().clear()
+}
+
+/// Calls `f` with `data` tagged as each version of `T` in turn until the driver accepts one.
+///
+/// The version that last worked for `api` is tried first, then the rest from newest to oldest.
+/// Only `NVAPI_INCOMPATIBLE_STRUCT_VERSION` moves on to the next version; any other status
+/// is returned as is.
+pub(crate) fn negotiate<T, F>(api: Api, data: &mut T, f: F) -> sys::Result<u32> where
+    T: VersionedStruct,
+    F: FnMut(&mut T) -> NvAPI_Status,
+{
+    negotiate_from(api, data, 0, f)
+}
+
+/// Like `negotiate`, but never tries a version older than `oldest`, for requests that
+/// older versions can't express.
+pub(crate) fn negotiate_from<T, F>(api: Api, data: &mut T, oldest: u32, mut f: F) -> sys::Result<u32> where
+    T: VersionedStruct,
+    F: FnMut(&mut T) -> NvAPI_Status,
+{
+    let usable = |v: u32| sys::GET_NVAPI_VERSION(v) >= sys::GET_NVAPI_VERSION(oldest);
+    let cached = negotiated_version(api).filter(|&v| usable(v));
+    let versions = cached.into_iter()
+        .chain(T::VERSIONS.iter().cloned().filter(|&v| Some(v) != cached && usable(v)));
+
+    let mut error = None;
+    for version in versions {
+        data.set_version(version);
+        let status = f(data);
+        if status == status::NVAPI_INCOMPATIBLE_STRUCT_VERSION {
+            trace!("{:?} rejected struct version {}", api, sys::GET_NVAPI_VERSION(version));
+            error = sys::status_result(status).err();
+            continue
+        }
+
+        if status == status::NVAPI_OK {
+            negotiated().insert(api, version);
+        }
+        return sys::status_result(status).map(|_| version)
+    }
+
+    negotiated().remove(&api);
+    Err(error.unwrap_or_else(|| sys::Status::IncompatibleStructVersion.into()))
+}
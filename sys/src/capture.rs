@@ -0,0 +1,487 @@
+//! Recording and replay of NVAPI calls.
+//!
+//! While recording, every call made through the bindings in this crate is appended to a
+//! capture: the `Api` that was called, the bytes of each argument before and after the
+//! call, and the returned status. A capture can later be replayed in place of the driver,
+//! which allows higher level code to be exercised against hardware that isn't present.
+//!
+//! Pointer arguments are captured as a single pointee, except for the arrays and strings
+//! listed in `layout`, which are captured as far as their count or nul terminator extends.
+//! Functions whose arguments embed further pointers or callbacks (such as
+//! `NvAPI_I2CRead`) can't be captured faithfully, so recording them fails.
+//!
+//! Replay is served through `nvapi::set_query_interface`: every binding hands the
+//! capture a function of its own signature, and the installed interface resolves the
+//! functions that appear in the capture to those.
+//!
+//! # Format
+//!
+//! Captures are stored as text, one call per line:
+//!
+//! ```text
+//! # nvapi capture 1
+//! 0xceee8e9f NvAPI_GPU_GetFullName 0 0001000000000000/- 00000000.../4765466f...
+//! ```
+//!
+//! Each line contains the interface id, the function name (informational only), the status,
+//! and one `input/output` hex pair per argument where `-` denotes no data.
+
+use std::io::{self, Write, BufRead};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::collections::BTreeMap;
+use std::os::raw::c_void;
+use std::{fmt, mem, ptr, slice};
+use crate::status::{self, NvAPI_Status};
+use crate::nvid::Api;
+
+const HEADER: &str = "# nvapi capture 1";
+
+/// An argument that can be captured before and after a call.
+pub trait CaptureArg {
+    /// The bytes of an argument passed by value.
+    fn value(&self) -> Vec<u8> {
+        Vec::new()
+    }
+
+    /// The address a pointer argument refers to, and the size of each element behind it.
+    fn pointer(&self) -> Option<(*const u8, usize)> {
+        None
+    }
+
+    /// Whether the callee may write through the argument.
+    fn is_output(&self) -> bool {
+        false
+    }
+
+    /// The version field of a versioned NVAPI structure this argument points to.
+    ///
+    /// # Safety
+    ///
+    /// Pointers must be null or valid for reads.
+    unsafe fn struct_version(&self) -> Option<u32> {
+        None
+    }
+}
+
+macro_rules! capture_value {
+    ($($ty:ty),*) => {
+        $(
+            impl CaptureArg for $ty {
+                fn value(&self) -> Vec<u8> {
+                    self.to_ne_bytes().to_vec()
+                }
+            }
+        )*
+    };
+}
+
+capture_value! { u8, u16, u32, u64, i8, i16, i32, i64, usize }
+
+/// Versioned structures lead with a `version` field that encodes their size.
+///
+/// The size may be smaller than `T` when a newer layout is passed as an older version.
+unsafe fn struct_version<T>(ptr: *const T) -> Option<u32> {
+    if ptr.is_null() || mem::size_of::<T>() <= mem::size_of::<u32>() {
+        return None
+    }
+
+    let version = ptr::read_unaligned(ptr as *const u32);
+    let size = crate::types::GET_NVAPI_SIZE(version);
+    match size > mem::size_of::<u32>() && size <= mem::size_of::<T>() && crate::types::GET_NVAPI_VERSION(version) != 0 {
+        true => Some(version),
+        false => None,
+    }
+}
+
+impl<T> CaptureArg for *const T {
+    fn pointer(&self) -> Option<(*const u8, usize)> {
+        Some((*self as *const u8, mem::size_of::<T>()))
+    }
+
+    unsafe fn struct_version(&self) -> Option<u32> {
+        struct_version(*self)
+    }
+}
+
+impl<T> CaptureArg for *mut T {
+    fn pointer(&self) -> Option<(*const u8, usize)> {
+        Some((*self as *const u8, mem::size_of::<T>()))
+    }
+
+    fn is_output(&self) -> bool {
+        true
+    }
+
+    unsafe fn struct_version(&self) -> Option<u32> {
+        struct_version(*self as *const T)
+    }
+}
+
+/// How far the data behind a pointer argument extends.
+#[derive(Debug, Copy, Clone)]
+enum Extent {
+    /// As many elements as the argument at this index counts, by value or through a pointer.
+    Counted(usize),
+    /// A nul-terminated string.
+    Terminated,
+}
+
+/// The pointer arguments of `api` that refer to more than a single element.
+///
+/// Fails for functions that can't be captured faithfully.
+fn layout(api: Api) -> Result<&'static [(usize, Extent)], &'static str> {
+    use self::Extent::*;
+
+    Ok(match api {
+        Api::NvAPI_GPU_GetAllDisplayIds | Api::NvAPI_GPU_GetConnectedDisplayIds => &[(1, Counted(2))],
+        Api::NvAPI_Mosaic_EnumDisplayModes => &[(1, Counted(2))],
+        Api::NvAPI_Mosaic_EnumDisplayGrids | Api::NvAPI_Mosaic_SetDisplayGrids => &[(0, Counted(1))],
+        Api::NvAPI_Mosaic_ValidateDisplayGrids => &[(1, Counted(3)), (2, Counted(3))],
+        Api::NvAPI_DRS_EnumApplications | Api::NvAPI_DRS_EnumSettings => &[(4, Counted(3))],
+        Api::NvAPI_DRS_EnumAvailableSettingIds => &[(0, Counted(1))],
+        Api::NvAPI_GSync_GetTopology => &[(2, Counted(1)), (4, Counted(3))],
+        Api::NvAPI_GSync_SetSyncStateSettings => &[(1, Counted(0))],
+        Api::NvAPI_GPU_RestoreCoolerSettings | Api::NvAPI_GPU_RestoreCoolerPolicyTable => &[(1, Counted(2))],
+        Api::NvAPI_GetAssociatedNvidiaDisplayHandle | Api::NvAPI_DISP_GetAssociatedUnAttachedNvidiaDisplayHandle |
+            Api::NvAPI_DISP_GetDisplayIdByDisplayName => &[(0, Terminated)],
+        Api::NvAPI_I2CRead | Api::NvAPI_I2CWrite | Api::NvAPI_I2CReadEx | Api::NvAPI_I2CWriteEx =>
+            return Err("the I2C buffers are referenced from within NV_I2C_INFO"),
+        Api::NvAPI_DISP_GetDisplayConfig | Api::NvAPI_DISP_SetDisplayConfig =>
+            return Err("the target and mode info is referenced from within NV_DISPLAYCONFIG_PATH_INFO"),
+        Api::NvAPI_Event_RegisterCallback => return Err("callbacks can't be replayed"),
+        _ => &[],
+    })
+}
+
+/// The number of bytes each argument refers to, or 0 for values and null pointers.
+///
+/// Counts are taken before the call, so outputs are captured up to the caller's capacity.
+unsafe fn extents(layout: &[(usize, Extent)], args: &[&dyn CaptureArg]) -> Vec<usize> {
+    let count = |arg: &dyn CaptureArg| -> Option<usize> {
+        let bytes = match arg.pointer() {
+            Some((ptr, size)) if !ptr.is_null() && size == mem::size_of::<u32>() => slice::from_raw_parts(ptr, size).to_vec(),
+            Some(..) => return None,
+            None => arg.value(),
+        };
+        match bytes[..] {
+            [a, b, c, d] => Some(u32::from_ne_bytes([a, b, c, d]) as usize),
+            _ => None,
+        }
+    };
+
+    args.iter().enumerate().map(|(i, arg)| match arg.pointer() {
+        Some((ptr, _)) if ptr.is_null() => 0,
+        Some((ptr, size)) => match layout.iter().find(|&&(index, _)| index == i).map(|&(_, extent)| extent) {
+            // raw calls pass fewer arguments than the layout describes
+            Some(Extent::Counted(n)) => args.get(n).and_then(|&arg| count(arg)).map(|count| count * size).unwrap_or(size),
+            Some(Extent::Terminated) => (0..).find(|&i| *ptr.add(i) == 0).map(|len| len + 1).unwrap_or(0),
+            None => size,
+        },
+        None => 0,
+    }).collect()
+}
+
+unsafe fn capture(arg: &dyn CaptureArg, len: usize) -> Vec<u8> {
+    match arg.pointer() {
+        Some((ptr, _)) if len > 0 => slice::from_raw_parts(ptr, len).to_vec(),
+        Some(..) => Vec::new(),
+        None => arg.value(),
+    }
+}
+
+/// The state of a single argument around a call.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct CallArg {
+    pub input: Vec<u8>,
+    /// Empty for arguments that are passed by value.
+    pub output: Vec<u8>,
+}
+
+/// A single recorded call.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Call {
+    pub api: Api,
+    pub status: NvAPI_Status,
+    pub args: Vec<CallArg>,
+}
+
+/// A sequence of recorded calls.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Capture {
+    pub calls: Vec<Call>,
+}
+
+fn invalid_data<E: fmt::Display>(e: E) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, e.to_string())
+}
+
+fn write_hex<W: Write>(w: &mut W, bytes: &[u8]) -> io::Result<()> {
+    if bytes.is_empty() {
+        write!(w, "-")
+    } else {
+        bytes.iter().try_for_each(|b| write!(w, "{:02x}", b))
+    }
+}
+
+fn parse_hex(s: &str) -> io::Result<Vec<u8>> {
+    match s {
+        "-" => Ok(Vec::new()),
+        s if s.len() % 2 == 0 => (0..s.len()).step_by(2)
+            .map(|i| s.get(i..i + 2).and_then(|b| u8::from_str_radix(b, 16).ok()).ok_or_else(|| invalid_data("invalid hex data")))
+            .collect(),
+        _ => Err(invalid_data("odd-length hex data")),
+    }
+}
+
+impl Call {
+    pub fn write<W: Write>(&self, mut w: W) -> io::Result<()> {
+        write!(w, "0x{:08x} {:?} {}", self.api.id(), self.api, self.status)?;
+        for arg in &self.args {
+            write!(w, " ")?;
+            write_hex(&mut w, &arg.input)?;
+            write!(w, "/")?;
+            write_hex(&mut w, &arg.output)?;
+        }
+        writeln!(w)
+    }
+
+    pub fn parse(line: &str) -> io::Result<Self> {
+        let mut fields = line.split_whitespace();
+        let id = fields.next().ok_or_else(|| invalid_data("missing interface id"))?;
+        let id = u32::from_str_radix(id.trim_start_matches("0x"), 16).map_err(invalid_data)?;
+        let api = Api::from_id(id).map_err(|_| invalid_data(format!("unknown interface id 0x{:08x}", id)))?;
+        let _name = fields.next();
+        let status = fields.next().ok_or_else(|| invalid_data("missing status"))?
+            .parse().map_err(invalid_data)?;
+        let args = fields.map(|arg| {
+            let mut arg = arg.splitn(2, '/');
+            let input = parse_hex(arg.next().unwrap_or("-"))?;
+            let output = parse_hex(arg.next().ok_or_else(|| invalid_data("missing argument output"))?)?;
+            Ok(CallArg { input, output })
+        }).collect::<io::Result<_>>()?;
+
+        Ok(Call {
+            api,
+            status,
+            args,
+        })
+    }
+}
+
+impl Capture {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn read<R: BufRead>(r: R) -> io::Result<Self> {
+        let mut calls = Vec::new();
+        for line in r.lines() {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue
+            }
+            calls.push(Call::parse(line)?);
+        }
+
+        Ok(Capture {
+            calls,
+        })
+    }
+
+    pub fn write<W: Write>(&self, mut w: W) -> io::Result<()> {
+        writeln!(w, "{}", HEADER)?;
+        self.calls.iter().try_for_each(|call| call.write(&mut w))
+    }
+}
+
+struct Recorder {
+    writer: Box<dyn Write + Send>,
+    error: Option<io::Error>,
+}
+
+struct Replay {
+    calls: Vec<Call>,
+    used: Vec<bool>,
+    /// The query interface to return to once replay stops.
+    previous: usize,
+}
+
+impl Replay {
+    /// Picks the recorded call that best matches the given inputs.
+    ///
+    /// Prefers an unused call with identical inputs, then any call with identical inputs,
+    /// then the next unused call to the same function, and finally its most recent call.
+    fn find(&mut self, api: Api, inputs: &[Vec<u8>]) -> Option<usize> {
+        let candidates: Vec<usize> = self.calls.iter().enumerate()
+            .filter(|&(_, call)| call.api == api)
+            .map(|(i, _)| i)
+            .collect();
+        let matches = |i: usize| {
+            let args = &self.calls[i].args;
+            args.len() == inputs.len() && args.iter().zip(inputs).all(|(arg, input)| &arg.input == input)
+        };
+
+        let index = candidates.iter().cloned().find(|&i| !self.used[i] && matches(i))
+            .or_else(|| candidates.iter().cloned().find(|&i| matches(i)))
+            .or_else(|| candidates.iter().cloned().find(|&i| !self.used[i]))
+            .or_else(|| candidates.last().cloned());
+        if let Some(index) = index {
+            self.used[index] = true;
+        }
+        index
+    }
+}
+
+static RECORDING: AtomicBool = AtomicBool::new(false);
+static RECORDER: Mutex<Option<Recorder>> = Mutex::new(None);
+static REPLAY: Mutex<Option<Replay>> = Mutex::new(None);
+static REPLAY_FNS: Mutex<BTreeMap<Api, usize>> = Mutex::new(BTreeMap::new());
+
+fn lock<T>(m: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    m.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Starts appending every call made through this crate to `writer`.
+///
+/// Any previous recording is stopped first.
+pub fn start_recording<W: Write + Send + 'static>(writer: W) -> io::Result<()> {
+    stop_recording()?;
+
+    let mut writer: Box<dyn Write + Send> = Box::new(writer);
+    writeln!(writer, "{}", HEADER)?;
+    *lock(&RECORDER) = Some(Recorder {
+        writer,
+        error: None,
+    });
+    RECORDING.store(true, Ordering::SeqCst);
+    Ok(())
+}
+
+/// Stops recording, flushing the writer and reporting the first error encountered.
+///
+/// This includes any call to a function that can't be recorded faithfully.
+pub fn stop_recording() -> io::Result<()> {
+    RECORDING.store(false, Ordering::SeqCst);
+    match lock(&RECORDER).take() {
+        Some(Recorder { error: Some(e), .. }) => Err(e),
+        Some(mut recorder) => recorder.writer.flush(),
+        None => Ok(()),
+    }
+}
+
+/// Serves every subsequent call from `capture` instead of the driver.
+///
+/// This installs a query interface with `nvapi::set_query_interface`, replacing the
+/// library or any interface installed before it until `stop_replay` is called.
+/// Functions that don't appear in the capture fail with `NVAPI_NO_IMPLEMENTATION`.
+pub fn start_replay(capture: Capture) {
+    let mut replay = lock(&REPLAY);
+    let previous = match replay.take() {
+        Some(replay) => replay.previous,
+        None => crate::nvapi::query_interface_ptr(),
+    };
+    *replay = Some(Replay {
+        used: vec![false; capture.calls.len()],
+        calls: capture.calls,
+        previous,
+    });
+    unsafe {
+        crate::nvapi::set_query_interface(replay_query_interface);
+    }
+}
+
+/// Returns calls to the query interface that was in use before `start_replay`.
+pub fn stop_replay() {
+    if let Some(replay) = lock(&REPLAY).take() {
+        crate::nvapi::restore_query_interface(replay.previous);
+    }
+}
+
+extern "C" fn replay_query_interface(id: u32) -> *const c_void {
+    let api = match Api::from_id(id) {
+        Ok(api) => api,
+        Err(..) => return ptr::null(),
+    };
+    let recorded = lock(&REPLAY).as_ref()
+        .map(|replay| replay.calls.iter().any(|call| call.api == api))
+        .unwrap_or(false);
+    match lock(&REPLAY_FNS).get(&api) {
+        Some(&f) if recorded => f as *const c_void,
+        _ => ptr::null(),
+    }
+}
+
+/// Whether calls are currently served from a capture.
+#[doc(hidden)]
+pub fn is_replaying() -> bool {
+    lock(&REPLAY).is_some()
+}
+
+/// Registers the function that replays calls to `api`, which must have its signature.
+#[doc(hidden)]
+pub fn register_replay(api: Api, f: usize) {
+    lock(&REPLAY_FNS).insert(api, f);
+}
+
+#[doc(hidden)]
+pub unsafe fn replay_call(api: Api, args: &[&dyn CaptureArg]) -> NvAPI_Status {
+    let mut replay = lock(&REPLAY);
+    let replay = match replay.as_mut() {
+        Some(replay) => replay,
+        None => return status::NVAPI_NO_IMPLEMENTATION,
+    };
+    let layout = layout(api).unwrap_or(&[]);
+    let extents = extents(layout, args);
+    let inputs: Vec<_> = args.iter().zip(&extents).map(|(&arg, &len)| capture(arg, len)).collect();
+    let call = match replay.find(api, &inputs) {
+        Some(index) => &replay.calls[index],
+        None => return status::NVAPI_NO_IMPLEMENTATION,
+    };
+
+    for ((arg, recorded), &len) in args.iter().zip(&call.args).zip(&extents) {
+        if let Some((ptr, _)) = arg.pointer().filter(|_| arg.is_output() && len > 0) {
+            ptr::copy_nonoverlapping(recorded.output.as_ptr(), ptr as *mut u8, recorded.output.len().min(len));
+        }
+    }
+    call.status
+}
+
+#[doc(hidden)]
+pub unsafe fn record_call<F: FnOnce() -> NvAPI_Status>(api: Api, args: &[&dyn CaptureArg], f: F) -> NvAPI_Status {
+    if !RECORDING.load(Ordering::Relaxed) {
+        return f()
+    }
+
+    let layout = match layout(api) {
+        Ok(layout) => layout,
+        Err(reason) => {
+            if let Some(ref mut recorder) = *lock(&RECORDER) {
+                if recorder.error.is_none() {
+                    let e = format!("{:?} can't be recorded: {}", api, reason);
+                    recorder.error = Some(io::Error::other(e));
+                }
+            }
+            return f()
+        },
+    };
+    let extents = extents(layout, args);
+    let inputs: Vec<_> = args.iter().zip(&extents).map(|(&arg, &len)| capture(arg, len)).collect();
+    let status = f();
+    let call = Call {
+        api,
+        status,
+        args: args.iter().zip(&extents).zip(inputs).map(|((&arg, &len), input)| CallArg {
+            input,
+            output: if arg.is_output() { capture(arg, len) } else { Vec::new() },
+        }).collect(),
+    };
+
+    if let Some(ref mut recorder) = *lock(&RECORDER) {
+        if recorder.error.is_none() {
+            recorder.error = call.write(&mut recorder.writer).err();
+        }
+    }
+    status
+}
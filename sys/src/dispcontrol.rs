@@ -1,6 +1,7 @@
 use std::os::raw::c_char;
 use crate::status::NvAPI_Status;
 use crate::handles;
+use crate::types;
 
 nvapi! {
     pub type EnumNvidiaDisplayHandleFn = extern "C" fn(thisEnum: u32, pNvDispHandle: *mut handles::NvDisplayHandle) -> NvAPI_Status;
@@ -35,10 +36,469 @@ nvapi! {
 }
 
 nvapi! {
-    pub type DISP_GetAssociatedUnAttachedNvidiaDisplayHandleFn = extern "C" fn(szDisplayName: *const c_char, pNvUnAttachedDispHandle: *mut handles::NvDisplayHandle) -> NvAPI_Status;
+    pub type DISP_GetAssociatedUnAttachedNvidiaDisplayHandleFn = extern "C" fn(szDisplayName: *const c_char, pNvUnAttachedDispHandle: *mut handles::NvUnAttachedDisplayHandle) -> NvAPI_Status;
 
     /// This function returns the handle of an unattached NVIDIA display that is
     /// associated with the given display name (such as "\\DISPLAY1").
     pub unsafe fn NvAPI_DISP_GetAssociatedUnAttachedNvidiaDisplayHandle;
 }
 
+
+nvapi! {
+    pub type GetAssociatedNvidiaDisplayNameFn = extern "C" fn(NvDispHandle: handles::NvDisplayHandle, szDisplayName: *mut types::NvAPI_ShortString) -> NvAPI_Status;
+
+    /// For a given NVIDIA display handle, this function returns a string (such as "\\.\DISPLAY1")
+    /// to identify the display.
+    pub unsafe fn NvAPI_GetAssociatedNvidiaDisplayName;
+}
+
+nvapi! {
+    pub type GetUnAttachedAssociatedDisplayNameFn = extern "C" fn(hNvUnAttachedDisp: handles::NvUnAttachedDisplayHandle, szDisplayName: *mut types::NvAPI_ShortString) -> NvAPI_Status;
+
+    /// This function returns the display name given, for example, "\\DISPLAY1", using the
+    /// unattached NVIDIA display handle.
+    pub unsafe fn NvAPI_GetUnAttachedAssociatedDisplayName;
+}
+
+nvapi! {
+    pub type GetPhysicalGPUsFromDisplayFn = extern "C" fn(hNvDisp: handles::NvDisplayHandle, nvGPUHandle: *mut [handles::NvPhysicalGpuHandle; types::NVAPI_MAX_PHYSICAL_GPUS], pGpuCount: *mut u32) -> NvAPI_Status;
+
+    /// This function returns an array of physical GPU handles associated with the specified display.
+    ///
+    /// The array nvGPUHandle will be filled with physical GPU handle values. The returned
+    /// gpuCount determines how many entries in the array are valid.
+    ///
+    /// If the display corresponds to more than one physical GPU, the first GPU returned
+    /// is the one with the attached active output.
+    pub unsafe fn NvAPI_GetPhysicalGPUsFromDisplay;
+}
+
+nvapi! {
+    pub type GetPhysicalGPUFromUnAttachedDisplayFn = extern "C" fn(hNvUnAttachedDisp: handles::NvUnAttachedDisplayHandle, pPhysicalGpu: *mut handles::NvPhysicalGpuHandle) -> NvAPI_Status;
+
+    /// This function returns a physical GPU handle associated with the specified unattached display.
+    /// The source GPU is a physical render GPU which renders the frame buffer but may or may not drive the scan out.
+    pub unsafe fn NvAPI_GetPhysicalGPUFromUnAttachedDisplay;
+}
+
+nvapi! {
+    pub type DISP_GetDisplayIdByDisplayNameFn = extern "C" fn(displayName: *const c_char, displayId: *mut u32) -> NvAPI_Status;
+
+    /// This function retrieves the display ID of a display, given its GDI name (such as "\\.\DISPLAY1").
+    pub unsafe fn NvAPI_DISP_GetDisplayIdByDisplayName;
+}
+
+nvstruct! {
+    pub struct NV_POSITION {
+        pub x: i32,
+        pub y: i32,
+    }
+}
+
+nvstruct! {
+    pub struct NV_RESOLUTION {
+        pub width: u32,
+        pub height: u32,
+        pub colorDepth: u32,
+    }
+}
+
+nvenum! {
+    /// Rotate modes
+    pub enum NV_ROTATE / Rotation {
+        NV_ROTATE_0 / Degrees0 = 0,
+        NV_ROTATE_90 / Degrees90 = 1,
+        NV_ROTATE_180 / Degrees180 = 2,
+        NV_ROTATE_270 / Degrees270 = 3,
+        NV_ROTATE_IGNORED / Ignored = 4,
+    }
+}
+
+nvenum_display! {
+    Rotation => {
+        Degrees0 = "0°",
+        Degrees90 = "90°",
+        Degrees180 = "180°",
+        Degrees270 = "270°",
+        _ = _,
+    }
+}
+
+nvenum! {
+    /// Color formats
+    pub enum NV_FORMAT / ColorFormat {
+        NV_FORMAT_UNKNOWN / Unknown = 0,
+        NV_FORMAT_P8 / P8 = 41,
+        NV_FORMAT_R5G6B5 / R5G6B5 = 23,
+        NV_FORMAT_A8R8G8B8 / A8R8G8B8 = 21,
+        NV_FORMAT_A16B16G16R16F / A16B16G16R16F = 113,
+    }
+}
+
+nvenum_display! {
+    ColorFormat => _
+}
+
+nvenum! {
+    /// Scaling modes
+    pub enum NV_SCALING / Scaling {
+        /// No change
+        NV_SCALING_DEFAULT / Default = 0,
+        /// Balanced - Full Screen
+        NV_SCALING_GPU_SCALING_TO_CLOSEST / ToClosest = 1,
+        /// Force GPU - Full Screen
+        NV_SCALING_GPU_SCALING_TO_NATIVE / ToNative = 2,
+        /// Force GPU - Centered\No Scaling
+        NV_SCALING_GPU_SCANOUT_TO_NATIVE / ScanoutToNative = 3,
+        /// Force GPU - Aspect Ratio
+        NV_SCALING_GPU_SCALING_TO_ASPECT_SCANOUT_TO_NATIVE / ToAspectScanoutToNative = 5,
+        /// Balanced - Aspect Ratio
+        NV_SCALING_GPU_SCALING_TO_ASPECT_SCANOUT_TO_CLOSEST / ToAspectScanoutToClosest = 6,
+        /// Balanced - Centered\No Scaling
+        NV_SCALING_GPU_SCANOUT_TO_CLOSEST / ScanoutToClosest = 7,
+        /// Force GPU - Integer Scaling
+        NV_SCALING_GPU_INTEGER_ASPECT_SCALING / IntegerAspect = 8,
+        NV_SCALING_CUSTOMIZED / Customized = 255,
+    }
+}
+
+nvenum_display! {
+    Scaling => _
+}
+
+nvenum! {
+    pub enum NV_DISPLAYCONFIG_SPANNING_ORIENTATION / SpanningOrientation {
+        NV_DISPLAYCONFIG_SPAN_NONE / None = 0,
+        NV_DISPLAYCONFIG_SPAN_HORIZONTAL / Horizontal = 1,
+        NV_DISPLAYCONFIG_SPAN_VERTICAL / Vertical = 2,
+    }
+}
+
+nvenum_display! {
+    SpanningOrientation => _
+}
+
+nvenum! {
+    /// Where the timing of a target comes from.
+    pub enum NV_TIMING_OVERRIDE / TimingOverride {
+        /// get the current timing
+        NV_TIMING_OVERRIDE_CURRENT / Current = 0,
+        /// the timing the driver will use based the current policy
+        NV_TIMING_OVERRIDE_AUTO / Auto = 1,
+        /// EDID timing
+        NV_TIMING_OVERRIDE_EDID / Edid = 2,
+        /// VESA DMT timing
+        NV_TIMING_OVERRIDE_DMT / Dmt = 3,
+        /// VESA DMT timing with reduced blanking
+        NV_TIMING_OVERRIDE_DMT_RB / DmtReducedBlanking = 4,
+        /// VESA CVT timing
+        NV_TIMING_OVERRIDE_CVT / Cvt = 5,
+        /// VESA CVT timing with reduced blanking
+        NV_TIMING_OVERRIDE_CVT_RB / CvtReducedBlanking = 6,
+        /// VESA GTF timing
+        NV_TIMING_OVERRIDE_GTF / Gtf = 7,
+        /// EIA 861x pre-defined timing
+        NV_TIMING_OVERRIDE_EIA861 / Eia861 = 8,
+        /// analog SD/HDTV timing
+        NV_TIMING_OVERRIDE_ANALOG_TV / AnalogTv = 9,
+        /// NV custom timings
+        NV_TIMING_OVERRIDE_CUST / Custom = 10,
+        /// NV pre-defined timing (basically the PsF timings)
+        NV_TIMING_OVERRIDE_NV_PREDEFINED / Predefined = 11,
+        /// Override for SDI timing
+        NV_TIMING_OVERRIDE_SDI / Sdi = 12,
+    }
+}
+
+nvenum_display! {
+    TimingOverride => _
+}
+
+nvstruct! {
+    pub struct NV_TIMINGEXT {
+        /// Reserved for NVIDIA hardware-based enhancement, such as double-scan.
+        pub flag: u32,
+        /// Logical refresh rate to present
+        pub rr: u16,
+        /// Physical vertical refresh rate in 0.001Hz
+        pub rrx1k: u32,
+        /// Display aspect ratio Hi(aspect):horizontal-aspect, Low(aspect):vertical-aspect
+        pub aspect: u32,
+        /// Bit-wise pixel repetition factor: 0x1:no pixel repetition; 0x2:each pixel repeats twice horizontally,..
+        pub rep: u16,
+        /// Timing standard
+        pub status: u32,
+        /// Timing name
+        pub name: [u8; 40],
+    }
+}
+
+nvstruct! {
+    /// The very basic timing structure based on the VESA standard:
+    ///
+    /// ```text
+    ///            |<----------------------------htotal--------------------------->|
+    ///             ---------"active" video-------->|<-------blanking------>|<-----
+    ///            |<-------hvisible-------->|<-hb->|<-hfp->|<-hsw->|<-hbp->|<-hb->|
+    ///  ----------+-------------------------+      |       |       |       |      |
+    ///    A     A |                         |      |       |       |       |      |
+    ///    :     : |                         |      |       |       |       |      |
+    ///    :     : |                         |      |       |       |       |      |
+    ///    :verical|    addressable video    |      |       |       |       |      |
+    ///    :visible|                         |      |       |       |       |      |
+    ///    :     : |                         |      |       |       |       |      |
+    ///    :     : |                         |      |       |       |       |      |
+    ///  verical V |                         |      |       |       |       |      |
+    ///   total  --+-------------------------+      |       |       |       |      |
+    ///    :     vb         border                  |       |       |       |      |
+    ///    :     -----------------------------------+       |       |       |      |
+    ///    :     vfp        front porch                     |       |       |      |
+    ///    :     -------------------------------------------+       |       |      |
+    ///    :     vsw        sync width                              |       |      |
+    ///    :     ---------------------------------------------------+       |      |
+    ///    :     vbp        back porch                                      |      |
+    ///    :     -----------------------------------------------------------+      |
+    ///    V     vb         border                                                 |
+    ///  --------------------------------------------------------------------------+
+    /// ```
+    pub struct NV_TIMING {
+        /// horizontal visible
+        pub HVisible: u16,
+        /// horizontal border
+        pub HBorder: u16,
+        /// horizontal front porch
+        pub HFrontPorch: u16,
+        /// horizontal sync width
+        pub HSyncWidth: u16,
+        /// horizontal total
+        pub HTotal: u16,
+        /// horizontal sync polarity: 1-negative, 0-positive
+        pub HSyncPol: u8,
+        /// vertical visible
+        pub VVisible: u16,
+        /// vertical border
+        pub VBorder: u16,
+        /// vertical front porch
+        pub VFrontPorch: u16,
+        /// vertical sync width
+        pub VSyncWidth: u16,
+        /// vertical total
+        pub VTotal: u16,
+        /// vertical sync polarity: 1-negative, 0-positive
+        pub VSyncPol: u8,
+        /// 1-interlaced, 0-progressive
+        pub interlaced: u16,
+        /// pixel clock in 10 kHz
+        pub pclk: u32,
+        /// Extra timing information.
+        pub etc: NV_TIMINGEXT,
+    }
+}
+
+nvbits! {
+    /// Bitfield in `NV_DISPLAYCONFIG_PATH_ADVANCED_TARGET_INFO`
+    pub enum NV_DISPLAYCONFIG_PATH_ADVANCED_TARGET_INFO_FLAGS / TargetInfoFlags {
+        /// Interlaced mode flag, ignored if refreshRate == 0
+        NV_DISPLAYCONFIG_TARGET_INTERLACED / INTERLACED = 0x01,
+        /// Declares primary display in clone configuration. This is *NOT* GDI Primary.
+        /// Only one target can be primary per source. If no primary is specified, the first
+        /// target will automatically be primary.
+        NV_DISPLAYCONFIG_TARGET_PRIMARY / PRIMARY = 0x02,
+        /// Whether on this target Pan and Scan is enabled or has to be enabled. Valid only
+        /// when the target is part of clone topology.
+        NV_DISPLAYCONFIG_TARGET_PAN_AND_SCAN / PAN_AND_SCAN = 0x04,
+        NV_DISPLAYCONFIG_TARGET_DISABLE_VIRTUAL_MODE_SUPPORT / DISABLE_VIRTUAL_MODE_SUPPORT = 0x08,
+        NV_DISPLAYCONFIG_TARGET_PREFERRED_UNSCALED / PREFERRED_UNSCALED = 0x10,
+    }
+}
+
+nvstruct! {
+    pub struct NV_DISPLAYCONFIG_PATH_ADVANCED_TARGET_INFO {
+        /// (IN) structure version
+        pub version: u32,
+        /// (IN/OUT) rotation setting.
+        pub rotation: NV_ROTATE,
+        /// (IN/OUT) scaling setting.
+        pub scaling: NV_SCALING,
+        /// (IN/OUT) Refresh rate of the viewport in milliHz. 0 means the current refresh rate.
+        pub refreshRate1K: u32,
+        pub flags: NV_DISPLAYCONFIG_PATH_ADVANCED_TARGET_INFO_FLAGS,
+        /// (OUT) NV_GPU_CONNECTOR_TYPE - Applicable only for tvFormat.
+        pub connector: u32,
+        /// (IN/OUT) NV_DISPLAY_TV_FORMAT - to choose the last TV format set this value to NV_DISPLAY_TV_FORMAT_NONE.
+        pub tvFormat: u32,
+        /// (IN/OUT) Used only for CUST_TIMING.
+        pub timingOverride: NV_TIMING_OVERRIDE,
+        /// (IN/OUT) Used only for CUST_TIMING.
+        pub timing: NV_TIMING,
+    }
+}
+
+nvversion! { NV_DISPLAYCONFIG_PATH_ADVANCED_TARGET_INFO_VER1(NV_DISPLAYCONFIG_PATH_ADVANCED_TARGET_INFO = 4 * 8 + 96, 1) }
+nvversion! { NV_DISPLAYCONFIG_PATH_ADVANCED_TARGET_INFO_VER = NV_DISPLAYCONFIG_PATH_ADVANCED_TARGET_INFO_VER1 }
+
+nvstruct! {
+    pub struct NV_DISPLAYCONFIG_PATH_TARGET_INFO_V1 {
+        /// Display ID
+        pub displayId: u32,
+        /// May be NULL if no advanced settings are required. NULL for Mosaic Surround.
+        pub details: *mut NV_DISPLAYCONFIG_PATH_ADVANCED_TARGET_INFO,
+    }
+}
+
+nvstruct! {
+    pub struct NV_DISPLAYCONFIG_PATH_TARGET_INFO_V2 {
+        /// Display ID
+        pub displayId: u32,
+        /// May be NULL if no advanced settings are required
+        pub details: *mut NV_DISPLAYCONFIG_PATH_ADVANCED_TARGET_INFO,
+        /// Windows CCD target ID. Must be present only for non-NVIDIA adapter, for NVIDIA adapter this parameter is ignored.
+        pub targetId: u32,
+    }
+}
+
+pub type NV_DISPLAYCONFIG_PATH_TARGET_INFO = NV_DISPLAYCONFIG_PATH_TARGET_INFO_V2;
+
+nvbits! {
+    /// Bitfield in `NV_DISPLAYCONFIG_SOURCE_MODE_INFO`
+    pub enum NV_DISPLAYCONFIG_SOURCE_MODE_INFO_FLAGS / SourceModeFlags {
+        /// Is this source the GDI primary?
+        NV_DISPLAYCONFIG_SOURCE_GDI_PRIMARY / GDI_PRIMARY = 0x01,
+        /// Is this source the SLI focus?
+        NV_DISPLAYCONFIG_SOURCE_SLI_FOCUS / SLI_FOCUS = 0x02,
+    }
+}
+
+nvstruct! {
+    pub struct NV_DISPLAYCONFIG_SOURCE_MODE_INFO_V1 {
+        pub resolution: NV_RESOLUTION,
+        /// Ignored at present, must be NV_FORMAT_UNKNOWN (0)
+        pub colorFormat: NV_FORMAT,
+        /// Is all positions are 0 or invalid, displays will be automatically
+        /// positioned from left to right with GDI Primary at 0,0, and all
+        /// other displays in the order of the path array.
+        pub position: NV_POSITION,
+        /// Spanning is only supported on XP
+        pub spanningOrientation: NV_DISPLAYCONFIG_SPANNING_ORIENTATION,
+        pub flags: NV_DISPLAYCONFIG_SOURCE_MODE_INFO_FLAGS,
+    }
+}
+
+pub type NV_DISPLAYCONFIG_SOURCE_MODE_INFO = NV_DISPLAYCONFIG_SOURCE_MODE_INFO_V1;
+
+nvstruct! {
+    pub struct NV_DISPLAYCONFIG_PATH_INFO_V1 {
+        /// (IN) structure version
+        pub version: u32,
+        /// This field is reserved. There is ongoing debate if we need this field.
+        /// Identifies sourceIds used by Windows. If all sourceIds are 0,
+        /// these will be computed automatically.
+        pub reserved_sourceId: u32,
+        /// Number of elements in targetInfo array
+        pub targetInfoCount: u32,
+        pub targetInfo: *mut NV_DISPLAYCONFIG_PATH_TARGET_INFO_V1,
+        /// May be NULL if mode info is not important
+        pub sourceModeInfo: *mut NV_DISPLAYCONFIG_SOURCE_MODE_INFO_V1,
+    }
+}
+
+nvbits! {
+    /// Bitfield in `NV_DISPLAYCONFIG_PATH_INFO_V2`
+    pub enum NV_DISPLAYCONFIG_PATH_INFO_FLAGS / PathInfoFlags {
+        /// True for non-NVIDIA adapter.
+        NV_DISPLAYCONFIG_PATH_NON_NVIDIA_ADAPTER / NON_NVIDIA_ADAPTER = 0x01,
+    }
+}
+
+nvstruct! {
+    pub struct NV_DISPLAYCONFIG_PATH_INFO_V2 {
+        /// (IN) structure version
+        pub version: u32,
+        /// Identifies sourceId used by Windows CCD. This can be optionally set.
+        pub sourceId: u32,
+        /// Number of elements in targetInfo array
+        pub targetInfoCount: u32,
+        pub targetInfo: *mut NV_DISPLAYCONFIG_PATH_TARGET_INFO_V2,
+        /// May be NULL if mode info is not important
+        pub sourceModeInfo: *mut NV_DISPLAYCONFIG_SOURCE_MODE_INFO_V1,
+        pub flags: NV_DISPLAYCONFIG_PATH_INFO_FLAGS,
+        /// Used by Non-NVIDIA adapter for pointer to OS Adapter of LUID
+        pub pOSAdapterID: *mut std::os::raw::c_void,
+    }
+}
+
+pub type NV_DISPLAYCONFIG_PATH_INFO = NV_DISPLAYCONFIG_PATH_INFO_V2;
+
+#[cfg(target_pointer_width = "64")]
+const NV_DISPLAYCONFIG_PATH_INFO_V1_SIZE: usize = 4 * 3 + 4 + 8 * 2;
+#[cfg(target_pointer_width = "32")]
+const NV_DISPLAYCONFIG_PATH_INFO_V1_SIZE: usize = 4 * 3 + 4 * 2;
+
+#[cfg(target_pointer_width = "64")]
+const NV_DISPLAYCONFIG_PATH_INFO_V2_SIZE: usize = NV_DISPLAYCONFIG_PATH_INFO_V1_SIZE + 4 + 4 + 8;
+#[cfg(target_pointer_width = "32")]
+const NV_DISPLAYCONFIG_PATH_INFO_V2_SIZE: usize = NV_DISPLAYCONFIG_PATH_INFO_V1_SIZE + 4 + 4;
+
+nvversion! { NV_DISPLAYCONFIG_PATH_INFO_VER1(NV_DISPLAYCONFIG_PATH_INFO_V1 = NV_DISPLAYCONFIG_PATH_INFO_V1_SIZE, 1) }
+nvversion! { NV_DISPLAYCONFIG_PATH_INFO_VER2(NV_DISPLAYCONFIG_PATH_INFO_V2 = NV_DISPLAYCONFIG_PATH_INFO_V2_SIZE, 2) }
+nvversion! { NV_DISPLAYCONFIG_PATH_INFO_VER = NV_DISPLAYCONFIG_PATH_INFO_VER2 }
+
+nvbits! {
+    /// Argument to `NvAPI_DISP_SetDisplayConfig`
+    pub enum NV_DISPLAYCONFIG_FLAGS / DisplayConfigFlags {
+        /// Validates the config only, without applying it
+        NV_DISPLAYCONFIG_VALIDATE_ONLY / VALIDATE_ONLY = 0x00000001,
+        /// Save the config to the persistence storage
+        NV_DISPLAYCONFIG_SAVE_TO_PERSISTENCE / SAVE_TO_PERSISTENCE = 0x00000002,
+        /// Driver reload is permitted if necessary
+        NV_DISPLAYCONFIG_DRIVER_RELOAD_ALLOWED / DRIVER_RELOAD_ALLOWED = 0x00000004,
+        /// Refresh OS mode list.
+        NV_DISPLAYCONFIG_FORCE_MODE_ENUMERATION / FORCE_MODE_ENUMERATION = 0x00000008,
+        /// Tell OS to avoid optimizing CommitVidPn call during a modeset
+        NV_FORCE_COMMIT_VIDPN / FORCE_COMMIT_VIDPN = 0x00000010,
+    }
+}
+
+nvapi! {
+    pub type DISP_GetDisplayConfigFn = extern "C" fn(pathInfoCount: *mut u32, pathInfo: *mut NV_DISPLAYCONFIG_PATH_INFO) -> NvAPI_Status;
+
+    /// This API lets caller retrieve the current global display configuration.
+    ///
+    /// # Usage
+    ///
+    /// The caller might have to call this three times to fetch all the required configuration details as follows:
+    ///
+    /// 1. First pass: Caller should Call NvAPI_DISP_GetDisplayConfig() with pathInfo set to NULL to fetch pathInfoCount.
+    /// 2. Second pass: Allocate memory for pathInfo with respect to the number of pathInfoCount(from First pass) to fetch
+    ///    targetInfoCount. If sourceModeInfo is needed allocate memory or it can be initialized to NULL.
+    /// 3. Third pass(Optional, only required if target information is required): Allocate memory for targetInfo with respect
+    ///    to number of targetInfoCount(from Second pass).
+    ///
+    /// # Returns
+    ///
+    /// - `NVAPI_INVALID_ARGUMENT`: Invalid input parameter.
+    /// - `NVAPI_DEVICE_BUSY`: ModeSet has not yet completed. Please wait and call it again.
+    pub unsafe fn NvAPI_DISP_GetDisplayConfig;
+}
+
+nvapi! {
+    pub type DISP_SetDisplayConfigFn = extern "C" fn(pathInfoCount: u32, pathInfo: *mut NV_DISPLAYCONFIG_PATH_INFO, flags: NV_DISPLAYCONFIG_FLAGS) -> NvAPI_Status;
+
+    /// This API lets caller apply a global display configuration
+    /// across multiple GPUs.
+    ///
+    /// If all sourceIds are zero, then NvAPI will pick up sourceId's based on the following criteria :
+    ///
+    /// - If user provides sourceModeInfo then we are trying to assign 0th sourceId always to GDIPrimary.
+    ///   This is needed since active windows always moves along with 0th sourceId.
+    /// - For rest of the paths, we are incrementally assigning the sourceId per adapter basis.
+    /// - If user doesn't provide sourceModeInfo then NVAPI just picks up some default sourceId's in incremental order.
+    ///
+    /// Note : NVAPI will not intelligently choose the sourceIDs for any configs that does not need a modeset.
+    ///
+    /// # Returns
+    ///
+    /// - `NVAPI_INVALID_ARGUMENT`: Invalid input parameter.
+    /// - `NVAPI_DEVICE_BUSY`: ModeSet has not yet completed. Please wait and call it again.
+    pub unsafe fn NvAPI_DISP_SetDisplayConfig;
+}
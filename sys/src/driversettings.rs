@@ -0,0 +1,264 @@
+//! Well-known driver setting ids and values, from `NvApiDriverSettings.h`.
+//!
+//! Each setting has an id, the name the driver reports for it through
+//! `NvAPI_DRS_GetSettingNameFromId`, and either an enumeration of its values or a
+//! numeric range. All of them are DWORD settings.
+
+pub const VSYNCMODE_ID: u32 = 0x00A879CF;
+pub const VSYNCMODE_STRING: &str = "Vertical Sync";
+
+nvenum! {
+    pub enum EValues_VSYNCMODE / VsyncMode {
+        VSYNCMODE_PASSIVE / UseApplicationSetting = 0x60925292,
+        VSYNCMODE_FORCEOFF / ForceOff = 0x08416747,
+        VSYNCMODE_FORCEON / ForceOn = 0x47814940,
+        VSYNCMODE_FLIPINTERVAL2 / FlipInterval2 = 0x32610244,
+        VSYNCMODE_FLIPINTERVAL3 / FlipInterval3 = 0x71271021,
+        VSYNCMODE_FLIPINTERVAL4 / FlipInterval4 = 0x13245256,
+        /// Fast sync
+        VSYNCMODE_VIRTUAL / FastSync = 0x18888888,
+    }
+}
+
+pub const VSYNCMODE_DEFAULT: EValues_VSYNCMODE = VSYNCMODE_PASSIVE;
+
+pub const VSYNCTEARCONTROL_ID: u32 = 0x005A375C;
+pub const VSYNCTEARCONTROL_STRING: &str = "Vertical Sync Tear Control";
+
+nvenum! {
+    /// Adaptive vsync when enabled.
+    pub enum EValues_VSYNCTEARCONTROL / VsyncTearControl {
+        VSYNCTEARCONTROL_DISABLE / Standard = 0x96861077u32,
+        VSYNCTEARCONTROL_ENABLE / Adaptive = 0x99941284u32,
+    }
+}
+
+pub const VSYNCTEARCONTROL_DEFAULT: EValues_VSYNCTEARCONTROL = VSYNCTEARCONTROL_DISABLE;
+
+pub const FRL_FPS_ID: u32 = 0x10835002;
+pub const FRL_FPS_STRING: &str = "Frame Rate Limiter V3";
+/// Frames per second, or `FRL_FPS_DISABLED`.
+pub const FRL_FPS_DISABLED: u32 = 0x00000000;
+pub const FRL_FPS_MIN: u32 = 0x00000000;
+pub const FRL_FPS_MAX: u32 = 0x000003ff;
+pub const FRL_FPS_DEFAULT: u32 = FRL_FPS_DISABLED;
+
+pub const PRERENDERLIMIT_ID: u32 = 0x007BA09E;
+pub const PRERENDERLIMIT_STRING: &str = "Maximum pre-rendered frames";
+/// Frames, or `PRERENDERLIMIT_APP_CONTROLLED`.
+pub const PRERENDERLIMIT_APP_CONTROLLED: u32 = 0x00000000;
+pub const PRERENDERLIMIT_MIN: u32 = 0x00;
+pub const PRERENDERLIMIT_MAX: u32 = 0xff;
+pub const PRERENDERLIMIT_DEFAULT: u32 = PRERENDERLIMIT_APP_CONTROLLED;
+
+/// The "Low Latency Mode" of the control panel, which isn't part of the public header.
+pub const LOW_LATENCY_MODE_ID: u32 = 0x0005F543;
+pub const LOW_LATENCY_MODE_STRING: &str = "Ultra Low Latency - CPL State";
+
+nvenum! {
+    pub enum EValues_LOW_LATENCY_MODE / LowLatencyMode {
+        LOW_LATENCY_MODE_OFF / Off = 0,
+        LOW_LATENCY_MODE_ON / On = 1,
+        LOW_LATENCY_MODE_ULTRA / Ultra = 2,
+    }
+}
+
+pub const LOW_LATENCY_MODE_DEFAULT: EValues_LOW_LATENCY_MODE = LOW_LATENCY_MODE_OFF;
+
+pub const PREFERRED_PSTATE_ID: u32 = 0x1057EB71;
+pub const PREFERRED_PSTATE_STRING: &str = "Power management mode";
+
+nvenum! {
+    pub enum EValues_PREFERRED_PSTATE / PowerManagementMode {
+        PREFERRED_PSTATE_ADAPTIVE / Adaptive = 0x00000000,
+        PREFERRED_PSTATE_PREFER_MAX / PreferMaxPerformance = 0x00000001,
+        PREFERRED_PSTATE_DRIVER_CONTROLLED / DriverControlled = 0x00000002,
+        PREFERRED_PSTATE_PREFER_CONSISTENT_PERFORMANCE / PreferConsistentPerformance = 0x00000003,
+        PREFERRED_PSTATE_PREFER_MIN / PreferMinPerformance = 0x00000004,
+        PREFERRED_PSTATE_OPTIMAL_POWER / OptimalPower = 0x00000005,
+    }
+}
+
+pub const PREFERRED_PSTATE_DEFAULT: EValues_PREFERRED_PSTATE = PREFERRED_PSTATE_OPTIMAL_POWER;
+
+pub const QUALITY_ENHANCEMENTS_ID: u32 = 0x00CE2691;
+pub const QUALITY_ENHANCEMENTS_STRING: &str = "Texture filtering - Quality";
+
+nvenum! {
+    pub enum EValues_QUALITY_ENHANCEMENTS / TextureFilteringQuality {
+        QUALITY_ENHANCEMENTS_HIGHQUALITY / HighQuality = 0xFFFFFFF6u32,
+        QUALITY_ENHANCEMENTS_QUALITY / Quality = 0x00000000,
+        QUALITY_ENHANCEMENTS_PERFORMANCE / Performance = 0x0000000A,
+        QUALITY_ENHANCEMENTS_HIGHPERFORMANCE / HighPerformance = 0x00000014,
+    }
+}
+
+pub const QUALITY_ENHANCEMENTS_DEFAULT: EValues_QUALITY_ENHANCEMENTS = QUALITY_ENHANCEMENTS_QUALITY;
+
+pub const PS_TEXFILTER_NO_NEG_LODBIAS_ID: u32 = 0x0019BB68;
+pub const PS_TEXFILTER_NO_NEG_LODBIAS_STRING: &str = "Texture filtering - Negative LOD bias";
+
+nvenum! {
+    pub enum EValues_PS_TEXFILTER_NO_NEG_LODBIAS / NegativeLodBias {
+        PS_TEXFILTER_NO_NEG_LODBIAS_OFF / Allow = 0x00000000,
+        PS_TEXFILTER_NO_NEG_LODBIAS_ON / Clamp = 0x00000001,
+    }
+}
+
+pub const PS_TEXFILTER_NO_NEG_LODBIAS_DEFAULT: EValues_PS_TEXFILTER_NO_NEG_LODBIAS = PS_TEXFILTER_NO_NEG_LODBIAS_OFF;
+
+pub const PS_TEXFILTER_BILINEAR_IN_ANISO_ID: u32 = 0x0084CD70;
+pub const PS_TEXFILTER_BILINEAR_IN_ANISO_STRING: &str = "Texture filtering - Trilinear optimization";
+
+nvenum! {
+    pub enum EValues_PS_TEXFILTER_BILINEAR_IN_ANISO / TrilinearOptimization {
+        PS_TEXFILTER_BILINEAR_IN_ANISO_OFF / Off = 0x00000000,
+        PS_TEXFILTER_BILINEAR_IN_ANISO_ON / On = 0x00000001,
+    }
+}
+
+pub const PS_TEXFILTER_BILINEAR_IN_ANISO_DEFAULT: EValues_PS_TEXFILTER_BILINEAR_IN_ANISO = PS_TEXFILTER_BILINEAR_IN_ANISO_OFF;
+
+pub const PS_TEXFILTER_ANISO_OPTS2_ID: u32 = 0x00E73211;
+pub const PS_TEXFILTER_ANISO_OPTS2_STRING: &str = "Texture filtering - Anisotropic sample optimization";
+
+nvenum! {
+    pub enum EValues_PS_TEXFILTER_ANISO_OPTS2 / AnisotropicSampleOptimization {
+        PS_TEXFILTER_ANISO_OPTS2_OFF / Off = 0x00000000,
+        PS_TEXFILTER_ANISO_OPTS2_ON / On = 0x00000001,
+    }
+}
+
+pub const PS_TEXFILTER_ANISO_OPTS2_DEFAULT: EValues_PS_TEXFILTER_ANISO_OPTS2 = PS_TEXFILTER_ANISO_OPTS2_OFF;
+
+pub const ANISO_MODE_SELECTOR_ID: u32 = 0x10D2BB16;
+pub const ANISO_MODE_SELECTOR_STRING: &str = "Anisotropic filtering mode";
+
+nvenum! {
+    /// Whether `ANISO_MODE_LEVEL` overrides the application.
+    pub enum EValues_ANISO_MODE_SELECTOR / AnisotropicFilteringMode {
+        ANISO_MODE_SELECTOR_APP / Application = 0x00000000,
+        ANISO_MODE_SELECTOR_USER / User = 0x00000001,
+    }
+}
+
+pub const ANISO_MODE_SELECTOR_DEFAULT: EValues_ANISO_MODE_SELECTOR = ANISO_MODE_SELECTOR_APP;
+
+pub const ANISO_MODE_LEVEL_ID: u32 = 0x101E61A9;
+pub const ANISO_MODE_LEVEL_STRING: &str = "Anisotropic filtering setting";
+
+nvenum! {
+    pub enum EValues_ANISO_MODE_LEVEL / AnisotropicFilteringLevel {
+        ANISO_MODE_LEVEL_NONE_POINT / Point = 0x00000000,
+        ANISO_MODE_LEVEL_NONE_LINEAR / Off = 0x00000001,
+        ANISO_MODE_LEVEL_2X / X2 = 0x00000002,
+        ANISO_MODE_LEVEL_4X / X4 = 0x00000004,
+        ANISO_MODE_LEVEL_8X / X8 = 0x00000008,
+        ANISO_MODE_LEVEL_16X / X16 = 0x00000010,
+    }
+}
+
+pub const ANISO_MODE_LEVEL_DEFAULT: EValues_ANISO_MODE_LEVEL = ANISO_MODE_LEVEL_NONE_LINEAR;
+
+pub const OGL_THREAD_CONTROL_ID: u32 = 0x20C1221E;
+pub const OGL_THREAD_CONTROL_STRING: &str = "Threaded optimization";
+
+nvenum! {
+    pub enum EValues_OGL_THREAD_CONTROL / ThreadedOptimization {
+        OGL_THREAD_CONTROL_AUTO / Auto = 0x00000000,
+        OGL_THREAD_CONTROL_ENABLE / On = 0x00000001,
+        OGL_THREAD_CONTROL_DISABLE / Off = 0x00000002,
+    }
+}
+
+pub const OGL_THREAD_CONTROL_DEFAULT: EValues_OGL_THREAD_CONTROL = OGL_THREAD_CONTROL_AUTO;
+
+pub const OGL_TRIPLE_BUFFER_ID: u32 = 0x20FDD1F9;
+pub const OGL_TRIPLE_BUFFER_STRING: &str = "Triple buffering";
+
+nvenum! {
+    pub enum EValues_OGL_TRIPLE_BUFFER / TripleBuffering {
+        OGL_TRIPLE_BUFFER_DISABLED / Off = 0x00000000,
+        OGL_TRIPLE_BUFFER_ENABLED / On = 0x00000001,
+    }
+}
+
+pub const OGL_TRIPLE_BUFFER_DEFAULT: EValues_OGL_TRIPLE_BUFFER = OGL_TRIPLE_BUFFER_DISABLED;
+
+pub const VRR_APP_OVERRIDE_ID: u32 = 0x10A879CF;
+pub const VRR_APP_OVERRIDE_STRING: &str = "G-SYNC";
+
+nvenum! {
+    /// G-SYNC application mode.
+    pub enum EValues_VRR_APP_OVERRIDE / GsyncApplicationMode {
+        VRR_APP_OVERRIDE_ALLOW / Allow = 0x00000000,
+        VRR_APP_OVERRIDE_FORCE_OFF / ForceOff = 0x00000001,
+        VRR_APP_OVERRIDE_DISALLOW / Disallow = 0x00000002,
+        VRR_APP_OVERRIDE_ULMB / Ulmb = 0x00000003,
+        VRR_APP_OVERRIDE_FIXED_REFRESH / FixedRefresh = 0x00000004,
+    }
+}
+
+pub const VRR_APP_OVERRIDE_DEFAULT: EValues_VRR_APP_OVERRIDE = VRR_APP_OVERRIDE_ALLOW;
+
+pub const VRR_MODE_ID: u32 = 0x1194F158;
+pub const VRR_MODE_STRING: &str = "Enable G-SYNC globally";
+
+nvenum! {
+    /// G-SYNC global mode.
+    pub enum EValues_VRR_MODE / GsyncGlobalMode {
+        VRR_MODE_DISABLED / Disabled = 0x00000000,
+        VRR_MODE_FULLSCREEN_ONLY / FullscreenOnly = 0x00000001,
+        VRR_MODE_FULLSCREEN_AND_WINDOWED / FullscreenAndWindowed = 0x00000002,
+    }
+}
+
+pub const VRR_MODE_DEFAULT: EValues_VRR_MODE = VRR_MODE_FULLSCREEN_ONLY;
+
+pub const REFRESH_RATE_OVERRIDE_ID: u32 = 0x0064B541;
+pub const REFRESH_RATE_OVERRIDE_STRING: &str = "Preferred refresh rate";
+
+nvenum! {
+    pub enum EValues_REFRESH_RATE_OVERRIDE / PreferredRefreshRate {
+        REFRESH_RATE_OVERRIDE_APPLICATION_CONTROLLED / ApplicationControlled = 0x00000000,
+        REFRESH_RATE_OVERRIDE_HIGHEST_AVAILABLE / HighestAvailable = 0x00000001,
+    }
+}
+
+pub const REFRESH_RATE_OVERRIDE_DEFAULT: EValues_REFRESH_RATE_OVERRIDE = REFRESH_RATE_OVERRIDE_APPLICATION_CONTROLLED;
+
+pub const FXAA_ENABLE_ID: u32 = 0x1074C972;
+pub const FXAA_ENABLE_STRING: &str = "Enable FXAA";
+
+nvenum! {
+    pub enum EValues_FXAA_ENABLE / Fxaa {
+        FXAA_ENABLE_OFF / Off = 0x00000000,
+        FXAA_ENABLE_ON / On = 0x00000001,
+    }
+}
+
+pub const FXAA_ENABLE_DEFAULT: EValues_FXAA_ENABLE = FXAA_ENABLE_OFF;
+
+pub const MAXWELL_B_SAMPLE_INTERLEAVE_ID: u32 = 0x0098C1AC;
+pub const MAXWELL_B_SAMPLE_INTERLEAVE_STRING: &str = "Enable sample interleaving (MFAA)";
+
+nvenum! {
+    pub enum EValues_MAXWELL_B_SAMPLE_INTERLEAVE / Mfaa {
+        MAXWELL_B_SAMPLE_INTERLEAVE_OFF / Off = 0x00000000,
+        MAXWELL_B_SAMPLE_INTERLEAVE_ON / On = 0x00000001,
+    }
+}
+
+pub const MAXWELL_B_SAMPLE_INTERLEAVE_DEFAULT: EValues_MAXWELL_B_SAMPLE_INTERLEAVE = MAXWELL_B_SAMPLE_INTERLEAVE_OFF;
+
+pub const PS_SHADERDISKCACHE_ID: u32 = 0x00198FFF;
+pub const PS_SHADERDISKCACHE_STRING: &str = "Shader Cache";
+
+nvenum! {
+    pub enum EValues_PS_SHADERDISKCACHE / ShaderCache {
+        PS_SHADERDISKCACHE_OFF / Off = 0x00000000,
+        PS_SHADERDISKCACHE_ON / On = 0x00000001,
+    }
+}
+
+pub const PS_SHADERDISKCACHE_DEFAULT: EValues_PS_SHADERDISKCACHE = PS_SHADERDISKCACHE_ON;
@@ -0,0 +1,504 @@
+use std::fmt;
+use crate::status::NvAPI_Status;
+use crate::handles::{NvDRSSessionHandle, NvDRSProfileHandle};
+use crate::types::{NvAPI_UnicodeString, NVAPI_BINARY_DATA_MAX};
+
+pub const NVAPI_SETTING_MAX_VALUES: usize = 100;
+
+nvenum! {
+    pub enum NVDRS_SETTING_TYPE / SettingType {
+        NVDRS_DWORD_TYPE / Dword = 0,
+        NVDRS_BINARY_TYPE / Binary = 1,
+        NVDRS_STRING_TYPE / String = 2,
+        NVDRS_WSTRING_TYPE / WString = 3,
+    }
+}
+
+nvenum_display! {
+    SettingType => _
+}
+
+nvenum! {
+    /// Where a setting's current value comes from.
+    pub enum NVDRS_SETTING_LOCATION / SettingLocation {
+        NVDRS_CURRENT_PROFILE_LOCATION / CurrentProfile = 0,
+        NVDRS_GLOBAL_PROFILE_LOCATION / GlobalProfile = 1,
+        NVDRS_BASE_PROFILE_LOCATION / BaseProfile = 2,
+        NVDRS_DEFAULT_PROFILE_LOCATION / Default = 3,
+    }
+}
+
+nvenum_display! {
+    SettingLocation => _
+}
+
+nvbits! {
+    /// Bitfield in `NVDRS_PROFILE`
+    pub enum NVDRS_GPU_SUPPORT / GpuSupport {
+        NVDRS_GPU_SUPPORT_GEFORCE / GEFORCE = 0x01,
+        NVDRS_GPU_SUPPORT_QUADRO / QUADRO = 0x02,
+        NVDRS_GPU_SUPPORT_NVS / NVS = 0x04,
+    }
+}
+
+nvstruct! {
+    pub struct NVDRS_BINARY_SETTING {
+        pub valueLength: u32,
+        pub valueData: [u8; NVAPI_BINARY_DATA_MAX],
+    }
+}
+
+/// The value of a setting, interpreted according to its `NVDRS_SETTING_TYPE`.
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union NVDRS_SETTING_UNION {
+    pub u32Value: u32,
+    pub binaryValue: NVDRS_BINARY_SETTING,
+    pub wszValue: NvAPI_UnicodeString,
+}
+
+impl NVDRS_SETTING_UNION {
+    pub fn zeroed() -> Self {
+        unsafe { ::std::mem::zeroed() }
+    }
+}
+
+impl fmt::Debug for NVDRS_SETTING_UNION {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // every field starts with a u32, so this is always initialized
+        f.debug_struct("NVDRS_SETTING_UNION")
+            .field("u32Value", &unsafe { self.u32Value })
+            .finish_non_exhaustive()
+    }
+}
+
+nvstruct! {
+    pub struct NVDRS_SETTING_VALUES {
+        /// Structure Version
+        pub version: u32,
+        /// Total number of values available in a setting.
+        pub numSettingValues: u32,
+        /// Type of setting value.
+        pub settingType: NVDRS_SETTING_TYPE,
+        /// Default value of setting.
+        pub defaultValue: NVDRS_SETTING_UNION,
+        /// All the values of the setting.
+        pub settingValues: [NVDRS_SETTING_UNION; NVAPI_SETTING_MAX_VALUES],
+    }
+}
+
+const NVDRS_SETTING_UNION_SIZE: usize = 4 + NVAPI_BINARY_DATA_MAX;
+
+const NVDRS_SETTING_VALUES_SIZE: usize = 4 * 3 + NVDRS_SETTING_UNION_SIZE * (1 + NVAPI_SETTING_MAX_VALUES);
+const _: () = assert!(::std::mem::size_of::<NVDRS_SETTING_VALUES>() == NVDRS_SETTING_VALUES_SIZE);
+
+/// The structure is too large for the 16 bits a version has for its size, so this
+/// is the overflowed value `MAKE_NVAPI_VERSION` produces in C.
+pub const NVDRS_SETTING_VALUES_VER1: u32 = NVDRS_SETTING_VALUES_SIZE as u32 | 1 << 16;
+nvversion! { NVDRS_SETTING_VALUES_VER = NVDRS_SETTING_VALUES_VER1 }
+
+nvstruct! {
+    pub struct NVDRS_SETTING_V1 {
+        /// Structure Version
+        pub version: u32,
+        /// String name of setting
+        pub settingName: NvAPI_UnicodeString,
+        /// 32 bit setting Id
+        pub settingId: u32,
+        /// Type of setting value.
+        pub settingType: NVDRS_SETTING_TYPE,
+        /// Storage location of setting value.
+        pub settingLocation: NVDRS_SETTING_LOCATION,
+        /// If the setting is the predefined value.
+        pub isCurrentPredefined: u32,
+        /// If `predefined` holds a valid value.
+        pub isPredefinedValid: u32,
+        /// Setting can have these types:
+        /// DWORD, Binary, or Unicode
+        pub predefined: NVDRS_SETTING_UNION,
+        /// Setting can have these types:
+        /// DWORD, Binary, or Unicode
+        pub current: NVDRS_SETTING_UNION,
+    }
+}
+
+pub type NVDRS_SETTING = NVDRS_SETTING_V1;
+
+nvversion! { NVDRS_SETTING_VER1(NVDRS_SETTING_V1 = 4 + 4096 + 4 * 5 + NVDRS_SETTING_UNION_SIZE * 2, 1) }
+nvversion! { NVDRS_SETTING_VER = NVDRS_SETTING_VER1 }
+
+nvstruct! {
+    pub struct NVDRS_APPLICATION_V1 {
+        /// Structure Version
+        pub version: u32,
+        /// Is the application userdefined/predefined
+        pub isPredefined: u32,
+        /// String name of the Application
+        pub appName: NvAPI_UnicodeString,
+        /// UserFriendly name of the Application
+        pub userFriendlyName: NvAPI_UnicodeString,
+        /// Indicates the name (if any) of the launcher that starts the application
+        pub launcher: NvAPI_UnicodeString,
+    }
+}
+
+nvstruct! {
+    pub struct NVDRS_APPLICATION_V2 {
+        pub v1: NVDRS_APPLICATION_V1,
+        /// Select this application only if this file is found.
+        /// When specifying multiple files, separate them using the ':' character.
+        pub fileInFolder: NvAPI_UnicodeString,
+    }
+}
+
+nvbits! {
+    /// Bitfield in `NVDRS_APPLICATION_V3`
+    pub enum NVDRS_APPLICATION_FLAGS / ApplicationFlags {
+        /// Windows 8 style app
+        NVDRS_APPLICATION_METRO / METRO = 0x01,
+        /// Command line parsing for the application name
+        NVDRS_APPLICATION_COMMAND_LINE / COMMAND_LINE = 0x02,
+    }
+}
+
+nvstruct! {
+    pub struct NVDRS_APPLICATION_V3 {
+        pub v2: NVDRS_APPLICATION_V2,
+        pub flags: NVDRS_APPLICATION_FLAGS,
+    }
+}
+
+nvstruct! {
+    pub struct NVDRS_APPLICATION_V4 {
+        pub v3: NVDRS_APPLICATION_V3,
+        /// If `COMMAND_LINE` is set, the command line the application must be started with.
+        pub commandLine: NvAPI_UnicodeString,
+    }
+}
+
+impl NVDRS_APPLICATION_V4 {
+    pub fn v1(&self) -> &NVDRS_APPLICATION_V1 {
+        &self.v3.v2.v1
+    }
+
+    pub fn v1_mut(&mut self) -> &mut NVDRS_APPLICATION_V1 {
+        &mut self.v3.v2.v1
+    }
+}
+
+pub type NVDRS_APPLICATION = NVDRS_APPLICATION_V4;
+
+nvversion! { NVDRS_APPLICATION_VER_V1(NVDRS_APPLICATION_V1 = 4 * 2 + 4096 * 3, 1) }
+nvversion! { NVDRS_APPLICATION_VER_V2(NVDRS_APPLICATION_V2 = 4 * 2 + 4096 * 4, 2) }
+nvversion! { NVDRS_APPLICATION_VER_V3(NVDRS_APPLICATION_V3 = 4 * 3 + 4096 * 4, 3) }
+nvversion! { NVDRS_APPLICATION_VER_V4(NVDRS_APPLICATION_V4 = 4 * 3 + 4096 * 5, 4) }
+nvversion! { NVDRS_APPLICATION_VER = NVDRS_APPLICATION_VER_V4 }
+
+impl crate::versioned::VersionedStruct for NVDRS_APPLICATION_V4 {
+    const VERSIONS: &'static [u32] = &[NVDRS_APPLICATION_VER_V4, NVDRS_APPLICATION_VER_V3, NVDRS_APPLICATION_VER_V2, NVDRS_APPLICATION_VER_V1];
+
+    fn version(&self) -> u32 {
+        self.v1().version
+    }
+
+    fn set_version(&mut self, version: u32) {
+        self.v1_mut().version = version;
+    }
+}
+
+nvstruct! {
+    pub struct NVDRS_PROFILE_V1 {
+        /// Structure Version
+        pub version: u32,
+        /// String name of the Profile
+        pub profileName: NvAPI_UnicodeString,
+        /// This read-only flag indicates the profile support on either
+        /// Quadro, or Geforce, or both.
+        pub gpuSupport: NVDRS_GPU_SUPPORT,
+        /// Is the Profile user-defined, or predefined
+        pub isPredefined: u32,
+        /// Total number of applications that belong to this profile. Read-only
+        pub numOfApps: u32,
+        /// Total number of settings applied for this Profile. Read-only
+        pub numOfSettings: u32,
+    }
+}
+
+pub type NVDRS_PROFILE = NVDRS_PROFILE_V1;
+
+nvversion! { NVDRS_PROFILE_VER1(NVDRS_PROFILE_V1 = 4 + 4096 + 4 * 4, 1) }
+nvversion! { NVDRS_PROFILE_VER = NVDRS_PROFILE_VER1 }
+
+nvapi! {
+    pub type DRS_CreateSessionFn = extern "C" fn(phSession: *mut NvDRSSessionHandle) -> NvAPI_Status;
+
+    /// This API allocates memory and initializes the session.
+    pub unsafe fn NvAPI_DRS_CreateSession;
+}
+
+nvapi! {
+    pub type DRS_DestroySessionFn = extern "C" fn(hSession: NvDRSSessionHandle) -> NvAPI_Status;
+
+    /// This API frees the allocation: cleanup of NvDrsSession.
+    pub unsafe fn NvAPI_DRS_DestroySession;
+}
+
+nvapi! {
+    pub type DRS_LoadSettingsFn = extern "C" fn(hSession: NvDRSSessionHandle) -> NvAPI_Status;
+
+    /// This API loads and parses the settings data.
+    pub unsafe fn NvAPI_DRS_LoadSettings;
+}
+
+nvapi! {
+    pub type DRS_SaveSettingsFn = extern "C" fn(hSession: NvDRSSessionHandle) -> NvAPI_Status;
+
+    /// This API saves the settings data to the system.
+    pub unsafe fn NvAPI_DRS_SaveSettings;
+}
+
+nvapi! {
+    pub type DRS_LoadSettingsFromFileFn = extern "C" fn(hSession: NvDRSSessionHandle, fileName: *const NvAPI_UnicodeString) -> NvAPI_Status;
+
+    /// This API loads settings from the given file path.
+    pub unsafe fn NvAPI_DRS_LoadSettingsFromFile;
+}
+
+nvapi! {
+    pub type DRS_SaveSettingsToFileFn = extern "C" fn(hSession: NvDRSSessionHandle, fileName: *const NvAPI_UnicodeString) -> NvAPI_Status;
+
+    /// This API saves settings to the given file path.
+    pub unsafe fn NvAPI_DRS_SaveSettingsToFile;
+}
+
+nvapi! {
+    pub type DRS_CreateProfileFn = extern "C" fn(hSession: NvDRSSessionHandle, pProfileInfo: *mut NVDRS_PROFILE, phProfile: *mut NvDRSProfileHandle) -> NvAPI_Status;
+
+    /// This API creates an empty profile.
+    pub unsafe fn NvAPI_DRS_CreateProfile;
+}
+
+nvapi! {
+    pub type DRS_DeleteProfileFn = extern "C" fn(hSession: NvDRSSessionHandle, hProfile: NvDRSProfileHandle) -> NvAPI_Status;
+
+    /// This API deletes a profile or sets it back to a predefined value.
+    pub unsafe fn NvAPI_DRS_DeleteProfile;
+}
+
+nvapi! {
+    pub type DRS_SetCurrentGlobalProfileFn = extern "C" fn(hSession: NvDRSSessionHandle, wszGlobalProfileName: *const NvAPI_UnicodeString) -> NvAPI_Status;
+
+    /// This API sets the current global profile in the driver.
+    pub unsafe fn NvAPI_DRS_SetCurrentGlobalProfile;
+}
+
+nvapi! {
+    pub type DRS_GetCurrentGlobalProfileFn = extern "C" fn(hSession: NvDRSSessionHandle, phProfile: *mut NvDRSProfileHandle) -> NvAPI_Status;
+
+    /// This API returns the handle to the current global profile.
+    pub unsafe fn NvAPI_DRS_GetCurrentGlobalProfile;
+}
+
+nvapi! {
+    pub type DRS_GetProfileInfoFn = extern "C" fn(hSession: NvDRSSessionHandle, hProfile: NvDRSProfileHandle, pProfileInfo: *mut NVDRS_PROFILE) -> NvAPI_Status;
+
+    /// This API gets information about the given profile. User needs to specify the name of the Profile.
+    pub unsafe fn NvAPI_DRS_GetProfileInfo;
+}
+
+nvapi! {
+    pub type DRS_SetProfileInfoFn = extern "C" fn(hSession: NvDRSSessionHandle, hProfile: NvDRSProfileHandle, pProfileInfo: *mut NVDRS_PROFILE) -> NvAPI_Status;
+
+    /// Specifies flags for a given profile. Currently only the NVDRS_GPU_SUPPORT is
+    /// used to update the profile. Neither the name, number of settings or applications
+    /// or other profile information can be changed with this function.
+    pub unsafe fn NvAPI_DRS_SetProfileInfo;
+}
+
+nvapi! {
+    pub type DRS_FindProfileByNameFn = extern "C" fn(hSession: NvDRSSessionHandle, profileName: *const NvAPI_UnicodeString, phProfile: *mut NvDRSProfileHandle) -> NvAPI_Status;
+
+    /// This API finds a profile in the current session.
+    ///
+    /// # Returns
+    ///
+    /// - `NVAPI_PROFILE_NOT_FOUND`: if profile is not found
+    pub unsafe fn NvAPI_DRS_FindProfileByName;
+}
+
+nvapi! {
+    pub type DRS_EnumProfilesFn = extern "C" fn(hSession: NvDRSSessionHandle, index: u32, phProfile: *mut NvDRSProfileHandle) -> NvAPI_Status;
+
+    /// This API enumerates through all the profiles in the session.
+    ///
+    /// # Returns
+    ///
+    /// - `NVAPI_END_ENUMERATION`: index exceeds the total number of available Profiles in DB.
+    pub unsafe fn NvAPI_DRS_EnumProfiles;
+}
+
+nvapi! {
+    pub type DRS_GetNumProfilesFn = extern "C" fn(hSession: NvDRSSessionHandle, numProfiles: *mut u32) -> NvAPI_Status;
+
+    /// This API obtains the number of profiles in the current session object.
+    pub unsafe fn NvAPI_DRS_GetNumProfiles;
+}
+
+nvapi! {
+    pub type DRS_CreateApplicationFn = extern "C" fn(hSession: NvDRSSessionHandle, hProfile: NvDRSProfileHandle, pApplication: *mut NVDRS_APPLICATION) -> NvAPI_Status;
+
+    /// This API adds an executable name to a profile.
+    ///
+    /// # Returns
+    ///
+    /// - `NVAPI_EXECUTABLE_ALREADY_IN_USE`: if the application is already part of another profile
+    pub unsafe fn NvAPI_DRS_CreateApplication;
+}
+
+nvapi! {
+    pub type DRS_DeleteApplicationExFn = extern "C" fn(hSession: NvDRSSessionHandle, hProfile: NvDRSProfileHandle, pApp: *mut NVDRS_APPLICATION) -> NvAPI_Status;
+
+    /// This API removes an executable from a profile.
+    pub unsafe fn NvAPI_DRS_DeleteApplicationEx;
+}
+
+nvapi! {
+    pub type DRS_DeleteApplicationFn = extern "C" fn(hSession: NvDRSSessionHandle, hProfile: NvDRSProfileHandle, appName: *const NvAPI_UnicodeString) -> NvAPI_Status;
+
+    /// This API removes an executable name from a profile.
+    pub unsafe fn NvAPI_DRS_DeleteApplication;
+}
+
+nvapi! {
+    pub type DRS_GetApplicationInfoFn = extern "C" fn(hSession: NvDRSSessionHandle, hProfile: NvDRSProfileHandle, appName: *const NvAPI_UnicodeString, pApplication: *mut NVDRS_APPLICATION) -> NvAPI_Status;
+
+    /// This API gets information about the given application. The input application name
+    /// must match exactly what the Profile has stored for the application.
+    /// This function is better used to retrieve application information from a previous
+    /// enumeration.
+    pub unsafe fn NvAPI_DRS_GetApplicationInfo;
+}
+
+nvapi! {
+    pub type DRS_EnumApplicationsFn = extern "C" fn(hSession: NvDRSSessionHandle, hProfile: NvDRSProfileHandle, startIndex: u32, appCount: *mut u32, pApplication: *mut NVDRS_APPLICATION) -> NvAPI_Status;
+
+    /// This API enumerates all the applications in a given profile from the starting index to the maximum length.
+    ///
+    /// `appCount` is the size of the `pApplication` array on input and the number of
+    /// applications returned on output.
+    ///
+    /// # Returns
+    ///
+    /// - `NVAPI_END_ENUMERATION`: `startIndex` exceeds the total appCount.
+    pub unsafe fn NvAPI_DRS_EnumApplications;
+}
+
+nvapi! {
+    pub type DRS_FindApplicationByNameFn = extern "C" fn(hSession: NvDRSSessionHandle, appName: *const NvAPI_UnicodeString, phProfile: *mut NvDRSProfileHandle, pApplication: *mut NVDRS_APPLICATION) -> NvAPI_Status;
+
+    /// This API searches the application and the associated profile for the given application name.
+    /// If a fully qualified path is provided, this function will always return the profile
+    /// the driver will apply upon running the application (on the path provided).
+    ///
+    /// # Returns
+    ///
+    /// - `NVAPI_EXECUTABLE_NOT_FOUND`: If app is not found in the current session
+    pub unsafe fn NvAPI_DRS_FindApplicationByName;
+}
+
+nvapi! {
+    pub type DRS_SetSettingFn = extern "C" fn(hSession: NvDRSSessionHandle, hProfile: NvDRSProfileHandle, pSetting: *mut NVDRS_SETTING) -> NvAPI_Status;
+
+    /// This API adds/modifies a setting to a profile.
+    pub unsafe fn NvAPI_DRS_SetSetting;
+}
+
+nvapi! {
+    pub type DRS_GetSettingFn = extern "C" fn(hSession: NvDRSSessionHandle, hProfile: NvDRSProfileHandle, settingId: u32, pSetting: *mut NVDRS_SETTING) -> NvAPI_Status;
+
+    /// This API gets information about the given setting.
+    pub unsafe fn NvAPI_DRS_GetSetting;
+}
+
+nvapi! {
+    pub type DRS_EnumSettingsFn = extern "C" fn(hSession: NvDRSSessionHandle, hProfile: NvDRSProfileHandle, startIndex: u32, settingsCount: *mut u32, pSetting: *mut NVDRS_SETTING) -> NvAPI_Status;
+
+    /// This API enumerates all the settings of a given profile from startIndex to the maximum length.
+    ///
+    /// # Returns
+    ///
+    /// - `NVAPI_END_ENUMERATION`: `startIndex` exceeds the total appCount.
+    pub unsafe fn NvAPI_DRS_EnumSettings;
+}
+
+nvapi! {
+    pub type DRS_EnumAvailableSettingIdsFn = extern "C" fn(pSettingIds: *mut u32, pMaxCount: *mut u32) -> NvAPI_Status;
+
+    /// This API enumerates all the Ids of all the settings recognized by NVAPI.
+    ///
+    /// `pMaxCount` is the size of the `pSettingIds` array on input and the number of
+    /// ids returned on output.
+    pub unsafe fn NvAPI_DRS_EnumAvailableSettingIds;
+}
+
+nvapi! {
+    pub type DRS_EnumAvailableSettingValuesFn = extern "C" fn(settingId: u32, pMaxNumValues: *mut u32, pSettingValues: *mut NVDRS_SETTING_VALUES) -> NvAPI_Status;
+
+    /// This API enumerates all available setting values for a given setting.
+    pub unsafe fn NvAPI_DRS_EnumAvailableSettingValues;
+}
+
+nvapi! {
+    pub type DRS_GetSettingIdFromNameFn = extern "C" fn(settingName: *const NvAPI_UnicodeString, pSettingId: *mut u32) -> NvAPI_Status;
+
+    /// This API gets the binary ID of a setting given the setting name.
+    pub unsafe fn NvAPI_DRS_GetSettingIdFromName;
+}
+
+nvapi! {
+    pub type DRS_GetSettingNameFromIdFn = extern "C" fn(settingId: u32, pSettingName: *mut NvAPI_UnicodeString) -> NvAPI_Status;
+
+    /// This API gets the setting name given the binary ID.
+    pub unsafe fn NvAPI_DRS_GetSettingNameFromId;
+}
+
+nvapi! {
+    pub type DRS_DeleteProfileSettingFn = extern "C" fn(hSession: NvDRSSessionHandle, hProfile: NvDRSProfileHandle, settingId: u32) -> NvAPI_Status;
+
+    /// This API deletes a setting or sets it back to predefined value.
+    pub unsafe fn NvAPI_DRS_DeleteProfileSetting;
+}
+
+nvapi! {
+    pub type DRS_RestoreAllDefaultsFn = extern "C" fn(hSession: NvDRSSessionHandle) -> NvAPI_Status;
+
+    /// This API restores the whole system to predefined(default) values.
+    pub unsafe fn NvAPI_DRS_RestoreAllDefaults;
+}
+
+nvapi! {
+    pub type DRS_RestoreProfileDefaultFn = extern "C" fn(hSession: NvDRSSessionHandle, hProfile: NvDRSProfileHandle) -> NvAPI_Status;
+
+    /// This API restores the given profile to predefined(default) values.
+    /// Any and all user specified modifications will be removed.
+    /// If the whole profile was set by the user, the profile will be removed.
+    ///
+    /// # Returns
+    ///
+    /// - `NVAPI_PROFILE_REMOVED`: Whole profile was set by user, so it was removed.
+    pub unsafe fn NvAPI_DRS_RestoreProfileDefault;
+}
+
+nvapi! {
+    pub type DRS_RestoreProfileDefaultSettingFn = extern "C" fn(hSession: NvDRSSessionHandle, hProfile: NvDRSProfileHandle, settingId: u32) -> NvAPI_Status;
+
+    /// This API restores the given profile setting to predefined(default) values.
+    pub unsafe fn NvAPI_DRS_RestoreProfileDefaultSetting;
+}
+
+nvapi! {
+    pub type DRS_GetBaseProfileFn = extern "C" fn(hSession: NvDRSSessionHandle, phProfile: *mut NvDRSProfileHandle) -> NvAPI_Status;
+
+    /// Returns the handle to the current global profile.
+    pub unsafe fn NvAPI_DRS_GetBaseProfile;
+}
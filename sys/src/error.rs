@@ -0,0 +1,186 @@
+use std::borrow::Cow;
+use std::cell::Cell;
+use std::convert::Infallible;
+use std::{fmt, error};
+use crate::status::{self, NvAPI_Status, Status};
+use crate::capture::CaptureArg;
+use crate::nvid::Api;
+use crate::ArgumentRangeError;
+
+/// A failed NVAPI call.
+///
+/// In addition to the returned status, this identifies the function that failed and
+/// the version of the structure it was passed, when known.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Error {
+    raw: NvAPI_Status,
+    api: Option<Api>,
+    version: Option<u32>,
+    context: Option<Cow<'static, str>>,
+}
+
+impl Error {
+    pub fn new(status: Status) -> Self {
+        Self::from_raw(status.raw())
+    }
+
+    /// Wraps a raw status code, which need not be one known to this crate.
+    pub fn from_raw(raw: NvAPI_Status) -> Self {
+        Error {
+            raw,
+            api: None,
+            version: None,
+            context: None,
+        }
+    }
+
+    /// The status, or `Status::Error` if the raw code isn't recognized.
+    pub fn status(&self) -> Status {
+        Status::from_raw(self.raw).unwrap_or(Status::Error)
+    }
+
+    /// The status code exactly as returned by the driver.
+    pub fn raw(&self) -> NvAPI_Status {
+        self.raw
+    }
+
+    /// The function that failed.
+    pub fn api(&self) -> Option<Api> {
+        self.api
+    }
+
+    /// The version of the structure passed to the failing function.
+    pub fn version(&self) -> Option<u32> {
+        self.version
+    }
+
+    pub fn context(&self) -> Option<&str> {
+        self.context.as_ref().map(|c| &c[..])
+    }
+
+    pub fn with_api(self, api: Api) -> Self {
+        Error {
+            api: Some(api),
+            .. self
+        }
+    }
+
+    pub fn with_version(self, version: u32) -> Self {
+        Error {
+            version: Some(version),
+            .. self
+        }
+    }
+
+    pub fn with_context<C: Into<Cow<'static, str>>>(self, context: C) -> Self {
+        Error {
+            context: Some(context.into()),
+            .. self
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(api) = self.api {
+            write!(f, "{:?}", api)?;
+            if let Some(version) = self.version {
+                write!(f, " (struct version {}, size {})", version >> 16, version & 0xffff)?;
+            }
+            write!(f, " failed: ")?;
+        }
+        match Status::from_raw(self.raw) {
+            Ok(status) => write!(f, "{:?}", status)?,
+            Err(..) => write!(f, "unknown status {}", self.raw)?,
+        }
+        if let Some(ref context) = self.context {
+            write!(f, " ({})", context)?;
+        }
+        Ok(())
+    }
+}
+
+impl error::Error for Error { }
+
+impl From<Status> for Error {
+    fn from(status: Status) -> Self {
+        Error::new(status)
+    }
+}
+
+impl From<Error> for Status {
+    fn from(e: Error) -> Self {
+        e.status()
+    }
+}
+
+impl From<ArgumentRangeError> for Error {
+    fn from(e: ArgumentRangeError) -> Self {
+        let error = Error::new(Status::from(e));
+        match e.name() {
+            Some(..) => error.with_context(e.to_string()),
+            None => error,
+        }
+    }
+}
+
+impl From<Infallible> for Error {
+    fn from(e: Infallible) -> Self {
+        match e { }
+    }
+}
+
+impl PartialEq<Status> for Error {
+    fn eq(&self, other: &Status) -> bool {
+        self.raw == other.raw()
+    }
+}
+
+impl PartialEq<Error> for Status {
+    fn eq(&self, other: &Error) -> bool {
+        other == self
+    }
+}
+
+#[derive(Copy, Clone)]
+struct Failure {
+    api: Api,
+    raw: NvAPI_Status,
+    version: Option<u32>,
+}
+
+thread_local! {
+    static LAST_FAILURE: Cell<Option<Failure>> = const { Cell::new(None) };
+}
+
+/// Notes the outcome of a call so that `status_result` can attribute its error.
+///
+/// Every call replaces the previous outcome, so a failure is never attributed once
+/// another call has been made since.
+#[doc(hidden)]
+pub unsafe fn track(api: Api, args: &[&dyn CaptureArg], raw: NvAPI_Status) {
+    let failure = match raw {
+        status::NVAPI_OK => None,
+        raw => Some(Failure {
+            api,
+            raw,
+            version: args.iter().filter_map(|arg| arg.struct_version()).next(),
+        }),
+    };
+    LAST_FAILURE.with(|f| f.set(failure));
+}
+
+/// Builds an error for `raw`, attributed to the most recent call on this thread if it failed with `raw`.
+pub(crate) fn last_failure(raw: NvAPI_Status) -> Error {
+    let error = Error::from_raw(raw);
+    match LAST_FAILURE.with(|f| f.take()) {
+        Some(failure) if failure.raw == raw => {
+            let error = error.with_api(failure.api);
+            match failure.version {
+                Some(version) => error.with_version(version),
+                None => error,
+            }
+        },
+        _ => error,
+    }
+}
@@ -0,0 +1,86 @@
+use std::os::raw::c_void;
+use crate::status::NvAPI_Status;
+use crate::handles::NvEventHandle;
+
+nvenum! {
+    /// The kinds of event a callback can be registered for.
+    pub enum NV_EVENT_TYPE / EventType {
+        NV_EVENT_TYPE_NONE / None = 0,
+        NV_EVENT_TYPE_SHUTDOWN / Shutdown = 2,
+        /// Quadro Sync events, delivered to `NVAPI_CALLBACK_QSYNCEVENT`.
+        NV_EVENT_TYPE_QSYNC / QSync = 6,
+    }
+}
+
+nvenum_display! {
+    EventType => _
+}
+
+nvenum! {
+    /// What happened to a sync device.
+    pub enum NV_QSYNC_EVENT / QSyncEvent {
+        NV_QSYNC_EVENT_NONE / None = 0,
+        NV_QSYNC_EVENT_SYNC_LOSS / SyncLoss = 1,
+        NV_QSYNC_EVENT_SYNC_GAIN / SyncGain = 2,
+        NV_QSYNC_EVENT_HOUSESYNC_GAIN / HouseSyncGain = 3,
+        NV_QSYNC_EVENT_HOUSESYNC_LOSS / HouseSyncLoss = 4,
+        NV_QSYNC_EVENT_RJ45_GAIN / Rj45Gain = 5,
+        NV_QSYNC_EVENT_RJ45_LOSS / Rj45Loss = 6,
+    }
+}
+
+nvenum_display! {
+    QSyncEvent => _
+}
+
+nvstruct! {
+    /// Passed by value to `NVAPI_CALLBACK_QSYNCEVENT`.
+    pub struct NV_QSYNC_EVENT_DATA {
+        pub eventType: NV_QSYNC_EVENT,
+        pub reserved: [u32; 7],
+    }
+}
+
+/// Called by the driver, on a thread of its own, whenever a Quadro Sync event occurs.
+pub type NVAPI_CALLBACK_QSYNCEVENT = extern "C" fn(qyncEventData: NV_QSYNC_EVENT_DATA, callbackParam: *mut c_void);
+
+nvstruct! {
+    /// Describes a callback to `NvAPI_Event_RegisterCallback`.
+    pub struct NV_EVENT_REGISTER_CALLBACK {
+        /// Version of this structure
+        pub version: u32,
+        /// The event to be notified of
+        pub eventId: NV_EVENT_TYPE,
+        /// Passed back to the callback unchanged
+        pub callbackParam: *mut c_void,
+        /// The callback, which must match `eventId`
+        pub nvCallBackFunc: Option<NVAPI_CALLBACK_QSYNCEVENT>,
+    }
+}
+
+nvversion! { NV_EVENT_REGISTER_CALLBACK_VERSION(NV_EVENT_REGISTER_CALLBACK = ::std::mem::size_of::<NV_EVENT_REGISTER_CALLBACK>(), 1) }
+
+nvapi! {
+    pub type Event_RegisterCallbackFn = extern "C" fn(eventCallback: *mut NV_EVENT_REGISTER_CALLBACK, phClient: *mut NvEventHandle) -> NvAPI_Status;
+
+    /// This API registers the process for events. This API should be called for each
+    /// eventcallback. The handle returned to the client will be common across all
+    /// eventCallbacks.
+    ///
+    /// # Returns
+    ///
+    /// - `NVAPI_INVALID_ARGUMENT`: Invalid argument.
+    /// - `NVAPI_ERROR`: The callback could not be registered.
+    pub unsafe fn NvAPI_Event_RegisterCallback;
+}
+
+nvapi! {
+    pub type Event_UnregisterCallbackFn = extern "C" fn(hClient: NvEventHandle) -> NvAPI_Status;
+
+    /// This API unregisters an event handle. No callbacks are made once it returns.
+    ///
+    /// # Returns
+    ///
+    /// - `NVAPI_INVALID_ARGUMENT`: hClient is not a registered handle.
+    pub unsafe fn NvAPI_Event_UnregisterCallback;
+}
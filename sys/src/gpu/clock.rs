@@ -55,6 +55,8 @@ nvversion! { NV_GPU_CLOCK_FREQUENCIES_VER_1(NV_GPU_CLOCK_FREQUENCIES_V1 = 4 * 2
 nvversion! { NV_GPU_CLOCK_FREQUENCIES_VER_2(NV_GPU_CLOCK_FREQUENCIES_V2 = 4 * 2 + (4 * 2) * NVAPI_MAX_GPU_PUBLIC_CLOCKS, 2) }
 nvversion! { NV_GPU_CLOCK_FREQUENCIES_VER_3(NV_GPU_CLOCK_FREQUENCIES_V2 = 4 * 2 + (4 * 2) * NVAPI_MAX_GPU_PUBLIC_CLOCKS, 3) }
 nvversion! { NV_GPU_CLOCK_FREQUENCIES_VER = NV_GPU_CLOCK_FREQUENCIES_VER_3 }
+// Version 1 ignores the clock type and always reports current clocks, so it's never a substitute
+nvversioned! { NV_GPU_CLOCK_FREQUENCIES_V2: [NV_GPU_CLOCK_FREQUENCIES_VER_3, NV_GPU_CLOCK_FREQUENCIES_VER_2] }
 
 nvenum! {
     /// Used in NvAPI_GPU_GetAllClockFrequencies()
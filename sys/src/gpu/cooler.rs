@@ -115,7 +115,7 @@ pub mod private {
     impl CoolerActivityLevel {
         pub fn get(&self) -> bool {
             match *self {
-                CoolerActivityLevel::Active => true,
+                CoolerActivityLevel::Active | CoolerActivityLevel::Unrecognized(..) => true,
                 CoolerActivityLevel::Inactive => false,
             }
         }
@@ -120,3 +120,81 @@ nvapi! {
     /// - `NVAPI_INSUFFICIENT_BUFFER`: When the input buffer(pDisplayIds) is less than the actual number of display IDs
     pub unsafe fn NvAPI_GPU_GetAllDisplayIds;
 }
+
+pub const NV_EDID_V1_DATA_SIZE: usize = 256;
+pub const NV_EDID_DATA_SIZE: usize = NV_EDID_V1_DATA_SIZE;
+
+nvstruct! {
+    pub struct NV_EDID_V1 {
+        pub version: u32,
+        pub EDID_Data: [u8; NV_EDID_V1_DATA_SIZE],
+    }
+}
+
+nvstruct! {
+    pub struct NV_EDID_V2 {
+        pub version: u32,
+        pub EDID_Data: [u8; NV_EDID_DATA_SIZE],
+        pub sizeofEDID: u32,
+    }
+}
+
+nvstruct! {
+    pub struct NV_EDID_V3 {
+        pub version: u32,
+        pub EDID_Data: [u8; NV_EDID_DATA_SIZE],
+        pub sizeofEDID: u32,
+        /// (output) ID which always returned in a monotonically increasing counter.
+        /// Across a split-EDID read we need to verify that all calls returned the same edidId.
+        /// This counter is incremented if we get the updated EDID.
+        pub edidId: u32,
+        /// (input) byte offset of the 256-byte page of the EDID to read, starting at 0.
+        pub offset: u32,
+    }
+}
+
+pub type NV_EDID = NV_EDID_V3;
+
+nvversion! { NV_EDID_VER1(NV_EDID_V1 = 4 + NV_EDID_V1_DATA_SIZE, 1) }
+nvversion! { NV_EDID_VER2(NV_EDID_V2 = 4 + NV_EDID_DATA_SIZE + 4, 2) }
+nvversion! { NV_EDID_VER3(NV_EDID_V3 = 4 + NV_EDID_DATA_SIZE + 4 * 3, 3) }
+nvversion! { NV_EDID_VER = NV_EDID_VER3 }
+nvversioned! { NV_EDID_V3: [NV_EDID_VER3, NV_EDID_VER2, NV_EDID_VER1] }
+
+nvapi! {
+    pub type GPU_GetEDIDFn = extern "C" fn(hPhysicalGpu: NvPhysicalGpuHandle, displayOutputId: u32, pEDID: *mut NV_EDID) -> NvAPI_Status;
+
+    /// This function returns the EDID data for the specified GPU handle and connection bit mask.
+    /// displayOutputId should have exactly 1 bit set to indicate a single display. See `NvAPI_GPU_GetConnectedOutputs`.
+    ///
+    /// Newer drivers also accept a display id here, as returned by `NvAPI_GPU_GetConnectedDisplayIds`.
+    ///
+    /// EDIDs larger than `NV_EDID_DATA_SIZE` are read one 256-byte page at a time by
+    /// advancing `offset`; `sizeofEDID` reports the full size and `edidId` must not change
+    /// between the reads.
+    ///
+    /// # Returns
+    ///
+    /// - `NVAPI_INVALID_ARGUMENT`: pEDID is NULL; displayOutputId has 0 or > 1 bits set
+    /// - `NVAPI_DATA_NOT_FOUND`: The requested display does not contain an EDID.
+    pub unsafe fn NvAPI_GPU_GetEDID;
+}
+
+nvapi! {
+    pub type GPU_SetEDIDFn = extern "C" fn(hPhysicalGpu: NvPhysicalGpuHandle, displayOutputId: u32, pEDID: *mut NV_EDID) -> NvAPI_Status;
+
+    /// This function sets the EDID data for the specified GPU handle and connection bit mask.
+    /// User can either send (Gpu handle & output id) or only display Id in variable displayOutputId parameter & hPhysicalGpu parameter can be default handle (0).
+    ///
+    /// Note: The EDID will be cached across the boot session and will be enumerated to the OS in this call.
+    /// To remove the EDID set sizeofEDID to zero.
+    /// OS and NVAPI connection status APIs will reflect the newly set or removed EDID dynamically.
+    ///
+    /// This feature will NOT be supported on the following boards: GeForce, Quadro VX, Tesla.
+    ///
+    /// # Returns
+    ///
+    /// - `NVAPI_INVALID_ARGUMENT`: pEDID is NULL; displayOutputId has 0 or > 1 bits set
+    /// - `NVAPI_NOT_SUPPORTED`: For the above mentioned GPUs
+    pub unsafe fn NvAPI_GPU_SetEDID;
+}
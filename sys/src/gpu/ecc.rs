@@ -0,0 +1,167 @@
+use crate::status::NvAPI_Status;
+use crate::handles::NvPhysicalGpuHandle;
+
+nvenum! {
+    /// How a change to the ECC configuration takes effect.
+    pub enum NV_ECC_CONFIGURATION / EccConfiguration {
+        NV_ECC_CONFIGURATION_NOT_SUPPORTED / NotSupported = 0,
+        /// Changes require a POST to take effect
+        NV_ECC_CONFIGURATION_DEFERRED / Deferred = 1,
+        /// Changes can optionally be made to take effect immediately
+        NV_ECC_CONFIGURATION_IMMEDIATE / Immediate = 2,
+    }
+}
+
+nvenum_display! {
+    EccConfiguration => {
+        NotSupported = "Not Supported",
+        _ = _,
+    }
+}
+
+nvbits! {
+    /// Bitfield in `NV_GPU_ECC_STATUS_INFO`
+    pub enum NV_GPU_ECC_STATUS_FLAGS / EccStatusFlags {
+        /// ECC memory feature support
+        NV_GPU_ECC_STATUS_IS_SUPPORTED / IS_SUPPORTED = 0x01,
+    }
+}
+
+nvbits! {
+    /// Bitfield in `NV_GPU_ECC_STATUS_INFO`
+    pub enum NV_GPU_ECC_STATUS_ENABLED_FLAGS / EccStatusEnabledFlags {
+        /// Active ECC memory setting
+        NV_GPU_ECC_STATUS_IS_ENABLED / IS_ENABLED = 0x01,
+    }
+}
+
+nvstruct! {
+    /// Used in NvAPI_GPU_GetECCStatusInfo()
+    pub struct NV_GPU_ECC_STATUS_INFO {
+        /// Structure version
+        pub version: u32,
+        /// Any of the NV_GPU_ECC_STATUS_IS_SUPPORTED flags.
+        pub flags: NV_GPU_ECC_STATUS_FLAGS,
+        /// Supported ECC memory feature configuration options
+        pub configurationOptions: NV_ECC_CONFIGURATION,
+        /// Any of the NV_GPU_ECC_STATUS_IS_ENABLED flags.
+        pub enabledFlags: NV_GPU_ECC_STATUS_ENABLED_FLAGS,
+    }
+}
+
+nvversion! { NV_GPU_ECC_STATUS_INFO_VER1(NV_GPU_ECC_STATUS_INFO = 4 * 4, 1) }
+nvversion! { NV_GPU_ECC_STATUS_INFO_VER = NV_GPU_ECC_STATUS_INFO_VER1 }
+
+nvapi! {
+    pub type GPU_GetECCStatusInfoFn = extern "C" fn(hPhysicalGpu: NvPhysicalGpuHandle, pECCStatusInfo: *mut NV_GPU_ECC_STATUS_INFO) -> NvAPI_Status;
+
+    /// This function returns ECC memory status information.
+    ///
+    /// # Returns
+    ///
+    /// - `NVAPI_INVALID_ARGUMENT`: pECCStatusInfo is NULL.
+    /// - `NVAPI_NVIDIA_DEVICE_NOT_FOUND`: No NVIDIA GPU driving a display was found
+    /// - `NVAPI_INCOMPATIBLE_STRUCT_VERSION`: The version of the structure is not supported
+    pub unsafe fn NvAPI_GPU_GetECCStatusInfo;
+}
+
+nvstruct! {
+    /// Anonymous struct in NV_GPU_ECC_ERROR_INFO
+    pub struct NV_GPU_ECC_ERROR_COUNTS {
+        /// Number of single-bit ECC errors detected
+        pub singleBitErrors: u64,
+        /// Number of double-bit ECC errors detected
+        pub doubleBitErrors: u64,
+    }
+}
+
+nvstruct! {
+    /// Used in NvAPI_GPU_GetECCErrorInfo()
+    pub struct NV_GPU_ECC_ERROR_INFO {
+        /// Structure version
+        pub version: u32,
+        /// Errors detected since last boot
+        pub current: NV_GPU_ECC_ERROR_COUNTS,
+        /// Errors detected since last counter reset
+        pub aggregate: NV_GPU_ECC_ERROR_COUNTS,
+    }
+}
+
+nvversion! { NV_GPU_ECC_ERROR_INFO_VER1(NV_GPU_ECC_ERROR_INFO = ::std::mem::size_of::<NV_GPU_ECC_ERROR_INFO>(), 1) }
+nvversion! { NV_GPU_ECC_ERROR_INFO_VER = NV_GPU_ECC_ERROR_INFO_VER1 }
+
+nvapi! {
+    pub type GPU_GetECCErrorInfoFn = extern "C" fn(hPhysicalGpu: NvPhysicalGpuHandle, pECCErrorInfo: *mut NV_GPU_ECC_ERROR_INFO) -> NvAPI_Status;
+
+    /// This function returns ECC memory error information.
+    ///
+    /// # Returns
+    ///
+    /// - `NVAPI_INVALID_ARGUMENT`: pECCErrorInfo is NULL.
+    /// - `NVAPI_NOT_SUPPORTED`: ECC memory is not supported by the GPU.
+    /// - `NVAPI_INCOMPATIBLE_STRUCT_VERSION`: The version of the structure is not supported
+    pub unsafe fn NvAPI_GPU_GetECCErrorInfo;
+}
+
+nvapi! {
+    pub type GPU_ResetECCErrorInfoFn = extern "C" fn(hPhysicalGpu: NvPhysicalGpuHandle, bResetCurrent: u8, bResetAggregate: u8) -> NvAPI_Status;
+
+    /// This function resets ECC memory error counters.
+    ///
+    /// # Returns
+    ///
+    /// - `NVAPI_NOT_SUPPORTED`: ECC memory is not supported by the GPU.
+    /// - `NVAPI_INVALID_USER_PRIVILEGE`: The caller does not have administrator privileges.
+    pub unsafe fn NvAPI_GPU_ResetECCErrorInfo;
+}
+
+nvbits! {
+    /// Bitfield in `NV_GPU_ECC_CONFIGURATION_INFO`
+    pub enum NV_GPU_ECC_CONFIGURATION_FLAGS / EccConfigurationFlags {
+        /// Current ECC configuration stored in non-volatile memory
+        NV_GPU_ECC_CONFIGURATION_IS_ENABLED / IS_ENABLED = 0x01,
+        /// Factory default ECC configuration (static)
+        NV_GPU_ECC_CONFIGURATION_IS_ENABLED_BY_DEFAULT / IS_ENABLED_BY_DEFAULT = 0x02,
+    }
+}
+
+nvstruct! {
+    /// Used in NvAPI_GPU_GetECCConfigurationInfo()
+    pub struct NV_GPU_ECC_CONFIGURATION_INFO {
+        /// Structure version
+        pub version: u32,
+        /// Any of the NV_GPU_ECC_CONFIGURATION_* flags.
+        pub flags: NV_GPU_ECC_CONFIGURATION_FLAGS,
+    }
+}
+
+nvversion! { NV_GPU_ECC_CONFIGURATION_INFO_VER1(NV_GPU_ECC_CONFIGURATION_INFO = 4 * 2, 1) }
+nvversion! { NV_GPU_ECC_CONFIGURATION_INFO_VER = NV_GPU_ECC_CONFIGURATION_INFO_VER1 }
+
+nvapi! {
+    pub type GPU_GetECCConfigurationInfoFn = extern "C" fn(hPhysicalGpu: NvPhysicalGpuHandle, pECCConfigurationInfo: *mut NV_GPU_ECC_CONFIGURATION_INFO) -> NvAPI_Status;
+
+    /// This function returns ECC memory configuration information.
+    ///
+    /// # Returns
+    ///
+    /// - `NVAPI_INVALID_ARGUMENT`: pECCConfigurationInfo is NULL.
+    /// - `NVAPI_NOT_SUPPORTED`: ECC memory is not supported by the GPU.
+    /// - `NVAPI_INCOMPATIBLE_STRUCT_VERSION`: The version of the structure is not supported
+    pub unsafe fn NvAPI_GPU_GetECCConfigurationInfo;
+}
+
+nvapi! {
+    pub type GPU_SetECCConfigurationFn = extern "C" fn(hPhysicalGpu: NvPhysicalGpuHandle, bEnable: u8, bEnableImmediately: u8) -> NvAPI_Status;
+
+    /// This function updates the ECC memory configuration setting.
+    ///
+    /// Unless `bEnableImmediately` is set and the GPU supports `NV_ECC_CONFIGURATION_IMMEDIATE`,
+    /// the new setting only takes effect after the next POST.
+    ///
+    /// # Returns
+    ///
+    /// - `NVAPI_NOT_SUPPORTED`: ECC memory is not supported by the GPU.
+    /// - `NVAPI_INVALID_USER_PRIVILEGE`: The caller does not have administrator privileges.
+    pub unsafe fn NvAPI_GPU_SetECCConfiguration;
+}
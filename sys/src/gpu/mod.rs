@@ -1,5 +1,5 @@
 use crate::status::NvAPI_Status;
-use crate::handles::NvPhysicalGpuHandle;
+use crate::handles::{NvPhysicalGpuHandle, NvLogicalGpuHandle, NvDisplayHandle};
 use crate::types;
 
 /// The GPU cooler APIs are used to get and set the fan level or equivalent
@@ -34,6 +34,10 @@ pub mod power;
 
 pub mod display;
 
+/// The ECC APIs report and configure error correction of GPU memory, found on
+/// workstation and datacenter boards.
+pub mod ecc;
+
 nvapi! {
     pub type EnumPhysicalGPUsFn = extern "C" fn(nvGPUHandle: *mut [NvPhysicalGpuHandle; types::NVAPI_MAX_PHYSICAL_GPUS], pGpuCount: *mut u32) -> NvAPI_Status;
 
@@ -56,6 +60,48 @@ nvapi! {
     pub unsafe fn NvAPI_EnumPhysicalGPUs;
 }
 
+nvapi! {
+    pub type EnumLogicalGPUsFn = extern "C" fn(nvGPUHandle: *mut [NvLogicalGpuHandle; types::NVAPI_MAX_LOGICAL_GPUS], pGpuCount: *mut u32) -> NvAPI_Status;
+
+    /// This function returns an array of logical GPU handles.
+    ///
+    /// Each handle represents one or more GPUs acting in concert as a single graphics device.
+    ///
+    /// At least one GPU must be present in the system and running an NVIDIA display driver.
+    ///
+    /// The array nvGPUHandle will be filled with logical GPU handle values. The returned
+    /// gpuCount determines how many entries in the array are valid.
+    ///
+    /// Note: All logical GPUs handles get invalidated on a GPU topology change, so the calling
+    /// application is required to renum the logical GPU handles to get latest physical handle
+    /// mapping after every GPU topology change activated by a call to NvAPI_SetGpuTopologies().
+    pub unsafe fn NvAPI_EnumLogicalGPUs;
+}
+
+nvapi! {
+    pub type GetPhysicalGPUsFromLogicalGPUFn = extern "C" fn(hLogicalGPU: NvLogicalGpuHandle, hPhysicalGPU: *mut [NvPhysicalGpuHandle; types::NVAPI_MAX_PHYSICAL_GPUS], pGpuCount: *mut u32) -> NvAPI_Status;
+
+    /// This function returns the physical GPU handles associated with the specified logical GPU handle.
+    ///
+    /// The array hPhysicalGPU will be filled with physical GPU handle values. The returned
+    /// gpuCount determines how many entries in the array are valid.
+    pub unsafe fn NvAPI_GetPhysicalGPUsFromLogicalGPU;
+}
+
+nvapi! {
+    pub type GetLogicalGPUFromPhysicalGPUFn = extern "C" fn(hPhysicalGPU: NvPhysicalGpuHandle, pLogicalGPU: *mut NvLogicalGpuHandle) -> NvAPI_Status;
+
+    /// This function returns the logical GPU handle associated with specified physical GPU handle.
+    pub unsafe fn NvAPI_GetLogicalGPUFromPhysicalGPU;
+}
+
+nvapi! {
+    pub type GetLogicalGPUFromDisplayFn = extern "C" fn(hNvDisp: NvDisplayHandle, pLogicalGPU: *mut NvLogicalGpuHandle) -> NvAPI_Status;
+
+    /// This function returns the logical GPU handle associated with the specified display.
+    pub unsafe fn NvAPI_GetLogicalGPUFromDisplay;
+}
+
 nvapi! {
     pub type GPU_GetFullNameFn = extern "C" fn(hPhysicalGPU: NvPhysicalGpuHandle, szName: *mut types::NvAPI_ShortString) -> NvAPI_Status;
 
@@ -87,6 +133,65 @@ nvapi! {
     pub unsafe fn NvAPI_GPU_GetPCIIdentifiers;
 }
 
+nvapi! {
+    pub type GPU_GetBusIdFn = extern "C" fn(hPhysicalGpu: NvPhysicalGpuHandle, pBusId: *mut u32) -> NvAPI_Status;
+
+    /// Returns the ID of the bus associated with this GPU.
+    pub unsafe fn NvAPI_GPU_GetBusId;
+}
+
+nvapi! {
+    pub type GPU_GetBusSlotIdFn = extern "C" fn(hPhysicalGpu: NvPhysicalGpuHandle, pBusSlotId: *mut u32) -> NvAPI_Status;
+
+    /// Returns the ID of the bus slot associated with this GPU.
+    pub unsafe fn NvAPI_GPU_GetBusSlotId;
+}
+
+nvenum! {
+    /// Used in NvAPI_GPU_GetBusType()
+    pub enum NV_GPU_BUS_TYPE / BusType {
+        NVAPI_GPU_BUS_TYPE_UNDEFINED / Undefined = 0,
+        NVAPI_GPU_BUS_TYPE_PCI / Pci = 1,
+        NVAPI_GPU_BUS_TYPE_AGP / Agp = 2,
+        NVAPI_GPU_BUS_TYPE_PCI_EXPRESS / PciExpress = 3,
+        NVAPI_GPU_BUS_TYPE_FPCI / Fpci = 4,
+        NVAPI_GPU_BUS_TYPE_AXI / Axi = 5,
+    }
+}
+
+nvenum_display! {
+    BusType => {
+        Pci = "PCI",
+        Agp = "AGP",
+        PciExpress = "PCI Express",
+        Fpci = "FPCI",
+        Axi = "AXI",
+        _ = _,
+    }
+}
+
+nvapi! {
+    pub type GPU_GetBusTypeFn = extern "C" fn(hPhysicalGpu: NvPhysicalGpuHandle, pBusType: *mut NV_GPU_BUS_TYPE) -> NvAPI_Status;
+
+    /// This function returns the type of bus associated with this GPU.
+    pub unsafe fn NvAPI_GPU_GetBusType;
+}
+
+nvapi! {
+    pub type GPU_GetIRQFn = extern "C" fn(hPhysicalGpu: NvPhysicalGpuHandle, pIRQ: *mut u32) -> NvAPI_Status;
+
+    /// This function returns the interrupt number associated with this GPU.
+    pub unsafe fn NvAPI_GPU_GetIRQ;
+}
+
+nvapi! {
+    pub type GPU_GetCurrentPCIEDownstreamWidthFn = extern "C" fn(hPhysicalGpu: NvPhysicalGpuHandle, pWidth: *mut u32) -> NvAPI_Status;
+
+    /// This function returns the number of PCIE lanes being used for the PCIE interface
+    /// downstream from the GPU.
+    pub unsafe fn NvAPI_GPU_GetCurrentPCIEDownstreamWidth;
+}
+
 nvenum! {
     /// Used in NvAPI_GPU_GetSystemType()
     pub enum NV_SYSTEM_TYPE / SystemType {
@@ -333,4 +438,39 @@ pub mod private {
     nvapi! {
         pub unsafe fn NvAPI_GetDriverModel(hPhysicalGpu: NvPhysicalGpuHandle, pDriverModel: *mut u32) -> NvAPI_Status;
     }
+
+    pub const NVAPI_MAX_PCIE_INFO_ENTRIES: usize = 5;
+
+    nvstruct! {
+        /// A PCIe link configuration; the first entry of `NV_GPU_PCIE_INFO` describes the
+        /// link as it is currently trained, the rest the fastest each performance level allows.
+        pub struct NV_GPU_PCIE_INFO_ENTRY {
+            /// Per-lane transfer rate in MT/s, or 0 for an unused entry
+            pub transferRate: u32,
+            pub unknown0: u32,
+            /// Link width
+            pub lanes: u32,
+            pub unknown1: u32,
+        }
+    }
+
+    nvstruct! {
+        pub struct NV_GPU_PCIE_INFO_V2 {
+            pub version: u32,
+            pub unknown: [u32; 7],
+            pub entries: [NV_GPU_PCIE_INFO_ENTRY; NVAPI_MAX_PCIE_INFO_ENTRIES],
+        }
+    }
+
+    pub type NV_GPU_PCIE_INFO = NV_GPU_PCIE_INFO_V2;
+
+    nvversion! { NV_GPU_PCIE_INFO_VER_2(NV_GPU_PCIE_INFO_V2 = 4 * 8 + 4 * 4 * NVAPI_MAX_PCIE_INFO_ENTRIES, 2) }
+    nvversion! { NV_GPU_PCIE_INFO_VER = NV_GPU_PCIE_INFO_VER_2 }
+
+    nvapi! {
+        pub type GPU_GetPCIEInfoFn = extern "C" fn(hPhysicalGpu: NvPhysicalGpuHandle, pPciInfo: *mut NV_GPU_PCIE_INFO) -> NvAPI_Status;
+
+        /// Undocumented function.
+        pub unsafe fn NvAPI_GPU_GetPCIEInfo;
+    }
 }
@@ -185,7 +185,8 @@ impl NV_GPU_PSTATE20_CLOCK_ENTRY_DATA {
                     freq_kHz: (self.0).minFreq_kHz,
                 }
             ),
-            PstateClockType::Range => NV_GPU_PSTATE20_CLOCK_ENTRY_DATA_VALUE::Range(self.0),
+            // an unrecognized type keeps all of the data
+            PstateClockType::Range | PstateClockType::Unrecognized(..) => NV_GPU_PSTATE20_CLOCK_ENTRY_DATA_VALUE::Range(self.0),
         }
     }
 
@@ -295,6 +296,7 @@ nvversion! { NV_GPU_PERF_PSTATES20_INFO_VER1(NV_GPU_PERF_PSTATES20_INFO_V1 = NV_
 nvversion! { NV_GPU_PERF_PSTATES20_INFO_VER2(NV_GPU_PERF_PSTATES20_INFO_V2 = NV_GPU_PERF_PSTATES20_INFO_V2_SIZE, 2) }
 nvversion! { NV_GPU_PERF_PSTATES20_INFO_VER3(NV_GPU_PERF_PSTATES20_INFO_V2 = NV_GPU_PERF_PSTATES20_INFO_V2_SIZE, 3) }
 nvversion! { NV_GPU_PERF_PSTATES20_INFO_VER = NV_GPU_PERF_PSTATES20_INFO_VER3 }
+nvversioned! { NV_GPU_PERF_PSTATES20_INFO_V2: [NV_GPU_PERF_PSTATES20_INFO_VER3, NV_GPU_PERF_PSTATES20_INFO_VER2, NV_GPU_PERF_PSTATES20_INFO_VER1] }
 
 nvapi! {
     pub type GPU_GetPstates20Fn = extern "C" fn(hPhysicalGPU: NvPhysicalGpuHandle, pPstatesInfo: *mut NV_GPU_PERF_PSTATES20_INFO) -> NvAPI_Status;
@@ -0,0 +1,506 @@
+use crate::status::NvAPI_Status;
+use crate::handles::{NvGSyncDeviceHandle, NvPhysicalGpuHandle};
+
+/// The most sync devices a system can have.
+pub const NVAPI_MAX_GSYNC_DEVICES: usize = 4;
+/// The number of RJ45 ports on a sync device.
+pub const NVAPI_MAX_RJ45_PER_GSYNC: usize = 2;
+
+nvenum! {
+    /// Whether a display drives the frame lock signal, follows it, or neither.
+    pub enum NVAPI_GSYNC_DISPLAY_SYNC_STATE / DisplaySyncState {
+        NVAPI_GSYNC_DISPLAY_SYNC_STATE_UNSYNCED / Unsynced = 0,
+        NVAPI_GSYNC_DISPLAY_SYNC_STATE_SLAVE / Slave = 1,
+        NVAPI_GSYNC_DISPLAY_SYNC_STATE_MASTER / Master = 2,
+    }
+}
+
+nvenum_display! {
+    DisplaySyncState => _
+}
+
+nvbits! {
+    /// Bitfield in `NV_GSYNC_DISPLAY`
+    pub enum NV_GSYNC_DISPLAY_FLAGS / GSyncDisplayFlags {
+        /// The display can be the sync master.
+        NV_GSYNC_DISPLAY_IS_MASTERABLE / IS_MASTERABLE = 0x01,
+    }
+}
+
+nvstruct! {
+    /// A display attached to a GPU that is connected to a sync device.
+    pub struct NV_GSYNC_DISPLAY {
+        /// Version of this structure
+        pub version: u32,
+        /// Display identifier for displays. The GPU to which it is connected, can be retrieved from NvAPI_SYS_GetPhysicalGpuFromDisplayId
+        pub displayId: u32,
+        /// Any of the NV_GSYNC_DISPLAY_* flags.
+        pub flags: NV_GSYNC_DISPLAY_FLAGS,
+        /// Sync state of the display
+        pub syncState: NVAPI_GSYNC_DISPLAY_SYNC_STATE,
+    }
+}
+
+nvversion! { NV_GSYNC_DISPLAY_VER1(NV_GSYNC_DISPLAY = 4 * 4, 1) }
+nvversion! { NV_GSYNC_DISPLAY_VER = NV_GSYNC_DISPLAY_VER1 }
+
+nvenum! {
+    /// The connector on the sync device a GPU is attached to.
+    pub enum NVAPI_GSYNC_GPU_TOPOLOGY_CONNECTOR / GSyncConnector {
+        NVAPI_GSYNC_GPU_TOPOLOGY_CONNECTOR_NONE / None = 0,
+        NVAPI_GSYNC_GPU_TOPOLOGY_CONNECTOR_PRIMARY / Primary = 1,
+        NVAPI_GSYNC_GPU_TOPOLOGY_CONNECTOR_SECONDARY / Secondary = 2,
+        NVAPI_GSYNC_GPU_TOPOLOGY_CONNECTOR_TERTIARY / Tertiary = 3,
+        NVAPI_GSYNC_GPU_TOPOLOGY_CONNECTOR_QUARTERNARY / Quarternary = 4,
+    }
+}
+
+nvenum_display! {
+    GSyncConnector => _
+}
+
+nvbits! {
+    /// Bitfield in `NV_GSYNC_GPU`
+    pub enum NV_GSYNC_GPU_FLAGS / GSyncGpuFlags {
+        /// The GPU is framelocked.
+        NV_GSYNC_GPU_IS_SYNCED / IS_SYNCED = 0x01,
+    }
+}
+
+nvstruct! {
+    /// A GPU connected to a sync device.
+    pub struct NV_GSYNC_GPU {
+        /// Version of this structure
+        pub version: u32,
+        /// GPU handle
+        pub hPhysicalGpu: NvPhysicalGpuHandle,
+        /// Indicates which connector on the device the GPU is connected to.
+        pub connector: NVAPI_GSYNC_GPU_TOPOLOGY_CONNECTOR,
+        /// GPU through which hPhysicalGpu is connected to the Sync device (if not directly connected)
+        /// - this is NULL otherwise
+        pub hProxyPhysicalGpu: NvPhysicalGpuHandle,
+        /// Any of the NV_GSYNC_GPU_* flags.
+        pub flags: NV_GSYNC_GPU_FLAGS,
+    }
+}
+
+nvversion! { NV_GSYNC_GPU_VER1(NV_GSYNC_GPU = ::std::mem::size_of::<NV_GSYNC_GPU>(), 1) }
+nvversion! { NV_GSYNC_GPU_VER = NV_GSYNC_GPU_VER1 }
+
+nvstruct! {
+    /// Describes the sync device board.
+    pub struct NV_GSYNC_CAPABILITIES_V1 {
+        /// Version of the structure
+        pub version: u32,
+        /// Board ID
+        pub boardId: u32,
+        /// FPGA major revision
+        pub revision: u32,
+        /// Capabilities of the Sync board. Reserved for future use
+        pub capFlags: u32,
+    }
+}
+
+nvstruct! {
+    pub struct NV_GSYNC_CAPABILITIES_V2 {
+        /// Version of the structure
+        pub version: u32,
+        /// Board ID
+        pub boardId: u32,
+        /// FPGA major revision
+        pub revision: u32,
+        /// Capabilities of the Sync board. Reserved for future use
+        pub capFlags: u32,
+        /// FPGA minor revision
+        pub extendedRevision: u32,
+    }
+}
+
+nvbits! {
+    /// Bitfield in `NV_GSYNC_CAPABILITIES_V3`
+    pub enum NV_GSYNC_CAPABILITIES_FLAGS / GSyncCapabilitiesFlags {
+        /// Whether the board supports multiplying or dividing the sync signal.
+        NV_GSYNC_CAPABILITIES_IS_MUL_DIV_SUPPORTED / IS_MUL_DIV_SUPPORTED = 0x01,
+    }
+}
+
+nvstruct! {
+    pub struct NV_GSYNC_CAPABILITIES_V3 {
+        /// Version of the structure
+        pub version: u32,
+        /// Board ID
+        pub boardId: u32,
+        /// FPGA major revision
+        pub revision: u32,
+        /// Capabilities of the Sync board. Reserved for future use
+        pub capFlags: u32,
+        /// FPGA minor revision
+        pub extendedRevision: u32,
+        /// Any of the NV_GSYNC_CAPABILITIES_* flags.
+        pub flags: NV_GSYNC_CAPABILITIES_FLAGS,
+        /// Maximum value the sync signal can be multiplied or divided by.
+        pub maxMulDivValue: u32,
+    }
+}
+
+pub type NV_GSYNC_CAPABILITIES = NV_GSYNC_CAPABILITIES_V3;
+
+nvversion! { NV_GSYNC_CAPABILITIES_VER1(NV_GSYNC_CAPABILITIES_V1 = 4 * 4, 1) }
+nvversion! { NV_GSYNC_CAPABILITIES_VER2(NV_GSYNC_CAPABILITIES_V2 = 4 * 5, 2) }
+nvversion! { NV_GSYNC_CAPABILITIES_VER3(NV_GSYNC_CAPABILITIES_V3 = 4 * 7, 3) }
+nvversion! { NV_GSYNC_CAPABILITIES_VER = NV_GSYNC_CAPABILITIES_VER3 }
+
+nvenum! {
+    /// The edge of the house sync signal that is used for sync.
+    pub enum NVAPI_GSYNC_POLARITY / GSyncPolarity {
+        NVAPI_GSYNC_POLARITY_RISING_EDGE / RisingEdge = 0,
+        NVAPI_GSYNC_POLARITY_FALLING_EDGE / FallingEdge = 1,
+        NVAPI_GSYNC_POLARITY_BOTH_EDGES / BothEdges = 2,
+    }
+}
+
+nvenum_display! {
+    GSyncPolarity => _
+}
+
+nvenum! {
+    /// The kind of house sync signal.
+    pub enum NVAPI_GSYNC_VIDEO_MODE / GSyncVideoMode {
+        NVAPI_GSYNC_VIDEO_MODE_NONE / None = 0,
+        NVAPI_GSYNC_VIDEO_MODE_TTL / Ttl = 1,
+        NVAPI_GSYNC_VIDEO_MODE_NTSCPALSECAM / NtscPalSecam = 2,
+        NVAPI_GSYNC_VIDEO_MODE_HDTV / Hdtv = 3,
+        NVAPI_GSYNC_VIDEO_MODE_COMPOSITE / Composite = 4,
+    }
+}
+
+nvenum_display! {
+    GSyncVideoMode => _
+}
+
+nvenum! {
+    /// Where the frame lock signal comes from.
+    pub enum NVAPI_GSYNC_SYNC_SOURCE / GSyncSource {
+        /// The vertical sync of the master display.
+        NVAPI_GSYNC_SYNC_SOURCE_VSYNC / Vsync = 0,
+        /// An external house sync signal.
+        NVAPI_GSYNC_SYNC_SOURCE_HOUSESYNC / HouseSync = 1,
+    }
+}
+
+nvenum_display! {
+    GSyncSource => _
+}
+
+nvenum! {
+    /// How the sync signal is scaled before use.
+    pub enum NVAPI_GSYNC_MULTIPLY_DIVIDE_MODE / GSyncMultiplyDivideMode {
+        NVAPI_GSYNC_UNDEFINED_MUL_DIV_MODE / None = 0,
+        NVAPI_GSYNC_MULTIPLY_MODE / Multiply = 1,
+        NVAPI_GSYNC_DIVIDE_MODE / Divide = 2,
+    }
+}
+
+nvenum_display! {
+    GSyncMultiplyDivideMode => _
+}
+
+nvstruct! {
+    /// A delay, in lines and pixels, applied to the sync signal.
+    pub struct NV_GSYNC_DELAY {
+        /// Version of the structure
+        pub version: u32,
+        /// delay to be induced in number of horizontal lines.
+        pub numLines: u32,
+        /// delay to be induced in number of pixels.
+        pub numPixels: u32,
+        /// maximum number of lines supported at current display mode to induce delay. Updated by NvAPI_GSync_GetControlParameters(). Read only.
+        pub maxLines: u32,
+        /// minimum number of pixels required at current display mode to induce delay. Updated by NvAPI_GSync_GetControlParameters(). Read only.
+        pub minPixels: u32,
+    }
+}
+
+nvversion! { NV_GSYNC_DELAY_VER1(NV_GSYNC_DELAY = 4 * 5, 1) }
+nvversion! { NV_GSYNC_DELAY_VER = NV_GSYNC_DELAY_VER1 }
+
+nvbits! {
+    /// Bitfield in `NV_GSYNC_CONTROL_PARAMS`
+    pub enum NV_GSYNC_CONTROL_FLAGS / GSyncControlFlags {
+        /// Sync with interlaced displays.
+        NV_GSYNC_CONTROL_INTERLACE_MODE / INTERLACE_MODE = 0x01,
+        /// Drive house sync out of the RJ45 ports. Only valid with the vsync source.
+        NV_GSYNC_CONTROL_SYNC_SOURCE_IS_OUTPUT / SYNC_SOURCE_IS_OUTPUT = 0x02,
+    }
+}
+
+nvstruct! {
+    /// The settings of a sync device.
+    pub struct NV_GSYNC_CONTROL_PARAMS_V1 {
+        /// Version of the structure
+        pub version: u32,
+        /// Leading edge / Falling edge / both
+        pub polarity: NVAPI_GSYNC_POLARITY,
+        /// None, TTL, NTSCPALSECAM, HDTV
+        pub vmode: NVAPI_GSYNC_VIDEO_MODE,
+        /// Number of pulses to wait between framelock signal generation
+        pub interval: u32,
+        /// VSync/House sync
+        pub source: NVAPI_GSYNC_SYNC_SOURCE,
+        /// Any of the NV_GSYNC_CONTROL_* flags.
+        pub flags: NV_GSYNC_CONTROL_FLAGS,
+        /// Time delay between the frame sync signal and the GPUs signal.
+        pub syncSkew: NV_GSYNC_DELAY,
+        /// Sync start delay for master.
+        pub startupDelay: NV_GSYNC_DELAY,
+    }
+}
+
+nvstruct! {
+    pub struct NV_GSYNC_CONTROL_PARAMS_V2 {
+        /// Version of the structure
+        pub version: u32,
+        /// Leading edge / Falling edge / both
+        pub polarity: NVAPI_GSYNC_POLARITY,
+        /// None, TTL, NTSCPALSECAM, HDTV
+        pub vmode: NVAPI_GSYNC_VIDEO_MODE,
+        /// Number of pulses to wait between framelock signal generation
+        pub interval: u32,
+        /// VSync/House sync
+        pub source: NVAPI_GSYNC_SYNC_SOURCE,
+        /// Any of the NV_GSYNC_CONTROL_* flags.
+        pub flags: NV_GSYNC_CONTROL_FLAGS,
+        /// Time delay between the frame sync signal and the GPUs signal.
+        pub syncSkew: NV_GSYNC_DELAY,
+        /// Sync start delay for master.
+        pub startupDelay: NV_GSYNC_DELAY,
+        /// Multiply or divide the sync signal.
+        pub multiplyDivideMode: NVAPI_GSYNC_MULTIPLY_DIVIDE_MODE,
+        /// What to multiply or divide the sync signal by.
+        pub multiplyDivideValue: u8,
+    }
+}
+
+pub type NV_GSYNC_CONTROL_PARAMS = NV_GSYNC_CONTROL_PARAMS_V2;
+
+nvversion! { NV_GSYNC_CONTROL_PARAMS_VER1(NV_GSYNC_CONTROL_PARAMS_V1 = 4 * 6 + 4 * 5 * 2, 1) }
+nvversion! { NV_GSYNC_CONTROL_PARAMS_VER2(NV_GSYNC_CONTROL_PARAMS_V2 = 4 * 6 + 4 * 5 * 2 + 4 * 2, 2) }
+nvversion! { NV_GSYNC_CONTROL_PARAMS_VER = NV_GSYNC_CONTROL_PARAMS_VER2 }
+
+nvenum! {
+    /// Which delay `NvAPI_GSync_AdjustSyncDelay` adjusts.
+    pub enum NVAPI_GSYNC_DELAY_TYPE / GSyncDelayType {
+        NVAPI_GSYNC_DELAY_TYPE_UNKNOWN / Unknown = 0,
+        NVAPI_GSYNC_DELAY_TYPE_SYNC_SKEW / SyncSkew = 1,
+        NVAPI_GSYNC_DELAY_TYPE_STARTUP / Startup = 2,
+    }
+}
+
+nvenum_display! {
+    GSyncDelayType => _
+}
+
+nvstruct! {
+    /// Whether a GPU is frame locked.
+    pub struct NV_GSYNC_STATUS {
+        /// Version of the structure
+        pub version: u32,
+        /// Is timing in sync?
+        pub bIsSynced: u32,
+        /// Does the phase of the timing signal from the GPU = the phase of the master sync signal?
+        pub bIsStereoSynced: u32,
+        /// Is the sync signal available?
+        pub bIsSyncSignalAvailable: u32,
+    }
+}
+
+nvversion! { NV_GSYNC_STATUS_VER1(NV_GSYNC_STATUS = 4 * 4, 1) }
+nvversion! { NV_GSYNC_STATUS_VER = NV_GSYNC_STATUS_VER1 }
+
+nvenum! {
+    /// The direction of an RJ45 port.
+    pub enum NVAPI_GSYNC_RJ45_IO / GSyncRj45Io {
+        NVAPI_GSYNC_RJ45_OUTPUT / Output = 0,
+        NVAPI_GSYNC_RJ45_INPUT / Input = 1,
+        /// This field is used to notify that the framelock is not actually present.
+        NVAPI_GSYNC_RJ45_UNUSED / Unused = 2,
+    }
+}
+
+nvenum_display! {
+    GSyncRj45Io => _
+}
+
+nvstruct! {
+    /// The signals a sync device is seeing.
+    pub struct NV_GSYNC_STATUS_PARAMS_V1 {
+        /// Version of the structure
+        pub version: u32,
+        /// The refresh rate, in 0.0001 Hz
+        pub refreshRate: u32,
+        /// Configured as input / output
+        pub RJ45_IO: [NVAPI_GSYNC_RJ45_IO; NVAPI_MAX_RJ45_PER_GSYNC],
+        /// Connected to ethernet hub? [ERRONEOUSLY CONNECTED!]
+        pub RJ45_Ethernet: [u32; NVAPI_MAX_RJ45_PER_GSYNC],
+        /// Incoming house sync frequency, in 0.0001 Hz
+        pub houseSyncIncoming: u32,
+        /// Is house sync connected?
+        pub bHouseSync: u32,
+    }
+}
+
+nvbits! {
+    /// Bitfield in `NV_GSYNC_STATUS_PARAMS_V2`
+    pub enum NV_GSYNC_STATUS_PARAMS_FLAGS / GSyncStatusParamsFlags {
+        /// The board is a slave of another sync device's master.
+        NV_GSYNC_STATUS_PARAMS_INTERNAL_SLAVE / INTERNAL_SLAVE = 0x01,
+    }
+}
+
+nvstruct! {
+    pub struct NV_GSYNC_STATUS_PARAMS_V2 {
+        /// Version of the structure
+        pub version: u32,
+        /// The refresh rate, in 0.0001 Hz
+        pub refreshRate: u32,
+        /// Configured as input / output
+        pub RJ45_IO: [NVAPI_GSYNC_RJ45_IO; NVAPI_MAX_RJ45_PER_GSYNC],
+        /// Connected to ethernet hub? [ERRONEOUSLY CONNECTED!]
+        pub RJ45_Ethernet: [u32; NVAPI_MAX_RJ45_PER_GSYNC],
+        /// Incoming house sync frequency, in 0.0001 Hz
+        pub houseSyncIncoming: u32,
+        /// Is house sync connected?
+        pub bHouseSync: u32,
+        /// Any of the NV_GSYNC_STATUS_PARAMS_* flags.
+        pub flags: NV_GSYNC_STATUS_PARAMS_FLAGS,
+    }
+}
+
+pub type NV_GSYNC_STATUS_PARAMS = NV_GSYNC_STATUS_PARAMS_V2;
+
+nvversion! { NV_GSYNC_STATUS_PARAMS_VER1(NV_GSYNC_STATUS_PARAMS_V1 = 4 * 8, 1) }
+nvversion! { NV_GSYNC_STATUS_PARAMS_VER2(NV_GSYNC_STATUS_PARAMS_V2 = 4 * 9, 2) }
+nvversion! { NV_GSYNC_STATUS_PARAMS_VER = NV_GSYNC_STATUS_PARAMS_VER2 }
+
+nvapi! {
+    pub type GSync_EnumSyncDevicesFn = extern "C" fn(nvGSyncHandles: *mut [NvGSyncDeviceHandle; NVAPI_MAX_GSYNC_DEVICES], gsyncCount: *mut u32) -> NvAPI_Status;
+
+    /// This API returns an array of Sync device handles. A Sync device handle represents a
+    /// single Sync device on the system.
+    ///
+    /// # Returns
+    ///
+    /// - `NVAPI_INVALID_ARGUMENT`: nvGSyncHandles or gsyncCount is NULL.
+    /// - `NVAPI_NVIDIA_DEVICE_NOT_FOUND`: The queried Graphics system does not have any Sync Device.
+    pub unsafe fn NvAPI_GSync_EnumSyncDevices;
+}
+
+nvapi! {
+    pub type GSync_QueryCapabilitiesFn = extern "C" fn(hNvGSyncDevice: NvGSyncDeviceHandle, pNvGSyncCapabilities: *mut NV_GSYNC_CAPABILITIES) -> NvAPI_Status;
+
+    /// This API returns the capabilities of the Sync device.
+    ///
+    /// # Returns
+    ///
+    /// - `NVAPI_INVALID_ARGUMENT`: hNvGSyncDevice is NULL.
+    /// - `NVAPI_NVIDIA_DEVICE_NOT_FOUND`: The queried Graphics system does not have any Sync Device.
+    pub unsafe fn NvAPI_GSync_QueryCapabilities;
+}
+
+nvapi! {
+    pub type GSync_GetTopologyFn = extern "C" fn(hNvGSyncDevice: NvGSyncDeviceHandle, gsyncGpuCount: *mut u32, gsyncGPUs: *mut NV_GSYNC_GPU, gsyncDisplayCount: *mut u32, gsyncDisplays: *mut NV_GSYNC_DISPLAY) -> NvAPI_Status;
+
+    /// This API returns the topology for the specified Sync device.
+    ///
+    /// If `gsyncGPUs` or `gsyncDisplays` is NULL, the matching count receives the number
+    /// of GPUs or displays connected to the device. Otherwise the count is the length of
+    /// the array, and receives the number of entries that were written.
+    ///
+    /// # Returns
+    ///
+    /// - `NVAPI_INVALID_ARGUMENT`: hNvGSyncDevice is NULL.
+    /// - `NVAPI_NVIDIA_DEVICE_NOT_FOUND`: The queried Graphics system does not have any Sync Device.
+    /// - `NVAPI_INCOMPATIBLE_STRUCT_VERSION`: The version of the structure passed in is not compatible with this entry point.
+    pub unsafe fn NvAPI_GSync_GetTopology;
+}
+
+nvapi! {
+    pub type GSync_SetSyncStateSettingsFn = extern "C" fn(gsyncDisplayCount: u32, pGsyncDisplays: *mut NV_GSYNC_DISPLAY, flags: u32) -> NvAPI_Status;
+
+    /// Sets a new sync topology for all the Sync devices in the system; displays that are
+    /// not listed become unsynced.
+    ///
+    /// At most one display in the whole system may be the master. `flags` is reserved
+    /// and must be 0.
+    ///
+    /// # Returns
+    ///
+    /// - `NVAPI_INVALID_ARGUMENT`: The pGsyncDisplays array is NULL or more than one display is set as master.
+    /// - `NVAPI_INVALID_SYNC_TOPOLOGY`: A display is not connected to a Sync device, or cannot be the master.
+    /// - `NVAPI_NVIDIA_DEVICE_NOT_FOUND`: The queried Graphics system does not have any Sync Device.
+    pub unsafe fn NvAPI_GSync_SetSyncStateSettings;
+}
+
+nvapi! {
+    pub type GSync_GetControlParametersFn = extern "C" fn(hNvGSyncDevice: NvGSyncDeviceHandle, pGsyncControls: *mut NV_GSYNC_CONTROL_PARAMS) -> NvAPI_Status;
+
+    /// This API queries for sync control parameters as defined in `NV_GSYNC_CONTROL_PARAMS`.
+    ///
+    /// # Returns
+    ///
+    /// - `NVAPI_INVALID_ARGUMENT`: hNvGSyncDevice is NULL.
+    /// - `NVAPI_NVIDIA_DEVICE_NOT_FOUND`: The queried Graphics system does not have any Sync Device.
+    /// - `NVAPI_INCOMPATIBLE_STRUCT_VERSION`: The version of the structure passed in is not compatible with this entry point.
+    pub unsafe fn NvAPI_GSync_GetControlParameters;
+}
+
+nvapi! {
+    pub type GSync_SetControlParametersFn = extern "C" fn(hNvGSyncDevice: NvGSyncDeviceHandle, pGsyncControls: *mut NV_GSYNC_CONTROL_PARAMS) -> NvAPI_Status;
+
+    /// This API sets control parameters as defined in `NV_GSYNC_CONTROL_PARAMS`.
+    ///
+    /// # Returns
+    ///
+    /// - `NVAPI_INVALID_ARGUMENT`: hNvGSyncDevice is NULL, or a parameter is out of range.
+    /// - `NVAPI_NVIDIA_DEVICE_NOT_FOUND`: The queried Graphics system does not have any Sync Device.
+    /// - `NVAPI_SYNC_MASTER_NOT_FOUND`: Control Parameters can only be set if there is a Sync Master enabled on the Gsync card.
+    /// - `NVAPI_INCOMPATIBLE_STRUCT_VERSION`: The version of the structure passed in is not compatible with this entry point.
+    pub unsafe fn NvAPI_GSync_SetControlParameters;
+}
+
+nvapi! {
+    pub type GSync_AdjustSyncDelayFn = extern "C" fn(hNvGSyncDevice: NvGSyncDeviceHandle, delayType: NVAPI_GSYNC_DELAY_TYPE, pGsyncDelay: *mut NV_GSYNC_DELAY, syncSteps: *mut u32) -> NvAPI_Status;
+
+    /// This API adjusts the skew and startDelay to the closest possible values. Use this
+    /// API before calling `NvAPI_GSync_SetControlParameters` for skew or startDelay.
+    ///
+    /// `syncSteps`, if not NULL, receives the number of sync steps the delay works out to.
+    pub unsafe fn NvAPI_GSync_AdjustSyncDelay;
+}
+
+nvapi! {
+    pub type GSync_GetSyncStatusFn = extern "C" fn(hNvGSyncDevice: NvGSyncDeviceHandle, hPhysicalGpu: NvPhysicalGpuHandle, status: *mut NV_GSYNC_STATUS) -> NvAPI_Status;
+
+    /// This API queries the sync status of a GPU - timing, stereosync and sync signal
+    /// availability.
+    ///
+    /// # Returns
+    ///
+    /// - `NVAPI_INVALID_ARGUMENT`: status is NULL.
+    /// - `NVAPI_NVIDIA_DEVICE_NOT_FOUND`: The queried Graphics system does not have any Sync Device.
+    /// - `NVAPI_EXPECTED_PHYSICAL_GPU_HANDLE`: hPhysicalGpu is not connected to the Sync device.
+    pub unsafe fn NvAPI_GSync_GetSyncStatus;
+}
+
+nvapi! {
+    pub type GSync_GetStatusParametersFn = extern "C" fn(hNvGSyncDevice: NvGSyncDeviceHandle, pStatusParams: *mut NV_GSYNC_STATUS_PARAMS) -> NvAPI_Status;
+
+    /// This API queries the sync status parameters as defined in `NV_GSYNC_STATUS_PARAMS`.
+    ///
+    /// # Returns
+    ///
+    /// - `NVAPI_INVALID_ARGUMENT`: The input parameter is NULL.
+    /// - `NVAPI_NVIDIA_DEVICE_NOT_FOUND`: The queried Graphics system does not have any Sync Device.
+    /// - `NVAPI_INCOMPATIBLE_STRUCT_VERSION`: The version of the structure passed in is not compatible with this entry point.
+    pub unsafe fn NvAPI_GSync_GetStatusParameters;
+}
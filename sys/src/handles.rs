@@ -75,6 +75,16 @@ nv_declare_handle! {
     Nv3DVPGlassesHandle
 }
 
+nv_declare_handle! {
+    /// A handle to a driver settings session
+    NvDRSSessionHandle
+}
+
+nv_declare_handle! {
+    /// A handle to a driver settings profile, valid within its session
+    NvDRSProfileHandle
+}
+
 /// A stereo handle, that corresponds to the device interface
 pub type StereoHandle = *const c_void;
 
@@ -155,6 +155,7 @@ nvversion! { NV_I2C_INFO_VER1(NV_I2C_INFO_V1 = NV_I2C_INFO_V1_SIZE, 1) }
 nvversion! { NV_I2C_INFO_VER2(NV_I2C_INFO_V2 = NV_I2C_INFO_V2_SIZE, 2) }
 nvversion! { NV_I2C_INFO_VER3(NV_I2C_INFO_V3 = NV_I2C_INFO_V3_SIZE, 3) }
 nvversion! { NV_I2C_INFO_VER = NV_I2C_INFO_VER3 }
+nvversioned! { NV_I2C_INFO_V3: [NV_I2C_INFO_VER3, NV_I2C_INFO_VER2, NV_I2C_INFO_VER1] }
 
 nvapi! {
     pub type NvAPI_I2CReadFn = extern "C" fn(hPhysicalGpu: NvPhysicalGpuHandle, pI2cInfo: *mut NV_I2C_INFO) -> NvAPI_Status;
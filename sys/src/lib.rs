@@ -8,6 +8,7 @@ pub mod nvid;
 pub mod nvapi;
 pub mod status;
 pub mod types;
+mod error;
 
 /// NVAPI Handles - These handles are retrieved from various calls and passed in
 /// to others in NvAPI These are meant to be opaque types. Do not assume they
@@ -34,28 +35,101 @@ pub mod dx;
 
 pub mod dispcontrol;
 
+/// Mosaic - Spanning a single desktop across a grid of displays.
+pub mod mosaic;
+
+/// G-SYNC and Quadro Sync - Frame lock across GPUs and systems through a sync device.
+pub mod gsync;
+
+/// Event - Callbacks the driver makes when something happens, such as a loss of sync.
+pub mod event;
+
+/// Driver settings (DRS) - Per-application driver settings, organized into profiles.
+pub mod drs;
+
+/// Well-known driver setting ids and values for use with the DRS APIs.
+pub mod driversettings;
+
+/// Recording and replay of NVAPI calls.
+pub mod capture;
+
+/// Structures that are accepted under more than one version.
+pub mod versioned;
+
+/// Calls to functions that have no typed binding in this crate.
+pub mod raw;
+
 pub use nvid::Api;
 pub use nvapi::nvapi_QueryInterface;
 pub use types::*;
 pub use status::{NvAPI_Status, Status};
+pub use error::Error;
 
-use std::result;
+use std::{fmt, result};
 use std::convert::Infallible;
 
 /// The result of a fallible NVAPI call.
-pub type Result<T> = result::Result<T, Status>;
+pub type Result<T> = result::Result<T, Error>;
 
 /// Treat `NVAPI_OK` as `Ok(())` and all else as an `Err(..)`.
+///
+/// When `status` came from the most recent call on this thread, the error
+/// identifies the function and structure version involved.
 pub fn status_result(status: NvAPI_Status) -> Result<()> {
     match status {
         status::NVAPI_OK => Ok(()),
-        status => Err(Status::from_raw(status).unwrap_or(Status::Error)),
+        status => Err(error::last_failure(status)),
     }
 }
 
+/// Lets `nvenum_display!` show values unknown to this crate by their raw value.
+#[doc(hidden)]
+pub trait UnrecognizedValue {
+    fn unrecognized_value(&self) -> Option<i64>;
+}
+
 /// Error type indicating a raw value is out of the range of known enum values.
-#[derive(Debug, Copy, Clone, Default)]
-pub struct ArgumentRangeError;
+///
+/// When a count or index reported by the driver is out of bounds, the error names
+/// the offending field and its value.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ArgumentRangeError {
+    field: Option<(&'static str, i64)>,
+}
+
+impl ArgumentRangeError {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// The value `value` of the field `name` is out of range.
+    pub fn field(name: &'static str, value: i64) -> Self {
+        ArgumentRangeError {
+            field: Some((name, value)),
+        }
+    }
+
+    /// The name of the out-of-range field, if known.
+    pub fn name(&self) -> Option<&'static str> {
+        self.field.map(|(name, _)| name)
+    }
+
+    /// The out-of-range value, if known.
+    pub fn value(&self) -> Option<i64> {
+        self.field.map(|(_, value)| value)
+    }
+}
+
+impl fmt::Display for ArgumentRangeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.field {
+            Some((name, value)) => write!(f, "{} out of range: {}", name, value),
+            None => write!(f, "value out of range"),
+        }
+    }
+}
+
+impl std::error::Error for ArgumentRangeError { }
 
 impl From<ArgumentRangeError> for Status {
     fn from(_: ArgumentRangeError) -> Self {
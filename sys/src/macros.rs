@@ -5,6 +5,7 @@ macro_rules! nv_declare_handle {
     ) => {
         $(#[$meta])*
         #[derive(Copy, Clone, Debug)]
+        #[repr(transparent)]
         pub struct $name(*const ::std::os::raw::c_void);
 
         impl Default for $name {
@@ -12,6 +13,23 @@ macro_rules! nv_declare_handle {
                 $name(::std::ptr::null())
             }
         }
+
+        impl $name {
+            /// Wraps a raw handle value, as produced by an NVAPI implementation.
+            pub fn from_raw(ptr: *const ::std::os::raw::c_void) -> Self {
+                $name(ptr)
+            }
+
+            pub fn as_ptr(&self) -> *const ::std::os::raw::c_void {
+                self.0
+            }
+        }
+
+        impl crate::capture::CaptureArg for $name {
+            fn value(&self) -> Vec<u8> {
+                (self.0 as usize).to_ne_bytes().to_vec()
+            }
+        }
     };
 }
 
@@ -76,27 +94,73 @@ macro_rules! nvenum {
 
         $(#[$meta])*
         #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-        #[derive(Debug, Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash)]
-        #[repr(i32)]
+        #[derive(Debug, Copy, Clone)]
         pub enum $enum_name {
             $(
                 $(#[$metai])*
-                $name = $symbol as _,
+                $name,
             )*
+            /// A value not known to this crate, such as one introduced by a newer driver.
+            Unrecognized($enum),
+        }
+
+        // compared by raw value, so that ordering follows the driver's values
+        impl PartialEq for $enum_name {
+            fn eq(&self, other: &Self) -> bool {
+                self.raw() == other.raw()
+            }
+        }
+
+        impl Eq for $enum_name { }
+
+        impl PartialOrd for $enum_name {
+            fn partial_cmp(&self, other: &Self) -> Option<::std::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Ord for $enum_name {
+            fn cmp(&self, other: &Self) -> ::std::cmp::Ordering {
+                self.raw().cmp(&other.raw())
+            }
+        }
+
+        impl ::std::hash::Hash for $enum_name {
+            fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+                self.raw().hash(state)
+            }
         }
 
         impl $enum_name {
+            /// Fails for values not known to this crate.
             pub fn from_raw(raw: $enum) -> ::std::result::Result<Self, crate::ArgumentRangeError> {
+                match Self::from_raw_lossless(raw) {
+                    $enum_name::Unrecognized(..) => Err(Default::default()),
+                    value => Ok(value),
+                }
+            }
+
+            /// Preserves values not known to this crate as `Unrecognized`.
+            pub fn from_raw_lossless(raw: $enum) -> Self {
                 match raw {
                     $(
-                        $symbol
-                    )|* => Ok(unsafe { ::std::mem::transmute(raw) }),
-                    _ => Err(Default::default()),
+                        $symbol => $enum_name::$name,
+                    )*
+                    raw => $enum_name::Unrecognized(raw),
                 }
             }
 
             pub fn raw(&self) -> $enum {
-                *self as _
+                match *self {
+                    $(
+                        $enum_name::$name => $symbol,
+                    )*
+                    $enum_name::Unrecognized(raw) => raw,
+                }
+            }
+
+            pub fn is_unrecognized(&self) -> bool {
+                matches!(*self, $enum_name::Unrecognized(..))
             }
 
             pub fn values() -> impl Iterator<Item=Self> {
@@ -108,9 +172,18 @@ macro_rules! nvenum {
             }
         }
 
-        impl Into<$enum> for $enum_name {
-            fn into(self) -> $enum {
-                self as _
+        impl crate::UnrecognizedValue for $enum_name {
+            fn unrecognized_value(&self) -> Option<i64> {
+                match *self {
+                    $enum_name::Unrecognized(raw) => Some(raw as i64),
+                    _ => None,
+                }
+            }
+        }
+
+        impl From<$enum_name> for $enum {
+            fn from(value: $enum_name) -> Self {
+                value.raw()
             }
         }
     };
@@ -145,6 +218,12 @@ macro_rules! nvbits {
             }
         }
 
+        impl crate::UnrecognizedValue for $enum_name {
+            fn unrecognized_value(&self) -> Option<i64> {
+                None
+            }
+        }
+
         impl Iterator for $enum_name {
             type Item = Self;
 
@@ -165,34 +244,48 @@ macro_rules! nvenum_display {
     ($enum:ident => _) => {
         impl ::std::fmt::Display for $enum {
             fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
-                ::std::fmt::Debug::fmt(self, f)
+                nvenum_display!(@expr self f _)
             }
         }
     };
     ($enum:ident => {
         $(
-            $name:tt = $value:tt,
+            $name:ident = $value:tt,
         )*
+        _ = $default:tt,
     }) => {
         impl ::std::fmt::Display for $enum {
             fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
                 match *self {
                 $(
-                    nvenum_display!(@q $enum $name) => nvenum_display!(@expr self f $value),
-                    //$enum::$name => nvenum_display!(@expr self f $value),
+                    $enum::$name => nvenum_display!(@expr self f $value),
                 )*
+                    _ => nvenum_display!(@expr self f $default),
                 }
             }
         }
     };
-    (@q $enum:ident _) => {
-        _
-    };
-    (@q $enum:ident $name:ident) => {
-        $enum::$name
+    ($enum:ident => {
+        $(
+            $name:ident = $value:tt,
+        )*
+    }) => {
+        impl ::std::fmt::Display for $enum {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                match *self {
+                $(
+                    $enum::$name => nvenum_display!(@expr self f $value),
+                )*
+                    $enum::Unrecognized(raw) => write!(f, "Unknown ({})", raw),
+                }
+            }
+        }
     };
     (@expr $this:tt $fmt:ident _) => {
-        ::std::fmt::Debug::fmt($this, $fmt)
+        match crate::UnrecognizedValue::unrecognized_value($this) {
+            Some(raw) => write!($fmt, "Unknown ({})", raw),
+            None => ::std::fmt::Debug::fmt($this, $fmt),
+        }
     };
     (@expr $this:tt $fmt:ident $expr:expr) => {
         write!($fmt, "{}", $expr)
@@ -205,13 +298,29 @@ macro_rules! nvapi {
         pub unsafe fn $fn:ident($($arg:ident: $arg_ty:ty),*) -> $ret:ty;
     ) => {
         $(#[$meta])*
+        ///
+        /// # Safety
+        ///
+        /// Pointers must be null or valid for the data NVAPI reads and writes through them,
+        /// and handles must have been handed out by NVAPI.
         pub unsafe fn $fn($($arg: $arg_ty),*) -> $ret {
-            static CACHE: ::std::sync::atomic::AtomicUsize = ::std::sync::atomic::AtomicUsize::new(0);
+            static CACHE: crate::nvapi::FnCache = crate::nvapi::FnCache::new();
 
-            match crate::nvapi::query_interface(crate::nvid::Api::$fn.id(), &CACHE) {
-                Ok(ptr) => ::std::mem::transmute::<_, extern "C" fn($($arg: $arg_ty),*) -> $ret>(ptr)($($arg),*),
-                Err(e) => e.raw(),
+            extern "C" fn replay($($arg: $arg_ty),*) -> $ret {
+                unsafe { crate::capture::replay_call(crate::nvid::Api::$fn, &[$(&$arg),*]) }
             }
+
+            let args: &[&dyn crate::capture::CaptureArg] = &[$(&$arg),*];
+            let replay: extern "C" fn($($arg_ty),*) -> $ret = replay;
+            let status = match CACHE.resolve(crate::nvid::Api::$fn, replay as usize) {
+                Ok(ptr) => {
+                    let f = ::std::mem::transmute::<usize, extern "C" fn($($arg: $arg_ty),*) -> $ret>(ptr);
+                    crate::capture::record_call(crate::nvid::Api::$fn, args, || f($($arg),*))
+                },
+                Err(e) => e.raw(),
+            };
+            crate::error::track(crate::nvid::Api::$fn, args, status);
+            status
         }
     };
     (
@@ -252,3 +361,19 @@ macro_rules! nvversion {
     };
 }
 
+
+macro_rules! nvversioned {
+    ($struct:ident: [$($ver:ident),+ $(,)*]) => {
+        impl crate::versioned::VersionedStruct for $struct {
+            const VERSIONS: &'static [u32] = &[$($ver),+];
+
+            fn version(&self) -> u32 {
+                self.version
+            }
+
+            fn set_version(&mut self, version: u32) {
+                self.version = version;
+            }
+        }
+    };
+}
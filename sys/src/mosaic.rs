@@ -0,0 +1,442 @@
+#![allow(non_upper_case_globals)]
+
+use crate::status::NvAPI_Status;
+use crate::types::{NV_RECT, NVAPI_MAX_DISPLAYS};
+use crate::dispcontrol::NV_ROTATE;
+
+/// The most displays a single grid can span.
+pub const NV_MOSAIC_MAX_DISPLAYS: usize = 64;
+
+nvenum! {
+    /// Mosaic topologies, named rows by columns.
+    pub enum NV_MOSAIC_TOPO / Topology {
+        NV_MOSAIC_TOPO_NONE / None = 0,
+        NV_MOSAIC_TOPO_1x2_BASIC / Basic1x2 = 1,
+        NV_MOSAIC_TOPO_2x1_BASIC / Basic2x1 = 2,
+        NV_MOSAIC_TOPO_1x3_BASIC / Basic1x3 = 3,
+        NV_MOSAIC_TOPO_3x1_BASIC / Basic3x1 = 4,
+        NV_MOSAIC_TOPO_1x4_BASIC / Basic1x4 = 5,
+        NV_MOSAIC_TOPO_4x1_BASIC / Basic4x1 = 6,
+        NV_MOSAIC_TOPO_2x2_BASIC / Basic2x2 = 7,
+        NV_MOSAIC_TOPO_2x3_BASIC / Basic2x3 = 8,
+        NV_MOSAIC_TOPO_2x4_BASIC / Basic2x4 = 9,
+        NV_MOSAIC_TOPO_3x2_BASIC / Basic3x2 = 10,
+        NV_MOSAIC_TOPO_4x2_BASIC / Basic4x2 = 11,
+        NV_MOSAIC_TOPO_1x5_BASIC / Basic1x5 = 12,
+        NV_MOSAIC_TOPO_1x6_BASIC / Basic1x6 = 13,
+        NV_MOSAIC_TOPO_7x1_BASIC / Basic7x1 = 14,
+        NV_MOSAIC_TOPO_1x2_PASSIVE_STEREO / PassiveStereo1x2 = 25,
+        NV_MOSAIC_TOPO_2x1_PASSIVE_STEREO / PassiveStereo2x1 = 26,
+        NV_MOSAIC_TOPO_1x3_PASSIVE_STEREO / PassiveStereo1x3 = 27,
+        NV_MOSAIC_TOPO_3x1_PASSIVE_STEREO / PassiveStereo3x1 = 28,
+        NV_MOSAIC_TOPO_1x4_PASSIVE_STEREO / PassiveStereo1x4 = 29,
+        NV_MOSAIC_TOPO_4x1_PASSIVE_STEREO / PassiveStereo4x1 = 30,
+        NV_MOSAIC_TOPO_2x2_PASSIVE_STEREO / PassiveStereo2x2 = 31,
+    }
+}
+
+nvenum_display! {
+    Topology => _
+}
+
+impl Topology {
+    /// The basic topology with the given shape, if there is one.
+    pub fn basic(rows: u32, columns: u32) -> Option<Self> {
+        Some(match (rows, columns) {
+            (1, 2) => Topology::Basic1x2,
+            (2, 1) => Topology::Basic2x1,
+            (1, 3) => Topology::Basic1x3,
+            (3, 1) => Topology::Basic3x1,
+            (1, 4) => Topology::Basic1x4,
+            (4, 1) => Topology::Basic4x1,
+            (2, 2) => Topology::Basic2x2,
+            (2, 3) => Topology::Basic2x3,
+            (2, 4) => Topology::Basic2x4,
+            (3, 2) => Topology::Basic3x2,
+            (4, 2) => Topology::Basic4x2,
+            (1, 5) => Topology::Basic1x5,
+            (1, 6) => Topology::Basic1x6,
+            (7, 1) => Topology::Basic7x1,
+            _ => return None,
+        })
+    }
+
+    /// The rows and columns of a known topology.
+    pub fn shape(&self) -> Option<(u32, u32)> {
+        Some(match *self {
+            Topology::Basic1x2 | Topology::PassiveStereo1x2 => (1, 2),
+            Topology::Basic2x1 | Topology::PassiveStereo2x1 => (2, 1),
+            Topology::Basic1x3 | Topology::PassiveStereo1x3 => (1, 3),
+            Topology::Basic3x1 | Topology::PassiveStereo3x1 => (3, 1),
+            Topology::Basic1x4 | Topology::PassiveStereo1x4 => (1, 4),
+            Topology::Basic4x1 | Topology::PassiveStereo4x1 => (4, 1),
+            Topology::Basic2x2 | Topology::PassiveStereo2x2 => (2, 2),
+            Topology::Basic2x3 => (2, 3),
+            Topology::Basic2x4 => (2, 4),
+            Topology::Basic3x2 => (3, 2),
+            Topology::Basic4x2 => (4, 2),
+            Topology::Basic1x5 => (1, 5),
+            Topology::Basic1x6 => (1, 6),
+            Topology::Basic7x1 => (7, 1),
+            _ => return None,
+        })
+    }
+}
+
+nvstruct! {
+    /// A brief description of a topology.
+    pub struct NV_MOSAIC_TOPO_BRIEF {
+        /// Version of this structure
+        pub version: u32,
+        /// The topology
+        pub topo: NV_MOSAIC_TOPO,
+        /// 1 if topo is enabled, else 0
+        pub enabled: u32,
+        /// 1 if topo can be enabled, else 0
+        pub isPossible: u32,
+    }
+}
+
+nvversion! { NVAPI_MOSAIC_TOPO_BRIEF_VER1(NV_MOSAIC_TOPO_BRIEF = 4 * 4, 1) }
+nvversion! { NVAPI_MOSAIC_TOPO_BRIEF_VER = NVAPI_MOSAIC_TOPO_BRIEF_VER1 }
+
+nvstruct! {
+    /// Basic per-display settings that are used in setting/getting the Mosaic mode
+    pub struct NV_MOSAIC_DISPLAY_SETTING_V1 {
+        /// Version of this structure
+        pub version: u32,
+        /// Per-display width
+        pub width: u32,
+        /// Per-display height
+        pub height: u32,
+        /// Bits per pixel
+        pub bpp: u32,
+        /// Display frequency
+        pub freq: u32,
+    }
+}
+
+nvstruct! {
+    pub struct NV_MOSAIC_DISPLAY_SETTING_V2 {
+        /// Version of this structure
+        pub version: u32,
+        /// Per-display width
+        pub width: u32,
+        /// Per-display height
+        pub height: u32,
+        /// Bits per pixel
+        pub bpp: u32,
+        /// Display frequency
+        pub freq: u32,
+        /// Display frequency in x1k
+        pub rrx1k: u32,
+    }
+}
+
+pub type NV_MOSAIC_DISPLAY_SETTING = NV_MOSAIC_DISPLAY_SETTING_V2;
+
+nvversion! { NVAPI_MOSAIC_DISPLAY_SETTING_VER1(NV_MOSAIC_DISPLAY_SETTING_V1 = 4 * 5, 1) }
+nvversion! { NVAPI_MOSAIC_DISPLAY_SETTING_VER2(NV_MOSAIC_DISPLAY_SETTING_V2 = 4 * 6, 2) }
+nvversion! { NVAPI_MOSAIC_DISPLAY_SETTING_VER = NVAPI_MOSAIC_DISPLAY_SETTING_VER2 }
+
+nvenum! {
+    /// How a display is shifted to spread wear on projectors with a lower native resolution.
+    pub enum NV_PIXEL_SHIFT_TYPE / PixelShiftType {
+        /// No pixel shift will be applied to this display.
+        NV_PIXEL_SHIFT_TYPE_NO_PIXEL_SHIFT / None = 0,
+        /// This display will be used to scanout top left pixels in 2x2 PixelShift configuration
+        NV_PIXEL_SHIFT_TYPE_2x2_TOP_LEFT_PIXELS / TopLeft2x2 = 1,
+        /// This display will be used to scanout bottom right pixels in 2x2 PixelShift configuration
+        NV_PIXEL_SHIFT_TYPE_2x2_BOTTOM_RIGHT_PIXELS / BottomRight2x2 = 2,
+        /// This display will be used to scanout top right pixels in 2x2 PixelShift configuration
+        NV_PIXEL_SHIFT_TYPE_2x2_TOP_RIGHT_PIXELS / TopRight2x2 = 4,
+        /// This display will be used to scanout bottom left pixels in 2x2 PixelShift configuration
+        NV_PIXEL_SHIFT_TYPE_2x2_BOTTOM_LEFT_PIXELS / BottomLeft2x2 = 8,
+    }
+}
+
+nvenum_display! {
+    PixelShiftType => _
+}
+
+nvstruct! {
+    pub struct NV_MOSAIC_GRID_TOPO_DISPLAY_V1 {
+        /// DisplayID of the display
+        pub displayId: u32,
+        /// (+overlap, -gap)
+        pub overlapX: i32,
+        /// (+overlap, -gap)
+        pub overlapY: i32,
+        /// Rotation of display
+        pub rotation: NV_ROTATE,
+        /// Reserved for future use
+        pub cloneGroup: u32,
+    }
+}
+
+nvstruct! {
+    pub struct NV_MOSAIC_GRID_TOPO_DISPLAY_V2 {
+        /// Version of this structure
+        pub version: u32,
+        /// DisplayID of the display
+        pub displayId: u32,
+        /// (+overlap, -gap)
+        pub overlapX: i32,
+        /// (+overlap, -gap)
+        pub overlapY: i32,
+        /// Rotation of display
+        pub rotation: NV_ROTATE,
+        /// Reserved for future use
+        pub cloneGroup: u32,
+        /// Type of the pixel shift enabled display
+        pub pixelShiftType: NV_PIXEL_SHIFT_TYPE,
+    }
+}
+
+pub type NV_MOSAIC_GRID_TOPO_DISPLAY = NV_MOSAIC_GRID_TOPO_DISPLAY_V2;
+
+nvversion! { NV_MOSAIC_GRID_TOPO_DISPLAY_VER2(NV_MOSAIC_GRID_TOPO_DISPLAY_V2 = 4 * 7, 2) }
+nvversion! { NV_MOSAIC_GRID_TOPO_DISPLAY_VER = NV_MOSAIC_GRID_TOPO_DISPLAY_VER2 }
+
+nvbits! {
+    /// Bitfield in `NV_MOSAIC_GRID_TOPO`
+    pub enum NV_MOSAIC_GRID_TOPO_FLAGS / GridTopologyFlags {
+        /// Enable as Bezel Corrected
+        NV_MOSAIC_GRID_TOPO_APPLY_WITH_BEZEL_CORRECT / APPLY_WITH_BEZEL_CORRECT = 0x01,
+        /// Enable as Immersive Gaming instead of Mosaic SLI (for Quadro-boards only)
+        NV_MOSAIC_GRID_TOPO_IMMERSIVE_GAMING / IMMERSIVE_GAMING = 0x02,
+        /// Enable as Base Mosaic (Panoramic) instead of Mosaic SLI (for NVS and Quadro-boards only)
+        NV_MOSAIC_GRID_TOPO_BASE_MOSAIC / BASE_MOSAIC = 0x04,
+        /// If necessary, reloading the driver is permitted (for Vista and above only). Will not be persisted.
+        NV_MOSAIC_GRID_TOPO_DRIVER_RELOAD_ALLOWED / DRIVER_RELOAD_ALLOWED = 0x08,
+        /// Enable SLI acceleration on the primary display while in single-wide mode (For Immersive Gaming only). Will not be persisted.
+        NV_MOSAIC_GRID_TOPO_ACCELERATE_PRIMARY_DISPLAY / ACCELERATE_PRIMARY_DISPLAY = 0x10,
+        /// Enable Pixel shift
+        NV_MOSAIC_GRID_TOPO_PIXEL_SHIFT / PIXEL_SHIFT = 0x20,
+    }
+}
+
+nvstruct! {
+    /// Grid topology structure that describes a Mosaic grid: its shape, the displays
+    /// that make it up and the mode they all run at.
+    pub struct NV_MOSAIC_GRID_TOPO_V1 {
+        /// Version of this structure
+        pub version: u32,
+        /// Number of rows
+        pub rows: u32,
+        /// Number of columns
+        pub columns: u32,
+        /// Number of display details
+        pub displayCount: u32,
+        pub flags: NV_MOSAIC_GRID_TOPO_FLAGS,
+        /// Displays are done as [(row * columns) + column]
+        pub displays: [NV_MOSAIC_GRID_TOPO_DISPLAY_V1; NV_MOSAIC_MAX_DISPLAYS],
+        /// Display settings
+        pub displaySettings: NV_MOSAIC_DISPLAY_SETTING_V1,
+    }
+}
+
+nvstruct! {
+    pub struct NV_MOSAIC_GRID_TOPO_V2 {
+        /// Version of this structure
+        pub version: u32,
+        /// Number of rows
+        pub rows: u32,
+        /// Number of columns
+        pub columns: u32,
+        /// Number of display details
+        pub displayCount: u32,
+        pub flags: NV_MOSAIC_GRID_TOPO_FLAGS,
+        /// Displays are done as [(row * columns) + column]
+        pub displays: [NV_MOSAIC_GRID_TOPO_DISPLAY_V2; NV_MOSAIC_MAX_DISPLAYS],
+        /// Display settings
+        pub displaySettings: NV_MOSAIC_DISPLAY_SETTING_V1,
+    }
+}
+
+pub type NV_MOSAIC_GRID_TOPO = NV_MOSAIC_GRID_TOPO_V2;
+
+const NV_MOSAIC_GRID_TOPO_V1_SIZE: usize = 4 * 5 + 4 * 5 * NV_MOSAIC_MAX_DISPLAYS + 4 * 5;
+const NV_MOSAIC_GRID_TOPO_V2_SIZE: usize = 4 * 5 + 4 * 7 * NV_MOSAIC_MAX_DISPLAYS + 4 * 5;
+
+nvversion! { NV_MOSAIC_GRID_TOPO_VER1(NV_MOSAIC_GRID_TOPO_V1 = NV_MOSAIC_GRID_TOPO_V1_SIZE, 1) }
+nvversion! { NV_MOSAIC_GRID_TOPO_VER2(NV_MOSAIC_GRID_TOPO_V2 = NV_MOSAIC_GRID_TOPO_V2_SIZE, 2) }
+nvversion! { NV_MOSAIC_GRID_TOPO_VER = NV_MOSAIC_GRID_TOPO_VER2 }
+
+nvbits! {
+    /// Errors in `NV_MOSAIC_DISPLAY_TOPO_STATUS`, for a whole grid or one of its displays.
+    pub enum NV_MOSAIC_DISPLAYCAPS_PROBLEM / DisplayCapsProblem {
+        NV_MOSAIC_DISPLAYCAPS_PROBLEM_DISPLAY_ON_INVALID_GPU / DISPLAY_ON_INVALID_GPU = 1 << 0,
+        NV_MOSAIC_DISPLAYCAPS_PROBLEM_DISPLAY_ON_WRONG_CONNECTOR / DISPLAY_ON_WRONG_CONNECTOR = 1 << 1,
+        NV_MOSAIC_DISPLAYCAPS_PROBLEM_NO_COMMON_TIMINGS / NO_COMMON_TIMINGS = 1 << 2,
+        NV_MOSAIC_DISPLAYCAPS_PROBLEM_NO_EDID_AVAILABLE / NO_EDID_AVAILABLE = 1 << 3,
+        NV_MOSAIC_DISPLAYCAPS_PROBLEM_MISMATCHED_OUTPUT_TYPE / MISMATCHED_OUTPUT_TYPE = 1 << 4,
+        NV_MOSAIC_DISPLAYCAPS_PROBLEM_NO_DISPLAY_CONNECTED / NO_DISPLAY_CONNECTED = 1 << 5,
+        NV_MOSAIC_DISPLAYCAPS_PROBLEM_NO_GPU_TOPOLOGY / NO_GPU_TOPOLOGY = 1 << 6,
+        NV_MOSAIC_DISPLAYCAPS_PROBLEM_NOT_SUPPORTED / NOT_SUPPORTED = 1 << 7,
+        NV_MOSAIC_DISPLAYCAPS_PROBLEM_NO_SLI_BRIDGE / NO_SLI_BRIDGE = 1 << 8,
+        NV_MOSAIC_DISPLAYCAPS_PROBLEM_ECC_ENABLED / ECC_ENABLED = 1 << 9,
+        NV_MOSAIC_DISPLAYCAPS_PROBLEM_GPU_TOPOLOGY_NOT_SUPPORTED / GPU_TOPOLOGY_NOT_SUPPORTED = 1 << 10,
+    }
+}
+
+nvbits! {
+    /// Warnings in `NV_MOSAIC_DISPLAY_TOPO_STATUS`, for a whole grid or one of its displays.
+    pub enum NV_MOSAIC_DISPLAYTOPO_WARNING / DisplayTopoWarning {
+        NV_MOSAIC_DISPLAYTOPO_WARNING_DISPLAY_POSITION / DISPLAY_POSITION = 1 << 0,
+        NV_MOSAIC_DISPLAYTOPO_WARNING_DRIVER_RELOAD_REQUIRED / DRIVER_RELOAD_REQUIRED = 1 << 1,
+    }
+}
+
+nvbits! {
+    /// Bitfield in `NV_MOSAIC_DISPLAY_TOPO_STATUS_DISPLAY`
+    pub enum NV_MOSAIC_DISPLAY_TOPO_STATUS_FLAGS / DisplayTopoStatusFlags {
+        NV_MOSAIC_DISPLAY_TOPO_STATUS_SUPPORTS_ROTATION / SUPPORTS_ROTATION = 0x01,
+    }
+}
+
+nvstruct! {
+    pub struct NV_MOSAIC_DISPLAY_TOPO_STATUS_DISPLAY {
+        /// The DisplayID of this display.
+        pub displayId: u32,
+        /// Any of the NV_MOSAIC_DISPLAYCAPS_PROBLEM_* flags.
+        pub errorFlags: NV_MOSAIC_DISPLAYCAPS_PROBLEM,
+        /// Any of the NV_MOSAIC_DISPLAYTOPO_WARNING_* flags.
+        pub warningFlags: NV_MOSAIC_DISPLAYTOPO_WARNING,
+        pub flags: NV_MOSAIC_DISPLAY_TOPO_STATUS_FLAGS,
+    }
+}
+
+nvstruct! {
+    /// The problems found with a grid by `NvAPI_Mosaic_ValidateDisplayGrids`.
+    pub struct NV_MOSAIC_DISPLAY_TOPO_STATUS {
+        /// Version of this structure
+        pub version: u32,
+        /// Any of the NV_MOSAIC_DISPLAYCAPS_PROBLEM_* flags.
+        pub errorFlags: NV_MOSAIC_DISPLAYCAPS_PROBLEM,
+        /// Any of the NV_MOSAIC_DISPLAYTOPO_WARNING_* flags.
+        pub warningFlags: NV_MOSAIC_DISPLAYTOPO_WARNING,
+        /// The number of valid entries in the displays array.
+        pub displayCount: u32,
+        pub displays: [NV_MOSAIC_DISPLAY_TOPO_STATUS_DISPLAY; NVAPI_MAX_DISPLAYS],
+    }
+}
+
+nvversion! { NV_MOSAIC_DISPLAY_TOPO_STATUS_VER1(NV_MOSAIC_DISPLAY_TOPO_STATUS = 4 * 4 + 4 * 4 * NVAPI_MAX_DISPLAYS, 1) }
+nvversion! { NV_MOSAIC_DISPLAY_TOPO_STATUS_VER = NV_MOSAIC_DISPLAY_TOPO_STATUS_VER1 }
+
+nvbits! {
+    /// Argument to `NvAPI_Mosaic_ValidateDisplayGrids` and `NvAPI_Mosaic_SetDisplayGrids`
+    pub enum NV_MOSAIC_SETDISPLAYTOPO_FLAGS / SetDisplayTopoFlags {
+        /// Do not change the current GPU topology. If the NO_DRIVER_RELOAD bit is not
+        /// specified, then it may still require a driver reload.
+        NV_MOSAIC_SETDISPLAYTOPO_FLAG_CURRENT_GPU_TOPOLOGY / CURRENT_GPU_TOPOLOGY = 1 << 0,
+        /// Do not allow a driver reload. That is, stick with the same master GPU as well
+        /// as the same SLI configuration.
+        NV_MOSAIC_SETDISPLAYTOPO_FLAG_NO_DRIVER_RELOAD / NO_DRIVER_RELOAD = 1 << 1,
+        /// When choosing a GPU topology, choose the topology with the best performance.
+        /// Without this flag, it will choose the topology that uses the smallest number
+        /// of GPUs.
+        NV_MOSAIC_SETDISPLAYTOPO_FLAG_MAXIMIZE_PERFORMANCE / MAXIMIZE_PERFORMANCE = 1 << 2,
+        /// Do not return an error if no configuration will work with all of the grids.
+        NV_MOSAIC_SETDISPLAYTOPO_FLAG_ALLOW_INVALID / ALLOW_INVALID = 1 << 3,
+    }
+}
+
+nvapi! {
+    pub type Mosaic_GetOverlapLimitsFn = extern "C" fn(pTopoBrief: *mut NV_MOSAIC_TOPO_BRIEF, pDisplaySetting: *mut NV_MOSAIC_DISPLAY_SETTING, pMinOverlapX: *mut i32, pMaxOverlapX: *mut i32, pMinOverlapY: *mut i32, pMaxOverlapY: *mut i32) -> NvAPI_Status;
+
+    /// This API returns the X and Y overlap limits required if the given Mosaic
+    /// topology and display settings are to be used.
+    ///
+    /// # Returns
+    ///
+    /// - `NVAPI_INVALID_ARGUMENT`: One or more arguments passed in are invalid.
+    /// - `NVAPI_API_NOT_INTIALIZED`: The NvAPI API needs to be initialized first.
+    /// - `NVAPI_NO_IMPLEMENTATION`: This entrypoint not available.
+    /// - `NVAPI_INCOMPATIBLE_STRUCT_VERSION`: The version of the structure passed in is not compatible with this entry point.
+    pub unsafe fn NvAPI_Mosaic_GetOverlapLimits;
+}
+
+nvapi! {
+    pub type Mosaic_EnumDisplayModesFn = extern "C" fn(pGridTopology: *mut NV_MOSAIC_GRID_TOPO, pDisplaySettings: *mut NV_MOSAIC_DISPLAY_SETTING, pDisplayCount: *mut u32) -> NvAPI_Status;
+
+    /// Determines the set of available display modes for a given grid topology.
+    ///
+    /// If `pDisplaySettings` is NULL, then `pDisplayCount` will receive the total
+    /// number of modes that are available.
+    ///
+    /// If `pDisplaySettings` is not NULL, then `pDisplayCount` should point to the number
+    /// of elements in the `pDisplaySettings` array. On return, it will contain the number
+    /// of modes that were actually returned.
+    pub unsafe fn NvAPI_Mosaic_EnumDisplayModes;
+}
+
+nvapi! {
+    pub type Mosaic_EnumDisplayGridsFn = extern "C" fn(pGridTopologies: *mut NV_MOSAIC_GRID_TOPO, pGridCount: *mut u32) -> NvAPI_Status;
+
+    /// Enumerates the current active grid topologies. This includes Mosaic, IG, and
+    /// Panoramic topologies, as well as single displays.
+    ///
+    /// If `pGridTopologies` is NULL, then `pGridCount` will be set to the number of active
+    /// grid topologies.
+    ///
+    /// If `pGridTopologies` is not NULL, then `pGridCount` contains the maximum number of
+    /// grid topologies to return. On return, `pGridCount` will be set to the number of
+    /// grid topologies that were returned.
+    pub unsafe fn NvAPI_Mosaic_EnumDisplayGrids;
+}
+
+nvapi! {
+    pub type Mosaic_SetDisplayGridsFn = extern "C" fn(pGridTopologies: *mut NV_MOSAIC_GRID_TOPO, gridCount: u32, setTopoFlags: NV_MOSAIC_SETDISPLAYTOPO_FLAGS) -> NvAPI_Status;
+
+    /// Sets a new display topology, replacing any existing topologies that use the
+    /// same displays.
+    ///
+    /// This function will look for an SLI configuration that will allow the display
+    /// topology to work.
+    ///
+    /// To revert to a single display, specify that display as a 1x1 grid.
+    ///
+    /// # Returns
+    ///
+    /// - `NVAPI_NO_ACTIVE_SLI_TOPOLOGY`: No matching GPU topologies could be found.
+    /// - `NVAPI_TOPO_NOT_POSSIBLE`: One or more of the display grids are not valid.
+    /// - `NVAPI_INVALID_ARGUMENT`: One or more arguments passed in are invalid.
+    /// - `NVAPI_MODE_CHANGE_FAILED`: There was an error changing the display mode.
+    pub unsafe fn NvAPI_Mosaic_SetDisplayGrids;
+}
+
+nvapi! {
+    pub type Mosaic_ValidateDisplayGridsFn = extern "C" fn(setTopoFlags: NV_MOSAIC_SETDISPLAYTOPO_FLAGS, pGridTopologies: *mut NV_MOSAIC_GRID_TOPO, pTopoStatus: *mut NV_MOSAIC_DISPLAY_TOPO_STATUS, gridCount: u32) -> NvAPI_Status;
+
+    /// Determines if a list of grid topologies is valid. It will choose an SLI
+    /// configuration in the same way that `NvAPI_Mosaic_SetDisplayGrids` does.
+    ///
+    /// On return, each element in the `pTopoStatus` array will contain any errors or
+    /// warnings about each grid topology. If any error flags are set, then the topology
+    /// is not valid. If any warning flags are set, then the topology is valid, but
+    /// sub-optimal.
+    ///
+    /// # Returns
+    ///
+    /// - `NVAPI_NO_ACTIVE_SLI_TOPOLOGY`: No matching GPU topologies could be found.
+    /// - `NVAPI_INVALID_ARGUMENT`: One or more arguments passed in are invalid.
+    pub unsafe fn NvAPI_Mosaic_ValidateDisplayGrids;
+}
+
+nvapi! {
+    pub type Mosaic_GetDisplayViewportsByResolutionFn = extern "C" fn(displayId: u32, srcWidth: u32, srcHeight: u32, viewports: *mut [NV_RECT; NV_MOSAIC_MAX_DISPLAYS], bezelCorrected: *mut u8) -> NvAPI_Status;
+
+    /// This API returns the viewports that would be applied on the requested display.
+    ///
+    /// Pass 0 for `srcWidth` and `srcHeight` to use the current resolution. The viewport
+    /// of every display in the same grid is returned, in grid order, with the rest left
+    /// zeroed. `bezelCorrected` is set if the viewports account for bezels.
+    ///
+    /// # Returns
+    ///
+    /// - `NVAPI_MOSAIC_NOT_ACTIVE`: The display does not belong to an active Mosaic topology.
+    /// - `NVAPI_INVALID_ARGUMENT`: One or more arguments passed in are invalid.
+    pub unsafe fn NvAPI_Mosaic_GetDisplayViewportsByResolution;
+}
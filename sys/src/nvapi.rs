@@ -2,6 +2,7 @@ use std::sync::atomic::{AtomicUsize, Ordering};
 use std::os::raw::c_void;
 use crate::status::{Status, NvAPI_Status};
 use crate::types;
+use crate::nvid::Api;
 
 pub type QueryInterfaceFn = extern "C" fn(id: u32) -> *const c_void;
 
@@ -10,60 +11,189 @@ pub const LIBRARY_NAME: &'static [u8; 10] = b"nvapi.dll\0";
 #[cfg(all(windows, target_pointer_width = "64"))]
 pub const LIBRARY_NAME: &'static [u8; 12] = b"nvapi64.dll\0";
 
+#[cfg(target_os = "linux")]
+pub const LIBRARY_NAME: &[u8; 19] = b"libnvidia-api.so.1\0";
+
 pub const FN_NAME: &'static [u8; 21] = b"nvapi_QueryInterface\0";
 
 static QUERY_INTERFACE_CACHE: AtomicUsize = AtomicUsize::new(0);
-
+/// Bumped whenever the query interface is replaced, invalidating every `FnCache`.
+static QUERY_INTERFACE_GENERATION: AtomicUsize = AtomicUsize::new(0);
+
+/// Overrides the `nvapi_QueryInterface` entry point used to resolve every NVAPI function.
+///
+/// Functions resolved through a previous entry point are resolved again on their next call.
+///
+/// # Safety
+///
+/// `ptr` must return functions with the signatures NVAPI defines for each interface id.
 pub unsafe fn set_query_interface(ptr: QueryInterfaceFn) {
-    QUERY_INTERFACE_CACHE.store(ptr as usize, Ordering::Relaxed);
+    restore_query_interface(ptr as usize);
 }
 
-#[cfg(not(windows))]
-pub fn nvapi_QueryInterface(id: u32) -> crate::Result<usize> {
+/// The entry point currently in use, or 0 if the library hasn't been loaded yet.
+pub(crate) fn query_interface_ptr() -> usize {
+    QUERY_INTERFACE_CACHE.load(Ordering::Relaxed)
+}
+
+/// Reinstates an entry point returned by `query_interface_ptr`.
+pub(crate) fn restore_query_interface(ptr: usize) {
+    QUERY_INTERFACE_CACHE.store(ptr, Ordering::Relaxed);
+    QUERY_INTERFACE_GENERATION.fetch_add(1, Ordering::SeqCst);
+}
+
+#[cfg(target_os = "linux")]
+mod library {
+    use std::sync::Mutex;
+    use std::path::PathBuf;
+    use std::ffi::{OsString, OsStr};
+    use std::os::unix::ffi::OsStrExt;
+
+    pub struct LibraryConfig {
+        pub name: Option<OsString>,
+        pub search_path: Vec<PathBuf>,
+    }
+
+    pub static CONFIG: Mutex<LibraryConfig> = Mutex::new(LibraryConfig {
+        name: None,
+        search_path: Vec::new(),
+    });
+
+    impl LibraryConfig {
+        /// Candidate paths to pass to `dlopen`, in order of preference.
+        pub fn candidates(&self) -> Vec<OsString> {
+            let name = match self.name {
+                Some(ref name) => name.clone(),
+                None => OsStr::from_bytes(&super::LIBRARY_NAME[..super::LIBRARY_NAME.len() - 1]).to_owned(),
+            };
+
+            if self.search_path.is_empty() {
+                vec![name]
+            } else {
+                self.search_path.iter().map(|dir| dir.join(&name).into_os_string()).collect()
+            }
+        }
+    }
+}
+
+/// Overrides the name of the shared library that NVAPI is loaded from.
+///
+/// Defaults to `libnvidia-api.so.1`. The name may also be a full path to the library.
+/// This must be called before any NVAPI function has been resolved.
+#[cfg(target_os = "linux")]
+pub fn set_library_name<S: Into<std::ffi::OsString>>(name: S) {
+    library::CONFIG.lock().unwrap_or_else(|e| e.into_inner()).name = Some(name.into());
+}
+
+/// Restricts the directories that the NVAPI shared library is searched for in.
+///
+/// When empty (the default) the system's dynamic linker search path is used.
+/// This must be called before any NVAPI function has been resolved.
+#[cfg(target_os = "linux")]
+pub fn set_library_search_path<I: IntoIterator<Item=P>, P: Into<std::path::PathBuf>>(paths: I) {
+    library::CONFIG.lock().unwrap_or_else(|e| e.into_inner()).search_path = paths.into_iter().map(Into::into).collect();
+}
+
+#[cfg(target_os = "linux")]
+unsafe fn load_query_interface() -> crate::Result<usize> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStringExt;
+    use std::os::raw::c_char;
+
+    let candidates = library::CONFIG.lock().unwrap_or_else(|e| e.into_inner()).candidates();
+    for candidate in candidates {
+        let path = match CString::new(candidate.into_vec()) {
+            Ok(path) => path,
+            Err(..) => continue,
+        };
+
+        // The library is intentionally never closed, matching the Windows loader
+        let lib = libc::dlopen(path.as_ptr(), libc::RTLD_NOW | libc::RTLD_LOCAL);
+        if lib.is_null() {
+            continue
+        }
+
+        let ptr = libc::dlsym(lib, FN_NAME.as_ptr() as *const c_char);
+        if ptr.is_null() {
+            libc::dlclose(lib);
+            continue
+        }
+
+        QUERY_INTERFACE_CACHE.store(ptr as usize, Ordering::Relaxed);
+        return Ok(ptr as usize)
+    }
+
+    Err(Status::LibraryNotFound.into())
+}
+
+#[cfg(not(any(windows, target_os = "linux")))]
+unsafe fn load_query_interface() -> crate::Result<usize> {
     // TODO: Apparently nvapi is available for macOS?
-    Err(Status::LibraryNotFound)
+    Err(Status::LibraryNotFound.into())
 }
 
 #[cfg(windows)]
-pub fn nvapi_QueryInterface(id: u32) -> crate::Result<usize> {
+unsafe fn load_query_interface() -> crate::Result<usize> {
     use winapi::um::libloaderapi::{GetProcAddress, LoadLibraryA};
-    use std::mem;
     use std::os::raw::c_char;
 
+    let lib = LoadLibraryA(LIBRARY_NAME.as_ptr() as *const c_char);
+    if lib.is_null() {
+        Err(Status::LibraryNotFound.into())
+    } else {
+        let ptr = GetProcAddress(lib, FN_NAME.as_ptr() as *const c_char);
+        if ptr.is_null() {
+            Err(Status::LibraryNotFound.into())
+        } else {
+            QUERY_INTERFACE_CACHE.store(ptr as usize, Ordering::Relaxed);
+            Ok(ptr as usize)
+        }
+    }
+}
+
+pub fn nvapi_QueryInterface(id: u32) -> crate::Result<usize> {
+    use std::mem;
+
     unsafe {
         let ptr = match QUERY_INTERFACE_CACHE.load(Ordering::Relaxed) {
-            0 => {
-                let lib = LoadLibraryA(LIBRARY_NAME.as_ptr() as *const c_char);
-                if lib.is_null() {
-                    Err(Status::LibraryNotFound)
-                } else {
-                    let ptr = GetProcAddress(lib, FN_NAME.as_ptr() as *const c_char);
-                    if ptr.is_null() {
-                        Err(Status::LibraryNotFound)
-                    } else {
-                        QUERY_INTERFACE_CACHE.store(ptr as usize, Ordering::Relaxed);
-                        Ok(ptr as usize)
-                    }
-                }
-            },
+            0 => load_query_interface(),
             ptr => Ok(ptr),
         }?;
 
-        match mem::transmute::<_, QueryInterfaceFn>(ptr)(id) as usize {
-            0 => Err(Status::NoImplementation),
+        match mem::transmute::<usize, QueryInterfaceFn>(ptr)(id) as usize {
+            0 => Err(Status::NoImplementation.into()),
             ptr => Ok(ptr),
         }
     }
 }
 
-pub(crate) fn query_interface(id: u32, cache: &AtomicUsize) -> crate::Result<usize> {
-    match cache.load(Ordering::Relaxed) {
-        0 => {
-            let value = nvapi_QueryInterface(id)?;
-            cache.store(value, Ordering::Relaxed);
-            Ok(value)
-        },
-        value => Ok(value),
+/// The function pointer a binding has resolved.
+pub(crate) struct FnCache {
+    ptr: AtomicUsize,
+    generation: AtomicUsize,
+}
+
+impl FnCache {
+    pub const fn new() -> Self {
+        FnCache {
+            ptr: AtomicUsize::new(0),
+            generation: AtomicUsize::new(0),
+        }
+    }
+
+    /// Resolves `api`, registering `replay` as the function that replays it.
+    pub fn resolve(&self, api: Api, replay: usize) -> crate::Result<usize> {
+        let generation = QUERY_INTERFACE_GENERATION.load(Ordering::SeqCst);
+        match self.ptr.load(Ordering::Relaxed) {
+            value if value != 0 && self.generation.load(Ordering::Relaxed) == generation => Ok(value),
+            _ => {
+                crate::capture::register_replay(api, replay);
+                let value = nvapi_QueryInterface(api.id())?;
+                self.ptr.store(value, Ordering::Relaxed);
+                self.generation.store(generation, Ordering::Relaxed);
+                Ok(value)
+            },
+        }
     }
 }
 
@@ -1,6 +1,6 @@
 #![allow(non_camel_case_types)]
 
-use std::mem;
+use std::{fmt, mem};
 
 macro_rules! nvapis {
     ($(
@@ -8,6 +8,7 @@ macro_rules! nvapis {
         $name:ident = $id:expr,
     )*) => {
         #[repr(u32)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
         #[derive(Debug, Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash)]
         pub enum Api {
         $(
@@ -21,7 +22,7 @@ macro_rules! nvapis {
                 match id {
                 $(
                     $id
-                )|* => Ok(unsafe { mem::transmute(id) }),
+                )|* => Ok(unsafe { mem::transmute::<u32, Api>(id) }),
                     _ => Err(()),
                 }
             }
@@ -29,10 +30,112 @@ macro_rules! nvapis {
             pub fn id(&self) -> u32 {
                 *self as _
             }
+
+            /// Every known function, in declaration order.
+            pub fn all() -> impl Iterator<Item=Self> {
+                APIS.iter().cloned()
+            }
+
+            /// The function's name, such as `"NvAPI_GPU_GetFullName"`.
+            pub fn name(&self) -> &'static str {
+                match *self {
+                $(
+                    Api::$name => stringify!($name),
+                )*
+                }
+            }
+
+            /// Looks up a function by name. The `NvAPI_` prefix may be omitted.
+            pub fn from_name(name: &str) -> Result<Self, crate::ArgumentRangeError> {
+                match name {
+                $(
+                    stringify!($name) => Ok(Api::$name),
+                )*
+                    _ if !name.starts_with(PREFIX) => Self::from_name(&format!("{}{}", PREFIX, name)),
+                    _ => Err(Default::default()),
+                }
+            }
         }
+
+        /// Every known function, in declaration order.
+        pub const APIS: &[Api] = &[
+        $(
+            Api::$name
+        ),*
+        ];
     };
 }
 
+const PREFIX: &str = "NvAPI_";
+
+/// The area of the driver a function belongs to, derived from its name.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash)]
+pub enum Category {
+    /// Initialization, handle enumeration and other unprefixed functions.
+    General,
+    Gpu,
+    Display,
+    I2c,
+    Sys,
+    /// Driver settings (profiles).
+    Drs,
+    Mosaic,
+    Stereo,
+    /// Video input/output.
+    Vio,
+    /// G-SYNC and Quadro Sync frame lock boards.
+    GSync,
+    /// Direct3D 9 through 12.
+    D3D,
+    OpenGl,
+    /// GPU Performance Scaling.
+    Gps,
+    Coproc,
+    Hybrid,
+    ThreeD,
+    Event,
+    /// Functions whose purpose hasn't been identified.
+    Unknown,
+}
+
+impl Api {
+    pub fn category(&self) -> Category {
+        let name = self.name();
+        let name = name.strip_prefix(PREFIX).unwrap_or(name);
+        let prefix = match name.find('_') {
+            Some(end) => &name[..end],
+            None if name.starts_with("I2C") => return Category::I2c,
+            None => return Category::General,
+        };
+        match prefix {
+            "GPU" => Category::Gpu,
+            "DISP" | "Disp" => Category::Display,
+            "SYS" => Category::Sys,
+            "DRS" => Category::Drs,
+            "Mosaic" => Category::Mosaic,
+            "Stereo" => Category::Stereo,
+            "VIO" => Category::Vio,
+            "GSync" => Category::GSync,
+            "D3D" | "D3D9" | "D3D10" | "D3D11" | "D3D12" | "D3D1x" => Category::D3D,
+            "OGL" => Category::OpenGl,
+            "GPS" => Category::Gps,
+            "Coproc" => Category::Coproc,
+            "Hybrid" => Category::Hybrid,
+            "3D" => Category::ThreeD,
+            "Event" => Category::Event,
+            "Unknown" => Category::Unknown,
+            _ => Category::General,
+        }
+    }
+}
+
+impl fmt::Display for Api {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
 nvapis! {
 
 // source: https://stackoverflow.com/a/16497265 (full dump as of May 2013)
@@ -527,6 +630,15 @@ NvAPI_GPU_GetIllumination = 0x9a1b9365,
 NvAPI_GPU_SetIllumination = 0x0254a187,
 NvAPI_D3D1x_IFR_SetUpTargetBufferToSys = 0x473f7828,
 NvAPI_D3D1x_IFR_TransferRenderTarget = 0x9fbae4eb,
+NvAPI_GSync_EnumSyncDevices = 0xd9639601,
+NvAPI_GSync_QueryCapabilities = 0x44a3f1d1,
+NvAPI_GSync_GetTopology = 0x4562bc38,
+NvAPI_GSync_SetSyncStateSettings = 0x60acdfdd,
+NvAPI_GSync_GetControlParameters = 0x16de1c6a,
+NvAPI_GSync_SetControlParameters = 0x8bbff88b,
+NvAPI_GSync_AdjustSyncDelay = 0x2d11ff51,
+NvAPI_GSync_GetSyncStatus = 0xf1f5b434,
+NvAPI_GSync_GetStatusParameters = 0x70d404ec,
 
 // source: https://github.com/Kaldaien/BMT/blob/master/BMT/dxgi.cpp
 
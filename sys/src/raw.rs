@@ -0,0 +1,106 @@
+//! Calls to functions that have no typed binding in this crate.
+//!
+//! Most NVAPI functions take either `(*mut T)` or `(NvPhysicalGpuHandle, *mut T)`,
+//! where `T` is a versioned structure. Such a function can be called by passing the
+//! structure as a byte buffer that starts with its `version` field, which is useful
+//! when investigating undocumented functions. Whether a buffer is versioned is stated
+//! by the caller rather than guessed from its contents. Calls made this way are recorded,
+//! replayed and attributed in errors like any other.
+
+use std::{mem, slice};
+use crate::capture::{self, CaptureArg};
+use crate::handles::NvPhysicalGpuHandle;
+use crate::status::{self, NvAPI_Status};
+use crate::types::{GET_NVAPI_SIZE, GET_NVAPI_VERSION};
+use crate::nvid::Api;
+
+/// A buffer of `size` zeroed bytes tagged with `version`.
+///
+/// Like `MAKE_NVAPI_VERSION`, the full size is combined with the version, which is what
+/// the driver expects of structures of 64 KiB or more.
+pub fn versioned_buffer(version: u16, size: usize) -> Vec<u8> {
+    let mut data = vec![0u8; size.max(mem::size_of::<u32>())];
+    let version = data.len() as u32 | (version as u32) << 16;
+    data[..mem::size_of::<u32>()].copy_from_slice(&version.to_ne_bytes());
+    data
+}
+
+/// The `version` field a versioned buffer starts with.
+pub fn buffer_version(data: &[u8]) -> Option<u32> {
+    data.get(..mem::size_of::<u32>())
+        .map(|v| u32::from_ne_bytes([v[0], v[1], v[2], v[3]]))
+}
+
+/// A byte buffer passed by pointer.
+struct Buffer {
+    ptr: *mut u8,
+    len: usize,
+    versioned: bool,
+}
+
+impl CaptureArg for Buffer {
+    fn pointer(&self) -> Option<(*const u8, usize)> {
+        Some((self.ptr as *const u8, self.len))
+    }
+
+    fn is_output(&self) -> bool {
+        true
+    }
+
+    unsafe fn struct_version(&self) -> Option<u32> {
+        match self.versioned {
+            true => buffer_version(slice::from_raw_parts(self.ptr, self.len)),
+            false => None,
+        }
+    }
+}
+
+type StructFn = extern "C" fn(*mut u8) -> NvAPI_Status;
+type GpuStructFn = extern "C" fn(NvPhysicalGpuHandle, *mut u8) -> NvAPI_Status;
+
+/// Calls `api` as `(*mut T)`, or as `(NvPhysicalGpuHandle, *mut T)` when `gpu` is given.
+///
+/// When `versioned`, `data` starts with a `version` field as built by `versioned_buffer`.
+///
+/// Fails with `NVAPI_INVALID_ARGUMENT` if `data` is empty, or if it is versioned and its
+/// version field is missing a version or claims a size larger than `data`.
+///
+/// # Safety
+///
+/// The function must actually take the arguments it is called with, and must not access
+/// more than `data.len()` bytes through the pointer.
+pub unsafe fn call(api: Api, gpu: Option<NvPhysicalGpuHandle>, data: &mut [u8], versioned: bool) -> NvAPI_Status {
+    let valid = match buffer_version(data) {
+        _ if data.is_empty() => false,
+        Some(version) if versioned => GET_NVAPI_VERSION(version) != 0 && GET_NVAPI_SIZE(version) <= data.len(),
+        None if versioned => false,
+        _ => true,
+    };
+    let buffer = Buffer {
+        ptr: data.as_mut_ptr(),
+        len: data.len(),
+        versioned,
+    };
+    let status = if !valid {
+        status::NVAPI_INVALID_ARGUMENT
+    } else {
+        let args: Vec<&dyn CaptureArg> = match gpu {
+            Some(ref gpu) => vec![gpu, &buffer],
+            None => vec![&buffer],
+        };
+        // There's no typed function for the replay interface to hand out, so raw calls
+        // are served from the capture directly
+        match capture::is_replaying() {
+            true => capture::replay_call(api, &args),
+            false => match crate::nvapi::nvapi_QueryInterface(api.id()) {
+                Ok(ptr) => capture::record_call(api, &args, || match gpu {
+                    Some(gpu) => mem::transmute::<usize, GpuStructFn>(ptr)(gpu, buffer.ptr),
+                    None => mem::transmute::<usize, StructFn>(ptr)(buffer.ptr),
+                }),
+                Err(e) => e.raw(),
+            },
+        }
+    };
+    crate::error::track(api, &[&buffer], status);
+    status
+}
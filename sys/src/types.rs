@@ -32,6 +32,8 @@ nvstruct! {
 pub const NVAPI_GENERIC_STRING_MAX: usize = 4096;
 pub const NVAPI_LONG_STRING_MAX: usize = 256;
 pub const NVAPI_SHORT_STRING_MAX: usize = 64;
+pub const NVAPI_UNICODE_STRING_MAX: usize = 2048;
+pub const NVAPI_BINARY_DATA_MAX: usize = 4096;
 
 nvstruct! {
     pub struct NvSBox {
@@ -85,6 +87,8 @@ pub const NVAPI_MAX_AUDIO_DEVICES: usize = 16;
 pub type NvAPI_String = [c_char; NVAPI_GENERIC_STRING_MAX];
 pub type NvAPI_LongString = [c_char; NVAPI_LONG_STRING_MAX];
 pub type NvAPI_ShortString = [c_char; NVAPI_SHORT_STRING_MAX];
+/// A nul-terminated UTF-16 string
+pub type NvAPI_UnicodeString = [u16; NVAPI_UNICODE_STRING_MAX];
 
 pub fn short_string() -> NvAPI_ShortString {
     [0; NVAPI_SHORT_STRING_MAX]
@@ -98,6 +102,10 @@ pub fn string() -> NvAPI_String {
     [0; NVAPI_GENERIC_STRING_MAX]
 }
 
+pub fn unicode_string() -> NvAPI_UnicodeString {
+    [0; NVAPI_UNICODE_STRING_MAX]
+}
+
 /// NvAPI Version Definition
 ///
 /// Maintain per structure specific version, meant to be a `const fn`.
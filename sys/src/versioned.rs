@@ -0,0 +1,20 @@
+//! Structures that are accepted under more than one version.
+//!
+//! NVAPI identifies the layout of a structure by the `version` field it leads with,
+//! and drivers reject versions they don't know with `NVAPI_INCOMPATIBLE_STRUCT_VERSION`.
+//! Later versions usually append fields, so the newest layout can be passed under an
+//! older version number and the driver will simply ignore the remainder. Whatever those
+//! fields request is lost when it does, so only versions that can still express the
+//! request may be tried; a version that reinterprets a field isn't listed at all.
+
+/// A structure layout that can be passed under any of several versions.
+pub trait VersionedStruct {
+    /// Every version this layout may be passed as, newest first.
+    const VERSIONS: &'static [u32];
+
+    /// The version the structure is currently tagged with.
+    fn version(&self) -> u32;
+
+    /// Retags the structure, leaving all other fields untouched.
+    fn set_version(&mut self, version: u32);
+}
@@ -0,0 +1,38 @@
+extern crate nvapi_sys;
+
+use nvapi_sys::Api;
+use nvapi_sys::nvid::Category;
+
+#[test]
+fn names() {
+    for api in Api::all() {
+        assert_eq!(Api::from_name(api.name()).unwrap(), api);
+        assert_eq!(Api::from_id(api.id()), Ok(api));
+    }
+
+    assert_eq!(Api::NvAPI_GPU_GetFullName.name(), "NvAPI_GPU_GetFullName");
+    assert_eq!(Api::NvAPI_GPU_GetFullName.to_string(), "NvAPI_GPU_GetFullName");
+    assert_eq!(Api::from_name("GPU_GetFullName").unwrap(), Api::NvAPI_GPU_GetFullName);
+    assert_eq!(Api::from_name("Unknown_GetROPCount").unwrap(), Api::Unknown_GetROPCount);
+    assert!(Api::from_name("NvAPI_GPU_DoesNotExist").is_err());
+    assert!(Api::from_name("").is_err());
+}
+
+#[test]
+fn categories() {
+    assert_eq!(Api::NvAPI_Initialize.category(), Category::General);
+    assert_eq!(Api::NvAPI_GPU_GetFullName.category(), Category::Gpu);
+    assert_eq!(Api::NvAPI_DRS_CreateSession.category(), Category::Drs);
+    assert_eq!(Api::NvAPI_D3D9_RegisterResource.category(), Category::D3D);
+    assert_eq!(Api::NvAPI_I2CRead.category(), Category::I2c);
+    assert_eq!(Api::NvAPI_GSync_GetSyncStatus.category(), Category::GSync);
+    assert_eq!(Api::Unknown_GetROPCount.category(), Category::Unknown);
+
+    // Every prefixed name is recognized
+    for api in Api::all() {
+        let name = api.name().trim_start_matches("NvAPI_");
+        if name.contains('_') {
+            assert_ne!(api.category(), Category::General, "{}", api);
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use std::collections::BTreeSet;
+use nvapi::KnownSetting;
+use nvapi::drs_settings::KNOWN_SETTINGS;
+use nvapi::sys::driversettings::{self, PowerManagementMode};
+
+#[test]
+fn table() {
+    let ids: BTreeSet<_> = KNOWN_SETTINGS.iter().map(|setting| setting.id).collect();
+    let keys: BTreeSet<_> = KNOWN_SETTINGS.iter().map(|setting| setting.key).collect();
+    assert_eq!((ids.len(), keys.len()), (KNOWN_SETTINGS.len(), KNOWN_SETTINGS.len()));
+
+    for setting in KNOWN_SETTINGS {
+        assert_eq!(KnownSetting::find(setting.key).unwrap().id, setting.id);
+        assert_eq!(KnownSetting::find(setting.name).unwrap().id, setting.id);
+        assert_eq!(KnownSetting::from_id(setting.id).unwrap().key, setting.key);
+        assert_eq!(setting.parse(&setting.format(setting.default)), Some(setting.default), "{}", setting);
+        for (name, value) in setting.values() {
+            assert!(name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_'), "{}", name);
+            assert_eq!(setting.parse(&name), Some(value));
+            assert_eq!(setting.format(value), name);
+        }
+    }
+}
+
+#[test]
+fn parse() {
+    let power = KnownSetting::find("power_management_mode").unwrap();
+    assert_eq!(power.id, 0x1057eb71);
+    assert_eq!(power.parse("prefer_max_performance"), Some(PowerManagementMode::PreferMaxPerformance.raw() as u32));
+    assert_eq!(power.parse("Prefer_Max_Performance"), Some(1));
+    // enumerations only accept their named values
+    assert_eq!(power.parse("1"), None);
+    assert_eq!(power.format(9), "0x00000009");
+
+    let quality = KnownSetting::find("Texture filtering - Quality").unwrap();
+    assert_eq!(quality.parse("high_quality"), Some(0xfffffff6));
+
+    let vsync = KnownSetting::find("vsync").unwrap();
+    assert_eq!(vsync.parse("fast_sync"), Some(driversettings::VSYNCMODE_VIRTUAL as u32));
+
+    let anisotropic = KnownSetting::find("anisotropic_filtering").unwrap();
+    assert_eq!(anisotropic.parse("x16"), Some(16));
+
+    let limiter = KnownSetting::find("frame_rate_limiter").unwrap();
+    assert_eq!(limiter.parse("off"), Some(0));
+    assert_eq!(limiter.parse("144"), Some(144));
+    assert_eq!(limiter.parse("0x3c"), Some(60));
+    assert_eq!(limiter.parse("1024"), None);
+    assert_eq!(limiter.format(144), "144");
+
+    assert!(KnownSetting::find("unknown").is_none());
+}
@@ -0,0 +1,133 @@
+// Whatever a display reports, decoding must fail with an error rather than a panic.
+
+use proptest::prelude::*;
+use nvapi::Range;
+use nvapi::edid::{Edid, EdidBuilder, DetailedTiming, HdrStaticMetadata, Mode, BLOCK_SIZE, MAX_PIXEL_CLOCK};
+
+/// Arbitrary blocks behind a valid header, with the checksums fixed up so decoding
+/// gets past validation.
+fn blocks() -> impl Strategy<Value = Vec<u8>> {
+    (0..4usize).prop_flat_map(|extensions| proptest::collection::vec(any::<u8>(), (1 + extensions) * BLOCK_SIZE))
+        .prop_map(|mut data| {
+            data[..8].copy_from_slice(&[0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00]);
+            data[126] = (data.len() / BLOCK_SIZE - 1) as u8;
+            for block in data.chunks_exact_mut(BLOCK_SIZE) {
+                let sum = block[..BLOCK_SIZE - 1].iter().fold(0u8, |sum, &b| sum.wrapping_add(b));
+                block[BLOCK_SIZE - 1] = 0u8.wrapping_sub(sum);
+            }
+            data
+        })
+}
+
+proptest! {
+    #[test]
+    fn parse_arbitrary(data in blocks()) {
+        let edid = Edid::parse(&data).unwrap();
+        prop_assert_eq!(edid.extensions.len(), data.len() / BLOCK_SIZE - 1);
+    }
+
+    #[test]
+    fn parse_arbitrary_cta(mut data in blocks()) {
+        // make every extension a CTA block, with data blocks up to an arbitrary offset
+        for block in data.chunks_exact_mut(BLOCK_SIZE).skip(1) {
+            let sum = block[0].wrapping_add(block[BLOCK_SIZE - 1]);
+            block[0] = 0x02;
+            block[BLOCK_SIZE - 1] = sum.wrapping_sub(0x02);
+        }
+        let edid = Edid::parse(&data).unwrap();
+        prop_assert_eq!(edid.cta_extensions().count(), data.len() / BLOCK_SIZE - 1);
+    }
+
+    #[test]
+    fn parse_garbage(data in proptest::collection::vec(any::<u8>(), 0..1024)) {
+        let _ = Edid::parse(&data);
+    }
+}
+
+fn mode(width: u16, height: u16, refresh: u16) -> Mode {
+    Mode { width, height, refresh, interlaced: false }
+}
+
+/// A 1080p panel with a 1440p high refresh mode, VRR and HDR.
+const HEADLESS: [u8; 256] = [
+    0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00, 0x3a, 0xc4, 0x56, 0x4e, 0x01, 0x00, 0x00, 0x00,
+    0x00, 0x1e, 0x01, 0x04, 0xb5, 0x3c, 0x22, 0x78, 0x07, 0xee, 0x91, 0xa3, 0x54, 0x4c, 0x99, 0x26,
+    0x0f, 0x50, 0x54, 0x00, 0x08, 0x00, 0x81, 0xc0, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01,
+    0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x14, 0x34, 0x80, 0x50, 0x70, 0x38, 0x1f, 0x40, 0x08, 0x20,
+    0x18, 0x04, 0x58, 0x54, 0x21, 0x00, 0x00, 0x1a, 0x00, 0x00, 0x00, 0xfd, 0x00, 0x30, 0x90, 0x2c,
+    0xdf, 0x3b, 0x01, 0x0a, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20, 0x00, 0x00, 0x00, 0xfc, 0x00, 0x48,
+    0x65, 0x61, 0x64, 0x6c, 0x65, 0x73, 0x73, 0x0a, 0x20, 0x20, 0x20, 0x20, 0x00, 0x00, 0x00, 0x10,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x4b,
+    0x02, 0x03, 0x14, 0x00, 0x6a, 0xd8, 0x5d, 0xc4, 0x01, 0x00, 0x00, 0x00, 0x00, 0x30, 0x90, 0xe4,
+    0x06, 0x05, 0x00, 0x60, 0x23, 0xe5, 0x00, 0x50, 0xa0, 0xa0, 0x67, 0x50, 0x08, 0x20, 0x98, 0x04,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x1a, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x47,
+];
+
+#[test]
+fn build() {
+    let edid = EdidBuilder::new("Headless", mode(1920, 1080, 60))
+        .serial_number(1)
+        .product_code(0x4e56)
+        .screen_size(60, 34)
+        .mode(mode(1280, 720, 60))
+        .mode(mode(1024, 768, 60))
+        .mode(mode(2560, 1440, 144))
+        .refresh_range(Range { min: 48, max: 144 })
+        .hdr(HdrStaticMetadata { sdr: true, pq: true, max_luminance: Some(0x60), .. Default::default() })
+        .build();
+    assert_eq!(&edid[..], &HEADLESS[..]);
+
+    let edid = Edid::parse(&edid).unwrap();
+    assert_eq!(edid.name(), Some("Headless"));
+    assert_eq!(edid.detailed_timings[0].pixel_clock, nvapi::Kilohertz(133320));
+    assert_eq!(edid.vrr_range(), Some(Range { min: 48, max: 144 }));
+    assert_eq!(edid.range_limits().unwrap().vertical, Range { min: 48, max: 144 });
+    assert_eq!(edid.hdr_static_metadata().unwrap().max_luminance_nits(), Some(400.0));
+}
+
+#[test]
+fn build_base_only() {
+    // nothing needs an extension
+    let data = EdidBuilder::new("A very long display name", mode(1920, 1200, 60))
+        .manufacturer("abc")
+        .serial_string("SN1")
+        .mode(mode(1680, 1050, 60))
+        .build();
+    assert_eq!(data.len(), BLOCK_SIZE);
+
+    let edid = Edid::parse(&data).unwrap();
+    assert_eq!(edid.manufacturer, "???");
+    assert_eq!(edid.name(), Some("A very long d"));
+    assert_eq!(edid.serial_string(), Some("SN1"));
+    assert_eq!(edid.standard_timings, [mode(1680, 1050, 60)]);
+    assert_eq!(edid.detailed_timings[0].mode(), mode(1920, 1200, 60));
+    assert!(edid.extensions.is_empty());
+}
+
+fn modes() -> impl Strategy<Value = Vec<Mode>> {
+    proptest::collection::vec((320..4096u16, 200..2400u16, 24..240u16).prop_map(|(w, h, r)| mode(w, h, r)), 0..40)
+}
+
+proptest! {
+    #[test]
+    fn build_round_trip(preferred in (640..2560u16, 480..1600u16, 24..120u16), modes in modes(), min in 1..120u16, max in 120..500u16) {
+        let preferred = mode(preferred.0, preferred.1, preferred.2);
+        let builder = modes.iter().fold(EdidBuilder::new("Round trip", preferred), |builder, &mode| builder.mode(mode))
+            .refresh_range(Range { min, max });
+        let edid = Edid::parse(&builder.build()).unwrap();
+
+        prop_assert_eq!(edid.detailed_timings[0].mode(), preferred);
+        prop_assert_eq!(edid.range_limits().unwrap().vertical, Range { min, max });
+        let listed: Vec<_> = edid.established_timings.iter().chain(&edid.standard_timings).cloned()
+            .chain(edid.detailed_timings.iter().chain(edid.cta_extensions().flat_map(|cta| &cta.detailed_timings)).map(|t| t.mode()))
+            .collect();
+        for mode in modes.into_iter().filter(|&mode| DetailedTiming::reduced_blanking(mode).pixel_clock <= MAX_PIXEL_CLOCK) {
+            prop_assert!(listed.contains(&mode), "{} missing", mode);
+        }
+    }
+}
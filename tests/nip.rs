@@ -0,0 +1,127 @@
+use proptest::prelude::*;
+use nvapi::SettingValue;
+use nvapi::nip::{self, NipProfile, NipSetting, NipError};
+
+/// As exported by NVIDIA Profile Inspector, minus the UTF-16 encoding.
+const EXPORTED: &str = "<?xml version=\"1.0\" encoding=\"utf-16\"?>\r
+<ArrayOfProfile xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\" xmlns:xsd=\"http://www.w3.org/2001/XMLSchema\">\r
+  <Profile>\r
+    <ProfileName>Tuned &amp; Tested</ProfileName>\r
+    <Executeables>\r
+      <string>game.exe</string>\r
+      <string>game_launcher.exe</string>\r
+    </Executeables>\r
+    <Settings>\r
+      <ProfileSetting>\r
+        <SettingNameInfo>Power management mode</SettingNameInfo>\r
+        <SettingID>274197361</SettingID>\r
+        <SettingValue>1</SettingValue>\r
+        <ValueType>Dword</ValueType>\r
+      </ProfileSetting>\r
+      <ProfileSetting>\r
+        <SettingNameInfo />\r
+        <SettingID>0x10000002</SettingID>\r
+        <SettingValue>0x00FF10</SettingValue>\r
+        <ValueType>Binary</ValueType>\r
+      </ProfileSetting>\r
+      <ProfileSetting>\r
+        <SettingNameInfo>Comment</SettingNameInfo>\r
+        <SettingID>268435459</SettingID>\r
+        <SettingValue><![CDATA[<raw>]]> &#x263A;</SettingValue>\r
+        <ValueType>String</ValueType>\r
+      </ProfileSetting>\r
+    </Settings>\r
+  </Profile>\r
+  <!-- profiles without settings -->\r
+  <Profile>\r
+    <ProfileName>Empty</ProfileName>\r
+    <Executeables />\r
+    <Settings />\r
+  </Profile>\r
+</ArrayOfProfile>\r
+";
+
+fn exported() -> Vec<NipProfile> {
+    vec![
+        NipProfile {
+            name: "Tuned & Tested".into(),
+            executables: vec!["game.exe".into(), "game_launcher.exe".into()],
+            settings: vec![
+                NipSetting { id: 0x1057eb71, name: "Power management mode".into(), value: SettingValue::Dword(1) },
+                NipSetting { id: 0x10000002, name: "".into(), value: SettingValue::Binary(vec![0x00, 0xff, 0x10]) },
+                NipSetting { id: 0x10000003, name: "Comment".into(), value: SettingValue::String("<raw> \u{263a}".into()) },
+            ],
+        },
+        NipProfile {
+            name: "Empty".into(),
+            .. Default::default()
+        },
+    ]
+}
+
+#[test]
+fn parse() {
+    assert_eq!(nip::from_xml(EXPORTED).unwrap(), exported());
+}
+
+#[test]
+fn encode() {
+    let data = nip::encode(&exported());
+    assert_eq!(&data[..4], &[0xff, 0xfe, b'<', 0]);
+    assert_eq!(nip::decode(&data).unwrap(), exported());
+    assert_eq!(nip::decode(EXPORTED.as_bytes()).unwrap(), exported());
+    assert_eq!(nip::decode(&data[..data.len() - 1]).unwrap_err(), NipError::Encoding);
+}
+
+#[test]
+fn errors() {
+    let invalid = |xml: &str| nip::from_xml(xml).unwrap_err();
+    assert_eq!(invalid("<ArrayOfProfile><Profile></ArrayOfProfile>"), NipError::Syntax { line: 1, reason: "mismatched end tag" });
+    assert!(matches!(invalid("<ArrayOfProfile>\n<Profile>"), NipError::Syntax { line: 2, .. }));
+    let nested = "<a>".repeat(200_000);
+    assert_eq!(invalid(&nested), NipError::Syntax { line: 1, reason: "elements nested too deeply" });
+    assert_eq!(invalid("<Profiles />"), NipError::Missing("ArrayOfProfile"));
+    assert_eq!(invalid("<ArrayOfProfile><Profile /></ArrayOfProfile>"), NipError::Missing("ProfileName"));
+
+    let setting = |id: &str, value: &str, ty: &str| invalid(&format!(
+        "<ArrayOfProfile><Profile><ProfileName>p</ProfileName><Settings><ProfileSetting>\
+        <SettingID>{}</SettingID><SettingValue>{}</SettingValue><ValueType>{}</ValueType>\
+        </ProfileSetting></Settings></Profile></ArrayOfProfile>", id, value, ty));
+    assert_eq!(setting("1", "x", "Dword"), NipError::Invalid { element: "SettingValue", value: "x".into() });
+    assert_eq!(setting("-1", "1", "Dword"), NipError::Invalid { element: "SettingID", value: "-1".into() });
+    assert_eq!(setting("1", "0xABC", "Binary"), NipError::Invalid { element: "SettingValue", value: "0xABC".into() });
+    assert_eq!(setting("1", "1", "Qword"), NipError::Invalid { element: "ValueType", value: "Qword".into() });
+}
+
+fn text() -> impl Strategy<Value = String> {
+    // whitespace, markup characters and line endings must all survive
+    "[a-zA-Z0-9 <>&'\"\r\n\t\u{e9}\u{263a}]{0,24}"
+}
+
+fn value() -> impl Strategy<Value = SettingValue> {
+    prop_oneof![
+        any::<u32>().prop_map(SettingValue::Dword),
+        proptest::collection::vec(any::<u8>(), 0..64).prop_map(SettingValue::Binary),
+        text().prop_map(SettingValue::String),
+    ]
+}
+
+fn profiles() -> impl Strategy<Value = Vec<NipProfile>> {
+    let setting = (any::<u32>(), text(), value()).prop_map(|(id, name, value)| NipSetting { id, name, value });
+    let profile = (text(), proptest::collection::vec(text(), 0..4), proptest::collection::vec(setting, 0..4))
+        .prop_map(|(name, executables, settings)| NipProfile { name, executables, settings });
+    proptest::collection::vec(profile, 0..4)
+}
+
+proptest! {
+    #[test]
+    fn round_trip(profiles in profiles()) {
+        prop_assert_eq!(nip::from_xml(&nip::to_xml(&profiles)).unwrap(), profiles.clone());
+        prop_assert_eq!(nip::decode(&nip::encode(&profiles)).unwrap(), profiles);
+    }
+
+    #[test]
+    fn parse_garbage(xml in "[<>/a-z!?\\[\\]&;# \n-]{0,64}") {
+        let _ = nip::from_xml(&xml);
+    }
+}
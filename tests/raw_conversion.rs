@@ -0,0 +1,91 @@
+// Conversions must reject whatever garbage the driver leaves in a structure
+// with an error rather than a panic.
+
+use std::{fmt, mem, ptr};
+use proptest::prelude::*;
+use nvapi::RawConversion;
+use nvapi::sys::{self, Status};
+use nvapi::sys::gpu::{clock, cooler, display, ecc, power, pstate, thermal};
+use nvapi::sys::driverapi;
+
+/// Reinterprets arbitrary bytes as a plain-old-data structure.
+fn from_bytes<T: Copy>(bytes: &[u8]) -> T {
+    assert_eq!(bytes.len(), mem::size_of::<T>());
+    unsafe { ptr::read_unaligned(bytes.as_ptr() as *const T) }
+}
+
+fn arbitrary<T: Copy + fmt::Debug>() -> impl Strategy<Value = T> {
+    proptest::collection::vec(any::<u8>(), mem::size_of::<T>())
+        .prop_map(|bytes| from_bytes::<T>(&bytes))
+}
+
+macro_rules! convert_arbitrary {
+    ($($name:ident: $ty:ty,)*) => {
+        proptest! {
+            $(
+                #[test]
+                fn $name(raw in arbitrary::<$ty>()) {
+                    let _ = raw.convert_raw();
+                }
+            )*
+        }
+    };
+}
+
+convert_arbitrary! {
+    short_string: sys::types::NvAPI_ShortString,
+    pstates20: pstate::NV_GPU_PERF_PSTATES20_INFO_V2,
+    dynamic_pstates: pstate::NV_GPU_DYNAMIC_PSTATES_INFO_EX,
+    memory_info: driverapi::NV_DISPLAY_DRIVER_MEMORY_INFO,
+    memory_info_ex: driverapi::NV_GPU_MEMORY_INFO_EX_V1,
+    display_ids: display::NV_GPU_DISPLAYIDS,
+    clock_frequencies: clock::NV_GPU_CLOCK_FREQUENCIES,
+    usages: clock::private::NV_USAGES_INFO,
+    vfp_mask: clock::private::NV_GPU_CLOCK_CLIENT_CLK_VF_POINTS_INFO,
+    vfp_control: clock::private::NV_GPU_CLOCK_CLIENT_CLK_VF_POINTS_CONTROL,
+    clock_domains: clock::private::NV_GPU_CLOCK_CLIENT_CLK_DOMAINS_INFO,
+    vfp_status: power::private::NV_GPU_CLOCK_CLIENT_CLK_VF_POINTS_STATUS,
+    volt_rails_status: power::private::NV_GPU_CLIENT_VOLT_RAILS_STATUS_V1,
+    volt_rails_control: power::private::NV_GPU_CLIENT_VOLT_RAILS_CONTROL_V1,
+    power_info: power::private::NV_GPU_POWER_INFO,
+    power_topology: power::private::NV_GPU_POWER_TOPO,
+    power_status: power::private::NV_GPU_POWER_STATUS,
+    perf_limits: clock::private::NV_GPU_PERF_CLIENT_LIMITS,
+    perf_info: power::private::NV_GPU_PERF_INFO,
+    perf_status: power::private::NV_GPU_PERF_STATUS,
+    volt_table: power::private::NV_VOLT_TABLE,
+    volt_status: power::private::NV_VOLT_STATUS,
+    thermal_settings: thermal::NV_GPU_THERMAL_SETTINGS,
+    thermal_info: thermal::private::NV_GPU_THERMAL_INFO,
+    thermal_policies: thermal::private::NV_GPU_CLIENT_THERMAL_POLICIES_STATUS,
+    cooler_settings: cooler::private::NV_GPU_COOLER_SETTINGS,
+    cooler_level: cooler::private::NV_GPU_SETCOOLER_LEVEL,
+    cooler_policy_table: cooler::private::NV_GPU_COOLER_POLICY_TABLE,
+    ecc_status: ecc::NV_GPU_ECC_STATUS_INFO,
+    ecc_errors: ecc::NV_GPU_ECC_ERROR_INFO,
+    ecc_configuration: ecc::NV_GPU_ECC_CONFIGURATION_INFO,
+    pcie_info: sys::gpu::private::NV_GPU_PCIE_INFO,
+}
+
+proptest! {
+    #[test]
+    fn thermal_settings_count(count in (thermal::NVAPI_MAX_THERMAL_SENSORS_PER_GPU as u32 + 1)..) {
+        let mut raw = thermal::NV_GPU_THERMAL_SETTINGS::zeroed();
+        raw.count = count;
+        let e = raw.convert_raw().unwrap_err();
+        prop_assert_eq!(e.name(), Some("count"));
+        prop_assert_eq!(e.value(), Some(count as i64));
+    }
+}
+
+#[test]
+fn clock_domains_count() {
+    let raw = clock::private::NV_GPU_CLOCK_CLIENT_CLK_DOMAINS_INFO::zeroed();
+    let e = raw.convert_raw().unwrap_err();
+    assert_eq!(e.name(), Some("numClocks"));
+    assert_eq!(e.value(), Some(-1));
+
+    let e = sys::Error::from(e);
+    assert_eq!(e.status(), Status::ArgumentExceedMaxSize);
+    assert_eq!(e.context(), Some("numClocks out of range: -1"));
+}